@@ -39,6 +39,9 @@ uninstall-success = Uninstallation Successful
 uninstall-confirm-title = Confirm Uninstall
 uninstall-confirm-single = Uninstalling "{ $name }" will delete the app and its data. This action cannot be undone.
 uninstall-confirm-batch = Uninstalling { $count } selected apps will delete them and their data. This action cannot be undone.
+disable-confirm-title = Confirm Disable
+disable-confirm-single = Disable "{ $name }" for the current user? It can be enabled again from the Debloat tab.
+disable-unsafe-blocked = "{ $name }" is marked Unsafe and was not disabled. Turn on "Allow unsafe app uninstall/disable" in the settings to disable it.
 install-error = Installation Error
 check-update = Check for Update
 update-now = Update Now
@@ -78,6 +81,7 @@ col-runtime-permissions = Runtime Permissions
 col-enabled = Enabled
 col-stalkerware = Stalkerware
 col-install-reason = Install Reason
+col-background = Background
 col-tasks = Tasks
 stalkerware = STALKERWARE
 not-listed = NOT LISTED
@@ -91,6 +95,7 @@ enable-selected = Enable Selected ({ $count })
 
 # Scan Tab
 virustotal-filter = VirusTotal Filter:
+vt-quota-remaining = { $remaining } requests left today
 scanning-packages = Scanning packages...
 malicious = Malicious ({ $enabled }/{ $total })
 malicious-ignored = Malicious-Ignored ({ $enabled }/{ $total })
@@ -99,16 +104,48 @@ safe = Safe ({ $enabled }/{ $total })
 no-specific-threat = No Specific Threat ({ $enabled }/{ $total })
 not-scanned = Not Scanned ({ $enabled }/{ $total })
 hybrid-analysis-filter = Hybrid-Analysis Filter:
+malwarebazaar-filter = MalwareBazaar Filter:
+koodous-filter = Koodous Filter:
+not-listed = Not Listed ({ $enabled }/{ $total })
 col-izzy-risk = IzzyRisk
 col-virustotal = VirusTotal
 col-hybrid-analysis = HybridAnalysis
+col-trackers = Trackers
+trackers-count = { $count } trackers
+no-trackers = None
+not-scanned-short = Not scanned
+scan-trackers = Scan Trackers
+scan-trackers-desc = Pull APKs and detect trackers from DEX class names (offline, nothing is uploaded)
+tracker-signatures-unavailable = Tracker signatures are not loaded
+col-rules = Rules
+rules-count = { $count } rules matched
+no-rule-matches = No match
+scan-rules = Scan Rules
+scan-rules-desc = Pull APKs and evaluate your local rules (strings, hex, regex, file names) against DEX, resources and native libraries. Nothing is uploaded. Rules file: { $path }
+scan-rules-loaded = { $count } rules loaded
+scan-rules-none = No rules defined
+scan-rules-invalid = Rules file has errors
+scan-native-libs = Native Libs
+scan-native-libs-desc = Pull APKs and list bundled native libraries (lib/<abi>/*.so) with architecture, imports and known-vulnerable versions; their hashes are also looked up by the scan providers
+vulnerable-native-lib = Vulnerable native library
+scan-export = Export Report
+scan-export-title = Export Scan Report
+scan-export-desc = Save VirusTotal, Hybrid Analysis, IzzyRisk, stalkerware, installer and UAD data for every package and file
+scan-export-format = Format:
+scan-export-folder = Folder:
+scan-export-run = Export
+scan-export-running = Exporting...
+scan-export-done = Saved to { $path }
 scan-not-initialized = Not initialized
 scan-not-scanned = Not scanned
 scan-scanning = Scanning... ({ $scanned }/{ $total })
 scan-error = Error
 scan-error-msg = Error: { $message }
 scan-skip = skip
+scan-trusted = trusted (allowlist)
 scan-404 = 404
+scan-changed-vt = Changed since last scan (was { $malicious } malicious, { $suspicious } suspicious)
+scan-changed-ha = Changed since last scan (was { $verdict })
 scan-malicious = malicious { $count }/{ $total }
 scan-suspicious = suspicious { $count }/{ $total }
 scan-clean = clean { $count }/{ $total }
@@ -120,6 +157,7 @@ ha-suspicious = suspicious
 ha-suspicious-score = suspicious ({ $score })
 ha-suspicious-tags = suspicious ({ $tags })
 ha-whitelisted = whitelisted
+ha-trusted = trusted (allowlist)
 ha-whitelisted-score = whitelisted ({ $score })
 ha-whitelisted-tags = whitelisted ({ $tags })
 ha-no-result = no-result
@@ -143,12 +181,235 @@ ha-wait-mins = { $text } (wait { $mins }m)
 ha-wait-less-than-min = { $text } (wait <1m)
 refresh-scan = Re-scan this package
 izzyrisk-calculation = IzzyRisk Calculation:
+izzyrisk-no-risky-permissions = No risky permissions granted
+izzyrisk-install = install-time
+izzyrisk-runtime-granted = runtime, granted
+izzyrisk-runtime-denied = runtime, not granted
+izzyrisk-combination = combination: { $name }
+izzyrisk-total = Total: { $total }
 calculating-risk-scores = Calculating risk scores...
+tracker-scan = Tracker Scan:
+scanning-trackers = Scanning APKs for trackers...
+rule-scan = Rule Scan:
+scanning-rules = Evaluating local rules against APKs...
+native-lib-scan = Native Library Scan:
+scanning-native-libs = Inventorying native libraries...
+
+# UAD-NG List Changelog
+uad-changelog = List Changes ({ $count })
+uad-changelog-title = UAD-NG List Changelog
+uad-changelog-compared = Compared lists downloaded { $old } and { $new }
+uad-changelog-no-history = Only one version of the UAD-NG lists has been downloaded so far.
+uad-changelog-no-changes = No changes between the last two list versions.
+uad-changelog-flagged = Recommendation changed since your last action ({ $count }):
+uad-changelog-category-changes = Category changes ({ $count }):
+uad-changelog-new-entries = New entries ({ $count }):
+uad-changelog-removed-entries = Removed entries ({ $count }):
+check-certificates = Check Certificates
+check-certificates-desc = Pull APKs and match their signing certificates against the stalkerware IoC
+stalkerware-report = Stalkerware Report
+stalkerware-report-desc = Check this device for stalkerware using IoC matches and surveillance capabilities
+stalkerware-report-title = Stalkerware Report
+stalkerware-report-generating = Collecting device state...
+stalkerware-report-summary = Device { $device }: { $count } packages checked at { $generated }
+stalkerware-report-no-indicators = Stalkerware IoC database is not loaded; only capability signals were checked.
+stalkerware-report-clean = No stalkerware indicators found.
+stalkerware-report-show-low = Show { $count } low-confidence findings (often legitimate apps)
+stalkerware-report-copy = Copy Report
+stalkerware-report-copy-desc = Copy the full report as text to keep as evidence
+stalkerware-report-copied = Copied to clipboard
+stalkerware-confidence-high = HIGH
+stalkerware-confidence-medium = MEDIUM
+stalkerware-confidence-low = LOW
+stalkerware-family = Family: { $family }
+stalkerware-evidence-package = Package name is a known { $family } indicator
+stalkerware-evidence-certificate = Signed with a known { $family } certificate
+stalkerware-evidence-label = App name "{ $label }" matches { $family }
+stalkerware-evidence-accessibility = Accessibility service enabled: { $component }
+stalkerware-evidence-admin = Active device admin: { $component }
+stalkerware-evidence-notification = Reads notifications: { $component }
+stalkerware-evidence-hidden-icon = Has no launcher icon
+stalkerware-safety-title = Before removing anything
+stalkerware-safety-alert = Removing or disabling a monitoring app may alert the person who installed it. If you are concerned for your safety, contact a local domestic violence hotline or support service first and make a safety plan.
+stalkerware-safety-evidence = Preserve evidence before removal: copy this report, take screenshots of the app and its permissions, and note dates. Evidence is lost once the app is uninstalled.
+stalkerware-safety-accounts = After removal, change passwords of important accounts from a device the other person has never had access to.
+stalkerware-removal-steps = Removal steps:
+stalkerware-remove-admin = Deactivate it under Settings > Security > Device admin apps; otherwise it cannot be uninstalled
+stalkerware-remove-access = Turn off its accessibility and notification access in Settings
+stalkerware-remove-uninstall = Uninstall it from the Debloat list
+stalkerware-remove-disable = Disable it from the Debloat list (system app)
+capability-audit = Capability Audit
+capability-audit-desc = List apps holding powerful capabilities such as accessibility, device admin and VPN
+capability-audit-title = Privileged Capability Audit
+capability-audit-generating = Querying device capabilities...
+capability-audit-summary = Device { $device }: { $count } capability grants
+capability-audit-partial = { $count } queries failed; some capabilities may be missing
+capability-accessibility = Accessibility services
+capability-accessibility-desc = Can read everything on screen and act on your behalf
+capability-device-admin = Device administrators
+capability-device-admin-desc = Can lock or wipe the device and block their own uninstall
+capability-notification-listener = Notification access
+capability-notification-listener-desc = Can read every incoming notification, including messages
+capability-vpn = VPN
+capability-vpn-desc = Can see and redirect all network traffic
+capability-sms-role = Default SMS app
+capability-sms-role-desc = Can read and send SMS messages
+capability-dialer-role = Default phone app
+capability-dialer-role-desc = Can place calls and see call history
+capability-usage-stats = Usage access
+capability-usage-stats-desc = Can see which apps you use and when
+capability-overlay = Display over other apps
+capability-overlay-desc = Can draw on top of other apps, e.g. fake login screens
+capability-install-unknown = Install unknown apps
+capability-install-unknown-desc = Can install other apps from outside the store
+capability-none = None
+capability-system = system
+capability-always-on = always-on
+capability-revoke = Revoke
+capability-revoke-desc = Remove only this capability from the app
+capability-disable-app = Disable App
+capability-disable-app-desc = Disable the whole app for the current user
+capability-action-done = Updated { $package }
+capability-action-failed = Failed to update { $package }: { $error }
+capability-device-admin-manual = Android only lets the shell remove test admins. Deactivate { $package } in Settings > Security > Device admin apps, then refresh
+provenance = Install Sources
+provenance-desc = Group installed packages by where they were installed from
+provenance-title = Install Provenance
+provenance-all = All ({ $count })
+provenance-review-only = Only sideloaded from browsers or messaging apps ({ $count })
+provenance-review-desc = Installed from an APK opened in a browser or messaging app; verify you installed it yourself
+provenance-no-packages = No installed packages loaded for this device.
+provenance-play-store = Play Store
+provenance-fdroid = F-Droid
+provenance-aurora = Aurora Store
+provenance-other-store = Other store
+provenance-adb = ADB / shell
+provenance-browser = Browser / download
+provenance-messaging = Messaging app
+provenance-preinstalled = Preinstalled
+provenance-unknown = Unknown
+uad-changed-badge = CHANGED
+uad-changed-badge-info = The UAD-NG recommendation for this package changed since you last acted on it
+permission-manager = Permissions
+permission-manager-desc = Grant or revoke runtime permissions across apps, e.g. every app with location
+permission-manager-title = Permission Manager
+permission-user = User { $user }
+permission-granted-only = Only granted
+permission-all = All ({ $count })
+permission-group-location = Location
+permission-group-camera = Camera
+permission-group-microphone = Microphone
+permission-group-contacts = Contacts
+permission-group-calendar = Calendar
+permission-group-phone = Phone
+permission-group-sms = SMS
+permission-group-storage = Storage / media
+permission-group-body-sensors = Body sensors
+permission-group-activity = Physical activity
+permission-group-nearby = Nearby devices
+permission-group-notifications = Notifications
+permission-group-other = Other
+permission-granted = Granted
+permission-denied = Denied
+permission-fixed = fixed
+permission-fixed-desc = Fixed by the system or a device policy; it cannot be changed with pm
+permission-select-all = Select All
+permission-revoke-selected = Revoke Selected ({ $count })
+permission-grant-selected = Grant Selected ({ $count })
+permission-applying = Applying permission changes...
+permission-batch-done = { $count } permission changes applied
+permission-batch-failed = { $count } permission changes failed ({ $succeeded } applied):
+permission-none = No runtime permissions match the filter.
+permission-journal = Journal ({ $count })
+permission-show-list = Permissions
+permission-journal-empty = No permission changes recorded for this device.
+permission-journal-granted = Granted
+permission-journal-revoked = Revoked
+permission-revert = Revert
+permission-reverted = reverted
+permission-revert-done = Reverted permission change for { $package }
+permission-revert-failed = Failed to revert permission change for { $package }: { $error }
+appops-selected = AppOps ({ $count })
+appops-desc = View and set app ops (background running, wake locks, clipboard, device identifiers, ...) of the selected packages
+appops-title = AppOps
+appops-batch = Set for all:
+appops-apply = Apply to { $count } packages
+appops-applying = Applying app op changes...
+appops-changes-done = { $count } app op changes applied
+appops-changes-failed = { $count } app op changes failed ({ $succeeded } applied):
+appops-used-only = Only accessed or rejected ops
+appops-loading = Reading app ops...
+appops-none = No app ops reported for this package.
+appops-uid-mode = uid
+appops-uid-mode-desc = Uid mode, shared by all packages running under the same uid
+appops-last-access = last access { $time }
+appops-duration = duration { $duration }
+appops-last-reject = last rejected { $time }
+components-selected = Components ({ $count })
+components-desc = Browse and disable individual activities, services, receivers and providers of the selected packages
+components-title = Components
+components-activities = Activities
+components-services = Services
+components-receivers = Receivers
+components-providers = Providers
+components-kind-header = { $kind } ({ $count }, { $disabled } disabled)
+components-exported = exported
+components-tracker = tracker: { $name }
+components-trackers-only = Only tracker components
+components-disable-trackers = Disable { $count } known tracker components
+components-disable-trackers-desc = Disable every enabled component whose class matches a tracker signature, in all listed packages
+components-no-signatures = Tracker signatures are not loaded; tracker components cannot be recognized.
+components-applying = Applying component changes...
+components-changes-done = { $count } component changes applied
+components-changes-failed = { $count } component changes failed ({ $succeeded } applied):
+components-loading = Reading manifest and component state...
+components-none = No components match.
+background-selected = Background ({ $count })
+background-desc = Set the standby bucket, battery optimization or background restriction of the selected packages
+background-standby-bucket = Standby bucket
+background-whitelist = Exempt from battery optimization
+background-unwhitelist = Optimize battery usage
+background-restrict = Restrict background activity
+background-unrestrict = Allow background activity
+background-unoptimized = unoptimized
+background-unoptimized-system = unoptimized (system)
+background-restricted = bg restricted
+background-loading = Reading background states...
+background-applying = Applying background changes...
+background-changes-done = { $count } background changes applied
+background-changes-failed = { $count } background changes failed ({ $succeeded } applied):
 
 # Usage Tab
 usage-control = Usage Control
 no-usage-stats = No usage statistics available. Select a device to view usage stats.
 usage-statistics = Usage Statistics:
+usage-loading = Reading usage stats...
+usage-load-failed = Failed to read usage stats: { $error }
+usage-never = Never
+usage-unused-only = Only not used in { $days } days
+usage-select-unused = Select { $count } unused in Debloat
+usage-select-unused-desc = Add the installed non-system packages not used in the last { $days } days to the Debloat tab selection. Packages UAD marks as Unsafe are skipped
+col-last-used = Last Used
+col-foreground-day = Foreground today
+col-foreground-week = Foreground this week
+col-foreground-month = Foreground this month
+col-launches = Launches
+usage-view-usage = App Usage
+usage-view-battery = Battery
+battery-loading = Reading battery stats...
+battery-load-failed = Failed to read battery stats: { $error }
+battery-rank-by = Rank by
+battery-rank-power = Power use
+battery-rank-wakelocks = Wakelock time
+battery-rank-alarms = Alarm wakeups
+battery-rank-jobs = Job runs
+battery-since-charge = Since the last full charge. Apps sharing a uid are listed together.
+battery-empty = No battery stats since the last charge
+battery-disable-desc = Disable this app for the current user after confirming in the Debloat tab
+col-power = mAh
+col-wakelocks = Wakelocks
+col-alarm-wakeups = Wakeups
+col-jobs = Jobs
 
 # Settings
 display-size = Display Size:
@@ -165,10 +426,42 @@ virustotal-api-key = VirusTotal(4/min) API Key:
 get-api-key = Get API Key
 allow-virustotal-upload = Allow VirusTotal file upload
 virustotal-upload-desc = (Upload files not found in VirusTotal for analysis)
+virustotal-quota = VirusTotal API tier:
+per-minute = /min
+per-day = /day
+per-month = /month
+virustotal-quota-desc = (Public API: 4/min, 500/day, 15500/month; 0 = unlimited)
 hybridanalysis-api-key = HybridAnalysis(200/min) API Key:
 allow-hybridanalysis-upload = Allow Hybrid Analysis file upload
 hybridanalysis-upload-desc = (Upload files not found in Hybrid Analysis for scanning)
 hybridanalysis-tag-ignorelist = HybridAnalysis Tag Ignorelist:
+hybridanalysis-environment-desc = APKs are analyzed in the Android Static Analysis environment. Hybrid Analysis has no public Android dynamic sandbox, and its desktop environments cannot run APKs.
+scan-max-age-days = Re-check results older than (days):
+scan-max-age-days-desc = Cached VirusTotal/HybridAnalysis results are looked up again after this many days (0 = never, the default; re-checks use API quota)
+rescan-flagged = Always re-check flagged apps
+rescan-flagged-desc = Look up malicious or suspicious results again on every scan (uses API quota)
+rescan-interval-hours = Background rescan interval (hours):
+rescan-interval-hours-desc = Rescan periodically while the app is open (0 = off)
+hash-allowlist = Known-good hash allowlist: { $count } hashes
+hash-allowlist-desc = VirusTotal and Hybrid Analysis skip allowlisted hashes and show them as trusted (allowlist), unless a local scan flagged them
+hash-allowlist-add-clean = Add Past Clean Results
+hash-allowlist-add-clean-desc = Add hashes with no VirusTotal detections or a Hybrid Analysis whitelisted verdict
+hash-allowlist-clear = Clear
+hash-allowlist-file = Allowlist file:
+hash-allowlist-file-hint = Shared file with one sha256 per line
+hash-allowlist-import = Import
+hash-allowlist-export = Export
+hash-allowlist-added = Added { $count } hashes to the allowlist
+hash-allowlist-added-refused = Added { $count } hashes to the allowlist; refused { $refused } hashes flagged by local scans
+hash-allowlist-exported = Exported { $count } hashes
+hash-allowlist-cleared = Removed { $count } hashes from the allowlist
+hash-allowlist-failed = Allowlist update failed: { $error }
+izzyrisk-weights-file = IzzyRisk weights file:
+izzyrisk-weights-file-desc = (Permission points and dangerous combinations; applied on the next risk calculation)
+scan-rules-file = Local scan rules file:
+scan-rules-file-desc = (Strings, hex, regex and file name rules evaluated offline by Scan Rules in the Scan tab)
+malwarebazaar-api-key = MalwareBazaar API Key:
+koodous-api-key = Koodous(10/min) API Key:
 google-play-renderer = Google Play Renderer
 google-play-renderer-desc = (Fetch and display app info from Google Play Store)
 fdroid-renderer = F-Droid Renderer
@@ -184,6 +477,20 @@ apkmirror-name = APKMirror Name:
 name-hint = Your Name (optional)
 apkmirror-auto-upload = APKMirror Auto-Upload
 apkmirror-auto-upload-desc = (Auto-upload APKs when device version is newer than APKMirror)
+uad-lists-source = UAD-NG Lists:
+stalkerware-ioc-source = Stalkerware IoC:
+tracker-signatures-source = Tracker signatures:
+dataset-source-cache = Downloaded { $date }
+dataset-source-embedded = Bundled snapshot { $date }
+dataset-source-none = Not loaded
+bundled-snapshot-date = (Bundled snapshot date: { $date })
+import-dataset-path = Import File:
+import-dataset-path-hint = /path/to/uad_lists.json or ioc.yaml
+import-uad-lists = Import UAD-NG Lists
+import-stalkerware-ioc = Import Stalkerware IoC
+import-tracker-signatures = Import Tracker Signatures
+import-dataset-success = Imported { $count } entries
+import-dataset-failed = Import failed: { $error }
 invalidate-cache = 404 Invalidate Cache
 invalidate-cache-desc = (Clear cached apps and scan results)
 show-logs = Show Logs:
@@ -196,6 +503,7 @@ flush = Flush
 status-checking-api = Checking API
 status-pulling-file = Pulling file
 status-uploading = Uploading
+status-rate-limit = Rate limit ({ $secs }s)
 error-invalid-package-id = Invalid package ID (less than 2 domain levels)
 error-timeout-rate-limit = Timeout waiting for rate limit
 error-email-not-configured = Email not configured
@@ -211,6 +519,8 @@ batch-disable = Batch Disable
 disabling-packages = Disabling packages...
 batch-enable = Batch Enable
 enabling-packages = Enabling packages...
+certificate-scan = Certificate Check:
+checking-certificates = Checking signing certificates...
 scan-description = Scan viruses with virustotal and hybridanalysis:
 set-api-keys = Please set virustotal or hybridanalysis apikey in settings.
 apps-description = Lists of Foss applications :
@@ -39,6 +39,9 @@ uninstall-success = 제거 완료
 uninstall-confirm-title = 제거 확인
 uninstall-confirm-single = "{ $name }" 앱과 데이터가 삭제됩니다. 이 작업은 되돌릴 수 없습니다.
 uninstall-confirm-batch = 선택한 { $count }개의 앱과 데이터가 삭제됩니다. 이 작업은 되돌릴 수 없습니다.
+disable-confirm-title = 비활성화 확인
+disable-confirm-single = 현재 사용자에 대해 "{ $name }"을(를) 비활성화할까요? 디블로트 탭에서 다시 활성화할 수 있습니다.
+disable-unsafe-blocked = "{ $name }"은(는) 위험 앱으로 표시되어 비활성화하지 않았습니다. 비활성화하려면 설정에서 "위험 앱 제거/비활성화 허용"을 켜세요.
 install-error = 설치 오류
 check-update = 업데이트 확인
 update-now = 지금 업데이트
@@ -78,6 +81,7 @@ col-runtime-permissions = 런타임 권한
 col-enabled = 활성화 여부
 col-stalkerware = 스토커웨어
 col-install-reason = 설치 사유
+col-background = 백그라운드
 col-tasks = 작업
 stalkerware = 스토커웨어
 not-listed = 목록에 없음
@@ -91,6 +95,7 @@ enable-selected = 선택 항목 활성화 ({ $count })
 
 # Scan Tab
 virustotal-filter = VirusTotal 필터:
+vt-quota-remaining = 오늘 남은 요청 { $remaining }회
 scanning-packages = 패키지 검사 중...
 malicious = 악성 ({ $enabled }/{ $total })
 malicious-ignored = 악성-무시됨 ({ $enabled }/{ $total })
@@ -99,16 +104,48 @@ safe = 안전 ({ $enabled }/{ $total })
 no-specific-threat = 특정 위협 없음 ({ $enabled }/{ $total })
 not-scanned = 미검사 ({ $enabled }/{ $total })
 hybrid-analysis-filter = Hybrid-Analysis 필터:
+malwarebazaar-filter = MalwareBazaar 필터:
+koodous-filter = Koodous 필터:
+not-listed = 미등재 ({ $enabled }/{ $total })
 col-izzy-risk = IzzyRisk
 col-virustotal = VirusTotal
 col-hybrid-analysis = HybridAnalysis
+col-trackers = 트래커
+trackers-count = 트래커 { $count }개
+no-trackers = 없음
+not-scanned-short = 검사 안 됨
+scan-trackers = 트래커 검사
+scan-trackers-desc = APK를 가져와 DEX 클래스 이름으로 트래커를 탐지합니다 (오프라인, 업로드 없음)
+tracker-signatures-unavailable = 트래커 시그니처가 로드되지 않았습니다
+col-rules = 규칙
+rules-count = 규칙 { $count }개 일치
+no-rule-matches = 일치 없음
+scan-rules = 규칙 검사
+scan-rules-desc = APK를 가져와 로컬 규칙(문자열, 16진수, 정규식, 파일 이름)을 DEX, 리소스, 네이티브 라이브러리에 적용합니다. 업로드 없음. 규칙 파일: { $path }
+scan-rules-loaded = 규칙 { $count }개 로드됨
+scan-rules-none = 정의된 규칙 없음
+scan-rules-invalid = 규칙 파일에 오류가 있습니다
+scan-native-libs = 네이티브 라이브러리
+scan-native-libs-desc = APK를 가져와 포함된 네이티브 라이브러리(lib/<abi>/*.so)의 아키텍처, 임포트, 알려진 취약 버전을 표시합니다. 해시는 검사 제공자에서도 조회됩니다
+vulnerable-native-lib = 취약한 네이티브 라이브러리
+scan-export = 보고서 내보내기
+scan-export-title = 검사 보고서 내보내기
+scan-export-desc = 모든 패키지와 파일의 VirusTotal, Hybrid Analysis, IzzyRisk, 스토커웨어, 설치 출처, UAD 정보를 저장합니다
+scan-export-format = 형식:
+scan-export-folder = 폴더:
+scan-export-run = 내보내기
+scan-export-running = 내보내는 중...
+scan-export-done = { $path }에 저장됨
 scan-not-initialized = 시작되지 않음
 scan-not-scanned = 미검사
 scan-scanning = 검사 중... ({ $scanned }/{ $total })
 scan-error = 오류
 scan-error-msg = 오류: { $message }
 scan-skip = 건너뜀
+scan-trusted = 신뢰됨 (허용 목록)
 scan-404 = 404
+scan-changed-vt = 지난 검사 이후 변경됨 (이전: 악성 { $malicious }, 의심 { $suspicious })
+scan-changed-ha = 지난 검사 이후 변경됨 (이전: { $verdict })
 scan-malicious = 악성 { $count }/{ $total }
 scan-suspicious = 의심 { $count }/{ $total }
 scan-clean = 안전 { $count }/{ $total }
@@ -120,6 +157,7 @@ ha-suspicious = 의심
 ha-suspicious-score = 의심 ({ $score })
 ha-suspicious-tags = 의심 ({ $tags })
 ha-whitelisted = 안전
+ha-trusted = 신뢰됨 (허용 목록)
 ha-whitelisted-score = 안전 ({ $score })
 ha-whitelisted-tags = 안전 ({ $tags })
 ha-no-result = 결과 없음
@@ -143,12 +181,235 @@ ha-wait-mins = { $text } (대기 { $mins }분)
 ha-wait-less-than-min = { $text } (대기 <1분)
 refresh-scan = 이 패키지 다시 검사
 izzyrisk-calculation = IzzyRisk 계산:
+izzyrisk-no-risky-permissions = 허용된 위험 권한 없음
+izzyrisk-install = 설치 시 권한
+izzyrisk-runtime-granted = 런타임, 허용됨
+izzyrisk-runtime-denied = 런타임, 허용 안 됨
+izzyrisk-combination = 조합: { $name }
+izzyrisk-total = 합계: { $total }
 calculating-risk-scores = 위험 점수 계산 중...
+tracker-scan = 트래커 검사:
+scanning-trackers = APK 트래커 검사 중...
+rule-scan = 규칙 검사:
+scanning-rules = APK에 로컬 규칙 적용 중...
+native-lib-scan = 네이티브 라이브러리 검사:
+scanning-native-libs = 네이티브 라이브러리 목록 작성 중...
+
+# UAD-NG List Changelog
+uad-changelog = 목록 변경 사항 ({ $count })
+uad-changelog-title = UAD-NG 목록 변경 기록
+uad-changelog-compared = { $old } 및 { $new }에 다운로드한 목록 비교
+uad-changelog-no-history = 지금까지 UAD-NG 목록 버전이 하나만 다운로드되었습니다.
+uad-changelog-no-changes = 최근 두 목록 버전 사이에 변경 사항이 없습니다.
+uad-changelog-flagged = 마지막 작업 이후 권장 사항이 변경됨 ({ $count }):
+uad-changelog-category-changes = 카테고리 변경 ({ $count }):
+uad-changelog-new-entries = 새 항목 ({ $count }):
+uad-changelog-removed-entries = 삭제된 항목 ({ $count }):
+check-certificates = 인증서 검사
+check-certificates-desc = APK를 가져와 서명 인증서를 스토커웨어 IoC와 대조합니다
+stalkerware-report = 스토커웨어 보고서
+stalkerware-report-desc = IoC 일치 및 감시 권한을 기반으로 이 기기의 스토커웨어를 검사합니다
+stalkerware-report-title = 스토커웨어 보고서
+stalkerware-report-generating = 기기 상태를 수집하는 중...
+stalkerware-report-summary = 기기 { $device }: { $generated }에 패키지 { $count }개 검사
+stalkerware-report-no-indicators = 스토커웨어 IoC 데이터베이스가 로드되지 않아 권한 신호만 검사했습니다.
+stalkerware-report-clean = 스토커웨어 징후가 발견되지 않았습니다.
+stalkerware-report-show-low = 낮은 신뢰도 결과 { $count }개 표시 (대부분 정상 앱)
+stalkerware-report-copy = 보고서 복사
+stalkerware-report-copy-desc = 증거로 보관할 수 있도록 전체 보고서를 텍스트로 복사합니다
+stalkerware-report-copied = 클립보드에 복사됨
+stalkerware-confidence-high = 높음
+stalkerware-confidence-medium = 중간
+stalkerware-confidence-low = 낮음
+stalkerware-family = 계열: { $family }
+stalkerware-evidence-package = 패키지 이름이 알려진 { $family } 지표입니다
+stalkerware-evidence-certificate = 알려진 { $family } 인증서로 서명됨
+stalkerware-evidence-label = 앱 이름 "{ $label }"이(가) { $family }와 일치합니다
+stalkerware-evidence-accessibility = 접근성 서비스 활성화됨: { $component }
+stalkerware-evidence-admin = 활성 기기 관리자: { $component }
+stalkerware-evidence-notification = 알림 읽기 권한: { $component }
+stalkerware-evidence-hidden-icon = 런처 아이콘이 없습니다
+stalkerware-safety-title = 제거하기 전에
+stalkerware-safety-alert = 감시 앱을 제거하거나 비활성화하면 설치한 사람에게 알려질 수 있습니다. 안전이 걱정된다면 먼저 가정폭력 상담 기관에 연락하고 안전 계획을 세우세요.
+stalkerware-safety-evidence = 제거 전에 증거를 보존하세요: 이 보고서를 복사하고, 앱과 권한 화면을 캡처하고, 날짜를 기록하세요. 앱을 제거하면 증거가 사라집니다.
+stalkerware-safety-accounts = 제거 후에는 상대방이 접근한 적 없는 기기에서 중요한 계정의 비밀번호를 변경하세요.
+stalkerware-removal-steps = 제거 단계:
+stalkerware-remove-admin = 설정 > 보안 > 기기 관리자 앱에서 비활성화하세요. 그렇지 않으면 제거할 수 없습니다
+stalkerware-remove-access = 설정에서 접근성 및 알림 접근 권한을 해제하세요
+stalkerware-remove-uninstall = 디블롯 목록에서 제거하세요
+stalkerware-remove-disable = 디블롯 목록에서 비활성화하세요 (시스템 앱)
+capability-audit = 권한 감사
+capability-audit-desc = 접근성, 기기 관리자, VPN 등 강력한 권한을 가진 앱을 나열합니다
+capability-audit-title = 특권 권한 감사
+capability-audit-generating = 기기 권한을 조회하는 중...
+capability-audit-summary = 기기 { $device }: 권한 부여 { $count }건
+capability-audit-partial = 쿼리 { $count }개가 실패했습니다. 일부 권한이 누락되었을 수 있습니다
+capability-accessibility = 접근성 서비스
+capability-accessibility-desc = 화면의 모든 내용을 읽고 사용자 대신 조작할 수 있습니다
+capability-device-admin = 기기 관리자
+capability-device-admin-desc = 기기를 잠그거나 초기화하고 자신의 삭제를 막을 수 있습니다
+capability-notification-listener = 알림 접근
+capability-notification-listener-desc = 메시지를 포함한 모든 알림을 읽을 수 있습니다
+capability-vpn = VPN
+capability-vpn-desc = 모든 네트워크 트래픽을 보고 우회시킬 수 있습니다
+capability-sms-role = 기본 SMS 앱
+capability-sms-role-desc = SMS 메시지를 읽고 보낼 수 있습니다
+capability-dialer-role = 기본 전화 앱
+capability-dialer-role-desc = 전화를 걸고 통화 기록을 볼 수 있습니다
+capability-usage-stats = 사용 정보 접근
+capability-usage-stats-desc = 어떤 앱을 언제 사용했는지 볼 수 있습니다
+capability-overlay = 다른 앱 위에 표시
+capability-overlay-desc = 가짜 로그인 화면처럼 다른 앱 위에 그릴 수 있습니다
+capability-install-unknown = 출처를 알 수 없는 앱 설치
+capability-install-unknown-desc = 스토어 외부에서 다른 앱을 설치할 수 있습니다
+capability-none = 없음
+capability-system = 시스템
+capability-always-on = 상시 연결
+capability-revoke = 회수
+capability-revoke-desc = 앱에서 이 권한만 제거합니다
+capability-disable-app = 앱 비활성화
+capability-disable-app-desc = 현재 사용자에 대해 앱 전체를 비활성화합니다
+capability-action-done = { $package } 업데이트됨
+capability-action-failed = { $package } 업데이트 실패: { $error }
+capability-device-admin-manual = Android는 셸에서 테스트 관리자만 제거할 수 있습니다. 설정 > 보안 > 기기 관리자 앱에서 { $package }을(를) 비활성화한 후 새로고침하세요
+provenance = 설치 출처
+provenance-desc = 설치된 패키지를 설치 출처별로 분류합니다
+provenance-title = 설치 출처 분석
+provenance-all = 전체 ({ $count })
+provenance-review-only = 브라우저 또는 메신저로 사이드로드된 앱만 ({ $count })
+provenance-review-desc = 브라우저나 메신저에서 연 APK로 설치되었습니다. 직접 설치한 앱인지 확인하세요
+provenance-no-packages = 이 기기에 로드된 설치 패키지가 없습니다.
+provenance-play-store = Play 스토어
+provenance-fdroid = F-Droid
+provenance-aurora = Aurora 스토어
+provenance-other-store = 기타 스토어
+provenance-adb = ADB / 셸
+provenance-browser = 브라우저 / 다운로드
+provenance-messaging = 메신저 앱
+provenance-preinstalled = 사전 설치됨
+provenance-unknown = 알 수 없음
+uad-changed-badge = 변경됨
+uad-changed-badge-info = 마지막 작업 이후 이 패키지의 UAD-NG 권장 사항이 변경되었습니다
+permission-manager = 권한
+permission-manager-desc = 여러 앱의 런타임 권한을 허용하거나 취소합니다 (예: 위치 권한이 있는 모든 앱)
+permission-manager-title = 권한 관리자
+permission-user = 사용자 { $user }
+permission-granted-only = 허용된 항목만
+permission-all = 전체 ({ $count })
+permission-group-location = 위치
+permission-group-camera = 카메라
+permission-group-microphone = 마이크
+permission-group-contacts = 연락처
+permission-group-calendar = 캘린더
+permission-group-phone = 전화
+permission-group-sms = SMS
+permission-group-storage = 저장소 / 미디어
+permission-group-body-sensors = 신체 센서
+permission-group-activity = 신체 활동
+permission-group-nearby = 근처 기기
+permission-group-notifications = 알림
+permission-group-other = 기타
+permission-granted = 허용됨
+permission-denied = 거부됨
+permission-fixed = 고정
+permission-fixed-desc = 시스템 또는 기기 정책에 의해 고정되어 pm으로 변경할 수 없습니다
+permission-select-all = 모두 선택
+permission-revoke-selected = 선택 항목 취소 ({ $count })
+permission-grant-selected = 선택 항목 허용 ({ $count })
+permission-applying = 권한 변경 적용 중...
+permission-batch-done = 권한 변경 { $count }개 적용됨
+permission-batch-failed = 권한 변경 { $count }개 실패 ({ $succeeded }개 적용됨):
+permission-none = 필터와 일치하는 런타임 권한이 없습니다.
+permission-journal = 기록 ({ $count })
+permission-show-list = 권한
+permission-journal-empty = 이 기기에 기록된 권한 변경이 없습니다.
+permission-journal-granted = 허용
+permission-journal-revoked = 취소
+permission-revert = 되돌리기
+permission-reverted = 되돌림
+permission-revert-done = { $package }의 권한 변경을 되돌렸습니다
+permission-revert-failed = { $package }의 권한 변경을 되돌리지 못했습니다: { $error }
+appops-selected = AppOps ({ $count })
+appops-desc = 선택한 패키지의 앱 작업(백그라운드 실행, 웨이크 락, 클립보드, 기기 식별자 등)을 확인하고 설정합니다
+appops-title = AppOps
+appops-batch = 모두 설정:
+appops-apply = 패키지 { $count }개에 적용
+appops-applying = 앱 작업 변경 적용 중...
+appops-changes-done = 앱 작업 변경 { $count }개 적용됨
+appops-changes-failed = 앱 작업 변경 { $count }개 실패 ({ $succeeded }개 적용됨):
+appops-used-only = 접근 또는 거부된 작업만
+appops-loading = 앱 작업 읽는 중...
+appops-none = 이 패키지에 보고된 앱 작업이 없습니다.
+appops-uid-mode = uid
+appops-uid-mode-desc = 같은 uid로 실행되는 모든 패키지가 공유하는 uid 모드
+appops-last-access = 마지막 접근 { $time }
+appops-duration = 지속 시간 { $duration }
+appops-last-reject = 마지막 거부 { $time }
+components-selected = 구성 요소 ({ $count })
+components-desc = 선택한 패키지의 액티비티, 서비스, 리시버, 프로바이더를 개별적으로 확인하고 비활성화합니다
+components-title = 구성 요소
+components-activities = 액티비티
+components-services = 서비스
+components-receivers = 리시버
+components-providers = 프로바이더
+components-kind-header = { $kind } ({ $count }개, { $disabled }개 비활성화됨)
+components-exported = 외부 노출
+components-tracker = 추적기: { $name }
+components-trackers-only = 추적기 구성 요소만
+components-disable-trackers = 알려진 추적기 구성 요소 { $count }개 비활성화
+components-disable-trackers-desc = 목록의 모든 패키지에서 클래스가 추적기 시그니처와 일치하는 활성 구성 요소를 모두 비활성화합니다
+components-no-signatures = 추적기 시그니처가 로드되지 않아 추적기 구성 요소를 인식할 수 없습니다.
+components-applying = 구성 요소 변경 적용 중...
+components-changes-done = 구성 요소 변경 { $count }건 적용됨
+components-changes-failed = 구성 요소 변경 { $count }건 실패 ({ $succeeded }건 적용됨):
+components-loading = 매니페스트와 구성 요소 상태를 읽는 중...
+components-none = 일치하는 구성 요소가 없습니다.
+background-selected = 백그라운드 ({ $count })
+background-desc = 선택한 패키지의 대기 버킷, 배터리 최적화 또는 백그라운드 제한을 설정합니다
+background-standby-bucket = 대기 버킷
+background-whitelist = 배터리 최적화에서 제외
+background-unwhitelist = 배터리 사용량 최적화
+background-restrict = 백그라운드 활동 제한
+background-unrestrict = 백그라운드 활동 허용
+background-unoptimized = 최적화 안 함
+background-unoptimized-system = 최적화 안 함 (시스템)
+background-restricted = 백그라운드 제한됨
+background-loading = 백그라운드 상태를 읽는 중...
+background-applying = 백그라운드 변경 적용 중...
+background-changes-done = 백그라운드 변경 { $count }건 적용됨
+background-changes-failed = 백그라운드 변경 { $count }건 실패 ({ $succeeded }건 적용됨):
 
 # Usage Tab
 usage-control = 사용량 제어
 no-usage-stats = 사용량 통계가 없습니다. 사용량 통계를 보려면 장치를 선택하세요.
 usage-statistics = 사용량 통계:
+usage-loading = 사용 통계를 읽는 중...
+usage-load-failed = 사용 통계를 읽지 못했습니다: { $error }
+usage-never = 사용 안 함
+usage-unused-only = { $days }일 동안 사용하지 않은 앱만
+usage-select-unused = 미사용 { $count }개를 디블로트에서 선택
+usage-select-unused-desc = 최근 { $days }일 동안 사용하지 않은 설치된 비시스템 패키지를 디블로트 탭 선택에 추가합니다. UAD가 Unsafe로 분류한 패키지는 제외됩니다
+col-last-used = 마지막 사용
+col-foreground-day = 오늘 포그라운드
+col-foreground-week = 이번 주 포그라운드
+col-foreground-month = 이번 달 포그라운드
+col-launches = 실행 횟수
+usage-view-usage = 앱 사용
+usage-view-battery = 배터리
+battery-loading = 배터리 통계를 읽는 중...
+battery-load-failed = 배터리 통계를 읽지 못했습니다: { $error }
+battery-rank-by = 순위 기준
+battery-rank-power = 전력 사용량
+battery-rank-wakelocks = 웨이크락 시간
+battery-rank-alarms = 알람 깨우기
+battery-rank-jobs = 작업 실행
+battery-since-charge = 마지막 완전 충전 이후 기준입니다. uid를 공유하는 앱은 함께 표시됩니다.
+battery-empty = 마지막 충전 이후 배터리 통계가 없습니다
+battery-disable-desc = 디블로트 탭에서 확인한 후 현재 사용자에 대해 이 앱을 비활성화합니다
+col-power = mAh
+col-wakelocks = 웨이크락
+col-alarm-wakeups = 깨우기
+col-jobs = 작업
 
 # Settings
 display-size = 화면 크기:
@@ -165,10 +426,42 @@ virustotal-api-key = VirusTotal(4/분) API 키:
 get-api-key = API 키 발급
 allow-virustotal-upload = VirusTotal 파일 업로드 허용
 virustotal-upload-desc = (분석을 위해 VirusTotal에 없는 파일 업로드)
+virustotal-quota = VirusTotal API 등급:
+per-minute = /분
+per-day = /일
+per-month = /월
+virustotal-quota-desc = (공개 API: 4/분, 500/일, 15500/월; 0 = 무제한)
 hybridanalysis-api-key = HybridAnalysis(200/분) API 키:
 allow-hybridanalysis-upload = Hybrid Analysis 파일 업로드 허용
 hybridanalysis-upload-desc = (검사를 위해 Hybrid Analysis에 없는 파일 업로드)
 hybridanalysis-tag-ignorelist = HybridAnalysis 태그 무시목록:
+hybridanalysis-environment-desc = APK는 Android 정적 분석 환경에서 분석됩니다. Hybrid Analysis에는 공개 Android 동적 샌드박스가 없으며 데스크톱 환경은 APK를 실행할 수 없습니다.
+scan-max-age-days = 결과 재확인 기간 (일):
+scan-max-age-days-desc = 캐시된 VirusTotal/HybridAnalysis 결과를 이 기간이 지나면 다시 조회합니다 (0 = 안 함, 기본값; 재조회는 API 할당량을 사용합니다)
+rescan-flagged = 탐지된 앱 항상 재확인
+rescan-flagged-desc = 악성 또는 의심 결과는 검사할 때마다 다시 조회합니다 (API 할당량 사용)
+rescan-interval-hours = 백그라운드 재검사 간격 (시간):
+rescan-interval-hours-desc = 앱이 열려 있는 동안 주기적으로 재검사합니다 (0 = 끔)
+hash-allowlist = 안전 해시 허용 목록: 해시 { $count }개
+hash-allowlist-desc = VirusTotal과 Hybrid Analysis는 로컬 검사에서 탐지되지 않은 허용 목록의 해시를 건너뛰고 신뢰됨(허용 목록)으로 표시합니다
+hash-allowlist-add-clean = 과거 정상 결과 추가
+hash-allowlist-add-clean-desc = VirusTotal 탐지가 없거나 Hybrid Analysis에서 whitelisted 판정을 받은 해시를 추가합니다
+hash-allowlist-clear = 비우기
+hash-allowlist-file = 허용 목록 파일:
+hash-allowlist-file-hint = 한 줄에 sha256 하나씩 있는 공유 파일
+hash-allowlist-import = 가져오기
+hash-allowlist-export = 내보내기
+hash-allowlist-added = 허용 목록에 해시 { $count }개 추가됨
+hash-allowlist-added-refused = 허용 목록에 해시 { $count }개 추가됨, 로컬 검사에서 탐지된 해시 { $refused }개는 거부됨
+hash-allowlist-exported = 해시 { $count }개 내보냄
+hash-allowlist-cleared = 허용 목록에서 해시 { $count }개 삭제됨
+hash-allowlist-failed = 허용 목록 업데이트 실패: { $error }
+izzyrisk-weights-file = IzzyRisk 가중치 파일:
+izzyrisk-weights-file-desc = (권한 점수와 위험한 권한 조합; 다음 위험 점수 계산 시 적용)
+scan-rules-file = 로컬 검사 규칙 파일:
+scan-rules-file-desc = (검사 탭의 규칙 검사가 오프라인으로 적용하는 문자열, 16진수, 정규식, 파일 이름 규칙)
+malwarebazaar-api-key = MalwareBazaar API 키:
+koodous-api-key = Koodous(10/분) API 키:
 google-play-renderer = Google Play 렌더러
 google-play-renderer-desc = (Google Play 스토어에서 앱 정보 가져오기 및 표시)
 fdroid-renderer = F-Droid 렌더러
@@ -184,6 +477,20 @@ apkmirror-name = APKMirror 이름:
 name-hint = 이름 (선택 사항)
 apkmirror-auto-upload = APKMirror 자동 업로드
 apkmirror-auto-upload-desc = (장치 버전이 APKMirror보다 최신인 경우 APK 자동 업로드)
+uad-lists-source = UAD-NG 목록:
+stalkerware-ioc-source = 스토커웨어 IoC:
+tracker-signatures-source = 트래커 시그니처:
+dataset-source-cache = 다운로드됨 { $date }
+dataset-source-embedded = 내장 스냅샷 { $date }
+dataset-source-none = 로드되지 않음
+bundled-snapshot-date = (내장 스냅샷 날짜: { $date })
+import-dataset-path = 파일 가져오기:
+import-dataset-path-hint = /경로/uad_lists.json 또는 ioc.yaml
+import-uad-lists = UAD-NG 목록 가져오기
+import-stalkerware-ioc = 스토커웨어 IoC 가져오기
+import-tracker-signatures = 트래커 시그니처 가져오기
+import-dataset-success = { $count }개 항목을 가져왔습니다
+import-dataset-failed = 가져오기 실패: { $error }
 invalidate-cache = 404 캐시 무효화
 invalidate-cache-desc = (캐시된 앱 및 검사 결과 지우기)
 show-logs = 로그 표시:
@@ -196,6 +503,7 @@ flush = 초기화
 status-checking-api = API 확인 중
 status-pulling-file = 파일 가져오는 중
 status-uploading = 업로드 중
+status-rate-limit = 요청 제한 대기 ({ $secs }초)
 error-invalid-package-id = 유효하지 않은 패키지 ID (도메인 수준 2 미만)
 error-timeout-rate-limit = 시간 제한 비율 도달
 error-email-not-configured = 이메일이 설정되지 않음
@@ -211,6 +519,8 @@ batch-disable = 일괄 비활성화
 disabling-packages = 패키지 비활성화 중...
 batch-enable = 일괄 활성화
 enabling-packages = 패키지 활성화 중...
+certificate-scan = 인증서 검사:
+checking-certificates = 서명 인증서 검사 중...
 scan-description = VirusTotal 및 HybridAnalysis로 바이러스 검사:
 set-api-keys = 설정에서 VirusTotal 혹은 HybridAnalysis API 키를 설정해주세요.
 apps-description = FOSS 애플리케이션 목록 :
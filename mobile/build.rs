@@ -3,9 +3,20 @@
 extern crate winres;
 
 fn main() {
+  // Embedded UAD-NG lists / stalkerware IoC / tracker signature snapshot (refreshed by update_snapshots.sh)
+  println!("cargo:rerun-if-changed=resources/snapshots/uad_lists.json");
+  println!("cargo:rerun-if-changed=resources/snapshots/stalkerware_ioc.yaml");
+  println!("cargo:rerun-if-changed=resources/snapshots/exodus_trackers.json");
+  println!("cargo:rerun-if-changed=resources/snapshots/snapshot_date.txt");
+  let snapshot_date = std::fs::read_to_string("resources/snapshots/snapshot_date.txt").unwrap_or_default();
+  println!("cargo:rustc-env=UAD_SNAPSHOT_DATE={}", snapshot_date.trim());
+  if snapshot_date.trim().is_empty() {
+    println!("cargo:warning=Embedded snapshots are placeholders; run update_snapshots.sh for an offline fallback");
+  }
+
   if cfg!(target_os = "windows") {
     let mut res = winres::WindowsResource::new();
     res.set_icon("resources/logo.ico");
     res.compile().unwrap();
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,5 @@
+-- Drop uad lists history tables
+DROP INDEX IF EXISTS idx_uad_package_actions_device;
+DROP TABLE IF EXISTS uad_package_actions;
+DROP INDEX IF EXISTS idx_uad_lists_versions_fetched_at;
+DROP TABLE IF EXISTS uad_lists_versions;
@@ -0,0 +1,23 @@
+-- Create uad_lists_versions table (one row per distinct downloaded uad_lists.json)
+CREATE TABLE uad_lists_versions (
+    id INTEGER PRIMARY KEY NOT NULL,
+    content_md5 TEXT NOT NULL UNIQUE,
+    app_count INTEGER NOT NULL,
+    raw_json TEXT NOT NULL,
+    fetched_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_uad_lists_versions_fetched_at ON uad_lists_versions(fetched_at);
+
+-- Create uad_package_actions table (last debloat action per package and device)
+CREATE TABLE uad_package_actions (
+    id INTEGER PRIMARY KEY NOT NULL,
+    pkg_id TEXT NOT NULL,
+    device_serial TEXT NOT NULL,
+    action TEXT NOT NULL,
+    removal TEXT NOT NULL,
+    acted_at INTEGER NOT NULL,
+    UNIQUE(pkg_id, device_serial)
+);
+
+CREATE INDEX idx_uad_package_actions_device ON uad_package_actions(device_serial);
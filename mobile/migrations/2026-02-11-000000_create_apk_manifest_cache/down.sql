@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_apk_manifest_cache_pkg_id;
+DROP TABLE IF EXISTS apk_manifest_cache;
@@ -0,0 +1,10 @@
+-- Create apk_manifest_cache table (parsed AndroidManifest.xml per APK sha256)
+CREATE TABLE apk_manifest_cache (
+    id INTEGER PRIMARY KEY NOT NULL,
+    apk_sha256sum TEXT NOT NULL UNIQUE,
+    pkg_id TEXT NOT NULL,
+    manifest_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_apk_manifest_cache_pkg_id ON apk_manifest_cache(pkg_id);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_apk_tracker_cache_pkg_id;
+DROP TABLE IF EXISTS apk_tracker_cache;
@@ -0,0 +1,11 @@
+-- Create apk_tracker_cache table (DEX tracker scan result per APK sha256)
+CREATE TABLE apk_tracker_cache (
+    id INTEGER PRIMARY KEY NOT NULL,
+    apk_sha256sum TEXT NOT NULL UNIQUE,
+    signatures_md5 TEXT NOT NULL,
+    pkg_id TEXT NOT NULL,
+    trackers_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_apk_tracker_cache_pkg_id ON apk_tracker_cache(pkg_id);
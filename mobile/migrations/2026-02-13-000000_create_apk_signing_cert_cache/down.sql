@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_apk_signing_cert_cache_signature_hashes;
+DROP TABLE IF EXISTS apk_signing_cert_cache;
@@ -0,0 +1,11 @@
+-- Create apk_signing_cert_cache table (signing certificates per APK sha256)
+CREATE TABLE apk_signing_cert_cache (
+    id INTEGER PRIMARY KEY NOT NULL,
+    apk_sha256sum TEXT NOT NULL UNIQUE,
+    pkg_id TEXT NOT NULL,
+    signature_hashes TEXT NOT NULL,
+    certificates_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_apk_signing_cert_cache_signature_hashes ON apk_signing_cert_cache(signature_hashes);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS malwarebazaar_results;
@@ -0,0 +1,16 @@
+-- Create malwarebazaar_results table (abuse.ch MalwareBazaar hash lookups)
+CREATE TABLE malwarebazaar_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    package_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    sha256 TEXT NOT NULL,
+    verdict TEXT NOT NULL,
+    signature TEXT,
+    file_type TEXT,
+    first_seen TEXT,
+    tags TEXT NOT NULL,
+    raw_response TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    UNIQUE(package_name, file_path, sha256)
+);
@@ -0,0 +1,18 @@
+-- Create koodous_results table (Koodous APK hash lookups)
+CREATE TABLE koodous_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    package_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    sha256 TEXT NOT NULL,
+    verdict TEXT NOT NULL,
+    app TEXT,
+    company TEXT,
+    rating INTEGER,
+    detected BOOLEAN NOT NULL DEFAULT FALSE,
+    trusted BOOLEAN NOT NULL DEFAULT FALSE,
+    tags TEXT NOT NULL,
+    raw_response TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    UNIQUE(package_name, file_path, sha256)
+);
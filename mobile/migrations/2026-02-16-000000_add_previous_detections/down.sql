@@ -0,0 +1,4 @@
+ALTER TABLE hybridanalysis_results DROP COLUMN previous_threat_score;
+ALTER TABLE hybridanalysis_results DROP COLUMN previous_verdict;
+ALTER TABLE virustotal_results DROP COLUMN previous_suspicious;
+ALTER TABLE virustotal_results DROP COLUMN previous_malicious;
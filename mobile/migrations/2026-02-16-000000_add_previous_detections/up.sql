@@ -0,0 +1,5 @@
+-- Detection results before the latest re-query, to highlight changed results
+ALTER TABLE virustotal_results ADD COLUMN previous_malicious INTEGER;
+ALTER TABLE virustotal_results ADD COLUMN previous_suspicious INTEGER;
+ALTER TABLE hybridanalysis_results ADD COLUMN previous_verdict TEXT;
+ALTER TABLE hybridanalysis_results ADD COLUMN previous_threat_score INTEGER;
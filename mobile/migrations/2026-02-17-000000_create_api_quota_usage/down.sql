@@ -0,0 +1,2 @@
+-- Drop api_quota_usage table
+DROP TABLE IF EXISTS api_quota_usage;
@@ -0,0 +1,8 @@
+-- Create api_quota_usage table (requests made per provider and UTC day, for daily and monthly API budgets)
+CREATE TABLE api_quota_usage (
+    id INTEGER PRIMARY KEY NOT NULL,
+    provider TEXT NOT NULL,
+    day TEXT NOT NULL,
+    requests INTEGER NOT NULL,
+    UNIQUE(provider, day)
+);
@@ -0,0 +1,2 @@
+-- Drop hash_allowlist table
+DROP TABLE IF EXISTS hash_allowlist;
@@ -0,0 +1,7 @@
+-- Create hash_allowlist table (sha256 hashes known to be clean; VirusTotal and Hybrid Analysis skip them)
+CREATE TABLE hash_allowlist (
+    sha256 TEXT PRIMARY KEY NOT NULL,
+    label TEXT,
+    source TEXT NOT NULL,
+    added_at INTEGER NOT NULL
+);
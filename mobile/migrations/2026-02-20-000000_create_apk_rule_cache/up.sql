@@ -0,0 +1,11 @@
+-- Create apk_rule_cache table (local scan rule matches per APK sha256)
+CREATE TABLE apk_rule_cache (
+    id INTEGER PRIMARY KEY NOT NULL,
+    apk_sha256sum TEXT NOT NULL UNIQUE,
+    rules_md5 TEXT NOT NULL,
+    pkg_id TEXT NOT NULL,
+    matches_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_apk_rule_cache_pkg_id ON apk_rule_cache(pkg_id);
@@ -0,0 +1,11 @@
+-- Create apk_native_lib_cache table (native library inventory per APK sha256)
+CREATE TABLE apk_native_lib_cache (
+    id INTEGER PRIMARY KEY NOT NULL,
+    apk_sha256sum TEXT NOT NULL UNIQUE,
+    inventory_version INTEGER NOT NULL,
+    pkg_id TEXT NOT NULL,
+    libs_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_apk_native_lib_cache_pkg_id ON apk_native_lib_cache(pkg_id);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_permission_journal_device;
+DROP TABLE IF EXISTS permission_journal;
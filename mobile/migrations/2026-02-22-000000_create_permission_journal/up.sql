@@ -0,0 +1,14 @@
+-- Create permission_journal table (runtime permission grants/revokes made from the app, so they can be reverted)
+CREATE TABLE permission_journal (
+    id INTEGER PRIMARY KEY NOT NULL,
+    device_serial TEXT NOT NULL,
+    user_id INTEGER NOT NULL,
+    pkg_id TEXT NOT NULL,
+    permission TEXT NOT NULL,
+    granted BOOLEAN NOT NULL,
+    previous_granted BOOLEAN NOT NULL,
+    changed_at INTEGER NOT NULL,
+    reverted_at INTEGER
+);
+
+CREATE INDEX idx_permission_journal_device ON permission_journal(device_serial, changed_at);
@@ -304,6 +304,17 @@ pub fn get_all_packages_fingerprints(device: &str) -> std::io::Result<Vec<Packag
     );
 
     // Step 2: Filter lines in Rust (equivalent to grep -e 'Pattern1' -e 'Pattern2' ...)
+    let fingerprints = filter_fingerprint_lines(&fingerprints_text);
+
+    debug!("Filtered to {} relevant lines", fingerprints.len());
+    let parsed_fingerprints = parse_package_fingerprints(fingerprints);
+    debug!("Parsed {} package fingerprints", parsed_fingerprints.len());
+    Ok(parsed_fingerprints)
+}
+
+/// Keep the `dumpsys package packages` lines that `parse_package_fingerprints` and the
+/// readers of `PackageFingerprint::dumpText` use
+pub fn filter_fingerprint_lines(fingerprints_text: &str) -> Vec<String> {
     // Patterns to match:
     // - 'Package ' - package header lines
     // - 'Path=' - codePath lines (note: original used 'Path=' but code expects 'codePath=')
@@ -316,7 +327,11 @@ pub fn get_all_packages_fingerprints(device: &str) -> std::io::Result<Vec<Packag
     // - 'privateFlags=' - private flags
     // - 'permissions:' - permissions section headers
     // - 'permission.' - individual permission lines
-    let fingerprints: Vec<String> = fingerprints_text
+    // - 'signatures=' - signing certificate hashes
+    // - 'installerPackageName=', 'initiatingPackageName=', 'originatingPackageName=',
+    //   'packageSource=' - install provenance
+    // - 'primaryCpuAbi=' - ABI of the native libraries the package runs with
+    fingerprints_text
         .lines()
         .filter(|line| {
             let trimmed = line.trim();
@@ -335,14 +350,15 @@ pub fn get_all_packages_fingerprints(device: &str) -> std::io::Result<Vec<Packag
                 || line.contains("privateFlags=")
                 || line.contains("permissions:")
                 || line.contains("permission.")
+                || line.contains("signatures=")
+                || line.contains("installerPackageName=")
+                || line.contains("initiatingPackageName=")
+                || line.contains("originatingPackageName=")
+                || line.contains("packageSource=")
+                || line.contains("primaryCpuAbi=")
         })
         .map(|line| line.to_string())
-        .collect();
-
-    debug!("Filtered to {} relevant lines", fingerprints.len());
-    let parsed_fingerprints = parse_package_fingerprints(fingerprints);
-    debug!("Parsed {} package fingerprints", parsed_fingerprints.len());
-    Ok(parsed_fingerprints)
+        .collect()
 }
 
 /// Parse package fingerprints from dumpsys output
@@ -1251,6 +1267,62 @@ pub fn enable_app(package_name: &str, device: &str) -> std::io::Result<String> {
     shell_exec(device, &format!("pm enable {}", package_name))
 }
 
+/// Grant a runtime permission (e.g. "android.permission.CAMERA") to a package for one user
+pub fn grant_runtime_permission(
+    device: &str,
+    package_name: &str,
+    permission: &str,
+    user_id: i32,
+) -> std::io::Result<String> {
+    debug!("Granting {} to {} (user {}) on device: {}", permission, package_name, user_id, device);
+    shell_exec(device, &format!("pm grant --user {} {} {}", user_id, package_name, permission))
+}
+
+/// Revoke a runtime permission from a package for one user
+pub fn revoke_runtime_permission(
+    device: &str,
+    package_name: &str,
+    permission: &str,
+    user_id: i32,
+) -> std::io::Result<String> {
+    debug!("Revoking {} from {} (user {}) on device: {}", permission, package_name, user_id, device);
+    shell_exec(device, &format!("pm revoke --user {} {} {}", user_id, package_name, permission))
+}
+
+/// Get raw `dumpsys package` output for a single package
+pub fn dump_package(device: &str, package_name: &str) -> std::io::Result<String> {
+    debug!("Dumping package {} on device: {}", package_name, device);
+    shell_exec(device, &format!("dumpsys package {}", package_name))
+}
+
+/// Disable a single activity, service, receiver or provider ("com.example.Tracker") for one user
+pub fn disable_component(
+    device: &str,
+    package_name: &str,
+    class_name: &str,
+    user_id: i32,
+) -> std::io::Result<String> {
+    debug!("Disabling {}/{} (user {}) on device: {}", package_name, class_name, user_id, device);
+    shell_exec(device, &component_state_command("disable", package_name, class_name, user_id))
+}
+
+/// Re-enable a component disabled with `disable_component`
+pub fn enable_component(
+    device: &str,
+    package_name: &str,
+    class_name: &str,
+    user_id: i32,
+) -> std::io::Result<String> {
+    debug!("Enabling {}/{} (user {}) on device: {}", package_name, class_name, user_id, device);
+    shell_exec(device, &component_state_command("enable", package_name, class_name, user_id))
+}
+
+/// `pm enable`/`pm disable` command for a component. The component is quoted because the
+/// device shell would expand the `$` of inner classes (e.g. `ConstraintProxy$BatteryChargingProxy`).
+fn component_state_command(verb: &str, package_name: &str, class_name: &str, user_id: i32) -> String {
+    format!("pm {} --user {} '{}/{}'", verb, user_id, package_name, class_name)
+}
+
 pub fn pull_file_to_temp(
     device_serial: &str,
     file_path: &str,
@@ -1400,6 +1472,23 @@ pub fn usagestats_history(device: &str) -> std::io::Result<String> {
     shell_exec(device, "dumpsys usagestats -history")
 }
 
+/// Get the device's current UTC offset ("+0900")
+pub fn get_utc_offset(device: &str) -> std::io::Result<String> {
+    Ok(shell_exec(device, "date +%z")?.trim().to_string())
+}
+
+/// Get battery statistics since the last full charge
+pub fn batterystats_charged(device: &str) -> std::io::Result<String> {
+    debug!("Getting battery stats since last charge on device: {}", device);
+    shell_exec(device, "dumpsys batterystats --charged")
+}
+
+/// List packages with their uid ("package:com.example uid:10123" per line)
+pub fn get_package_uids(device: &str) -> std::io::Result<String> {
+    debug!("Listing package uids on device: {}", device);
+    shell_exec(device, "pm list packages -U")
+}
+
 #[allow(dead_code)]
 pub fn extract_apk(device: &str, user_id: Option<&str>) -> std::io::Result<String> {
     let user = user_id.unwrap_or("0");
@@ -1466,6 +1555,112 @@ pub fn get_cpu_abi_list(device: &str) -> std::io::Result<Vec<String>> {
     Ok(abis)
 }
 
+/// Read a `settings secure` value (e.g. "enabled_accessibility_services")
+pub fn get_secure_setting(device: &str, key: &str) -> std::io::Result<String> {
+    debug!("Reading secure setting {} on device: {}", key, device);
+    shell_exec(device, &format!("settings get secure {}", key))
+}
+
+/// Write a `settings secure` value; an empty value deletes the key
+pub fn put_secure_setting(device: &str, key: &str, value: &str) -> std::io::Result<String> {
+    debug!("Writing secure setting {} on device: {}", key, device);
+    if value.is_empty() {
+        shell_exec(device, &format!("settings delete secure {}", key))
+    } else {
+        shell_exec(device, &format!("settings put secure {} '{}'", key, value))
+    }
+}
+
+/// List packages whose app op is set to the given mode, one package per line
+pub fn query_app_op_packages(device: &str, op: &str, mode: &str) -> std::io::Result<String> {
+    debug!("Querying app op {} ({}) on device: {}", op, mode, device);
+    shell_exec(device, &format!("cmd appops query-op {} {}", op, mode))
+}
+
+/// Get raw `cmd appops get` output for a package (modes and last access times of its ops)
+pub fn get_app_ops(device: &str, package_name: &str) -> std::io::Result<String> {
+    debug!("Getting app ops of {} on device: {}", package_name, device);
+    shell_exec(device, &format!("cmd appops get {}", package_name))
+}
+
+/// Set an app op mode (allow, ignore, deny, default) for a package
+pub fn set_app_op(device: &str, package_name: &str, op: &str, mode: &str) -> std::io::Result<String> {
+    debug!("Setting app op {} to {} for {} on device: {}", op, mode, package_name, device);
+    shell_exec(device, &format!("cmd appops set {} {} {}", package_name, op, mode))
+}
+
+/// Set an app op uid mode for the uid of a package (overrides its package mode)
+pub fn set_uid_app_op(device: &str, package_name: &str, op: &str, mode: &str) -> std::io::Result<String> {
+    debug!("Setting uid app op {} to {} for {} on device: {}", op, mode, package_name, device);
+    shell_exec(device, &format!("cmd appops set --uid {} {} {}", package_name, op, mode))
+}
+
+/// List the app standby bucket of every package ("com.example: 10" per line)
+pub fn get_standby_buckets(device: &str) -> std::io::Result<String> {
+    debug!("Getting standby buckets on device: {}", device);
+    shell_exec(device, "am get-standby-bucket")
+}
+
+/// Put a package into an app standby bucket (active, working_set, frequent, rare, restricted)
+pub fn set_standby_bucket(device: &str, package_name: &str, bucket: &str) -> std::io::Result<String> {
+    debug!("Setting standby bucket of {} to {} on device: {}", package_name, bucket, device);
+    shell_exec(device, &format!("am set-standby-bucket {} {}", package_name, bucket))
+}
+
+/// List the battery optimization whitelist ("user,com.example,10123" per line)
+pub fn get_deviceidle_whitelist(device: &str) -> std::io::Result<String> {
+    debug!("Getting device idle whitelist on device: {}", device);
+    shell_exec(device, "cmd deviceidle whitelist")
+}
+
+/// Add a package to (`add` true) or remove it from the battery optimization whitelist
+pub fn set_deviceidle_whitelist(device: &str, package_name: &str, add: bool) -> std::io::Result<String> {
+    let entry = format!("{}{}", if add { '+' } else { '-' }, package_name);
+    debug!("Updating device idle whitelist ({}) on device: {}", entry, device);
+    shell_exec(device, &format!("cmd deviceidle whitelist {}", entry))
+}
+
+/// List holders of a role (e.g. "android.app.role.SMS"), separated by `;`
+pub fn get_role_holders(device: &str, role: &str) -> std::io::Result<String> {
+    debug!("Getting holders of role {} on device: {}", role, device);
+    shell_exec(device, &format!("cmd role get-role-holders {}", role))
+}
+
+pub fn remove_role_holder(device: &str, role: &str, package_name: &str) -> std::io::Result<String> {
+    shell_exec(device, &format!("cmd role remove-role-holder {} {}", role, package_name))
+}
+
+/// Revoke notification access from a listener component (`pkg/class`)
+pub fn disallow_notification_listener(device: &str, component: &str) -> std::io::Result<String> {
+    shell_exec(device, &format!("cmd notification disallow_listener '{}'", component))
+}
+
+/// Deactivate a device admin component (`pkg/class`)
+pub fn remove_active_admin(device: &str, component: &str) -> std::io::Result<String> {
+    shell_exec(device, &format!("dpm remove-active-admin '{}'", component))
+}
+
+/// Get raw `dumpsys device_policy` output (lists active device admins)
+pub fn get_device_policy_dump(device: &str) -> std::io::Result<String> {
+    debug!("Getting device policy dump for device: {}", device);
+    shell_exec(device, "dumpsys device_policy")
+}
+
+/// Read a system property (e.g. "ro.build.version.release")
+pub fn get_prop(device: &str, key: &str) -> std::io::Result<String> {
+    debug!("Reading property {} on device: {}", key, device);
+    Ok(shell_exec(device, &format!("getprop {}", key))?.trim().to_string())
+}
+
+/// List activities that appear in the launcher, one component per line
+pub fn query_launcher_activities(device: &str) -> std::io::Result<String> {
+    debug!("Querying launcher activities for device: {}", device);
+    shell_exec(
+        device,
+        "cmd package query-activities --brief -a android.intent.action.MAIN -c android.intent.category.LAUNCHER",
+    )
+}
+
 // adb
 // Mac https://dl.google.com/android/repository/platform-tools-latest-darwin.zip
 // Linux https://dl.google.com/android/repository/platform-tools-latest-linux.zip
@@ -2136,6 +2331,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_component_state_command_quotes_inner_classes() {
+        let command = component_state_command(
+            "disable",
+            "com.example.app",
+            "androidx.work.impl.background.systemalarm.ConstraintProxy$BatteryChargingProxy",
+            0,
+        );
+        assert_eq!(
+            command,
+            "pm disable --user 0 'com.example.app/androidx.work.impl.background.systemalarm.ConstraintProxy$BatteryChargingProxy'"
+        );
+    }
+
     #[test]
     #[ignore] // This test requires actual adb device connection
     fn test_pull_file_to_temp_velvet_apk() {
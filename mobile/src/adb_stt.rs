@@ -5,7 +5,7 @@ pub struct UserInfo {
 }
 
 #[allow(non_snake_case)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AdbPackageInfoUser {
     pub userId: i32,
     pub ceDataInode: i64,
@@ -154,7 +154,7 @@ pub struct ApkInfo {
 }
 
 #[allow(non_snake_case)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PackageFingerprint {
     pub pkg: String,
     pub codePath: String,
@@ -168,3 +168,30 @@ pub struct PackageFingerprint {
     pub pkgChecksum: String,
     pub dumpText: String,
 }
+
+/// Test fixtures shared by the calc_* test modules
+#[cfg(test)]
+impl PackageFingerprint {
+    /// Package with only its id, flags and `dumpsys package` text set
+    pub fn fixture(pkg: &str, flags: &str, dump_text: &str) -> Self {
+        Self {
+            pkg: pkg.to_string(),
+            flags: flags.to_string(),
+            dumpText: dump_text.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl AdbPackageInfoUser {
+    /// Installed user with the given runtime permission lines ("android.permission.X: granted=true")
+    pub fn fixture(user_id: i32, runtime_permissions: &[&str]) -> Self {
+        Self {
+            userId: user_id,
+            installed: true,
+            runtimePermissions: runtime_permissions.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+}
@@ -275,7 +275,7 @@ pub fn ha_submit_file(
     // Create multipart form using ureq_multipart
     log::debug!("Building multipart form for file: {:?}", file_path);
     let (content_type, body) = ureq_multipart::MultipartBuilder::new()
-        .add_text("environment_id", "200")? // 200 = Android Static Analysis
+        .add_text("environment_id", &ENVIRONMENT_ANDROID_STATIC.to_string())?
         .add_file("file", file_path)?
         .finish()?;
 
@@ -9,6 +9,11 @@ where
     Ok(opt.unwrap_or_default())
 }
 
+/// Sandbox environment APKs are submitted to. Android Static Analysis is the only
+/// Android environment: Hybrid Analysis has no public Android dynamic sandbox, and its
+/// Windows, Linux and Mac environments cannot run APKs (so there are no screenshots either).
+pub const ENVIRONMENT_ANDROID_STATIC: i32 = 200;
+
 /// Hybrid Analysis API response for hash search
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HybridAnalysisHashResponse {
@@ -36,7 +41,7 @@ pub struct HybridAnalysisReportInfo {
 }
 
 /// Hybrid Analysis API response for report summary
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HybridAnalysisReportResponse {
     #[serde(default)]
     pub classification_tags: Vec<String>,
@@ -80,6 +85,70 @@ pub struct HybridAnalysisReportResponse {
     pub total_processes: Option<i32>,
     #[serde(default)]
     pub total_signatures: Option<i32>,
+    #[serde(default)]
+    pub signatures: Vec<HybridAnalysisSignature>,
+    #[serde(default)]
+    pub mitre_attcks: Vec<HybridAnalysisMitreAttack>,
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub extracted_files: Vec<HybridAnalysisExtractedFile>,
+}
+
+/// Behavior signature matched during analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridAnalysisSignature {
+    #[serde(default, deserialize_with = "deserialize_null_string")]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 0 = informative, 1 = suspicious, 2 = malicious
+    #[serde(default)]
+    pub threat_level: Option<i32>,
+    #[serde(default)]
+    pub threat_level_human: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub attck_id: Option<String>,
+    #[serde(default)]
+    pub attck_id_wiki: Option<String>,
+}
+
+/// MITRE ATT&CK technique mapped from the matched signatures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridAnalysisMitreAttack {
+    #[serde(default)]
+    pub tactic: Option<String>,
+    #[serde(default)]
+    pub technique: Option<String>,
+    #[serde(default)]
+    pub attck_id: Option<String>,
+    #[serde(default)]
+    pub attck_id_wiki: Option<String>,
+    #[serde(default)]
+    pub malicious_identifiers_count: Option<i32>,
+    #[serde(default)]
+    pub suspicious_identifiers_count: Option<i32>,
+}
+
+/// File dropped or extracted during analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridAnalysisExtractedFile {
+    #[serde(default, deserialize_with = "deserialize_null_string")]
+    pub name: String,
+    #[serde(default)]
+    pub file_path: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<i64>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub type_tags: Vec<String>,
+    #[serde(default)]
+    pub threat_level_readable: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
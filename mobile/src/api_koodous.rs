@@ -0,0 +1,76 @@
+pub use crate::api_koodous_stt::*;
+use std::error::Error;
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Koodous API base URL
+pub const API_URL: &str = "https://developer.koodous.com";
+
+/// Error types for Koodous API
+#[derive(Debug)]
+pub enum KoodousError {
+    NotFound,
+    RateLimit { retry_after: u64 },
+    HttpError(Box<dyn Error>),
+    ParseError(Box<dyn Error>),
+}
+
+impl std::fmt::Display for KoodousError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KoodousError::NotFound => write!(f, "APK not found in Koodous"),
+            KoodousError::RateLimit { retry_after } => write!(
+                f,
+                "Rate limit exceeded, retry after {} seconds",
+                retry_after
+            ),
+            KoodousError::HttpError(e) => write!(f, "HTTP error: {}", e),
+            KoodousError::ParseError(e) => write!(f, "Parse error: {}", e),
+        }
+    }
+}
+
+impl Error for KoodousError {}
+
+/// Look up an APK by SHA256 on Koodous (blocking)
+pub fn get_apk(sha256: &str, api_key: &str) -> Result<KoodousApk, KoodousError> {
+    get_apk_at(API_URL, sha256, api_key)
+}
+
+/// Look up an APK by SHA256 against a Koodous-compatible base URL (blocking)
+pub fn get_apk_at(base_url: &str, sha256: &str, api_key: &str) -> Result<KoodousApk, KoodousError> {
+    let url = format!("{}/apks/{}/", base_url.trim_end_matches('/'), sha256);
+
+    let response = ureq::get(&url)
+        .timeout(std::time::Duration::from_secs(60))
+        .set("accept", "application/json")
+        .set("Authorization", &format!("Token {}", api_key))
+        .set("User-Agent", USER_AGENT)
+        .call();
+
+    match response {
+        Ok(resp) => {
+            log::trace!("Koodous get apk HTTP response status: {}", resp.status());
+            let response_text = resp
+                .into_string()
+                .map_err(|e| KoodousError::HttpError(Box::new(e)))?;
+            log::trace!("Koodous get apk HTTP response body: {}", response_text);
+            serde_json::from_str(&response_text).map_err(|e| KoodousError::ParseError(Box::new(e)))
+        }
+        Err(ureq::Error::Status(code, resp)) => {
+            log::trace!("Koodous get apk HTTP error status: {}", code);
+            if code == 404 {
+                Err(KoodousError::NotFound)
+            } else if code == 429 {
+                let retry_after = resp
+                    .header("retry-after")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60);
+                Err(KoodousError::RateLimit { retry_after })
+            } else {
+                Err(KoodousError::HttpError(format!("Koodous HTTP {}", code).into()))
+            }
+        }
+        Err(e) => Err(KoodousError::HttpError(Box::new(e))),
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Koodous API response for an APK
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KoodousApk {
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(default)]
+    pub app: Option<String>,
+    #[serde(default)]
+    pub package_name: Option<String>,
+    #[serde(default)]
+    pub company: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub size: Option<i64>,
+    /// Community rating, negative when voted malicious
+    #[serde(default)]
+    pub rating: Option<i32>,
+    /// Matched by a Koodous detection rule
+    #[serde(default)]
+    pub detected: bool,
+    /// Signed by a trusted certificate
+    #[serde(default)]
+    pub trusted: bool,
+    #[serde(default)]
+    pub corrupted: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
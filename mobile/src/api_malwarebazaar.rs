@@ -0,0 +1,92 @@
+pub use crate::api_malwarebazaar_stt::*;
+use std::error::Error;
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// MalwareBazaar API endpoint
+pub const API_URL: &str = "https://mb-api.abuse.ch/api/v1/";
+
+/// Error types for MalwareBazaar API
+#[derive(Debug)]
+pub enum MbError {
+    NotFound,
+    RateLimit { retry_after: u64 },
+    /// `query_status` other than "ok" or "hash_not_found"
+    ApiError(String),
+    HttpError(Box<dyn Error>),
+    ParseError(Box<dyn Error>),
+}
+
+impl std::fmt::Display for MbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MbError::NotFound => write!(f, "Hash not found in MalwareBazaar"),
+            MbError::RateLimit { retry_after } => write!(
+                f,
+                "Rate limit exceeded, retry after {} seconds",
+                retry_after
+            ),
+            MbError::ApiError(status) => write!(f, "MalwareBazaar error: {}", status),
+            MbError::HttpError(e) => write!(f, "HTTP error: {}", e),
+            MbError::ParseError(e) => write!(f, "Parse error: {}", e),
+        }
+    }
+}
+
+impl Error for MbError {}
+
+/// Look up a file hash on MalwareBazaar (blocking)
+pub fn query_hash(sha256: &str, api_key: &str) -> Result<MalwareBazaarSample, MbError> {
+    query_hash_at(API_URL, sha256, api_key)
+}
+
+/// Look up a file hash against a MalwareBazaar-compatible endpoint (blocking)
+pub fn query_hash_at(
+    api_url: &str,
+    sha256: &str,
+    api_key: &str,
+) -> Result<MalwareBazaarSample, MbError> {
+    let response = ureq::post(api_url)
+        .timeout(std::time::Duration::from_secs(60))
+        .set("accept", "application/json")
+        .set("Auth-Key", api_key)
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("query", "get_info"), ("hash", sha256)]);
+
+    match response {
+        Ok(resp) => {
+            log::trace!("MalwareBazaar get_info HTTP response status: {}", resp.status());
+            let response_text = resp
+                .into_string()
+                .map_err(|e| MbError::HttpError(Box::new(e)))?;
+            log::trace!("MalwareBazaar get_info HTTP response body: {}", response_text);
+            let mb_response: MalwareBazaarResponse = serde_json::from_str(&response_text)
+                .map_err(|e| MbError::ParseError(Box::new(e)))?;
+
+            match mb_response.query_status.as_str() {
+                "ok" => mb_response
+                    .data
+                    .into_iter()
+                    .find(|s| s.sha256_hash.eq_ignore_ascii_case(sha256))
+                    .ok_or(MbError::NotFound),
+                "hash_not_found" => Err(MbError::NotFound),
+                status => Err(MbError::ApiError(status.to_string())),
+            }
+        }
+        Err(ureq::Error::Status(code, resp)) => {
+            log::trace!("MalwareBazaar get_info HTTP error status: {}", code);
+            if code == 429 {
+                let retry_after = resp
+                    .header("retry-after")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60);
+                Err(MbError::RateLimit { retry_after })
+            } else {
+                Err(MbError::HttpError(
+                    format!("MalwareBazaar HTTP {}", code).into(),
+                ))
+            }
+        }
+        Err(e) => Err(MbError::HttpError(Box::new(e))),
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// MalwareBazaar API response for `get_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MalwareBazaarResponse {
+    /// "ok", "hash_not_found", "illegal_hash", ...
+    pub query_status: String,
+    #[serde(default)]
+    pub data: Vec<MalwareBazaarSample>,
+}
+
+/// A sample listed on MalwareBazaar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MalwareBazaarSample {
+    pub sha256_hash: String,
+    #[serde(default)]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub file_type: Option<String>,
+    /// Malware family, e.g. "SpyNote"
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub first_seen: Option<String>,
+    #[serde(default)]
+    pub last_seen: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
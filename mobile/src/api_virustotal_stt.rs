@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// VirusTotal API response for file reports
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,7 +15,7 @@ pub struct VirusTotalData {
     pub attributes: VirusTotalAttributes,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VirusTotalAttributes {
     /// Last analysis date - may be missing if file hasn't been analyzed yet
     #[serde(default)]
@@ -28,6 +29,77 @@ pub struct VirusTotalAttributes {
     pub androguard: Option<AndroidGuard>,
     #[serde(default)]
     pub status: Option<String>,
+    /// Per-engine verdicts of the last analysis, keyed by engine name
+    #[serde(default)]
+    pub last_analysis_results: BTreeMap<String, EngineResult>,
+    #[serde(default)]
+    pub popular_threat_classification: Option<PopularThreatClassification>,
+    #[serde(default)]
+    pub crowdsourced_yara_results: Vec<YaraResult>,
+    #[serde(default)]
+    pub sigma_analysis_results: Vec<SigmaResult>,
+    #[serde(default)]
+    pub first_submission_date: Option<i64>,
+    /// File names the sample was submitted under
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineResult {
+    #[serde(default)]
+    pub engine_name: String,
+    /// "malicious", "suspicious", "undetected", "harmless", "timeout", "type-unsupported", ...
+    #[serde(default)]
+    pub category: String,
+    /// Detection label; null when the engine did not flag the file
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default)]
+    pub engine_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PopularThreatClassification {
+    #[serde(default)]
+    pub suggested_threat_label: Option<String>,
+    #[serde(default)]
+    pub popular_threat_category: Vec<ThreatCount>,
+    #[serde(default)]
+    pub popular_threat_name: Vec<ThreatCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatCount {
+    pub value: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YaraResult {
+    #[serde(default)]
+    pub rule_name: String,
+    #[serde(default)]
+    pub ruleset_name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigmaResult {
+    #[serde(default)]
+    pub rule_title: String,
+    /// "critical", "high", "medium" or "low"
+    #[serde(default)]
+    pub rule_level: String,
+    #[serde(default)]
+    pub rule_description: Option<String>,
+    #[serde(default)]
+    pub rule_source: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
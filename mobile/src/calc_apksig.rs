@@ -0,0 +1,565 @@
+pub use crate::calc_apksig_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::shared_store_stt::get_shared_store;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// APK Signing Block, see https://source.android.com/docs/security/features/apksigning/v2
+const APK_SIG_BLOCK_MAGIC: &[u8; 16] = b"APK Sig Block 42";
+const APK_SIGNATURE_SCHEME_V2_BLOCK_ID: u32 = 0x7109_871a;
+const APK_SIGNATURE_SCHEME_V3_BLOCK_ID: u32 = 0xf053_68c0;
+const APK_SIGNATURE_SCHEME_V31_BLOCK_ID: u32 = 0x1b93_ad61;
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const EOCD_MIN_SIZE: usize = 22;
+
+// DER tags used by PKCS#7 SignedData
+const DER_SEQUENCE: u8 = 0x30;
+const DER_CONTEXT_0: u8 = 0xa0;
+
+/// Normalize a certificate fingerprint ("AB:CD:..." or "abcd...") to lowercase hex
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Java `Arrays.hashCode(byte[])`, which `Signature.hashCode()` returns
+pub fn java_signature_hash(der: &[u8]) -> String {
+    let hash = der
+        .iter()
+        .fold(1i32, |acc, b| acc.wrapping_mul(31).wrapping_add(*b as i8 as i32));
+    format!("{:x}", hash as u32)
+}
+
+/// Signature hashes from the `signatures=PackageSignatures{...}` line of `dumpsys package`
+/// (past signing certificates are not included)
+pub fn parse_dumpsys_signature_hashes(dump_text: &str) -> Vec<String> {
+    let Some(line) = dump_text
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| l.starts_with("signatures=PackageSignatures{"))
+    else {
+        return Vec::new();
+    };
+    let Some(start) = line.find(" signatures:[") else {
+        return Vec::new();
+    };
+    let rest = &line[start + " signatures:[".len()..];
+    let Some(end) = rest.find(']') else {
+        return Vec::new();
+    };
+
+    let mut hashes: Vec<String> = rest[..end]
+        .split(',')
+        .map(|h| h.trim().to_ascii_lowercase())
+        .filter(|h| !h.is_empty() && h.chars().all(|c| c.is_ascii_hexdigit()))
+        .collect();
+    hashes.sort();
+    hashes.dedup();
+    hashes
+}
+
+fn der_read(data: &[u8], pos: usize) -> Result<(u8, usize, usize), String> {
+    let tag = *data.get(pos).ok_or("Unexpected end of DER data")?;
+    let first = *data.get(pos + 1).ok_or("Unexpected end of DER data")?;
+    let (len, header) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 {
+            return Err("Unsupported DER length".to_string());
+        }
+        let bytes = data.get(pos + 2..pos + 2 + count).ok_or("Unexpected end of DER data")?;
+        (bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize), 2 + count)
+    };
+    let start = pos + header;
+    let end = start.checked_add(len).filter(|e| *e <= data.len()).ok_or("DER length out of range")?;
+    Ok((tag, start, end))
+}
+
+/// DER certificates of a PKCS#7 SignedData blob (v1 META-INF/*.RSA, *.DSA, *.EC)
+pub fn pkcs7_certificates(data: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    // ContentInfo ::= SEQUENCE { contentType OID, content [0] EXPLICIT SignedData }
+    let (tag, start, end) = der_read(data, 0)?;
+    if tag != DER_SEQUENCE {
+        return Err("PKCS#7 ContentInfo is not a SEQUENCE".to_string());
+    }
+    let (_, _, oid_end) = der_read(data, start)?;
+    let (tag, explicit_start, _) = der_read(data, oid_end)?;
+    if tag != DER_CONTEXT_0 {
+        return Err("PKCS#7 content is missing".to_string());
+    }
+
+    // SignedData ::= SEQUENCE { version, digestAlgorithms, contentInfo, [0] certificates, ... }
+    let (tag, signed_start, signed_end) = der_read(data, explicit_start)?;
+    if tag != DER_SEQUENCE || signed_end > end {
+        return Err("PKCS#7 SignedData is not a SEQUENCE".to_string());
+    }
+    let mut pos = signed_start;
+    for _ in 0..3 {
+        let (_, _, field_end) = der_read(data, pos)?;
+        pos = field_end;
+    }
+    let (tag, certs_start, certs_end) = der_read(data, pos)?;
+    if tag != DER_CONTEXT_0 {
+        return Err("PKCS#7 SignedData has no certificates".to_string());
+    }
+
+    let mut certificates = Vec::new();
+    let mut pos = certs_start;
+    while pos < certs_end {
+        let (tag, _, cert_end) = der_read(data, pos)?;
+        if tag == DER_SEQUENCE {
+            certificates.push(data[pos..cert_end].to_vec());
+        }
+        pos = cert_end;
+    }
+    Ok(certificates)
+}
+
+/// Read a u32 length-prefixed slice and advance `pos`
+fn length_prefixed<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a [u8], String> {
+    // usize is 32-bit on armv7, so the offsets are checked
+    let start = pos.checked_add(4).ok_or("Truncated signing block")?;
+    let len_bytes = data.get(*pos..start).ok_or("Truncated signing block")?;
+    let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
+    let end = start.checked_add(len).ok_or("Truncated signing block entry")?;
+    let slice = data.get(start..end).ok_or("Truncated signing block entry")?;
+    *pos = end;
+    Ok(slice)
+}
+
+/// Certificates of a v2/v3 scheme block: signers -> signed data -> certificates
+fn scheme_block_certificates(block: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let mut certificates = Vec::new();
+    let mut pos = 0;
+    let signers = length_prefixed(block, &mut pos)?;
+
+    let mut signer_pos = 0;
+    while signer_pos < signers.len() {
+        let signer = length_prefixed(signers, &mut signer_pos)?;
+        let mut pos = 0;
+        let signed_data = length_prefixed(signer, &mut pos)?;
+
+        let mut pos = 0;
+        let _digests = length_prefixed(signed_data, &mut pos)?;
+        let certs = length_prefixed(signed_data, &mut pos)?;
+
+        let mut cert_pos = 0;
+        while cert_pos < certs.len() {
+            certificates.push(length_prefixed(certs, &mut cert_pos)?.to_vec());
+        }
+    }
+    Ok(certificates)
+}
+
+/// Certificates from the APK Signing Block (v2, v3 and v3.1 schemes)
+fn signing_block_certificates<R: Read + Seek>(reader: &mut R) -> Result<Vec<(SignatureScheme, Vec<u8>)>, String> {
+    let io_err = |e: std::io::Error| format!("Failed to read APK: {}", e);
+    let file_len = reader.seek(SeekFrom::End(0)).map_err(io_err)?;
+
+    // End of central directory: the last record, followed by a comment of up to 64 KiB
+    if file_len < EOCD_MIN_SIZE as u64 {
+        return Err("File too small to be an APK".to_string());
+    }
+    let tail_len = file_len.min((EOCD_MIN_SIZE + 0xffff) as u64);
+    reader.seek(SeekFrom::Start(file_len - tail_len)).map_err(io_err)?;
+    let mut tail = vec![0u8; tail_len as usize];
+    reader.read_exact(&mut tail).map_err(io_err)?;
+    let eocd = (0..=tail.len() - EOCD_MIN_SIZE)
+        .rev()
+        .find(|i| u32::from_le_bytes([tail[*i], tail[i + 1], tail[i + 2], tail[i + 3]]) == EOCD_SIGNATURE)
+        .ok_or("End of central directory not found")?;
+    let cd_offset = u32::from_le_bytes([tail[eocd + 16], tail[eocd + 17], tail[eocd + 18], tail[eocd + 19]]) as u64;
+
+    // Signing block footer: u64 size, 16-byte magic, right before the central directory
+    if cd_offset < 24 || cd_offset > file_len {
+        return Ok(Vec::new());
+    }
+    reader.seek(SeekFrom::Start(cd_offset - 24)).map_err(io_err)?;
+    let mut footer = [0u8; 24];
+    reader.read_exact(&mut footer).map_err(io_err)?;
+    if &footer[8..24] != APK_SIG_BLOCK_MAGIC {
+        return Ok(Vec::new());
+    }
+    // The size comes from the file; it must fit before the central directory, which
+    // also bounds the allocation below by the file length
+    let block_size = u64::from_le_bytes(footer[0..8].try_into().unwrap());
+    if block_size < 24 || block_size.checked_add(8).map_or(true, |end| end > cd_offset) {
+        return Err("Invalid APK Signing Block size".to_string());
+    }
+
+    // ID-value pairs between the leading size field and the footer
+    reader.seek(SeekFrom::Start(cd_offset - block_size)).map_err(io_err)?;
+    let mut pairs = vec![0u8; (block_size - 24) as usize];
+    reader.read_exact(&mut pairs).map_err(io_err)?;
+
+    let mut certificates = Vec::new();
+    let mut pos = 0;
+    while pos + 12 <= pairs.len() {
+        let len = u64::from_le_bytes(pairs[pos..pos + 8].try_into().unwrap()) as usize;
+        let value_start = pos + 12;
+        let value_end = (pos + 8).checked_add(len).filter(|e| *e <= pairs.len() && len >= 4)
+            .ok_or("Invalid APK Signing Block entry")?;
+        let id = u32::from_le_bytes(pairs[pos + 8..pos + 12].try_into().unwrap());
+        let scheme = match id {
+            APK_SIGNATURE_SCHEME_V2_BLOCK_ID => Some(SignatureScheme::V2),
+            APK_SIGNATURE_SCHEME_V3_BLOCK_ID | APK_SIGNATURE_SCHEME_V31_BLOCK_ID => Some(SignatureScheme::V3),
+            _ => None,
+        };
+        if let Some(scheme) = scheme {
+            for der in scheme_block_certificates(&pairs[value_start..value_end])? {
+                certificates.push((scheme, der));
+            }
+        }
+        pos = value_end;
+    }
+    Ok(certificates)
+}
+
+/// Merge DER certificates found in several schemes into fingerprinted certificates
+fn fingerprint_certificates(found: Vec<(SignatureScheme, Vec<u8>)>) -> Vec<SigningCertificate> {
+    let mut certificates: Vec<SigningCertificate> = Vec::new();
+    for (scheme, der) in found {
+        let sha256 = to_hex(&Sha256::digest(&der));
+        if let Some(existing) = certificates.iter_mut().find(|c| c.sha256 == sha256) {
+            if !existing.schemes.contains(&scheme) {
+                existing.schemes.push(scheme);
+            }
+            continue;
+        }
+        certificates.push(SigningCertificate {
+            schemes: vec![scheme],
+            sha1: to_hex(&Sha1::digest(&der)),
+            sha256,
+            java_hash: java_signature_hash(&der),
+        });
+    }
+    certificates
+}
+
+/// Extract the v1/v2/v3 signing certificates of an APK file
+pub fn extract_signing_certificates(apk_path: &Path) -> Result<Vec<SigningCertificate>, String> {
+    let mut file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+
+    let mut found = match signing_block_certificates(&mut file) {
+        Ok(found) => found,
+        Err(e) => {
+            log::warn!("Failed to read APK Signing Block of {:?}: {}", apk_path, e);
+            Vec::new()
+        }
+    };
+
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+    let signature_files: Vec<String> = archive
+        .file_names()
+        .filter(|n| {
+            let upper = n.to_ascii_uppercase();
+            upper.starts_with("META-INF/")
+                && !upper["META-INF/".len()..].contains('/')
+                && (upper.ends_with(".RSA") || upper.ends_with(".DSA") || upper.ends_with(".EC"))
+        })
+        .map(|n| n.to_string())
+        .collect();
+    for name in signature_files {
+        let mut data = Vec::new();
+        if let Err(e) = archive.by_name(&name).map_err(|e| e.to_string()).and_then(|mut entry| {
+            entry.read_to_end(&mut data).map_err(|e| e.to_string())
+        }) {
+            log::warn!("Failed to read {} in {:?}: {}", name, apk_path, e);
+            continue;
+        }
+        match pkcs7_certificates(&data) {
+            Ok(certs) => found.extend(certs.into_iter().map(|der| (SignatureScheme::V1, der))),
+            Err(e) => log::warn!("Failed to parse {} in {:?}: {}", name, apk_path, e),
+        }
+    }
+
+    if found.is_empty() {
+        return Err(format!("No signing certificates found in {:?}", apk_path));
+    }
+    Ok(fingerprint_certificates(found))
+}
+
+fn signature_hashes_key(certificates: &[SigningCertificate]) -> String {
+    let mut hashes: Vec<&str> = certificates.iter().map(|c| c.java_hash.as_str()).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes.join(" ")
+}
+
+fn parse_cached_certificates(certificates_json: &str) -> Option<Vec<SigningCertificate>> {
+    serde_json::from_str(certificates_json).ok()
+}
+
+/// Signing certificates of a package. Looked up in the cache by APK sha256sum, then by the
+/// dumpsys signature hashes (same signer as an APK seen before); the base APK is pulled
+/// only when `pull_missing` is set.
+fn certificates_for_package(
+    device_serial: &str,
+    package: &PackageFingerprint,
+    cached_apk_sha256sum: Option<&str>,
+    pull_missing: bool,
+    tmp_dir: &str,
+) -> Option<Vec<SigningCertificate>> {
+    if let Some(sha256) = cached_apk_sha256sum {
+        if let Some(cached) = crate::db_package_cache::get_cached_apk_signing_certs(sha256) {
+            return parse_cached_certificates(&cached.certificates_json);
+        }
+    }
+
+    let dumpsys_hashes = parse_dumpsys_signature_hashes(&package.dumpText);
+    if !dumpsys_hashes.is_empty() {
+        let key = dumpsys_hashes.join(" ");
+        if let Some(cached) = crate::db_package_cache::get_signing_certs_by_signature_hashes(&key) {
+            return parse_cached_certificates(&cached.certificates_json);
+        }
+    }
+
+    if !pull_missing {
+        return None;
+    }
+
+    let (apk_path, apk_sha256sum) = match crate::calc_axml::locate_base_apk(device_serial, &package.pkg) {
+        Ok(located) => located,
+        Err(e) => {
+            log::warn!("{}", e);
+            return None;
+        }
+    };
+    if let Some(cached) = crate::db_package_cache::get_cached_apk_signing_certs(&apk_sha256sum) {
+        return parse_cached_certificates(&cached.certificates_json);
+    }
+
+    let local_path = match crate::adb::pull_file_to_temp(device_serial, &apk_path, tmp_dir, &package.pkg) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("Failed to pull {}: {}", apk_path, e);
+            return None;
+        }
+    };
+    let result = extract_signing_certificates(Path::new(&local_path));
+    let _ = std::fs::remove_file(&local_path);
+    let certificates = match result {
+        Ok(certificates) => certificates,
+        Err(e) => {
+            log::warn!("Failed to extract certificates of {}: {}", package.pkg, e);
+            return None;
+        }
+    };
+
+    match serde_json::to_string(&certificates) {
+        Ok(json) => {
+            if let Err(e) = crate::db_package_cache::upsert_apk_signing_certs_cache(
+                &apk_sha256sum,
+                &package.pkg,
+                &signature_hashes_key(&certificates),
+                &json,
+            ) {
+                log::error!("Failed to cache signing certificates for {}: {}", package.pkg, e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize signing certificates for {}: {}", package.pkg, e),
+    }
+
+    Some(certificates)
+}
+
+/// Resolve signing certificates of packages in a background thread.
+/// With `pull_missing` false only cached certificates are loaded (no APK is pulled).
+pub fn run_certificate_scan_async(
+    installed_packages: Vec<PackageFingerprint>,
+    device_serial: String,
+    pull_missing: bool,
+    progress_clone: Arc<Mutex<Option<f32>>>,
+    cancelled_clone: Arc<Mutex<bool>>,
+) {
+    std::thread::spawn(move || {
+        let store = get_shared_store();
+        let tmp_dir = match crate::Config::new() {
+            Ok(config) => config.tmp_dir.to_string_lossy().to_string(),
+            Err(e) => {
+                log::error!("Config not available for certificate scan: {}", e);
+                if let Ok(mut p) = progress_clone.lock() {
+                    *p = None;
+                }
+                return;
+            }
+        };
+
+        let cached_sha256sums: HashMap<String, String> =
+            crate::db_package_cache::get_all_cached_packages(&device_serial)
+                .into_iter()
+                .filter_map(|cp| {
+                    // Only single-APK entries map directly to the base APK hash
+                    let sha256 = cp.apk_sha256sum?;
+                    (!sha256.contains(' ')).then_some((cp.pkg_id, sha256))
+                })
+                .collect();
+
+        let total = installed_packages.len();
+        for (i, package) in installed_packages.iter().enumerate() {
+            if let Ok(cancelled) = cancelled_clone.lock() {
+                if *cancelled {
+                    log::info!("Certificate scan cancelled by user");
+                    break;
+                }
+            }
+            if let Ok(mut p) = progress_clone.lock() {
+                *p = Some(i as f32 / total.max(1) as f32);
+            }
+
+            if let Some(certificates) = certificates_for_package(
+                &device_serial,
+                package,
+                cached_sha256sums.get(&package.pkg).map(|s| s.as_str()),
+                pull_missing,
+                &tmp_dir,
+            ) {
+                store.set_signing_certificates(package.pkg.clone(), certificates);
+            }
+        }
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = None;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// DER TLV with a short or long form length
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if content.len() < 0x80 {
+            out.push(content.len() as u8);
+        } else {
+            out.push(0x82);
+            out.extend_from_slice(&(content.len() as u16).to_be_bytes());
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    fn length_prefix(content: &[u8]) -> Vec<u8> {
+        let mut out = (content.len() as u32).to_le_bytes().to_vec();
+        out.extend_from_slice(content);
+        out
+    }
+
+    #[test]
+    fn test_parse_dumpsys_signature_hashes() {
+        let dump = "  Package [com.example] (1a2b3c):\n    signatures=PackageSignatures{3b2a6e5 version:2, signatures:[8F3C4A1E, 12ab], past signatures:[99ff flags: 17]}\n";
+        assert_eq!(parse_dumpsys_signature_hashes(dump), vec!["12ab".to_string(), "8f3c4a1e".to_string()]);
+        assert!(parse_dumpsys_signature_hashes("versionCode=1").is_empty());
+    }
+
+    #[test]
+    fn test_java_signature_hash() {
+        // Arrays.hashCode(new byte[] {1, -1}) == 31 * (31 * 1 + 1) - 1 == 991
+        assert_eq!(java_signature_hash(&[0x01, 0xff]), "3df");
+        assert_eq!(java_signature_hash(&[]), "1");
+    }
+
+    #[test]
+    fn test_pkcs7_certificates() {
+        let cert = der(DER_SEQUENCE, &der(DER_SEQUENCE, &[0x02, 0x01, 0x05]));
+        let signed_data = der(
+            DER_SEQUENCE,
+            &[
+                der(0x02, &[0x01]),
+                der(0x31, &[]),
+                der(DER_SEQUENCE, &der(0x06, &[0x2a, 0x86, 0x48])),
+                der(DER_CONTEXT_0, &cert),
+                der(0x31, &[]),
+            ]
+            .concat(),
+        );
+        let content_info = der(
+            DER_SEQUENCE,
+            &[der(0x06, &[0x2a, 0x86, 0x48, 0x86, 0xf7]), der(DER_CONTEXT_0, &signed_data)].concat(),
+        );
+
+        assert_eq!(pkcs7_certificates(&content_info).unwrap(), vec![cert]);
+        assert!(pkcs7_certificates(&[0x02, 0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_signing_block_certificates() {
+        let cert = der(DER_SEQUENCE, &[0x02, 0x01, 0x07]);
+        let signed_data = [length_prefix(&[]), length_prefix(&length_prefix(&cert)), length_prefix(&[])].concat();
+        let signer = [length_prefix(&signed_data), length_prefix(&[]), length_prefix(&[])].concat();
+        let v2_value = length_prefix(&length_prefix(&signer));
+
+        let mut pair = ((v2_value.len() + 4) as u64).to_le_bytes().to_vec();
+        pair.extend_from_slice(&APK_SIGNATURE_SCHEME_V2_BLOCK_ID.to_le_bytes());
+        pair.extend_from_slice(&v2_value);
+
+        let block_size = (pair.len() + 24) as u64;
+        let mut apk = b"local file data".to_vec();
+        apk.extend_from_slice(&block_size.to_le_bytes());
+        apk.extend_from_slice(&pair);
+        apk.extend_from_slice(&block_size.to_le_bytes());
+        apk.extend_from_slice(APK_SIG_BLOCK_MAGIC);
+        let cd_offset = apk.len() as u32;
+
+        let mut eocd = EOCD_SIGNATURE.to_le_bytes().to_vec();
+        eocd.extend_from_slice(&[0u8; 12]);
+        eocd.extend_from_slice(&cd_offset.to_le_bytes());
+        eocd.extend_from_slice(&[0u8; 2]);
+        apk.extend_from_slice(&eocd);
+
+        let found = signing_block_certificates(&mut std::io::Cursor::new(apk)).unwrap();
+        assert_eq!(found, vec![(SignatureScheme::V2, cert.clone())]);
+
+        let certificates = fingerprint_certificates(found);
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].sha1, to_hex(&Sha1::digest(&cert)));
+        assert_eq!(certificates[0].sha256.len(), 64);
+    }
+
+    #[test]
+    fn test_signing_block_hostile_sizes() {
+        // Footer whose block size wraps around when 8 is added
+        let hostile_apk = |block_size: u64| {
+            let mut apk = b"local file data".to_vec();
+            apk.extend_from_slice(&block_size.to_le_bytes());
+            apk.extend_from_slice(APK_SIG_BLOCK_MAGIC);
+            let cd_offset = apk.len() as u32;
+            apk.extend_from_slice(&EOCD_SIGNATURE.to_le_bytes());
+            apk.extend_from_slice(&[0u8; 12]);
+            apk.extend_from_slice(&cd_offset.to_le_bytes());
+            apk.extend_from_slice(&[0u8; 2]);
+            apk
+        };
+        for block_size in [u64::MAX, u64::MAX - 7, 1 << 40, 24 + 1024] {
+            assert!(signing_block_certificates(&mut std::io::Cursor::new(hostile_apk(block_size))).is_err());
+        }
+
+        // Length prefixes past the end of the data
+        let mut pos = 0;
+        assert!(length_prefixed(&u32::MAX.to_le_bytes(), &mut pos).is_err());
+        let mut pos = usize::MAX - 2;
+        assert!(length_prefixed(&[0u8; 8], &mut pos).is_err());
+    }
+
+    #[test]
+    fn test_normalize_fingerprint() {
+        assert_eq!(normalize_fingerprint("AB:cd:0F"), "abcd0f");
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// APK signature scheme a certificate was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureScheme {
+    /// JAR signing (META-INF/*.RSA, *.DSA, *.EC)
+    V1,
+    /// APK Signature Scheme v2
+    V2,
+    /// APK Signature Scheme v3 / v3.1
+    V3,
+}
+
+/// X.509 signing certificate of an APK
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SigningCertificate {
+    pub schemes: Vec<SignatureScheme>,
+    /// Lowercase hex SHA-1 of the DER certificate
+    pub sha1: String,
+    /// Lowercase hex SHA-256 of the DER certificate
+    pub sha256: String,
+    /// Hex of Java `Signature.hashCode()`, as printed by `dumpsys package` (signatures:[...])
+    pub java_hash: String,
+}
@@ -0,0 +1,230 @@
+pub use crate::calc_appops_stt::*;
+use std::sync::{Arc, Mutex};
+
+/// Ops offered for batch changes; mostly privacy controls without a settings UI
+pub const PRIVACY_OPS: &[&str] = &[
+    "RUN_ANY_IN_BACKGROUND",
+    "RUN_IN_BACKGROUND",
+    "WAKE_LOCK",
+    "READ_CLIPBOARD",
+    "WRITE_CLIPBOARD",
+    "READ_DEVICE_IDENTIFIERS",
+    "MONITOR_LOCATION",
+    "MONITOR_HIGH_POWER_LOCATION",
+    "COARSE_LOCATION",
+    "FINE_LOCATION",
+    "CAMERA",
+    "RECORD_AUDIO",
+    "POST_NOTIFICATION",
+    "SCHEDULE_EXACT_ALARM",
+    "START_FOREGROUND",
+    "SYSTEM_ALERT_WINDOW",
+    "GET_USAGE_STATS",
+    "REQUEST_INSTALL_PACKAGES",
+    "BLUETOOTH_SCAN",
+    "WIFI_SCAN",
+    "CHANGE_WIFI_STATE",
+    "READ_EXTERNAL_STORAGE",
+    "WRITE_EXTERNAL_STORAGE",
+];
+
+fn is_op_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Text of an `Access:`/`Reject:` line after the uid state ("[fg-s] 2024-05-01 ... (-2h) duration=+1s"),
+/// split into the time and the duration
+fn parse_access_line(text: &str) -> (String, Option<String>) {
+    let text = text.split_once(']').map(|(_, rest)| rest).unwrap_or(text).trim();
+    match text.split_once(" duration=") {
+        Some((time, duration)) => (time.trim().to_string(), Some(duration.trim().to_string())),
+        None => (text.to_string(), None),
+    }
+}
+
+/// Keep the later of two reported times. Dated times ("2024-05-01 10:00:00.000 (-2h)") compare as text.
+fn keep_latest(current: &mut Option<String>, time: String) {
+    if current.as_ref().map_or(true, |c| time > *c) {
+        *current = Some(time);
+    }
+}
+
+/// Parse `cmd appops get <pkg>` output.
+///
+/// Handles the single-line format of older releases
+/// ("WAKE_LOCK: allow; time=+1m ago; duration=+5s") and the per-attribution blocks of newer ones
+/// ("COARSE_LOCATION: allow" followed by indented "Access: [fg-s] 2024-05-01 10:00:00.000 (-2h)" lines).
+pub fn parse_app_ops(output: &str) -> Vec<AppOpEntry> {
+    let mut entries: Vec<AppOpEntry> = Vec::new();
+
+    for raw in output.lines() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if raw.starts_with(char::is_whitespace) {
+            let Some(entry) = entries.last_mut() else {
+                continue;
+            };
+            if let Some(text) = line.strip_prefix("Access:") {
+                let (time, duration) = parse_access_line(text);
+                if entry.last_access.as_ref().map_or(true, |c| time > *c) {
+                    entry.duration = duration;
+                }
+                keep_latest(&mut entry.last_access, time);
+            } else if let Some(text) = line.strip_prefix("Reject:") {
+                keep_latest(&mut entry.last_reject, parse_access_line(text).0);
+            }
+            continue;
+        }
+
+        let (uid_mode, line) = match line.strip_prefix("Uid mode:") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, line),
+        };
+        let Some((op, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let op = op.trim();
+        if !is_op_name(op) {
+            continue;
+        }
+        let mut parts = rest.split(';');
+        let mode = parts.next().unwrap_or_default().trim().to_string();
+        let mut entry = AppOpEntry {
+            op: op.to_string(),
+            mode,
+            uid_mode,
+            last_access: None,
+            last_reject: None,
+            duration: None,
+        };
+        for part in parts {
+            let Some((key, value)) = part.trim().split_once('=') else {
+                continue;
+            };
+            match key {
+                "time" => entry.last_access = Some(value.to_string()),
+                "rejectTime" => entry.last_reject = Some(value.to_string()),
+                "duration" => entry.duration = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        entries.push(entry);
+    }
+
+    entries
+}
+
+/// Read and parse the app ops of a package
+pub fn get_app_ops(device: &str, pkg_id: &str) -> Result<Vec<AppOpEntry>, String> {
+    let output = crate::adb::get_app_ops(device, pkg_id).map_err(|e| e.to_string().trim().to_string())?;
+    if let Some(error) = output.lines().find(|l| l.starts_with("Error") || l.contains("Unknown package")) {
+        return Err(error.trim().to_string());
+    }
+    Ok(parse_app_ops(&output))
+}
+
+/// Load the app ops of packages in a background thread, replacing their entries in `results`
+pub fn run_app_ops_load_async(device: String, packages: Vec<String>, results: AppOpsResults) {
+    std::thread::spawn(move || {
+        for pkg_id in packages {
+            let ops = get_app_ops(&device, &pkg_id);
+            if let Err(e) = &ops {
+                log::error!("Failed to read app ops of {}: {}", pkg_id, e);
+            }
+            if let Ok(mut r) = results.lock() {
+                r.insert(pkg_id, ops);
+            }
+        }
+    });
+}
+
+/// Set app op modes in a background thread, then reload the ops of the changed packages
+pub fn run_app_op_changes_async(
+    device: String,
+    changes: Vec<AppOpChange>,
+    outcome_result: Arc<Mutex<Option<AppOpBatchOutcome>>>,
+    results: AppOpsResults,
+) {
+    std::thread::spawn(move || {
+        let mut outcome = AppOpBatchOutcome::default();
+        let mut changed: Vec<String> = Vec::new();
+        for change in &changes {
+            let set = if change.uid_mode { crate::adb::set_uid_app_op } else { crate::adb::set_app_op };
+            match set(&device, &change.pkg_id, &change.op, change.mode.as_str()) {
+                Ok(_) => {
+                    outcome.succeeded += 1;
+                    if !changed.contains(&change.pkg_id) {
+                        changed.push(change.pkg_id.clone());
+                    }
+                }
+                Err(e) => {
+                    log::error!("Failed to set {} to {} for {}: {}", change.op, change.mode.as_str(), change.pkg_id, e);
+                    outcome
+                        .failures
+                        .push(format!("{} {}: {}", change.pkg_id, change.op, e.to_string().trim()));
+                }
+            }
+        }
+        log::info!(
+            "App op changes on {}: {} succeeded, {} failed",
+            device,
+            outcome.succeeded,
+            outcome.failures.len()
+        );
+
+        for pkg_id in changed {
+            let ops = get_app_ops(&device, &pkg_id);
+            if let Ok(mut r) = results.lock() {
+                r.insert(pkg_id, ops);
+            }
+        }
+        if let Ok(mut r) = outcome_result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_app_ops_single_line() {
+        let output = "Uid mode: RUN_ANY_IN_BACKGROUND: ignore\n\
+                      WAKE_LOCK: allow; time=+1m2s ago; duration=+5s\n\
+                      READ_CLIPBOARD: ignore; rejectTime=+3d ago\n\
+                      RUN_IN_BACKGROUND: allow\n";
+        let ops = parse_app_ops(output);
+        assert_eq!(ops.len(), 4);
+        assert_eq!(ops[0].op, "RUN_ANY_IN_BACKGROUND");
+        assert!(ops[0].uid_mode);
+        assert_eq!(ops[0].mode, "ignore");
+        assert_eq!(ops[1].last_access.as_deref(), Some("+1m2s ago"));
+        assert_eq!(ops[1].duration.as_deref(), Some("+5s"));
+        assert_eq!(ops[2].last_reject.as_deref(), Some("+3d ago"));
+        assert!(!ops[3].uid_mode);
+        assert_eq!(ops[3].last_access, None);
+    }
+
+    #[test]
+    fn test_parse_app_ops_blocks() {
+        let output = "COARSE_LOCATION: allow\n\
+                      \x20  null=[\n\
+                      \x20    Access: [fg-s] 2024-05-01 10:00:00.000 (-3h) duration=+1s\n\
+                      \x20    Access: [top-s] 2024-05-01 12:00:00.000 (-1h) duration=+2s\n\
+                      \x20    Reject: [bg-s] 2024-04-30 08:00:00.000 (-1d)\n\
+                      \x20  ]\n\
+                      No operations.\n\
+                      READ_DEVICE_IDENTIFIERS: deny\n";
+        let ops = parse_app_ops(output);
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].last_access.as_deref(), Some("2024-05-01 12:00:00.000 (-1h)"));
+        assert_eq!(ops[0].duration.as_deref(), Some("+2s"));
+        assert_eq!(ops[0].last_reject.as_deref(), Some("2024-04-30 08:00:00.000 (-1d)"));
+        assert_eq!(ops[1].op, "READ_DEVICE_IDENTIFIERS");
+        assert_eq!(AppOpMode::parse(&ops[1].mode), Some(AppOpMode::Deny));
+    }
+}
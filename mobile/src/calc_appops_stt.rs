@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Mode of an app op as accepted by `cmd appops set`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppOpMode {
+    Allow,
+    Ignore,
+    Deny,
+    Default,
+    /// Allowed only while the app is in the foreground
+    Foreground,
+}
+
+impl AppOpMode {
+    /// Modes offered in the editor
+    pub const ALL: [AppOpMode; 5] = [
+        AppOpMode::Allow,
+        AppOpMode::Ignore,
+        AppOpMode::Deny,
+        AppOpMode::Default,
+        AppOpMode::Foreground,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppOpMode::Allow => "allow",
+            AppOpMode::Ignore => "ignore",
+            AppOpMode::Deny => "deny",
+            AppOpMode::Default => "default",
+            AppOpMode::Foreground => "foreground",
+        }
+    }
+
+    pub fn parse(mode: &str) -> Option<Self> {
+        AppOpMode::ALL.into_iter().find(|m| m.as_str() == mode)
+    }
+}
+
+/// One op from `cmd appops get <pkg>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppOpEntry {
+    /// Op name (RUN_ANY_IN_BACKGROUND, WAKE_LOCK, ...)
+    pub op: String,
+    /// Mode as printed; kept as text because some ops report modes outside `AppOpMode`
+    pub mode: String,
+    /// Reported as the uid mode, shared by every package of the uid
+    pub uid_mode: bool,
+    /// Last access as reported ("+2h3m ago" or "2024-05-01 10:00:00.000 (-2h3m)")
+    pub last_access: Option<String>,
+    /// Last time the op was rejected
+    pub last_reject: Option<String>,
+    /// Duration of the last access
+    pub duration: Option<String>,
+}
+
+/// Mode change of one op for one package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppOpChange {
+    pub pkg_id: String,
+    pub op: String,
+    pub mode: AppOpMode,
+    /// Set the uid mode (`--uid`) instead of the package mode
+    pub uid_mode: bool,
+}
+
+/// Outcome of a batch of app op changes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppOpBatchOutcome {
+    pub succeeded: usize,
+    /// One line per failed change
+    pub failures: Vec<String>,
+}
+
+/// App ops loaded per package; `Err` holds the adb error
+pub type AppOpsResults = Arc<Mutex<HashMap<String, Result<Vec<AppOpEntry>, String>>>>;
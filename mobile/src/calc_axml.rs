@@ -0,0 +1,709 @@
+pub use crate::calc_axml_stt::*;
+use crate::shared_store_stt::get_shared_store;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+// Chunk types from frameworks/base/libs/androidfw/include/androidfw/ResourceTypes.h
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_XML_TYPE: u16 = 0x0003;
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+const RES_XML_END_ELEMENT_TYPE: u16 = 0x0103;
+const RES_XML_RESOURCE_MAP_TYPE: u16 = 0x0180;
+
+const UTF8_FLAG: u32 = 1 << 8;
+const NO_INDEX: u32 = 0xFFFF_FFFF;
+
+// Res_value data types
+const TYPE_STRING: u8 = 0x03;
+const TYPE_INT_DEC: u8 = 0x10;
+const TYPE_INT_HEX: u8 = 0x11;
+const TYPE_INT_BOOLEAN: u8 = 0x12;
+
+/// android.R.attr ids of the attributes we read. Used when the attribute
+/// name strings are stripped or obfuscated but the resource map is intact.
+const ANDROID_ATTR_IDS: &[(u32, &str)] = &[
+    (0x0101_0003, "name"),
+    (0x0101_0006, "permission"),
+    (0x0101_000f, "debuggable"),
+    (0x0101_0010, "exported"),
+    (0x0101_0027, "scheme"),
+    (0x0101_020c, "minSdkVersion"),
+    (0x0101_021b, "versionCode"),
+    (0x0101_021c, "versionName"),
+    (0x0101_0270, "targetSdkVersion"),
+    (0x0101_0280, "allowBackup"),
+    (0x0101_04ec, "usesCleartextTraffic"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttrValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct XmlAttribute {
+    name: String,
+    value: AttrValue,
+}
+
+#[derive(Debug, Clone)]
+enum XmlEvent {
+    Start { name: String, attributes: Vec<XmlAttribute> },
+    End { name: String },
+}
+
+/// `base + add`, failing instead of overflowing on offsets read from the file
+fn offset_add(base: usize, add: usize) -> Result<usize, String> {
+    base.checked_add(add)
+        .ok_or_else(|| format!("AXML offset overflow at {} + {}", base, add))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset.saturating_add(2))
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| format!("Unexpected end of AXML data at offset {}", offset))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset.saturating_add(4))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("Unexpected end of AXML data at offset {}", offset))
+}
+
+fn parse_string_pool(data: &[u8], chunk_start: usize) -> Result<Vec<String>, String> {
+    let header_size = read_u16(data, chunk_start + 2)? as usize;
+    let string_count = read_u32(data, chunk_start + 8)? as usize;
+    let flags = read_u32(data, chunk_start + 16)?;
+    let strings_start = read_u32(data, chunk_start + 20)? as usize;
+    let is_utf8 = flags & UTF8_FLAG != 0;
+
+    // Every string needs a 4 byte offset entry, so a count beyond that is corrupt (or hostile)
+    if string_count > data.len() / 4 {
+        return Err(format!("Invalid string count {}", string_count));
+    }
+    let offsets_start = offset_add(chunk_start, header_size)?;
+    let pool_start = offset_add(chunk_start, strings_start)?;
+
+    let mut strings = Vec::with_capacity(string_count);
+    for i in 0..string_count {
+        let offset = read_u32(data, offset_add(offsets_start, i * 4)?)? as usize;
+        let pos = offset_add(pool_start, offset)?;
+        strings.push(if is_utf8 {
+            read_utf8_string(data, pos)?
+        } else {
+            read_utf16_string(data, pos)?
+        });
+    }
+
+    Ok(strings)
+}
+
+fn read_utf8_length(data: &[u8], pos: &mut usize) -> Result<usize, String> {
+    let first = *data.get(*pos).ok_or("Unexpected end of string pool")? as usize;
+    *pos += 1;
+    if first & 0x80 != 0 {
+        let second = *data.get(*pos).ok_or("Unexpected end of string pool")? as usize;
+        *pos += 1;
+        Ok(((first & 0x7f) << 8) | second)
+    } else {
+        Ok(first)
+    }
+}
+
+fn read_utf8_string(data: &[u8], pos: usize) -> Result<String, String> {
+    let mut pos = pos;
+    let _utf16_len = read_utf8_length(data, &mut pos)?;
+    let byte_len = read_utf8_length(data, &mut pos)?;
+    let bytes = data
+        .get(pos..offset_add(pos, byte_len)?)
+        .ok_or("Unexpected end of string pool")?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn read_utf16_string(data: &[u8], pos: usize) -> Result<String, String> {
+    let mut pos = pos;
+    let mut len = read_u16(data, pos)? as usize;
+    pos += 2;
+    if len & 0x8000 != 0 {
+        len = ((len & 0x7fff) << 16) | read_u16(data, pos)? as usize;
+        pos += 2;
+    }
+    let bytes = data
+        .get(pos..offset_add(pos, len.saturating_mul(2))?)
+        .ok_or("Unexpected end of string pool")?;
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+fn parse_xml_events(data: &[u8]) -> Result<Vec<XmlEvent>, String> {
+    if read_u16(data, 0)? != RES_XML_TYPE {
+        return Err("Not a binary XML file".to_string());
+    }
+    let file_header_size = read_u16(data, 2)? as usize;
+    let file_size = (read_u32(data, 4)? as usize).min(data.len());
+
+    let mut strings: Vec<String> = Vec::new();
+    let mut resource_ids: Vec<u32> = Vec::new();
+    let attr_names: HashMap<u32, &str> = ANDROID_ATTR_IDS.iter().copied().collect();
+    let string_at = |strings: &[String], idx: u32| -> String {
+        strings.get(idx as usize).cloned().unwrap_or_default()
+    };
+
+    let mut events = Vec::new();
+    let mut offset = file_header_size;
+
+    while offset + 8 <= file_size {
+        let chunk_type = read_u16(data, offset)?;
+        let header_size = read_u16(data, offset + 2)? as usize;
+        let chunk_size = read_u32(data, offset + 4)? as usize;
+        if chunk_size < 8 || header_size > chunk_size || offset_add(offset, chunk_size)? > file_size {
+            return Err(format!("Invalid chunk size {} at offset {}", chunk_size, offset));
+        }
+
+        match chunk_type {
+            RES_STRING_POOL_TYPE => {
+                strings = parse_string_pool(data, offset)?;
+            }
+            RES_XML_RESOURCE_MAP_TYPE => {
+                // header_size <= chunk_size is checked above
+                let count = (chunk_size - header_size) / 4;
+                resource_ids = (0..count)
+                    .map(|i| read_u32(data, offset + header_size + i * 4))
+                    .collect::<Result<Vec<u32>, String>>()?;
+            }
+            RES_XML_START_ELEMENT_TYPE => {
+                let ext = offset + header_size;
+                let name = string_at(&strings, read_u32(data, ext + 4)?);
+                let attribute_start = read_u16(data, ext + 8)? as usize;
+                let attribute_size = read_u16(data, ext + 10)? as usize;
+                let attribute_count = read_u16(data, ext + 12)? as usize;
+
+                let mut attributes = Vec::with_capacity(attribute_count.min(chunk_size / 20));
+                for i in 0..attribute_count {
+                    let attr = ext + attribute_start + i * attribute_size;
+                    let name_idx = read_u32(data, attr + 4)?;
+                    let raw_value = read_u32(data, attr + 8)?;
+                    let data_type = *data.get(attr + 15).ok_or("Unexpected end of attribute")?;
+                    let value_data = read_u32(data, attr + 16)?;
+
+                    // Prefer the well-known resource id over the (possibly obfuscated) name
+                    let attr_name = resource_ids
+                        .get(name_idx as usize)
+                        .and_then(|id| attr_names.get(id))
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| string_at(&strings, name_idx));
+
+                    let value = if raw_value != NO_INDEX {
+                        AttrValue::Str(string_at(&strings, raw_value))
+                    } else {
+                        match data_type {
+                            TYPE_STRING => AttrValue::Str(string_at(&strings, value_data)),
+                            TYPE_INT_DEC | TYPE_INT_HEX => AttrValue::Int(value_data as i32 as i64),
+                            TYPE_INT_BOOLEAN => AttrValue::Bool(value_data != 0),
+                            _ => AttrValue::Other,
+                        }
+                    };
+
+                    attributes.push(XmlAttribute { name: attr_name, value });
+                }
+
+                events.push(XmlEvent::Start { name, attributes });
+            }
+            RES_XML_END_ELEMENT_TYPE => {
+                let ext = offset + header_size;
+                let name = string_at(&strings, read_u32(data, ext + 4)?);
+                events.push(XmlEvent::End { name });
+            }
+            _ => {}
+        }
+
+        offset += chunk_size;
+    }
+
+    Ok(events)
+}
+
+fn attr<'a>(attributes: &'a [XmlAttribute], name: &str) -> Option<&'a AttrValue> {
+    attributes.iter().find(|a| a.name == name).map(|a| &a.value)
+}
+
+fn attr_str(attributes: &[XmlAttribute], name: &str) -> Option<String> {
+    match attr(attributes, name)? {
+        AttrValue::Str(s) => Some(s.clone()),
+        AttrValue::Int(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+fn attr_int(attributes: &[XmlAttribute], name: &str) -> Option<i64> {
+    match attr(attributes, name)? {
+        AttrValue::Int(i) => Some(*i),
+        AttrValue::Str(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn attr_bool(attributes: &[XmlAttribute], name: &str) -> Option<bool> {
+    match attr(attributes, name)? {
+        AttrValue::Bool(b) => Some(*b),
+        AttrValue::Str(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Expand ".Foo" and "Foo" class names relative to the manifest package
+fn resolve_class_name(package: &str, name: &str) -> String {
+    if name.starts_with('.') {
+        format!("{}{}", package, name)
+    } else if !name.contains('.') && !package.is_empty() {
+        format!("{}.{}", package, name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Parse a binary (AXML) AndroidManifest.xml
+pub fn parse_axml_manifest(data: &[u8]) -> Result<ApkManifest, String> {
+    let events = parse_xml_events(data)?;
+
+    let mut manifest = ApkManifest::default();
+    let mut component: Option<(String, ManifestComponent)> = None;
+    let mut intent_filter: Option<ManifestIntentFilter> = None;
+
+    for event in events {
+        match event {
+            XmlEvent::Start { name, attributes } => match name.as_str() {
+                "manifest" => {
+                    manifest.package = attr_str(&attributes, "package").unwrap_or_default();
+                    manifest.version_code = attr_int(&attributes, "versionCode");
+                    manifest.version_name = attr_str(&attributes, "versionName");
+                }
+                "uses-sdk" => {
+                    manifest.min_sdk = attr_int(&attributes, "minSdkVersion").map(|v| v as i32);
+                    manifest.target_sdk = attr_int(&attributes, "targetSdkVersion").map(|v| v as i32);
+                }
+                "uses-permission" | "uses-permission-sdk-23" => {
+                    if let Some(permission) = attr_str(&attributes, "name") {
+                        if !manifest.permissions.contains(&permission) {
+                            manifest.permissions.push(permission);
+                        }
+                    }
+                }
+                "application" => {
+                    manifest.debuggable = attr_bool(&attributes, "debuggable");
+                    manifest.allow_backup = attr_bool(&attributes, "allowBackup");
+                    manifest.uses_cleartext_traffic = attr_bool(&attributes, "usesCleartextTraffic");
+                }
+                "activity" | "activity-alias" | "service" | "receiver" | "provider" => {
+                    let class_name = attr_str(&attributes, "name").unwrap_or_default();
+                    component = Some((
+                        name.clone(),
+                        ManifestComponent {
+                            name: resolve_class_name(&manifest.package, &class_name),
+                            exported: attr_bool(&attributes, "exported"),
+                            permission: attr_str(&attributes, "permission"),
+                            intent_filters: Vec::new(),
+                        },
+                    ));
+                }
+                "intent-filter" if component.is_some() => {
+                    intent_filter = Some(ManifestIntentFilter::default());
+                }
+                "action" | "category" | "data" => {
+                    if let Some(filter) = intent_filter.as_mut() {
+                        match name.as_str() {
+                            "action" => filter.actions.extend(attr_str(&attributes, "name")),
+                            "category" => filter.categories.extend(attr_str(&attributes, "name")),
+                            _ => filter.data_schemes.extend(attr_str(&attributes, "scheme")),
+                        }
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::End { name } => match name.as_str() {
+                "intent-filter" => {
+                    if let (Some(filter), Some((_, comp))) = (intent_filter.take(), component.as_mut()) {
+                        comp.intent_filters.push(filter);
+                    }
+                }
+                "activity" | "activity-alias" | "service" | "receiver" | "provider" => {
+                    if let Some((kind, comp)) = component.take() {
+                        match kind.as_str() {
+                            "service" => manifest.services.push(comp),
+                            "receiver" => manifest.receivers.push(comp),
+                            "provider" => manifest.providers.push(comp),
+                            _ => manifest.activities.push(comp),
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    if manifest.package.is_empty() {
+        return Err("Manifest has no package attribute".to_string());
+    }
+
+    Ok(manifest)
+}
+
+/// Read and parse AndroidManifest.xml from an APK file
+pub fn parse_apk_manifest(apk_path: &Path) -> Result<ApkManifest, String> {
+    let file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+    let mut entry = archive
+        .by_name("AndroidManifest.xml")
+        .map_err(|e| format!("AndroidManifest.xml not found in {:?}: {}", apk_path, e))?;
+
+    let mut data = Vec::new();
+    entry
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Failed to read AndroidManifest.xml: {}", e))?;
+
+    parse_axml_manifest(&data)
+}
+
+/// Get a previously parsed manifest by APK sha256sum
+pub fn get_cached_manifest(apk_sha256sum: &str) -> Option<ApkManifest> {
+    let cached = crate::db_package_cache::get_cached_apk_manifest(apk_sha256sum)?;
+    serde_json::from_str(&cached.manifest_json)
+        .map_err(|e| log::warn!("Failed to parse cached manifest for {}: {}", cached.pkg_id, e))
+        .ok()
+}
+
+/// Parse the manifest of a local APK, reusing the cached result for the same sha256sum
+pub fn load_apk_manifest(apk_path: &Path, apk_sha256sum: &str, pkg_id: &str) -> Result<ApkManifest, String> {
+    if let Some(manifest) = get_cached_manifest(apk_sha256sum) {
+        return Ok(manifest);
+    }
+
+    let manifest = parse_apk_manifest(apk_path)?;
+    match serde_json::to_string(&manifest) {
+        Ok(json) => {
+            if let Err(e) = crate::db_package_cache::upsert_apk_manifest_cache(apk_sha256sum, pkg_id, &json) {
+                log::error!("Failed to cache manifest for {}: {}", pkg_id, e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize manifest for {}: {}", pkg_id, e),
+    }
+
+    Ok(manifest)
+}
+
+/// Find the base APK of a package on the device. Returns (device path, sha256sum).
+pub fn locate_base_apk(device_serial: &str, pkg_id: &str) -> Result<(String, String), String> {
+    let (paths_str, sha256sums_str) = crate::adb::get_single_package_sha256sum(device_serial, pkg_id)
+        .map_err(|e| format!("Failed to locate APK of {}: {}", pkg_id, e))?;
+
+    let paths: Vec<&str> = paths_str.split_whitespace().collect();
+    let sha256sums: Vec<&str> = sha256sums_str.split_whitespace().collect();
+
+    let idx = paths
+        .iter()
+        .position(|p| p.ends_with("/base.apk"))
+        .or_else(|| paths.iter().position(|p| p.ends_with(".apk")))
+        .ok_or_else(|| format!("No APK found for {}", pkg_id))?;
+
+    match (paths.get(idx), sha256sums.get(idx)) {
+        (Some(path), Some(sha256)) => Ok((path.to_string(), sha256.to_string())),
+        _ => Err(format!("No APK found for {}", pkg_id)),
+    }
+}
+
+/// Pull and parse the base APK manifest of an installed package, reusing the cached result
+pub fn analyze_package_manifest(device_serial: &str, pkg_id: &str) -> Result<ApkManifest, String> {
+    let (apk_path, apk_sha256sum) = locate_base_apk(device_serial, pkg_id)?;
+
+    if let Some(manifest) = get_cached_manifest(&apk_sha256sum) {
+        log::debug!("Using cached manifest for {} ({})", pkg_id, apk_sha256sum);
+        return Ok(manifest);
+    }
+
+    let config = crate::Config::new().map_err(|e| format!("Config not available: {}", e))?;
+    let tmp_dir_str = config.tmp_dir.to_str().ok_or("Invalid tmp directory path")?;
+    let local_path = crate::adb::pull_file_to_temp(device_serial, &apk_path, tmp_dir_str, pkg_id)
+        .map_err(|e| format!("Failed to pull {}: {}", apk_path, e))?;
+
+    let result = load_apk_manifest(Path::new(&local_path), &apk_sha256sum, pkg_id);
+    let _ = std::fs::remove_file(&local_path);
+    result
+}
+
+/// Pull and parse a package's manifest in the background, publishing the
+/// progress and result to the shared store
+pub fn spawn_manifest_analysis(device_serial: String, pkg_id: String, ctx: eframe::egui::Context) {
+    let store = get_shared_store();
+    store.set_apk_manifest_state(pkg_id.clone(), ManifestLoadState::Loading);
+
+    std::thread::spawn(move || {
+        let state = match analyze_package_manifest(&device_serial, &pkg_id) {
+            Ok(manifest) => ManifestLoadState::Loaded(manifest),
+            Err(e) => {
+                log::error!("Manifest analysis failed for {}: {}", pkg_id, e);
+                ManifestLoadState::Failed(e)
+            }
+        };
+        get_shared_store().set_apk_manifest_state(pkg_id, state);
+        ctx.request_repaint();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal AXML writer for building test manifests
+    struct AxmlBuilder {
+        strings: Vec<String>,
+        resource_ids: Vec<u32>,
+        body: Vec<u8>,
+    }
+
+    impl AxmlBuilder {
+        fn new(attr_names: &[(&str, u32)]) -> Self {
+            Self {
+                strings: attr_names.iter().map(|(n, _)| n.to_string()).collect(),
+                resource_ids: attr_names.iter().map(|(_, id)| *id).collect(),
+                body: Vec::new(),
+            }
+        }
+
+        fn string(&mut self, s: &str) -> u32 {
+            if let Some(idx) = self.strings.iter().position(|x| x == s) {
+                return idx as u32;
+            }
+            self.strings.push(s.to_string());
+            (self.strings.len() - 1) as u32
+        }
+
+        fn start(&mut self, name: &str, attrs: &[(&str, AttrValue)]) {
+            let name_idx = self.string(name);
+            let attrs: Vec<(u32, u32, u8, u32)> = attrs
+                .iter()
+                .map(|(n, v)| {
+                    let n_idx = self.string(n);
+                    match v {
+                        AttrValue::Str(s) => {
+                            let s_idx = self.string(s);
+                            (n_idx, s_idx, TYPE_STRING, s_idx)
+                        }
+                        AttrValue::Int(i) => (n_idx, NO_INDEX, TYPE_INT_DEC, *i as u32),
+                        AttrValue::Bool(b) => (n_idx, NO_INDEX, TYPE_INT_BOOLEAN, if *b { 0xFFFF_FFFF } else { 0 }),
+                        AttrValue::Other => (n_idx, NO_INDEX, 0, 0),
+                    }
+                })
+                .collect();
+
+            let size = 16 + 20 + attrs.len() * 20;
+            push_header(&mut self.body, RES_XML_START_ELEMENT_TYPE, 16, size);
+            push_u32(&mut self.body, 1); // line number
+            push_u32(&mut self.body, NO_INDEX); // comment
+            push_u32(&mut self.body, NO_INDEX); // namespace
+            push_u32(&mut self.body, name_idx);
+            push_u16(&mut self.body, 20); // attributeStart
+            push_u16(&mut self.body, 20); // attributeSize
+            push_u16(&mut self.body, attrs.len() as u16);
+            push_u16(&mut self.body, 0); // idIndex
+            push_u16(&mut self.body, 0); // classIndex
+            push_u16(&mut self.body, 0); // styleIndex
+            for (n_idx, raw, data_type, data) in attrs {
+                push_u32(&mut self.body, NO_INDEX);
+                push_u32(&mut self.body, n_idx);
+                push_u32(&mut self.body, raw);
+                push_u16(&mut self.body, 8);
+                self.body.push(0);
+                self.body.push(data_type);
+                push_u32(&mut self.body, data);
+            }
+        }
+
+        fn end(&mut self, name: &str) {
+            let name_idx = self.string(name);
+            push_header(&mut self.body, RES_XML_END_ELEMENT_TYPE, 16, 24);
+            push_u32(&mut self.body, 1);
+            push_u32(&mut self.body, NO_INDEX);
+            push_u32(&mut self.body, NO_INDEX);
+            push_u32(&mut self.body, name_idx);
+        }
+
+        fn build(&self) -> Vec<u8> {
+            // UTF-16 string pool
+            let mut string_data = Vec::new();
+            let mut offsets = Vec::new();
+            for s in &self.strings {
+                offsets.push(string_data.len() as u32);
+                let units: Vec<u16> = s.encode_utf16().collect();
+                push_u16(&mut string_data, units.len() as u16);
+                for u in units {
+                    push_u16(&mut string_data, u);
+                }
+                push_u16(&mut string_data, 0);
+            }
+            while string_data.len() % 4 != 0 {
+                string_data.push(0);
+            }
+            let strings_start = 28 + offsets.len() * 4;
+            let mut pool = Vec::new();
+            push_header(&mut pool, RES_STRING_POOL_TYPE, 28, strings_start + string_data.len());
+            push_u32(&mut pool, offsets.len() as u32);
+            push_u32(&mut pool, 0); // styleCount
+            push_u32(&mut pool, 0); // flags (UTF-16)
+            push_u32(&mut pool, strings_start as u32);
+            push_u32(&mut pool, 0); // stylesStart
+            for o in offsets {
+                push_u32(&mut pool, o);
+            }
+            pool.extend(string_data);
+
+            let mut res_map = Vec::new();
+            push_header(&mut res_map, RES_XML_RESOURCE_MAP_TYPE, 8, 8 + self.resource_ids.len() * 4);
+            for id in &self.resource_ids {
+                push_u32(&mut res_map, *id);
+            }
+
+            let mut out = Vec::new();
+            push_header(&mut out, RES_XML_TYPE, 8, 8 + pool.len() + res_map.len() + self.body.len());
+            out.extend(pool);
+            out.extend(res_map);
+            out.extend(&self.body);
+            out
+        }
+    }
+
+    fn push_u16(buf: &mut Vec<u8>, v: u16) {
+        buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn push_u32(buf: &mut Vec<u8>, v: u32) {
+        buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn push_header(buf: &mut Vec<u8>, chunk_type: u16, header_size: u16, size: usize) {
+        push_u16(buf, chunk_type);
+        push_u16(buf, header_size);
+        push_u32(buf, size as u32);
+    }
+
+    fn s(v: &str) -> AttrValue {
+        AttrValue::Str(v.to_string())
+    }
+
+    fn sample_manifest() -> Vec<u8> {
+        // Attribute names listed first so they line up with the resource map.
+        // "x1" stands in for an obfuscated "exported" attribute name.
+        let mut b = AxmlBuilder::new(&[
+            ("name", 0x0101_0003),
+            ("x1", 0x0101_0010),
+            ("versionCode", 0x0101_021b),
+            ("versionName", 0x0101_021c),
+            ("minSdkVersion", 0x0101_020c),
+            ("targetSdkVersion", 0x0101_0270),
+            ("debuggable", 0x0101_000f),
+            ("allowBackup", 0x0101_0280),
+            ("scheme", 0x0101_0027),
+        ]);
+        b.start("manifest", &[("package", s("com.example.app")), ("versionCode", AttrValue::Int(42)), ("versionName", s("1.2.3"))]);
+        b.start("uses-sdk", &[("minSdkVersion", AttrValue::Int(21)), ("targetSdkVersion", AttrValue::Int(34))]);
+        b.end("uses-sdk");
+        b.start("uses-permission", &[("name", s("android.permission.INTERNET"))]);
+        b.end("uses-permission");
+        b.start("uses-permission-sdk-23", &[("name", s("android.permission.CAMERA"))]);
+        b.end("uses-permission-sdk-23");
+        b.start("application", &[("debuggable", AttrValue::Bool(true)), ("allowBackup", AttrValue::Bool(false))]);
+        b.start("activity", &[("name", s(".MainActivity")), ("x1", AttrValue::Bool(true))]);
+        b.start("intent-filter", &[]);
+        b.start("action", &[("name", s("android.intent.action.VIEW"))]);
+        b.end("action");
+        b.start("category", &[("name", s("android.intent.category.BROWSABLE"))]);
+        b.end("category");
+        b.start("data", &[("scheme", s("https"))]);
+        b.end("data");
+        b.end("intent-filter");
+        b.end("activity");
+        b.start("service", &[("name", s("SyncService"))]);
+        b.end("service");
+        b.start("receiver", &[("name", s("com.other.Receiver"))]);
+        b.start("intent-filter", &[]);
+        b.start("action", &[("name", s("android.intent.action.BOOT_COMPLETED"))]);
+        b.end("action");
+        b.end("intent-filter");
+        b.end("receiver");
+        b.start("provider", &[("name", s(".Provider")), ("x1", AttrValue::Bool(false))]);
+        b.end("provider");
+        b.end("application");
+        b.end("manifest");
+        b.build()
+    }
+
+    #[test]
+    fn test_parse_axml_manifest() {
+        let manifest = parse_axml_manifest(&sample_manifest()).unwrap();
+
+        assert_eq!(manifest.package, "com.example.app");
+        assert_eq!(manifest.version_code, Some(42));
+        assert_eq!(manifest.version_name.as_deref(), Some("1.2.3"));
+        assert_eq!(manifest.min_sdk, Some(21));
+        assert_eq!(manifest.target_sdk, Some(34));
+        assert_eq!(
+            manifest.permissions,
+            vec!["android.permission.INTERNET".to_string(), "android.permission.CAMERA".to_string()]
+        );
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.allow_backup, Some(false));
+        assert_eq!(manifest.uses_cleartext_traffic, None);
+
+        assert_eq!(manifest.activities.len(), 1);
+        let activity = &manifest.activities[0];
+        assert_eq!(activity.name, "com.example.app.MainActivity");
+        assert_eq!(activity.exported, Some(true));
+        assert_eq!(activity.intent_filters[0].actions, vec!["android.intent.action.VIEW".to_string()]);
+        assert_eq!(activity.intent_filters[0].categories, vec!["android.intent.category.BROWSABLE".to_string()]);
+        assert_eq!(activity.intent_filters[0].data_schemes, vec!["https".to_string()]);
+
+        assert_eq!(manifest.services[0].name, "com.example.app.SyncService");
+        assert!(!manifest.services[0].is_exported());
+        assert_eq!(manifest.receivers[0].name, "com.other.Receiver");
+        assert!(manifest.receivers[0].is_exported());
+        assert_eq!(manifest.providers[0].name, "com.example.app.Provider");
+        assert!(!manifest.providers[0].is_exported());
+    }
+
+    #[test]
+    fn test_parse_axml_rejects_text_xml() {
+        assert!(parse_axml_manifest(b"<?xml version=\"1.0\"?><manifest/>").is_err());
+        assert!(parse_axml_manifest(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_axml_truncated() {
+        let data = sample_manifest();
+        assert!(parse_axml_manifest(&data[..data.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn test_parse_axml_hostile_sizes() {
+        // String pool claiming 4 billion strings must fail instead of allocating
+        let mut data = sample_manifest();
+        data[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_axml_manifest(&data).is_err());
+
+        // Resource map with a header larger than the chunk
+        let mut data = sample_manifest();
+        let pool_size = u32::from_le_bytes([data[12], data[13], data[14], data[15]]) as usize;
+        let res_map = 8 + pool_size;
+        data[res_map + 2..res_map + 4].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(parse_axml_manifest(&data).is_err());
+
+        // String offset pointing past the end of the file
+        let mut data = sample_manifest();
+        data[36..40].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_axml_manifest(&data).is_err());
+    }
+}
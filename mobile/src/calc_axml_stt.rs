@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// `<intent-filter>` declared on a manifest component
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestIntentFilter {
+    pub actions: Vec<String>,
+    pub categories: Vec<String>,
+    /// `android:scheme` values of the `<data>` elements
+    pub data_schemes: Vec<String>,
+}
+
+/// Activity, service, receiver or provider declared in the manifest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestComponent {
+    /// Fully qualified class name
+    pub name: String,
+    /// Explicit `android:exported` value, None when not declared
+    pub exported: Option<bool>,
+    /// `android:permission` required to access the component
+    pub permission: Option<String>,
+    pub intent_filters: Vec<ManifestIntentFilter>,
+}
+
+impl ManifestComponent {
+    /// Whether other apps can reach the component. Without an explicit
+    /// `android:exported`, components with intent filters are exported.
+    pub fn is_exported(&self) -> bool {
+        self.exported.unwrap_or(!self.intent_filters.is_empty())
+    }
+}
+
+/// Data extracted from the binary AndroidManifest.xml of an APK
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApkManifest {
+    pub package: String,
+    pub version_code: Option<i64>,
+    pub version_name: Option<String>,
+    pub min_sdk: Option<i32>,
+    pub target_sdk: Option<i32>,
+    /// `<uses-permission>` and `<uses-permission-sdk-23>` entries
+    pub permissions: Vec<String>,
+    /// Activities and activity aliases
+    pub activities: Vec<ManifestComponent>,
+    pub services: Vec<ManifestComponent>,
+    pub receivers: Vec<ManifestComponent>,
+    pub providers: Vec<ManifestComponent>,
+    pub debuggable: Option<bool>,
+    pub allow_backup: Option<bool>,
+    pub uses_cleartext_traffic: Option<bool>,
+}
+
+/// Manifest analysis state of a package shown in the package details dialog
+#[derive(Debug, Clone)]
+pub enum ManifestLoadState {
+    Loading,
+    Loaded(ApkManifest),
+    Failed(String),
+}
@@ -0,0 +1,202 @@
+pub use crate::calc_background_stt::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// App op that blocks running in the background when set to ignore
+const RUN_ANY_IN_BACKGROUND: &str = "RUN_ANY_IN_BACKGROUND";
+
+/// Parse `am get-standby-bucket` output ("com.example: 10" per line)
+pub fn parse_standby_buckets(output: &str) -> HashMap<String, StandbyBucket> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pkg, value) = line.trim().split_once(':')?;
+            let bucket = StandbyBucket::from_value(value.trim().parse().ok()?)?;
+            Some((pkg.trim().to_string(), bucket))
+        })
+        .collect()
+}
+
+/// Parse `cmd deviceidle whitelist` output ("system,com.android.phone,1001" / "user,com.example,10123").
+/// `system-excidle` entries are still subject to idle restrictions and are left out.
+pub fn parse_deviceidle_whitelist(output: &str) -> HashMap<String, BatteryWhitelist> {
+    let mut whitelist = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.trim().split(',');
+        let (Some(kind), Some(pkg)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let entry = match kind {
+            "system" => BatteryWhitelist::System,
+            "user" => BatteryWhitelist::User,
+            _ => continue,
+        };
+        // A system entry wins over a user entry for the same package
+        let current = whitelist.entry(pkg.to_string()).or_insert(entry);
+        *current = (*current).max(entry);
+    }
+    whitelist
+}
+
+/// Read the standby bucket, battery optimization whitelist and background restriction of every package.
+/// Failed queries are logged and leave the corresponding value unset.
+pub fn load_background_states(device: &str) -> HashMap<String, BackgroundState> {
+    let buckets = crate::adb::get_standby_buckets(device)
+        .map(|o| parse_standby_buckets(&o))
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to read standby buckets: {}", e);
+            HashMap::new()
+        });
+    let whitelist = crate::adb::get_deviceidle_whitelist(device)
+        .map(|o| parse_deviceidle_whitelist(&o))
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to read device idle whitelist: {}", e);
+            HashMap::new()
+        });
+    let restricted = crate::adb::query_app_op_packages(device, RUN_ANY_IN_BACKGROUND, "ignore")
+        .map(|o| crate::calc_capabilities::parse_package_list(&o))
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to query background restricted packages: {}", e);
+            Vec::new()
+        });
+
+    let mut states: HashMap<String, BackgroundState> = HashMap::new();
+    for (pkg, bucket) in buckets {
+        states.entry(pkg).or_default().bucket = Some(bucket);
+    }
+    for (pkg, entry) in whitelist {
+        states.entry(pkg).or_default().whitelist = Some(entry);
+    }
+    for pkg in restricted {
+        states.entry(pkg).or_default().restricted = true;
+    }
+    states
+}
+
+/// Apply one background change to a package
+pub fn apply_background_action(device: &str, pkg_id: &str, action: BackgroundAction) -> std::io::Result<String> {
+    match action {
+        BackgroundAction::SetBucket(bucket) => crate::adb::set_standby_bucket(device, pkg_id, bucket.as_str()),
+        BackgroundAction::Whitelist => crate::adb::set_deviceidle_whitelist(device, pkg_id, true),
+        BackgroundAction::Unwhitelist => crate::adb::set_deviceidle_whitelist(device, pkg_id, false),
+        BackgroundAction::Restrict => crate::adb::set_app_op(device, pkg_id, RUN_ANY_IN_BACKGROUND, "ignore"),
+        BackgroundAction::Unrestrict => crate::adb::set_app_op(device, pkg_id, RUN_ANY_IN_BACKGROUND, "allow"),
+    }
+}
+
+/// Apply one background change to several packages, collecting the failures
+pub fn apply_background_actions(device: &str, packages: &[String], action: BackgroundAction) -> BackgroundBatchOutcome {
+    let mut outcome = BackgroundBatchOutcome::default();
+    for pkg_id in packages {
+        match apply_background_action(device, pkg_id, action) {
+            Ok(_) => outcome.succeeded += 1,
+            Err(e) => {
+                log::error!("Failed to apply {:?} to {}: {}", action, pkg_id, e);
+                outcome.failures.push(format!("{}: {}", pkg_id, e.to_string().trim()));
+            }
+        }
+    }
+    log::info!(
+        "Background changes on {}: {} succeeded, {} failed",
+        device,
+        outcome.succeeded,
+        outcome.failures.len()
+    );
+    outcome
+}
+
+/// Replace `states` with states loaded from `device`, unless another device was selected
+/// meanwhile. Returns whether the states were stored.
+fn store_background_states(states: &BackgroundStates, device: &str, loaded: HashMap<String, BackgroundState>) -> bool {
+    let Ok(mut s) = states.lock() else {
+        return false;
+    };
+    if s.device != device {
+        log::debug!("Dropping background states of {}, device changed to {}", device, s.device);
+        return false;
+    }
+    s.states = loaded;
+    true
+}
+
+/// Load the background states of all packages in a background thread, replacing `states`
+/// if `device` is still the selected one
+pub fn run_background_load_async(device: String, states: BackgroundStates, loading: Arc<Mutex<bool>>) {
+    if let Ok(mut l) = loading.lock() {
+        *l = true;
+    }
+    std::thread::spawn(move || {
+        let loaded = load_background_states(&device);
+        log::info!("Loaded background states of {} packages", loaded.len());
+        // A stale load must not end the loading of the newly selected device
+        if store_background_states(&states, &device, loaded) {
+            if let Ok(mut l) = loading.lock() {
+                *l = false;
+            }
+        }
+    });
+}
+
+/// Apply a background change to packages in a background thread, then reload the states.
+/// The device rejects some changes silently (e.g. buckets of exempted apps), so the
+/// reloaded states are what the column shows.
+pub fn run_background_actions_async(
+    device: String,
+    packages: Vec<String>,
+    action: BackgroundAction,
+    states: BackgroundStates,
+    outcome_result: Arc<Mutex<Option<BackgroundBatchOutcome>>>,
+) {
+    std::thread::spawn(move || {
+        let outcome = apply_background_actions(&device, &packages, action);
+
+        store_background_states(&states, &device, load_background_states(&device));
+        if let Ok(mut r) = outcome_result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_standby_buckets() {
+        let output = "com.android.phone: 5\ncom.example.news: 40\ncom.example.chat: 10\nNo such package\n";
+        let buckets = parse_standby_buckets(output);
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets.get("com.android.phone"), Some(&StandbyBucket::Exempted));
+        assert_eq!(buckets.get("com.example.news"), Some(&StandbyBucket::Rare));
+        assert_eq!(buckets.get("com.example.chat"), Some(&StandbyBucket::Active));
+    }
+
+    #[test]
+    fn test_parse_deviceidle_whitelist() {
+        let output = "system-excidle,com.android.shell,2000\n\
+                      system,com.android.phone,1001\n\
+                      system,com.google.android.gms,10150\n\
+                      user,com.google.android.gms,10150\n\
+                      user,com.example.alarm,10234\n";
+        let whitelist = parse_deviceidle_whitelist(output);
+        assert_eq!(whitelist.len(), 3);
+        assert_eq!(whitelist.get("com.android.shell"), None);
+        assert_eq!(whitelist.get("com.android.phone"), Some(&BatteryWhitelist::System));
+        assert_eq!(whitelist.get("com.google.android.gms"), Some(&BatteryWhitelist::System));
+        assert_eq!(whitelist.get("com.example.alarm"), Some(&BatteryWhitelist::User));
+    }
+
+    #[test]
+    fn test_store_background_states_drops_stale_device() {
+        let states: BackgroundStates = Arc::new(Mutex::new(DeviceBackgroundStates {
+            device: "new-serial".to_string(),
+            states: HashMap::new(),
+        }));
+        let loaded = HashMap::from([("com.example.news".to_string(), BackgroundState::default())]);
+
+        assert!(!store_background_states(&states, "old-serial", loaded.clone()));
+        assert!(states.lock().unwrap().states.is_empty());
+        assert!(store_background_states(&states, "new-serial", loaded));
+        assert_eq!(states.lock().unwrap().states.len(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// App standby bucket reported by `am get-standby-bucket`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StandbyBucket {
+    /// System and whitelisted apps that are never restricted
+    Exempted,
+    Active,
+    WorkingSet,
+    Frequent,
+    Rare,
+    /// Android 12+; jobs and alarms run at most once a day
+    Restricted,
+    Never,
+}
+
+impl StandbyBucket {
+    /// Buckets accepted by `am set-standby-bucket`
+    pub const SETTABLE: [StandbyBucket; 5] = [
+        StandbyBucket::Active,
+        StandbyBucket::WorkingSet,
+        StandbyBucket::Frequent,
+        StandbyBucket::Rare,
+        StandbyBucket::Restricted,
+    ];
+
+    /// Bucket from the numeric value used by UsageStatsManager
+    pub fn from_value(value: i32) -> Option<Self> {
+        match value {
+            5 => Some(StandbyBucket::Exempted),
+            10 => Some(StandbyBucket::Active),
+            20 => Some(StandbyBucket::WorkingSet),
+            30 => Some(StandbyBucket::Frequent),
+            40 => Some(StandbyBucket::Rare),
+            45 => Some(StandbyBucket::Restricted),
+            50 => Some(StandbyBucket::Never),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StandbyBucket::Exempted => "exempted",
+            StandbyBucket::Active => "active",
+            StandbyBucket::WorkingSet => "working_set",
+            StandbyBucket::Frequent => "frequent",
+            StandbyBucket::Rare => "rare",
+            StandbyBucket::Restricted => "restricted",
+            StandbyBucket::Never => "never",
+        }
+    }
+}
+
+/// Entry of a package in the battery optimization (device idle) whitelist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BatteryWhitelist {
+    /// Added by the user or with `cmd deviceidle whitelist +pkg`
+    User,
+    /// Whitelisted by the system image; cannot be removed
+    System,
+}
+
+/// Background behaviour of a package
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackgroundState {
+    pub bucket: Option<StandbyBucket>,
+    /// `None` when battery optimization applies to the package
+    pub whitelist: Option<BatteryWhitelist>,
+    /// RUN_ANY_IN_BACKGROUND is set to ignore
+    pub restricted: bool,
+}
+
+/// Change to the background behaviour of a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundAction {
+    SetBucket(StandbyBucket),
+    /// Exempt from battery optimization
+    Whitelist,
+    /// Subject to battery optimization again
+    Unwhitelist,
+    /// Block running in the background
+    Restrict,
+    Unrestrict,
+}
+
+/// Outcome of a batch of background changes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackgroundBatchOutcome {
+    pub succeeded: usize,
+    /// One line per failed change
+    pub failures: Vec<String>,
+}
+
+/// Background states of the installed packages of one device
+#[derive(Debug, Clone, Default)]
+pub struct DeviceBackgroundStates {
+    /// Serial of the selected device; states loaded from any other device are dropped
+    pub device: String,
+    /// By package name
+    pub states: HashMap<String, BackgroundState>,
+}
+
+pub type BackgroundStates = Arc<Mutex<DeviceBackgroundStates>>;
@@ -0,0 +1,308 @@
+pub use crate::calc_batterystats_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::calc_background_stt::{BackgroundAction, BackgroundBatchOutcome};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const PER_USER_RANGE: u32 = 100_000;
+const FIRST_APPLICATION_UID: u32 = 10_000;
+
+/// Numeric uid from the batterystats notation ("u0a190" = 10190, "u10a5" = 1010005, "1000")
+pub fn parse_uid(token: &str) -> Option<u32> {
+    if let Ok(uid) = token.parse::<u32>() {
+        return Some(uid);
+    }
+    let (user, app) = token.strip_prefix('u')?.split_once('a')?;
+    Some(user.parse::<u32>().ok()? * PER_USER_RANGE + FIRST_APPLICATION_UID + app.parse::<u32>().ok()?)
+}
+
+/// Milliseconds from a batterystats duration ("1h 2m 3s 456ms"); parsing stops at the first other token
+pub fn parse_duration_ms(text: &str) -> u64 {
+    let mut total = 0;
+    for token in text.split_whitespace() {
+        let split = token.find(|c: char| !c.is_ascii_digit()).unwrap_or(token.len());
+        let (number, unit) = token.split_at(split);
+        let Ok(number) = number.parse::<u64>() else {
+            break;
+        };
+        total += number
+            * match unit {
+                "d" => 86_400_000,
+                "h" => 3_600_000,
+                "m" => 60_000,
+                "s" => 1_000,
+                "ms" => 1,
+                _ => break,
+            };
+    }
+    total
+}
+
+/// Count from "(12 times)" or ": 12 times"
+fn parse_times(text: &str) -> u64 {
+    text.split_once(" times")
+        .and_then(|(before, _)| before.rsplit(['(', ' ']).next())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse `pm list packages -U` output into packages per app id (uid without the user part)
+pub fn parse_package_uids(output: &str) -> HashMap<u32, Vec<String>> {
+    let mut packages: HashMap<u32, Vec<String>> = HashMap::new();
+    for line in output.lines() {
+        let Some(rest) = line.trim().strip_prefix("package:") else {
+            continue;
+        };
+        let Some((pkg, uid)) = rest.split_once(" uid:") else {
+            continue;
+        };
+        // Multi-user devices may list several uids ("uid:10190,1010190")
+        let Some(uid) = uid.split(',').next().and_then(|u| u.trim().parse::<u32>().ok()) else {
+            continue;
+        };
+        packages.entry(uid % PER_USER_RANGE).or_default().push(pkg.trim().to_string());
+    }
+    for list in packages.values_mut() {
+        list.sort();
+    }
+    packages
+}
+
+/// Parse `dumpsys batterystats --charged` output into per-uid usage.
+///
+/// Power estimates come from the "Estimated power use" lines ("UID u0a190: 8.56 ( cpu=... )");
+/// wakelocks, wakeup alarms and jobs from the per-uid sections that start with a "u0a190:" header.
+pub fn parse_batterystats(output: &str) -> HashMap<u32, UidBatteryUsage> {
+    let mut usage: HashMap<u32, UidBatteryUsage> = HashMap::new();
+    // Uid of the current per-uid section and the indentation of its header
+    let mut section: Option<(u32, usize)> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(rest) = trimmed.strip_prefix("UID ") {
+            if let Some((uid, power)) = rest.split_once(':') {
+                if let (Some(uid), Some(power)) = (
+                    parse_uid(uid.trim()),
+                    power.split_whitespace().next().and_then(|p| p.parse::<f64>().ok()),
+                ) {
+                    usage.entry(uid).or_insert_with(|| UidBatteryUsage { uid, ..Default::default() }).power_mah = power;
+                }
+            }
+            continue;
+        }
+
+        if let Some(uid) = trimmed.strip_suffix(':').and_then(parse_uid) {
+            section = Some((uid, indent));
+            continue;
+        }
+        let Some((uid, header_indent)) = section else {
+            continue;
+        };
+        if indent <= header_indent {
+            section = None;
+            continue;
+        }
+        let entry = usage.entry(uid).or_insert_with(|| UidBatteryUsage { uid, ..Default::default() });
+
+        if let Some(rest) = trimmed.strip_prefix("Wake lock ") {
+            // "Wake lock *alarm*: 1s 234ms partial (12 times) max=5000 realtime"
+            if let Some((held, after)) = rest.split_once(" partial") {
+                let duration = held.rsplit_once(": ").map(|(_, d)| d).unwrap_or(held);
+                entry.wakelock_ms += parse_duration_ms(duration);
+                entry.wakelock_count += parse_times(after);
+            }
+        } else if trimmed.starts_with("Wakeup alarm ") {
+            entry.alarm_wakeups += parse_times(trimmed);
+        } else if let Some(rest) = trimmed.strip_prefix("Job ") {
+            // "Job com.example/.SyncJob: 1m 2s 345ms realtime (4 times)"; skips "Job Completions ..."
+            if let Some((_, stats)) = rest.split_once(": ") {
+                if !rest.starts_with("Completions") {
+                    entry.job_ms += parse_duration_ms(stats);
+                    entry.job_count += parse_times(stats);
+                }
+            }
+        }
+    }
+
+    usage
+}
+
+/// Attach packages to the uids and drop uids that do not map to any package
+pub fn map_packages(usage: HashMap<u32, UidBatteryUsage>, package_uids: &HashMap<u32, Vec<String>>) -> Vec<UidBatteryUsage> {
+    usage
+        .into_values()
+        .filter_map(|mut u| {
+            u.packages = package_uids.get(&(u.uid % PER_USER_RANGE))?.clone();
+            Some(u)
+        })
+        .collect()
+}
+
+/// Package of a row that may be disabled from the report. Shared and core uids (`android`,
+/// `com.android.phone`, ...) are excluded: disabling them can leave the device unusable.
+pub fn disable_target(row: &UidBatteryUsage, installed: &[PackageFingerprint]) -> Option<String> {
+    if row.uid % PER_USER_RANGE < FIRST_APPLICATION_UID {
+        return None;
+    }
+    let [pkg_id] = row.packages.as_slice() else {
+        return None;
+    };
+    installed
+        .iter()
+        .find(|p| &p.pkg == pkg_id)
+        .filter(|p| !p.flags.contains("SYSTEM"))
+        .map(|p| p.pkg.clone())
+}
+
+/// Sort the worst offenders for a metric first
+pub fn rank(rows: &mut [UidBatteryUsage], ranking: BatteryRanking) {
+    rows.sort_by(|a, b| match ranking {
+        BatteryRanking::Power => b.power_mah.total_cmp(&a.power_mah),
+        BatteryRanking::WakelockTime => b.wakelock_ms.cmp(&a.wakelock_ms),
+        BatteryRanking::AlarmWakeups => b.alarm_wakeups.cmp(&a.alarm_wakeups),
+        BatteryRanking::Jobs => b.job_count.cmp(&a.job_count),
+    });
+}
+
+/// Read battery stats since the last charge and map them to packages
+pub fn load_battery_report(device: &str) -> Result<Vec<UidBatteryUsage>, String> {
+    let stats = crate::adb::batterystats_charged(device).map_err(|e| e.to_string().trim().to_string())?;
+    let uids = crate::adb::get_package_uids(device).map_err(|e| e.to_string().trim().to_string())?;
+    let mut rows = map_packages(parse_batterystats(&stats), &parse_package_uids(&uids));
+
+    let installed = crate::shared_store_stt::get_shared_store().get_installed_packages();
+    for row in rows.iter_mut() {
+        row.disable_target = disable_target(row, &installed);
+    }
+    Ok(rows)
+}
+
+/// Load the battery report in a background thread
+pub fn run_battery_report_async(device: String, result: BatteryStatsResult) {
+    std::thread::spawn(move || {
+        let report = load_battery_report(&device);
+        match &report {
+            Ok(rows) => log::info!("Loaded battery stats of {} uids", rows.len()),
+            Err(e) => log::error!("Failed to read battery stats: {}", e),
+        }
+        if let Ok(mut r) = result.lock() {
+            *r = Some(report);
+        }
+    });
+}
+
+/// Apply a background change to the packages of a uid in a background thread
+pub fn run_battery_action_async(
+    device: String,
+    packages: Vec<String>,
+    action: BackgroundAction,
+    outcome_result: Arc<Mutex<Option<BackgroundBatchOutcome>>>,
+) {
+    std::thread::spawn(move || {
+        let outcome = crate::calc_background::apply_background_actions(&device, &packages, action);
+        if let Ok(mut r) = outcome_result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "Statistics since last charge:\n\
+        \x20 Estimated power use (mAh):\n\
+        \x20   Capacity: 4000, Computed drain: 310, actual drain: 300-320\n\
+        \x20   UID u0a190: 42.5 fg: 10.1 bg: 32.4 ( cpu=20.1 wifi=22.4 )\n\
+        \x20   UID 1000: 12.0 ( cpu=12.0 )\n\
+        \x20   UID u0a77: 1.25\n\
+        \x20 u0a190:\n\
+        \x20   Wake lock *alarm*: 3s 500ms partial (10 times) max=900 realtime\n\
+        \x20   Wake lock SyncLoop: 1m 2s partial (5 times) realtime\n\
+        \x20   Wake lock Screen: 10s full (1 times) realtime\n\
+        \x20   Job com.example.ads/.SyncJob: 1m 5s 20ms realtime (4 times)\n\
+        \x20   Job Completions com.example.ads/.SyncJob: canceled(1x)\n\
+        \x20   Apk com.example.ads:\n\
+        \x20     Wakeup alarm *walarm*:com.example.ads.PING: 12 times\n\
+        \x20     Wakeup alarm *walarm*:com.example.ads.SYNC: 0ms realtime (3 times)\n\
+        \x20 u0a77:\n\
+        \x20   Wake lock job/com.example.notes: 200ms partial (1 times) realtime\n\
+        Daily stats:\n\
+        \x20 Wake lock unrelated: 5s partial (9 times)\n";
+
+    #[test]
+    fn test_parse_uid_and_duration() {
+        assert_eq!(parse_uid("u0a190"), Some(10190));
+        assert_eq!(parse_uid("u10a5"), Some(1010005));
+        assert_eq!(parse_uid("1000"), Some(1000));
+        assert_eq!(parse_uid("u0i12"), None);
+        assert_eq!(parse_duration_ms("1h 2m 3s 456ms realtime"), 3_723_456);
+        assert_eq!(parse_duration_ms("0ms"), 0);
+    }
+
+    #[test]
+    fn test_parse_batterystats() {
+        let usage = parse_batterystats(DUMP);
+
+        let ads = &usage[&10190];
+        assert_eq!(ads.power_mah, 42.5);
+        assert_eq!(ads.wakelock_count, 15);
+        assert_eq!(ads.wakelock_ms, 65_500);
+        assert_eq!(ads.job_count, 4);
+        assert_eq!(ads.job_ms, 65_020);
+        assert_eq!(ads.alarm_wakeups, 15);
+
+        let notes = &usage[&10077];
+        assert_eq!(notes.wakelock_count, 1);
+        assert_eq!(notes.wakelock_ms, 200);
+        assert_eq!(usage[&1000].power_mah, 12.0);
+    }
+
+    #[test]
+    fn test_map_and_rank() {
+        let uids = parse_package_uids(
+            "package:com.example.ads uid:10190\n\
+             package:com.example.notes uid:10077\n\
+             package:android uid:1000\n\
+             package:com.android.settings uid:1000\n",
+        );
+        assert_eq!(uids[&1000], vec!["android".to_string(), "com.android.settings".to_string()]);
+
+        let mut usage = parse_batterystats(DUMP);
+        usage.insert(10999, UidBatteryUsage { uid: 10999, power_mah: 99.0, ..Default::default() });
+        let mut rows = map_packages(usage, &uids);
+        assert_eq!(rows.len(), 3);
+
+        rank(&mut rows, BatteryRanking::Power);
+        assert_eq!(rows[0].packages, vec!["com.example.ads".to_string()]);
+        rank(&mut rows, BatteryRanking::WakelockTime);
+        assert_eq!(rows[2].uid, 1000);
+    }
+
+    #[test]
+    fn test_disable_target() {
+        let installed = vec![
+            PackageFingerprint::fixture("com.example.ads", "", ""),
+            PackageFingerprint::fixture("com.android.phone", "[ SYSTEM ]", ""),
+            PackageFingerprint::fixture("android", "[ SYSTEM ]", ""),
+        ];
+        let row = |uid: u32, packages: &[&str]| UidBatteryUsage {
+            uid,
+            packages: packages.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(disable_target(&row(10190, &["com.example.ads"]), &installed), Some("com.example.ads".to_string()));
+        // Same app in a secondary user
+        assert_eq!(disable_target(&row(1010190, &["com.example.ads"]), &installed), Some("com.example.ads".to_string()));
+        assert_eq!(disable_target(&row(10001, &["com.android.phone"]), &installed), None);
+        assert_eq!(disable_target(&row(1000, &["android"]), &installed), None);
+        assert_eq!(disable_target(&row(10050, &["com.example.ads", "com.example.other"]), &installed), None);
+        assert_eq!(disable_target(&row(10051, &["com.example.missing"]), &installed), None);
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::{Arc, Mutex};
+
+/// Battery usage of one uid since the last full charge, from `dumpsys batterystats --charged`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UidBatteryUsage {
+    pub uid: u32,
+    /// Packages running under the uid (several for shared uids)
+    pub packages: Vec<String>,
+    /// Estimated power use in mAh
+    pub power_mah: f64,
+    /// Partial wakelock acquisitions and total held time
+    pub wakelock_count: u64,
+    pub wakelock_ms: u64,
+    /// Alarms that woke the device up
+    pub alarm_wakeups: u64,
+    /// Job runs and total run time
+    pub job_count: u64,
+    pub job_ms: u64,
+    /// Package offered for disabling: set only for app uids with a single non-system package
+    pub disable_target: Option<String>,
+}
+
+/// Metric the battery report is ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryRanking {
+    Power,
+    WakelockTime,
+    AlarmWakeups,
+    Jobs,
+}
+
+impl BatteryRanking {
+    pub const ALL: [BatteryRanking; 4] = [
+        BatteryRanking::Power,
+        BatteryRanking::WakelockTime,
+        BatteryRanking::AlarmWakeups,
+        BatteryRanking::Jobs,
+    ];
+}
+
+/// Parsed battery report; `Err` holds the adb error
+pub type BatteryStatsResult = Arc<Mutex<Option<Result<Vec<UidBatteryUsage>, String>>>>;
@@ -0,0 +1,338 @@
+pub use crate::calc_capabilities_stt::*;
+use crate::calc_stalkerware::{parse_component_setting, parse_device_admins};
+use std::collections::HashMap;
+
+/// Whether a token looks like an Android package name (`com.example.app`)
+fn is_package_name(token: &str) -> bool {
+    token.contains('.')
+        && !token.starts_with('.')
+        && !token.ends_with('.')
+        && token.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+/// Extract package names from `cmd appops query-op` or `cmd role get-role-holders`
+/// output. Both print bare package names; roles are joined with `;`.
+pub fn parse_package_list(output: &str) -> Vec<String> {
+    let mut packages: Vec<String> = output
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .filter(|t| is_package_name(t))
+        .map(|t| t.to_string())
+        .collect();
+    packages.sort();
+    packages.dedup();
+    packages
+}
+
+/// Package name part of a `pkg/class` component
+fn component_package(component: &str) -> &str {
+    component.split('/').next().unwrap_or(component)
+}
+
+/// Collect every audited capability from a device. Failed queries are recorded in
+/// `errors` and treated as "no holders" so one unsupported command does not hide the rest.
+pub fn collect_capability_signals(device: &str) -> DeviceCapabilitySignals {
+    let queries = std::cell::Cell::new(0);
+    let errors = std::cell::RefCell::new(Vec::new());
+    let record = |query: String, e: std::io::Error| {
+        log::warn!("Failed to {}: {}", query, e);
+        errors.borrow_mut().push(format!("{}: {}", query, e.to_string().trim()));
+    };
+    let secure_setting = |key: &str| {
+        queries.set(queries.get() + 1);
+        crate::adb::get_secure_setting(device, key).unwrap_or_else(|e| {
+            record(format!("read secure setting {}", key), e);
+            String::new()
+        })
+    };
+    let app_op_packages = |kind: CapabilityKind| {
+        let op = kind.app_op().unwrap_or_default();
+        queries.set(queries.get() + 1);
+        crate::adb::query_app_op_packages(device, op, "allow")
+            .map(|o| parse_package_list(&o))
+            .unwrap_or_else(|e| {
+                record(format!("query app op {}", op), e);
+                Vec::new()
+            })
+    };
+    // Roles need Android 10+; older releases keep the default apps in secure settings
+    let role_holders = |kind: CapabilityKind, fallback_key: &str| {
+        let role = kind.role().unwrap_or_default();
+        let holders = crate::adb::get_role_holders(device, role)
+            .map(|o| parse_package_list(&o))
+            .unwrap_or_default();
+        if holders.is_empty() {
+            parse_package_list(&secure_setting(fallback_key))
+        } else {
+            holders
+        }
+    };
+
+    let device_admins = {
+        queries.set(queries.get() + 1);
+        crate::adb::get_device_policy_dump(device)
+            .map(|d| parse_device_admins(&d))
+            .unwrap_or_else(|e| {
+                record("read device policy".to_string(), e);
+                Vec::new()
+            })
+    };
+
+    let mut signals = DeviceCapabilitySignals {
+        accessibility_services: parse_component_setting(&secure_setting("enabled_accessibility_services")),
+        device_admins,
+        notification_listeners: parse_component_setting(&secure_setting("enabled_notification_listeners")),
+        vpn_packages: app_op_packages(CapabilityKind::Vpn),
+        always_on_vpn: parse_package_list(&secure_setting("always_on_vpn_app")).into_iter().next(),
+        sms_holders: role_holders(CapabilityKind::SmsRole, "sms_default_application"),
+        dialer_holders: role_holders(CapabilityKind::DialerRole, "dialer_default_application"),
+        usage_stats_packages: app_op_packages(CapabilityKind::UsageStats),
+        overlay_packages: app_op_packages(CapabilityKind::Overlay),
+        install_packages: app_op_packages(CapabilityKind::InstallUnknownSources),
+        ..Default::default()
+    };
+    signals.queries = queries.get();
+    signals.errors = errors.into_inner();
+    signals
+}
+
+/// Turn collected signals into a per-app capability list
+pub fn build_capability_audit(
+    device_serial: &str,
+    packages: &[crate::adb::PackageFingerprint],
+    labels: &HashMap<String, String>,
+    signals: &DeviceCapabilitySignals,
+) -> CapabilityAudit {
+    let system_packages: HashMap<&str, bool> = packages
+        .iter()
+        .map(|p| (p.pkg.as_str(), p.flags.contains("SYSTEM")))
+        .collect();
+    let grant = |kind: CapabilityKind, pkg_id: &str, component: Option<&String>, always_on: bool| CapabilityGrant {
+        kind,
+        pkg_id: pkg_id.to_string(),
+        component: component.cloned(),
+        label: labels.get(pkg_id).cloned(),
+        is_system: system_packages.get(pkg_id).copied().unwrap_or(false),
+        always_on,
+    };
+
+    let mut grants: Vec<CapabilityGrant> = Vec::new();
+    let components = [
+        (CapabilityKind::Accessibility, &signals.accessibility_services),
+        (CapabilityKind::DeviceAdmin, &signals.device_admins),
+        (CapabilityKind::NotificationListener, &signals.notification_listeners),
+    ];
+    for (kind, list) in components {
+        for component in list {
+            grants.push(grant(kind, component_package(component), Some(component), false));
+        }
+    }
+
+    let mut vpn_packages = signals.vpn_packages.clone();
+    if let Some(always_on) = &signals.always_on_vpn {
+        if !vpn_packages.contains(always_on) {
+            vpn_packages.push(always_on.clone());
+        }
+    }
+    for pkg in &vpn_packages {
+        let always_on = signals.always_on_vpn.as_deref() == Some(pkg.as_str());
+        grants.push(grant(CapabilityKind::Vpn, pkg, None, always_on));
+    }
+
+    let package_lists = [
+        (CapabilityKind::SmsRole, &signals.sms_holders),
+        (CapabilityKind::DialerRole, &signals.dialer_holders),
+        (CapabilityKind::UsageStats, &signals.usage_stats_packages),
+        (CapabilityKind::Overlay, &signals.overlay_packages),
+        (CapabilityKind::InstallUnknownSources, &signals.install_packages),
+    ];
+    for (kind, list) in package_lists {
+        for pkg in list {
+            grants.push(grant(kind, pkg, None, false));
+        }
+    }
+
+    grants.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then_with(|| a.pkg_id.cmp(&b.pkg_id))
+            .then_with(|| a.component.cmp(&b.component))
+    });
+    grants.dedup();
+
+    CapabilityAudit {
+        device_serial: device_serial.to_string(),
+        generated_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+        grants,
+        errors: signals.errors.clone(),
+    }
+}
+
+/// Generate the capability audit for a device in a background thread
+pub fn run_capability_audit_async(
+    device: String,
+    result: std::sync::Arc<std::sync::Mutex<Option<Result<CapabilityAudit, String>>>>,
+) {
+    std::thread::spawn(move || {
+        let store = crate::shared_store_stt::get_shared_store();
+        let packages = store.get_installed_packages();
+        let labels = crate::calc_stalkerware::collect_package_labels(&packages);
+        let signals = collect_capability_signals(&device);
+        let audit = if signals.queries > 0 && signals.errors.len() == signals.queries {
+            // Nothing could be read, so an empty audit would look like a clean device
+            log::error!("Capability audit for {} failed: every query failed", device);
+            Err(signals.errors.join("\n"))
+        } else {
+            let audit = build_capability_audit(&device, &packages, &labels, &signals);
+            log::info!(
+                "Capability audit for {}: {} grants, {} failed queries",
+                device,
+                audit.grants.len(),
+                audit.errors.len()
+            );
+            Ok(audit)
+        };
+        if let Ok(mut r) = result.lock() {
+            *r = Some(audit);
+        }
+    });
+}
+
+/// Remove a component from a colon-separated secure setting value
+pub fn remove_component_from_setting(value: &str, component: &str) -> String {
+    parse_component_setting(value)
+        .into_iter()
+        .filter(|c| c != component)
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Whether `dpm remove-active-admin` refused to remove an admin. The shell can only remove
+/// admins of testOnly apps; other admins must be deactivated in Settings by the user.
+pub fn is_non_test_admin_error(output: &str) -> bool {
+    output.contains("non-test admin") || output.contains("SecurityException")
+}
+
+/// Revoke a single capability grant without touching the rest of the app.
+/// Fails with `PermissionDenied` for device admins the shell cannot remove.
+pub fn revoke_capability(device: &str, grant: &CapabilityGrant) -> std::io::Result<String> {
+    let component = || {
+        grant.component.as_deref().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "Grant has no component")
+        })
+    };
+    match grant.kind {
+        CapabilityKind::Accessibility => {
+            let key = "enabled_accessibility_services";
+            let current = crate::adb::get_secure_setting(device, key)?;
+            crate::adb::put_secure_setting(device, key, &remove_component_from_setting(&current, component()?))
+        }
+        CapabilityKind::NotificationListener => crate::adb::disallow_notification_listener(device, component()?),
+        CapabilityKind::DeviceAdmin => {
+            let output = crate::adb::remove_active_admin(device, component()?).map_err(|e| {
+                if is_non_test_admin_error(&e.to_string()) {
+                    std::io::Error::new(std::io::ErrorKind::PermissionDenied, e.to_string())
+                } else {
+                    e
+                }
+            })?;
+            // Shizuku returns the exception text as output
+            if is_non_test_admin_error(&output) {
+                return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, output.trim().to_string()));
+            }
+            Ok(output)
+        }
+        CapabilityKind::SmsRole | CapabilityKind::DialerRole => {
+            crate::adb::remove_role_holder(device, grant.kind.role().unwrap_or_default(), &grant.pkg_id)
+        }
+        CapabilityKind::Vpn => {
+            if grant.always_on {
+                crate::adb::put_secure_setting(device, "always_on_vpn_app", "")?;
+            }
+            crate::adb::set_app_op(device, &grant.pkg_id, "ACTIVATE_VPN", "ignore")
+        }
+        CapabilityKind::UsageStats | CapabilityKind::Overlay | CapabilityKind::InstallUnknownSources => {
+            crate::adb::set_app_op(device, &grant.pkg_id, grant.kind.app_op().unwrap_or_default(), "ignore")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::PackageFingerprint;
+
+    #[test]
+    fn test_parse_package_list() {
+        assert_eq!(
+            parse_package_list("com.b.app\ncom.a.app\ncom.b.app\n"),
+            vec!["com.a.app".to_string(), "com.b.app".to_string()]
+        );
+        assert_eq!(
+            parse_package_list("com.google.android.apps.messaging;com.other.sms\n"),
+            vec!["com.google.android.apps.messaging".to_string(), "com.other.sms".to_string()]
+        );
+        assert!(parse_package_list("No operations.\n").is_empty());
+        assert!(parse_package_list("null").is_empty());
+    }
+
+    #[test]
+    fn test_remove_component_from_setting() {
+        assert_eq!(
+            remove_component_from_setting("com.a/.Svc:com.b/com.b.Other", "com.a/.Svc"),
+            "com.b/com.b.Other"
+        );
+        assert_eq!(remove_component_from_setting("com.a/.Svc", "com.a/.Svc"), "");
+    }
+
+    #[test]
+    fn test_is_non_test_admin_error() {
+        assert!(is_non_test_admin_error(
+            "Exception occurred while executing 'remove-active-admin':\n\
+             java.lang.SecurityException: Attempt to remove non-test admin ComponentInfo{com.spy.app/com.spy.app.Admin} 0"
+        ));
+        assert!(!is_non_test_admin_error("Success: Admin removed ComponentInfo{com.test/.Admin}"));
+    }
+
+    #[test]
+    fn test_build_capability_audit() {
+        let packages = vec![
+            PackageFingerprint::fixture("com.spy.app", "", ""),
+            PackageFingerprint::fixture("com.android.phone", "[ SYSTEM ]", ""),
+        ];
+        let labels = HashMap::from([("com.spy.app".to_string(), "Sync".to_string())]);
+        let signals = DeviceCapabilitySignals {
+            accessibility_services: vec!["com.spy.app/.Access".to_string()],
+            notification_listeners: vec!["com.spy.app/.Notif".to_string()],
+            vpn_packages: vec!["com.spy.app".to_string()],
+            always_on_vpn: Some("com.spy.app".to_string()),
+            dialer_holders: vec!["com.android.phone".to_string()],
+            overlay_packages: vec!["com.spy.app".to_string()],
+            ..Default::default()
+        };
+        let audit = build_capability_audit("dev", &packages, &labels, &signals);
+
+        assert_eq!(audit.grants.len(), 5);
+        let kinds: Vec<CapabilityKind> = audit.grants.iter().map(|g| g.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CapabilityKind::Accessibility,
+                CapabilityKind::NotificationListener,
+                CapabilityKind::Vpn,
+                CapabilityKind::DialerRole,
+                CapabilityKind::Overlay,
+            ]
+        );
+        let access = &audit.grants[0];
+        assert_eq!(access.pkg_id, "com.spy.app");
+        assert_eq!(access.component.as_deref(), Some("com.spy.app/.Access"));
+        assert_eq!(access.label.as_deref(), Some("Sync"));
+        assert!(audit.grants[2].always_on);
+        assert!(audit.grants[3].is_system);
+        assert_eq!(audit.grants_of(CapabilityKind::Vpn).count(), 1);
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// Powerful capability an app can hold on a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CapabilityKind {
+    /// Enabled accessibility service (can read the screen and inject input)
+    Accessibility,
+    /// Active device administrator (can resist uninstall, lock or wipe the device)
+    DeviceAdmin,
+    /// Enabled notification listener (can read incoming messages)
+    NotificationListener,
+    /// Consented or always-on VPN (can see and redirect network traffic)
+    Vpn,
+    /// Default SMS app role
+    SmsRole,
+    /// Default phone (dialer) app role
+    DialerRole,
+    /// Usage access (`GET_USAGE_STATS` app op)
+    UsageStats,
+    /// Draw over other apps (`SYSTEM_ALERT_WINDOW` app op)
+    Overlay,
+    /// Install unknown apps (`REQUEST_INSTALL_PACKAGES` app op)
+    InstallUnknownSources,
+}
+
+impl CapabilityKind {
+    /// All kinds in the order they are listed in the audit
+    pub const ALL: [CapabilityKind; 9] = [
+        CapabilityKind::Accessibility,
+        CapabilityKind::DeviceAdmin,
+        CapabilityKind::NotificationListener,
+        CapabilityKind::Vpn,
+        CapabilityKind::SmsRole,
+        CapabilityKind::DialerRole,
+        CapabilityKind::UsageStats,
+        CapabilityKind::Overlay,
+        CapabilityKind::InstallUnknownSources,
+    ];
+
+    /// App op that backs this capability, if it is granted through app ops
+    pub fn app_op(&self) -> Option<&'static str> {
+        match self {
+            CapabilityKind::Vpn => Some("ACTIVATE_VPN"),
+            CapabilityKind::UsageStats => Some("GET_USAGE_STATS"),
+            CapabilityKind::Overlay => Some("SYSTEM_ALERT_WINDOW"),
+            CapabilityKind::InstallUnknownSources => Some("REQUEST_INSTALL_PACKAGES"),
+            _ => None,
+        }
+    }
+
+    /// Role name for role-based capabilities
+    pub fn role(&self) -> Option<&'static str> {
+        match self {
+            CapabilityKind::SmsRole => Some("android.app.role.SMS"),
+            CapabilityKind::DialerRole => Some("android.app.role.DIALER"),
+            _ => None,
+        }
+    }
+}
+
+/// One app holding one capability
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityGrant {
+    pub kind: CapabilityKind,
+    pub pkg_id: String,
+    /// Component (`pkg/class`) for component-based capabilities
+    pub component: Option<String>,
+    pub label: Option<String>,
+    pub is_system: bool,
+    /// VPN is set as always-on, so it reconnects on boot
+    pub always_on: bool,
+}
+
+/// Raw capability state collected over adb
+#[derive(Debug, Clone, Default)]
+pub struct DeviceCapabilitySignals {
+    pub accessibility_services: Vec<String>,
+    pub device_admins: Vec<String>,
+    pub notification_listeners: Vec<String>,
+    /// Packages the user allowed to establish a VPN
+    pub vpn_packages: Vec<String>,
+    /// Package set as always-on VPN, if any
+    pub always_on_vpn: Option<String>,
+    pub sms_holders: Vec<String>,
+    pub dialer_holders: Vec<String>,
+    pub usage_stats_packages: Vec<String>,
+    pub overlay_packages: Vec<String>,
+    pub install_packages: Vec<String>,
+    /// Number of adb queries run
+    pub queries: usize,
+    /// Failed queries, as "query: error"
+    pub errors: Vec<String>,
+}
+
+/// Device-wide capability audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityAudit {
+    pub device_serial: String,
+    /// When the audit was generated (Unix timestamp)
+    pub generated_at: i64,
+    /// Grants sorted by kind, then package
+    pub grants: Vec<CapabilityGrant>,
+    /// Queries that failed; their capabilities may be missing from `grants`
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl CapabilityAudit {
+    /// Grants of a single kind
+    pub fn grants_of(&self, kind: CapabilityKind) -> impl Iterator<Item = &CapabilityGrant> {
+        self.grants.iter().filter(move |g| g.kind == kind)
+    }
+}
@@ -0,0 +1,235 @@
+pub use crate::calc_components_stt::*;
+use crate::calc_axml_stt::{ApkManifest, ManifestLoadState};
+use crate::calc_trackers_stt::TrackerSignatures;
+use crate::shared_store_stt::get_shared_store;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Components are listed and changed for the owner user, like the other debloat actions
+const USER_ID: i32 = 0;
+
+/// Components listed under `disabledComponents:` for one user in `dumpsys package <pkg>` output
+pub fn parse_disabled_components(dump: &str, user_id: i32) -> HashSet<String> {
+    let user_header = format!("User {}:", user_id);
+    let mut disabled = HashSet::new();
+    let mut in_user = false;
+    // Indentation of the `disabledComponents:` header while reading its entries
+    let mut list_indent: Option<usize> = None;
+
+    for line in dump.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(header_indent) = list_indent {
+            if indent > header_indent {
+                disabled.insert(trimmed.to_string());
+                continue;
+            }
+            list_indent = None;
+        }
+
+        if trimmed.starts_with("User ") && trimmed.contains(':') {
+            in_user = trimmed.starts_with(&user_header);
+        } else if in_user && trimmed == "disabledComponents:" {
+            list_indent = Some(indent);
+        }
+    }
+
+    disabled
+}
+
+/// Name of the tracker whose class prefixes match a component class
+pub fn tracker_for_component<'a>(class_name: &str, signatures: &'a TrackerSignatures) -> Option<&'a str> {
+    signatures
+        .trackers
+        .iter()
+        .find(|t| t.code_prefixes.iter().any(|p| class_name.starts_with(p.as_str())))
+        .map(|t| t.name.as_str())
+}
+
+/// Combine the manifest components with the disabled set reported by the device
+pub fn manifest_components(
+    manifest: &ApkManifest,
+    disabled: &HashSet<String>,
+    signatures: Option<&TrackerSignatures>,
+) -> Vec<AppComponent> {
+    ComponentKind::ALL
+        .into_iter()
+        .flat_map(|kind| {
+            let components = match kind {
+                ComponentKind::Activity => &manifest.activities,
+                ComponentKind::Service => &manifest.services,
+                ComponentKind::Receiver => &manifest.receivers,
+                ComponentKind::Provider => &manifest.providers,
+            };
+            components.iter().map(move |c| AppComponent {
+                kind,
+                name: c.name.clone(),
+                exported: c.is_exported(),
+                enabled: !disabled.contains(&c.name),
+                tracker: signatures
+                    .and_then(|s| tracker_for_component(&c.name, s))
+                    .map(|name| name.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Read the components of a package from its manifest and their state from the device
+pub fn get_components(device: &str, pkg_id: &str) -> Result<Vec<AppComponent>, String> {
+    let store = get_shared_store();
+    let manifest = match store.get_apk_manifest_state(pkg_id) {
+        Some(ManifestLoadState::Loaded(manifest)) => manifest,
+        _ => {
+            let manifest = crate::calc_axml::analyze_package_manifest(device, pkg_id)?;
+            store.set_apk_manifest_state(pkg_id.to_string(), ManifestLoadState::Loaded(manifest.clone()));
+            manifest
+        }
+    };
+
+    let dump = crate::adb::dump_package(device, pkg_id).map_err(|e| e.to_string().trim().to_string())?;
+    let disabled = parse_disabled_components(&dump, USER_ID);
+    let signatures = store.get_tracker_signatures();
+    Ok(manifest_components(&manifest, &disabled, signatures.as_ref()))
+}
+
+/// Load the components of packages in a background thread, replacing their entries in `results`
+pub fn run_components_load_async(device: String, packages: Vec<String>, results: ComponentsResults) {
+    std::thread::spawn(move || {
+        for pkg_id in packages {
+            let components = get_components(&device, &pkg_id);
+            if let Err(e) = &components {
+                log::error!("Failed to read components of {}: {}", pkg_id, e);
+            }
+            if let Ok(mut r) = results.lock() {
+                r.insert(pkg_id, components);
+            }
+        }
+    });
+}
+
+/// Enable or disable components in a background thread, then reload the changed packages
+pub fn run_component_changes_async(
+    device: String,
+    changes: Vec<ComponentChange>,
+    outcome_result: Arc<Mutex<Option<ComponentBatchOutcome>>>,
+    results: ComponentsResults,
+) {
+    std::thread::spawn(move || {
+        let mut outcome = ComponentBatchOutcome::default();
+        let mut changed: Vec<String> = Vec::new();
+        for change in &changes {
+            let result = if change.enable {
+                crate::adb::enable_component(&device, &change.pkg_id, &change.component, USER_ID)
+            } else {
+                crate::adb::disable_component(&device, &change.pkg_id, &change.component, USER_ID)
+            };
+            match result {
+                Ok(_) => {
+                    outcome.succeeded += 1;
+                    if !changed.contains(&change.pkg_id) {
+                        changed.push(change.pkg_id.clone());
+                    }
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to {} {}/{}: {}",
+                        if change.enable { "enable" } else { "disable" },
+                        change.pkg_id,
+                        change.component,
+                        e
+                    );
+                    outcome
+                        .failures
+                        .push(format!("{}/{}: {}", change.pkg_id, change.component, e.to_string().trim()));
+                }
+            }
+        }
+        log::info!(
+            "Component changes on {}: {} succeeded, {} failed",
+            device,
+            outcome.succeeded,
+            outcome.failures.len()
+        );
+
+        for pkg_id in changed {
+            let components = get_components(&device, &pkg_id);
+            if let Ok(mut r) = results.lock() {
+                r.insert(pkg_id, components);
+            }
+        }
+        if let Ok(mut r) = outcome_result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc_axml_stt::ManifestComponent;
+    use crate::calc_trackers_stt::TrackerSignature;
+
+    #[test]
+    fn test_parse_disabled_components() {
+        let dump = "Packages:\n\
+                    \x20 Package [com.example] (1a2b3c):\n\
+                    \x20   User 0: ceDataInode=123 installed=true hidden=false\n\
+                    \x20     gids=[3003]\n\
+                    \x20     disabledComponents:\n\
+                    \x20       com.example.analytics.Collector\n\
+                    \x20       com.example.BootReceiver\n\
+                    \x20     enabledComponents:\n\
+                    \x20       com.example.Main\n\
+                    \x20   User 10: ceDataInode=456 installed=true hidden=false\n\
+                    \x20     disabledComponents:\n\
+                    \x20       com.example.Main\n";
+
+        let owner = parse_disabled_components(dump, 0);
+        assert_eq!(owner.len(), 2);
+        assert!(owner.contains("com.example.analytics.Collector"));
+        assert!(owner.contains("com.example.BootReceiver"));
+        assert!(!owner.contains("com.example.Main"));
+
+        let work = parse_disabled_components(dump, 10);
+        assert_eq!(work, HashSet::from(["com.example.Main".to_string()]));
+    }
+
+    #[test]
+    fn test_manifest_components_flags_trackers() {
+        let component = |name: &str| ManifestComponent {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let manifest = ApkManifest {
+            package: "com.example".to_string(),
+            activities: vec![component("com.example.Main")],
+            services: vec![component("com.google.firebase.analytics.AnalyticsService")],
+            receivers: vec![component("com.example.BootReceiver")],
+            ..Default::default()
+        };
+        let signatures = TrackerSignatures {
+            trackers: vec![TrackerSignature {
+                id: 49,
+                name: "Google Firebase Analytics".to_string(),
+                categories: vec!["Analytics".to_string()],
+                website: String::new(),
+                code_prefixes: vec!["com.google.firebase.analytics.".to_string()],
+            }],
+            content_md5: String::new(),
+        };
+        let disabled = HashSet::from(["com.example.BootReceiver".to_string()]);
+
+        let components = manifest_components(&manifest, &disabled, Some(&signatures));
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0].kind, ComponentKind::Activity);
+        assert!(components[0].enabled);
+        assert_eq!(components[1].tracker.as_deref(), Some("Google Firebase Analytics"));
+        assert_eq!(components[2].kind, ComponentKind::Receiver);
+        assert!(!components[2].enabled);
+        assert_eq!(components[2].tracker, None);
+    }
+}
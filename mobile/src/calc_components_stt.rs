@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Manifest component type that `pm disable` can act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    Activity,
+    Service,
+    Receiver,
+    Provider,
+}
+
+impl ComponentKind {
+    /// Kinds in the order they are listed
+    pub const ALL: [ComponentKind; 4] = [
+        ComponentKind::Activity,
+        ComponentKind::Service,
+        ComponentKind::Receiver,
+        ComponentKind::Provider,
+    ];
+}
+
+/// Component declared by a package, with its current state on the device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppComponent {
+    pub kind: ComponentKind,
+    /// Fully qualified class name
+    pub name: String,
+    pub exported: bool,
+    /// False when listed under `disabledComponents` for the user
+    pub enabled: bool,
+    /// Name of the tracker whose signature matches the class, if any
+    pub tracker: Option<String>,
+}
+
+/// Enable or disable of one component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentChange {
+    pub pkg_id: String,
+    pub component: String,
+    pub enable: bool,
+}
+
+/// Outcome of a batch of component changes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentBatchOutcome {
+    pub succeeded: usize,
+    /// One line per failed change
+    pub failures: Vec<String>,
+}
+
+/// Components loaded per package; `Err` holds the manifest or adb error
+pub type ComponentsResults = Arc<Mutex<HashMap<String, Result<Vec<AppComponent>, String>>>>;
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use crate::api_hybridanalysis_stt::{
+    HybridAnalysisExtractedFile, HybridAnalysisMitreAttack, HybridAnalysisSignature,
+};
+use std::time::Instant;
 
 /// Status of a Hybrid Analysis scan for a file
 #[derive(Debug, Clone)]
@@ -33,19 +34,8 @@ pub struct FileScanResult {
     pub job_id: Option<String>,
     /// For error states, additional error details
     pub error_message: Option<String>,
-}
-
-/// Status of a Hybrid Analysis scan for a package (may contain multiple files)
-#[derive(Debug, Clone)]
-pub enum ScanStatus {
-    Pending,
-    Scanning {
-        scanned: usize,
-        total: usize,
-        operation: String,
-    },
-    Completed(CalcHybridAnalysis),
-    Error(String),
+    /// Verdict before the latest re-query, if the file was looked up before
+    pub previous_verdict: Option<String>,
 }
 
 /// Result of a Hybrid Analysis scan for a package (may contain multiple files)
@@ -62,22 +52,23 @@ impl Default for CalcHybridAnalysis {
     }
 }
 
+/// Full report details of a finished analysis, parsed from the cached report summary
+#[derive(Debug, Clone, Default)]
+pub struct HaReportDetails {
+    pub environment_description: String,
+    /// Sorted by threat level, most severe first
+    pub signatures: Vec<HybridAnalysisSignature>,
+    pub mitre_attcks: Vec<HybridAnalysisMitreAttack>,
+    /// Contacted domains and hosts
+    pub hosts: Vec<String>,
+    pub extracted_files: Vec<HybridAnalysisExtractedFile>,
+}
+
+/// Status of a Hybrid Analysis scan for a package (may contain multiple files)
+pub type ScanStatus = crate::calc_scanprovider_stt::ScanStatus<CalcHybridAnalysis>;
+
 /// Shared state for Hybrid Analysis scanning
-pub type ScannerState = Arc<Mutex<HashMap<String, ScanStatus>>>;
+pub type ScannerState = crate::calc_scanprovider_stt::ScannerState<CalcHybridAnalysis>;
 
 /// Shared rate limiter for Hybrid Analysis API
-pub type SharedRateLimiter = Arc<Mutex<RateLimiter>>;
-
-/// Rate limiter for Hybrid Analysis API (3 seconds minimum interval between requests)
-pub struct RateLimiter {
-    pub last_request: Option<Instant>,
-    pub min_interval: Duration,
-    /// When a 429 is received for search/report requests, wait until this time
-    pub rate_limit_until: Option<Instant>,
-    /// When a 429 is received for upload requests, wait 1 hour
-    pub upload_rate_limit_until: Option<Instant>,
-    /// Track requests in the last minute for 5 requests/minute limit
-    pub requests_last_minute: Vec<Instant>,
-    /// Track requests in the last hour for 200 requests/hour limit
-    pub requests_last_hour: Vec<Instant>,
-}
+pub type SharedRateLimiter = crate::calc_scanprovider_stt::SharedRateLimiter;
@@ -1,213 +1,453 @@
 use crate::adb::PackageFingerprint;
 use crate::models::PackageInfoCache;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::RwLock;
 
-// IzzyRisk Permission Risk Points
+pub use crate::calc_izzyrisk_stt::*;
+
+// Default IzzyRisk Permission Risk Points
 // Source: https://android.izzysoft.de/applists/perms
+const DEFAULT_RISK_POINTS: &[(&str, i32)] = &[
+    ("ACCESS_BACKGROUND_LOCATION", 3),
+    ("ACCESS_COARSE_LOCATION", 2),
+    ("ACCESS_FINE_LOCATION", 3),
+    ("ACCESS_LOCATION_EXTRA_COMMANDS", 3),
+    ("ACCESS_MEDIA_LOCATION", 2),
+    ("ACCESS_MOCK_LOCATION", 2),
+    ("ACCESS_NOTIFICATIONS", 2),
+    ("ACCESS_WIFI_STATE", 3),
+    ("ACTIVITY_RECOGNITION", 1),
+    ("AD_ID", 1),
+    ("ANSWER_PHONE_CALLS", 0),
+    ("BIND_ACCESSIBILITY_SERVICE", 3),
+    ("BLUETOOTH", 1),
+    ("BLUETOOTH_ADMIN", 2),
+    ("BODY_SENSORS", 2),
+    ("BRICK", 0),
+    ("CALL_PHONE", 3),
+    ("CAMERA", 4),
+    ("CAPTURE_AUDIO_OUTPUT", 3),
+    ("CHANGE_COMPONENT_ENABLED_STATE", 2),
+    ("CHANGE_CONFIGURATION", 3),
+    ("CHANGE_WIFI_MULTICAST_STATE", 0),
+    ("CLEAR_APP_CACHE", 0),
+    ("CONFIGURE_SIP", 3),
+    ("CONNECTIVITY_INTERNAL", 2),
+    ("DELETE_PACKAGES", 1),
+    ("DEVICE_POWER", 1),
+    ("DIAGNOSTIC", 0),
+    ("DISABLE_KEYGUARD", 3),
+    ("DOWNLOAD_WITHOUT_NOTIFICATION", 1),
+    ("DUMP", 4),
+    ("EXPAND_STATUS_BAR", 3),
+    ("FORCE_STOP_PACKAGES", 0),
+    ("GET_PACKAGE_SIZE", 1),
+    ("GET_TASKS", 3),
+    ("GET_TOP_ACTIVITY_INFO", 0),
+    ("GLOBAL_SEARCH", 0),
+    ("GLOBAL_SEARCH_CONTROL", 0),
+    ("GOOGLE_AUTH.mail", 1),
+    ("GOOGLE_AUTH.wise", 1),
+    ("GOOGLE_AUTH.writely", 1),
+    ("GOOGLE_PHOTOS", 1),
+    ("google.MAPS_RECEIVE", 2),
+    ("GTALK_SERVICE", 1),
+    ("HARDWARE_TEST", 0),
+    ("im.permission.READ_ONLY", 3),
+    ("INJECT_EVENTS", 0),
+    ("INSTALL_DRM", 3),
+    ("INSTALL_PACKAGES", 5),
+    ("INSTALL_SHORTCUT", 3),
+    ("INTERACT_ACROSS_USERS", 2),
+    ("INTERACT_ACROSS_USERS_FULL", 3),
+    ("INTERNAL_SYSTEM_WINDOW", 3),
+    ("INTERNET", 0),
+    ("k9.permission.DELETE_MESSAGES", 3),
+    ("k9.permission.READ_ATTACHMENT", 3),
+    ("k9.permission.READ_MESSAGES", 3),
+    ("KILL_BACKGROUND_PROCESSES", 4),
+    ("LOCATION_HARDWARE", 3),
+    ("MANAGE_EXTERNAL_STORAGE", 3),
+    ("MANAGE_USERS", 2),
+    ("MASTER_CLEAR", 1),
+    ("MODIFY_AUDIO_SETTINGS", 0),
+    ("MODIFY_PHONE_STATE", 4),
+    ("MOUNT_FORMAT_FILESYSTEMS", 3),
+    ("MOUNT_UNMOUNT_FILESYSTEMS", 4),
+    ("NEARBY_WIFI_DEVICES", 0),
+    ("NFC", 2),
+    ("PACKAGE_USAGE_STATS", 1),
+    ("PERSISTENT_ACTIVITY", 0),
+    ("PREVENT_POWER_KEY", 0),
+    ("PROCESS_OUTGOING_CALLS", 4),
+    ("QUERY_ALL_PACKAGES", 3),
+    ("READ_ATTACHMENT", 3),
+    ("READ_CALENDAR", 2),
+    ("READ_CALL_LOG", 2),
+    ("READ_CLIPBOARD", 3),
+    ("READ_CONTACTS", 2),
+    ("READ_CONTENT_PROVIDER", 0),
+    ("READ_EXTERNAL_STORAGE", 0),
+    ("READ_FRAME_BUFFER", 4),
+    ("READ_GMAIL", 1),
+    ("READ_GMAIL_PROVIDER", 1),
+    ("READ_GSERVICES", 1),
+    ("READ_HISTORY_BOOKMARKS", 3),
+    ("READ_INPUT_STATE", 4),
+    ("READ_LOGS", 3),
+    ("READ_MEDIA_AUDIO", 0),
+    ("READ_MEDIA_IMAGES", 0),
+    ("READ_MEDIA_VIDEO", 0),
+    ("READ_OWNER_DATA", 0),
+    ("READ_PHONE_NUMBERS", 0),
+    ("READ_PHONE_STATE", 0),
+    ("READ_PRIVILEGED_PHONE_STATE", 3),
+    ("READ_PROFILE", 3),
+    ("READ_SMS", 3),
+    ("READ_SOCIAL_STREAM", 3),
+    ("READ_SYNC_SETTINGS", 0),
+    ("READ_SYNC_STATS", 0),
+    ("READ_USER_DICTIONARY", 0),
+    ("RECEIVE_BOOT_COMPLETED", 0),
+    ("RECEIVE_MMS", 3),
+    ("RECEIVE_SENSITIVE_NOTIFICATIONS", 4),
+    ("RECEIVE_SMS", 3),
+    ("RECEIVE_WAP_PUSH", 0),
+    ("RECORD_AUDIO", 3),
+    ("REORDER_TASKS", 0),
+    ("REQUEST_IGNORE_BATTERY_OPTIMIZATIONS", 0),
+    ("RESTART_PACKAGES", 3),
+    ("SEND_SMS", 0),
+    ("SEND_SMS_NO_CONFIRMATION", 3),
+    ("SET_ACTIVITY_WATCHER", 0),
+    ("SET_ALARM", 0),
+    ("SET_ALWAYS_FINISH", 0),
+    ("SET_DEBUG_APP", 0),
+    ("SET_PREFERRED_APPLICATIONS", 2),
+    ("WRITE_APN_SETTINGS", 3),
+    ("WRITE_CALENDAR", 2),
+    ("WRITE_CALL_LOG", 2),
+    ("WRITE_CONTACTS", 2),
+    ("WRITE_EXTERNAL_STORAGE", 0),
+    ("WRITE_GSERVICES", 3),
+    ("WRITE_HISTORY_BOOKMARKS", 3),
+    ("WRITE_SECURE_SETTINGS", 4),
+    ("WRITE_SETTINGS", 3),
+    ("WRITE_SMS", 3),
+    ("WRITE_SYNC_SETTINGS", 2),
+    ("WRITE_USER_DICTIONARY", 2),
+];
+
+/// Combination signal held by apps with an enabled accessibility service. Apps never hold
+/// BIND_ACCESSIBILITY_SERVICE themselves; their service is protected by it.
+pub const ACCESSIBILITY_SIGNAL: &str = "BIND_ACCESSIBILITY_SERVICE";
+/// Combination signal held by apps with an enabled notification listener
+pub const NOTIFICATION_LISTENER_SIGNAL: &str = "BIND_NOTIFICATION_LISTENER_SERVICE";
+
+/// Default dangerous permission combinations, as (name, permissions, points)
+const DEFAULT_COMBINATIONS: &[(&str, &[&str], i32)] = &[
+    (
+        "SMS access with accessibility control",
+        &["READ_SMS", "INTERNET", "BIND_ACCESSIBILITY_SERVICE"],
+        10,
+    ),
+    (
+        "App installer with accessibility control",
+        &["REQUEST_INSTALL_PACKAGES", "INTERNET", "BIND_ACCESSIBILITY_SERVICE"],
+        8,
+    ),
+    (
+        "Background location tracking",
+        &["ACCESS_BACKGROUND_LOCATION", "RECEIVE_BOOT_COMPLETED", "INTERNET"],
+        5,
+    ),
+    (
+        "Background audio recording",
+        &["RECORD_AUDIO", "RECEIVE_BOOT_COMPLETED", "INTERNET"],
+        5,
+    ),
+    (
+        "Call and SMS log upload",
+        &["READ_CALL_LOG", "READ_SMS", "INTERNET"],
+        5,
+    ),
+    (
+        "Notification reading",
+        &["BIND_NOTIFICATION_LISTENER_SERVICE", "INTERNET"],
+        4,
+    ),
+];
+
+const CONFIG_FILE_NAME: &str = "izzyrisk.yaml";
+
+const CONFIG_FILE_HEADER: &str = "\
+# IzzyRisk weights used by the Scan tab. Edit and recalculate scores to apply.
+# permissions: points per permission, by short name (CAMERA) or full name
+# denied_runtime_factor: share of points for runtime permissions that are not granted (0.0 - 1.0)
+# combinations: extra points when every listed permission is granted
+#   BIND_ACCESSIBILITY_SERVICE / BIND_NOTIFICATION_LISTENER_SERVICE match apps whose
+#   accessibility service / notification listener is enabled
+";
+
 lazy_static::lazy_static! {
-    static ref IZZY_RISK_POINTS: HashMap<&'static str, i32> = {
-        let mut m = HashMap::new();
-        m.insert("ACCESS_BACKGROUND_LOCATION", 3);
-        m.insert("ACCESS_COARSE_LOCATION", 2);
-        m.insert("ACCESS_FINE_LOCATION", 3);
-        m.insert("ACCESS_LOCATION_EXTRA_COMMANDS", 3);
-        m.insert("ACCESS_MEDIA_LOCATION", 2);
-        m.insert("ACCESS_MOCK_LOCATION", 2);
-        m.insert("ACCESS_NOTIFICATIONS", 2);
-        m.insert("ACCESS_WIFI_STATE", 3);
-        m.insert("ACTIVITY_RECOGNITION", 1);
-        m.insert("AD_ID", 1);
-        m.insert("ANSWER_PHONE_CALLS", 0);
-        m.insert("BIND_ACCESSIBILITY_SERVICE", 3);
-        m.insert("BLUETOOTH", 1);
-        m.insert("BLUETOOTH_ADMIN", 2);
-        m.insert("BODY_SENSORS", 2);
-        m.insert("BRICK", 0);
-        m.insert("CALL_PHONE", 3);
-        m.insert("CAMERA", 4);
-        m.insert("CAPTURE_AUDIO_OUTPUT", 3);
-        m.insert("CHANGE_COMPONENT_ENABLED_STATE", 2);
-        m.insert("CHANGE_CONFIGURATION", 3);
-        m.insert("CHANGE_WIFI_MULTICAST_STATE", 0);
-        m.insert("CLEAR_APP_CACHE", 0);
-        m.insert("CONFIGURE_SIP", 3);
-        m.insert("CONNECTIVITY_INTERNAL", 2);
-        m.insert("DELETE_PACKAGES", 1);
-        m.insert("DEVICE_POWER", 1);
-        m.insert("DIAGNOSTIC", 0);
-        m.insert("DISABLE_KEYGUARD", 3);
-        m.insert("DOWNLOAD_WITHOUT_NOTIFICATION", 1);
-        m.insert("DUMP", 4);
-        m.insert("EXPAND_STATUS_BAR", 3);
-        m.insert("FORCE_STOP_PACKAGES", 0);
-        m.insert("GET_PACKAGE_SIZE", 1);
-        m.insert("GET_TASKS", 3);
-        m.insert("GET_TOP_ACTIVITY_INFO", 0);
-        m.insert("GLOBAL_SEARCH", 0);
-        m.insert("GLOBAL_SEARCH_CONTROL", 0);
-        m.insert("GOOGLE_AUTH.mail", 1);
-        m.insert("GOOGLE_AUTH.wise", 1);
-        m.insert("GOOGLE_AUTH.writely", 1);
-        m.insert("GOOGLE_PHOTOS", 1);
-        m.insert("google.MAPS_RECEIVE", 2);
-        m.insert("GTALK_SERVICE", 1);
-        m.insert("HARDWARE_TEST", 0);
-        m.insert("im.permission.READ_ONLY", 3);
-        m.insert("INJECT_EVENTS", 0);
-        m.insert("INSTALL_DRM", 3);
-        m.insert("INSTALL_PACKAGES", 5);
-        m.insert("INSTALL_SHORTCUT", 3);
-        m.insert("INTERACT_ACROSS_USERS", 2);
-        m.insert("INTERACT_ACROSS_USERS_FULL", 3);
-        m.insert("INTERNAL_SYSTEM_WINDOW", 3);
-        m.insert("INTERNET", 0);
-        m.insert("k9.permission.DELETE_MESSAGES", 3);
-        m.insert("k9.permission.READ_ATTACHMENT", 3);
-        m.insert("k9.permission.READ_MESSAGES", 3);
-        m.insert("KILL_BACKGROUND_PROCESSES", 4);
-        m.insert("LOCATION_HARDWARE", 3);
-        m.insert("MANAGE_EXTERNAL_STORAGE", 3);
-        m.insert("MANAGE_USERS", 2);
-        m.insert("MASTER_CLEAR", 1);
-        m.insert("MODIFY_AUDIO_SETTINGS", 0);
-        m.insert("MODIFY_PHONE_STATE", 4);
-        m.insert("MOUNT_FORMAT_FILESYSTEMS", 3);
-        m.insert("MOUNT_UNMOUNT_FILESYSTEMS", 4);
-        m.insert("NEARBY_WIFI_DEVICES", 0);
-        m.insert("NFC", 2);
-        m.insert("PACKAGE_USAGE_STATS", 1);
-        m.insert("PERSISTENT_ACTIVITY", 0);
-        m.insert("PREVENT_POWER_KEY", 0);
-        m.insert("PROCESS_OUTGOING_CALLS", 4);
-        m.insert("QUERY_ALL_PACKAGES", 3);
-        m.insert("READ_ATTACHMENT", 3);
-        m.insert("READ_CALENDAR", 2);
-        m.insert("READ_CALL_LOG", 2);
-        m.insert("READ_CLIPBOARD", 3);
-        m.insert("READ_CONTACTS", 2);
-        m.insert("READ_CONTENT_PROVIDER", 0);
-        m.insert("READ_EXTERNAL_STORAGE", 0);
-        m.insert("READ_FRAME_BUFFER", 4);
-        m.insert("READ_GMAIL", 1);
-        m.insert("READ_GMAIL_PROVIDER", 1);
-        m.insert("READ_GSERVICES", 1);
-        m.insert("READ_HISTORY_BOOKMARKS", 3);
-        m.insert("READ_INPUT_STATE", 4);
-        m.insert("READ_LOGS", 3);
-        m.insert("READ_MEDIA_AUDIO", 0);
-        m.insert("READ_MEDIA_IMAGES", 0);
-        m.insert("READ_MEDIA_VIDEO", 0);
-        m.insert("READ_OWNER_DATA", 0);
-        m.insert("READ_PHONE_NUMBERS", 0);
-        m.insert("READ_PHONE_STATE", 0);
-        m.insert("READ_PRIVILEGED_PHONE_STATE", 3);
-        m.insert("READ_PROFILE", 3);
-        m.insert("READ_SMS", 3);
-        m.insert("READ_SOCIAL_STREAM", 3);
-        m.insert("READ_SYNC_SETTINGS", 0);
-        m.insert("READ_SYNC_STATS", 0);
-        m.insert("READ_USER_DICTIONARY", 0);
-        m.insert("RECEIVE_BOOT_COMPLETED", 0);
-        m.insert("RECEIVE_MMS", 3);
-        m.insert("RECEIVE_SENSITIVE_NOTIFICATIONS", 4);
-        m.insert("RECEIVE_SMS", 3);
-        m.insert("RECEIVE_WAP_PUSH", 0);
-        m.insert("RECORD_AUDIO", 3);
-        m.insert("REORDER_TASKS", 0);
-        m.insert("REQUEST_IGNORE_BATTERY_OPTIMIZATIONS", 0);
-        m.insert("RESTART_PACKAGES", 3);
-        m.insert("SEND_SMS", 0);
-        m.insert("SEND_SMS_NO_CONFIRMATION", 3);
-        m.insert("SET_ACTIVITY_WATCHER", 0);
-        m.insert("SET_ALARM", 0);
-        m.insert("SET_ALWAYS_FINISH", 0);
-        m.insert("SET_DEBUG_APP", 0);
-        m.insert("SET_PREFERRED_APPLICATIONS", 2);
-        m.insert("WRITE_APN_SETTINGS", 3);
-        m.insert("WRITE_CALENDAR", 2);
-        m.insert("WRITE_CALL_LOG", 2);
-        m.insert("WRITE_CONTACTS", 2);
-        m.insert("WRITE_EXTERNAL_STORAGE", 0);
-        m.insert("WRITE_GSERVICES", 3);
-        m.insert("WRITE_HISTORY_BOOKMARKS", 3);
-        m.insert("WRITE_SECURE_SETTINGS", 4);
-        m.insert("WRITE_SETTINGS", 3);
-        m.insert("WRITE_SMS", 3);
-        m.insert("WRITE_SYNC_SETTINGS", 2);
-        m.insert("WRITE_USER_DICTIONARY", 2);
-        m
+    static ref IZZY_RISK_CONFIG: RwLock<IzzyRiskConfig> = RwLock::new(load_config());
+}
+
+impl Default for IzzyRiskConfig {
+    fn default() -> Self {
+        Self {
+            permissions: DEFAULT_RISK_POINTS
+                .iter()
+                .map(|(name, points)| (name.to_string(), *points))
+                .collect(),
+            denied_runtime_factor: 0.0,
+            combinations: DEFAULT_COMBINATIONS
+                .iter()
+                .map(|(name, permissions, points)| PermissionCombination {
+                    name: name.to_string(),
+                    permissions: permissions.iter().map(|p| p.to_string()).collect(),
+                    points: *points,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Path of the user-editable weights file
+pub fn config_path() -> Option<PathBuf> {
+    crate::Config::new()
+        .ok()
+        .map(|config| config.config_dir.join(CONFIG_FILE_NAME))
+}
+
+/// Load weights from the config file, writing the defaults on first use.
+/// A file that fails to parse is left alone and the defaults are used.
+fn load_config() -> IzzyRiskConfig {
+    let Some(path) = config_path() else {
+        return IzzyRiskConfig::default();
     };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => match serde_yaml::from_str::<IzzyRiskConfig>(&contents) {
+            Ok(config) => {
+                log::info!(
+                    "Loaded IzzyRisk weights from {:?} ({} permissions, {} combinations)",
+                    path,
+                    config.permissions.len(),
+                    config.combinations.len()
+                );
+                config
+            }
+            Err(e) => {
+                log::error!("Failed to parse {:?}, using default weights: {}", path, e);
+                IzzyRiskConfig::default()
+            }
+        },
+        Err(_) => {
+            let config = IzzyRiskConfig::default();
+            match serde_yaml::to_string(&config) {
+                Ok(yaml) => {
+                    if let Err(e) = std::fs::write(&path, format!("{}{}", CONFIG_FILE_HEADER, yaml)) {
+                        log::warn!("Failed to write default IzzyRisk weights to {:?}: {}", path, e);
+                    }
+                }
+                Err(e) => log::warn!("Failed to serialize default IzzyRisk weights: {}", e),
+            }
+            config
+        }
+    }
 }
 
-/// Calculate IzzyRisk score for a single package based on its runtime permissions
-pub fn calculate_izzyrisk(package: &PackageFingerprint) -> i32 {
-    let mut total_risk = 0;
+/// Re-read the weights file so edits apply to the next calculation
+pub fn reload_config() {
+    let config = load_config();
+    if let Ok(mut current) = IZZY_RISK_CONFIG.write() {
+        *current = config;
+    }
+}
+
+/// Split a dumpsys permission line ("android.permission.CAMERA: granted=true, flags=[ ...]")
+/// into the permission name and whether it is granted
+fn parse_permission_line(line: &str) -> (&str, bool) {
+    let (name, attributes) = line.split_once(':').unwrap_or((line, ""));
+    let granted = !attributes.contains("granted=false");
+    (name.trim(), granted)
+}
+
+/// Last dot-separated part of a permission name
+fn short_permission_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Collect the permissions a package holds, keyed by full name.
+/// A runtime permission counts as granted when any user has granted it.
+fn collect_permission_states(package: &PackageFingerprint) -> BTreeMap<String, PermissionState> {
+    let mut states = BTreeMap::new();
+
+    for line in &package.installPermissions {
+        let (name, granted) = parse_permission_line(line);
+        if granted {
+            states.insert(name.to_string(), PermissionState::Install);
+        }
+    }
 
-    // Iterate through all users in the package
     for user in &package.users {
-        // Check each runtime permission for the user
-        for permission in &user.runtimePermissions {
-            // Extract the permission name from the full permission string
-            // Android permissions are typically formatted as "android.permission.PERMISSION_NAME: granted=true"
-            // First, strip any metadata starting with ':'
-            let clean_permission = permission
-                .split(':')
-                .next()
-                .unwrap_or(permission.as_str())
-                .trim();
-
-            // Then extract the last part of the dot-separated string
-            let permission_name = if let Some(last_part) = clean_permission.split('.').last() {
-                last_part
+        for line in &user.runtimePermissions {
+            let (name, granted) = parse_permission_line(line);
+            let state = if granted {
+                PermissionState::RuntimeGranted
             } else {
-                clean_permission
+                PermissionState::RuntimeDenied
             };
+            match states.get(name) {
+                Some(PermissionState::Install) | Some(PermissionState::RuntimeGranted) => {}
+                _ => {
+                    states.insert(name.to_string(), state);
+                }
+            }
+        }
+    }
 
-            // Look up the risk score in the IZZY_RISK_POINTS table
-            if let Some(&risk_score) = IZZY_RISK_POINTS.get(permission_name) {
-                total_risk += risk_score;
-                log::trace!(
-                    "Package {}: permission {} has risk score {}",
-                    package.pkg,
-                    permission_name,
-                    risk_score
-                );
+    states
+}
+
+/// Combination signals per package from the enabled accessibility services and
+/// notification listeners (`pkg/class` components from secure settings)
+pub fn enabled_service_signals(
+    accessibility_services: &[String],
+    notification_listeners: &[String],
+) -> HashMap<String, Vec<&'static str>> {
+    let mut signals: HashMap<String, Vec<&'static str>> = HashMap::new();
+    for (components, signal) in [
+        (accessibility_services, ACCESSIBILITY_SIGNAL),
+        (notification_listeners, NOTIFICATION_LISTENER_SIGNAL),
+    ] {
+        for component in components {
+            let pkg_id = component.split('/').next().unwrap_or(component);
+            let entry = signals.entry(pkg_id.to_string()).or_default();
+            if !entry.contains(&signal) {
+                entry.push(signal);
             }
         }
     }
+    signals
+}
+
+/// Read the enabled accessibility services and notification listeners of a device.
+/// Failures are logged and leave the service combinations unmatched.
+pub fn load_service_signals(device: &str) -> HashMap<String, Vec<&'static str>> {
+    let setting = |key: &str| {
+        crate::adb::get_secure_setting(device, key)
+            .map(|value| crate::calc_stalkerware::parse_component_setting(&value))
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to read secure setting {}: {}", key, e);
+                Vec::new()
+            })
+    };
+    enabled_service_signals(
+        &setting("enabled_accessibility_services"),
+        &setting("enabled_notification_listeners"),
+    )
+}
+
+/// Calculate the IzzyRisk score of a package with the given weights, explaining each contribution.
+/// `service_signals` are the combination signals the package holds through enabled services.
+pub fn explain_izzyrisk_with(
+    package: &PackageFingerprint,
+    config: &IzzyRiskConfig,
+    service_signals: &[&str],
+) -> IzzyRiskBreakdown {
+    let states = collect_permission_states(package);
+    let mut contributions = Vec::new();
+
+    for (name, state) in &states {
+        let short_name = short_permission_name(name);
+        let Some(&points) = config
+            .permissions
+            .get(name.as_str())
+            .or_else(|| config.permissions.get(short_name))
+        else {
+            continue;
+        };
+
+        let points = match state {
+            PermissionState::RuntimeDenied => (points as f32 * config.denied_runtime_factor).round() as i32,
+            _ => points,
+        };
+        if points != 0 {
+            contributions.push(RiskContribution {
+                reason: RiskReason::Permission {
+                    name: short_name.to_string(),
+                    state: *state,
+                },
+                points,
+            });
+        }
+    }
+
+    let granted: HashSet<&str> = states
+        .iter()
+        .filter(|(_, state)| **state != PermissionState::RuntimeDenied)
+        .flat_map(|(name, _)| [name.as_str(), short_permission_name(name)])
+        .chain(service_signals.iter().copied())
+        .collect();
+    for combination in &config.combinations {
+        if !combination.permissions.is_empty()
+            && combination.permissions.iter().all(|p| granted.contains(p.as_str()))
+        {
+            contributions.push(RiskContribution {
+                reason: RiskReason::Combination {
+                    name: combination.name.clone(),
+                },
+                points: combination.points,
+            });
+        }
+    }
+
+    contributions.sort_by_key(|c| std::cmp::Reverse(c.points));
+    IzzyRiskBreakdown {
+        total: contributions.iter().map(|c| c.points).sum(),
+        contributions,
+    }
+}
+
+/// Calculate the IzzyRisk score of a package with the loaded weights, explaining each contribution
+pub fn explain_izzyrisk(package: &PackageFingerprint, service_signals: &[&str]) -> IzzyRiskBreakdown {
+    match IZZY_RISK_CONFIG.read() {
+        Ok(config) => explain_izzyrisk_with(package, &config, service_signals),
+        Err(_) => explain_izzyrisk_with(package, &IzzyRiskConfig::default(), service_signals),
+    }
+}
 
+/// Calculate IzzyRisk score for a single package based on its granted permissions
+pub fn calculate_izzyrisk(package: &PackageFingerprint) -> i32 {
+    let breakdown = explain_izzyrisk(package, &[]);
     log::debug!(
         "Package {} has total IzzyRisk score: {}",
         package.pkg,
-        total_risk
+        breakdown.total
     );
-    total_risk
+    breakdown.total
 }
 
-/// Calculate IzzyRisk score with database caching.
-/// Returns the cached score if available, otherwise computes and persists it.
+/// Calculate IzzyRisk score and persist it to the package cache.
+/// Scores depend on the current grant state and the user-editable weights, so they are
+/// always recomputed; the cache is only written when the score changed.
 pub fn calculate_and_cache_izzyrisk(
     package: &PackageFingerprint,
     cached_pkg: Option<&PackageInfoCache>,
     device_serial: &str,
-) -> i32 {
-    // Check if cached score exists
-    if let Some(cached) = cached_pkg {
-        if let Some(score) = cached.izzyscore {
-            log::debug!(
-                "Package {} izzyrisk cache hit: {}",
-                package.pkg,
-                score
-            );
-            return score;
-        }
-    }
-
-    // Cache miss: calculate from permissions
-    let score = calculate_izzyrisk(package);
+    service_signals: &[&str],
+) -> IzzyRiskBreakdown {
+    let breakdown = explain_izzyrisk(package, service_signals);
+    let score = breakdown.total;
 
     // Persist to database
     if let Some(cached) = cached_pkg {
+        if cached.izzyscore == Some(score) {
+            return breakdown;
+        }
         // Update existing cache entry
         if let Err(e) = crate::db_package_cache::update_package_izzyscore(cached.id, score) {
             log::error!(
@@ -240,7 +480,7 @@ pub fn calculate_and_cache_izzyrisk(
         }
     }
 
-    score
+    breakdown
 }
 
 /// Calculate risk scores for all packages in a vector
@@ -266,12 +506,16 @@ pub fn calculate_all_risk_scores_async(
     installed_packages: Vec<PackageFingerprint>,
     device_serial: Option<String>,
     shared_scores: std::sync::Arc<std::sync::Mutex<HashMap<String, i32>>>,
+    shared_breakdowns: std::sync::Arc<std::sync::Mutex<HashMap<String, IzzyRiskBreakdown>>>,
     progress_clone: std::sync::Arc<std::sync::Mutex<Option<f32>>>,
     cancelled_clone: std::sync::Arc<std::sync::Mutex<bool>>,
 ) {
     use std::thread;
 
     thread::spawn(move || {
+        // Pick up edits to the weights file
+        reload_config();
+
         let device_serial_str = device_serial.as_deref().unwrap_or("");
 
         let cached_packages_map: HashMap<String, crate::models::PackageInfoCache> =
@@ -283,9 +527,14 @@ pub fn calculate_all_risk_scores_async(
             } else {
                 HashMap::new()
             };
+        // Enabled services back the accessibility and notification listener combinations
+        let service_signals = if device_serial_str.is_empty() {
+            HashMap::new()
+        } else {
+            load_service_signals(device_serial_str)
+        };
 
-        let mut cache_hits = 0;
-        let mut cache_misses = 0;
+        let mut changed = 0;
         let total = installed_packages.len();
 
         for (i, package) in installed_packages.iter().enumerate() {
@@ -302,35 +551,37 @@ pub fn calculate_all_risk_scores_async(
                 *p = Some(i as f32 / total as f32);
             }
 
-            let risk_score = if device_serial_str.is_empty() {
+            let package_signals = service_signals.get(&package.pkg).map(Vec::as_slice).unwrap_or_default();
+            let breakdown = if device_serial_str.is_empty() {
                 // No device serial: calculate without caching
-                calculate_izzyrisk(package)
+                explain_izzyrisk(package, package_signals)
             } else {
                 let cached_pkg = cached_packages_map.get(&package.pkg);
-                let score = calculate_and_cache_izzyrisk(
+                let breakdown = calculate_and_cache_izzyrisk(
                     package,
                     cached_pkg,
                     device_serial_str,
+                    package_signals,
                 );
-                if cached_pkg.and_then(|c| c.izzyscore).is_some() {
-                    cache_hits += 1;
-                } else {
-                    cache_misses += 1;
+                if cached_pkg.and_then(|c| c.izzyscore) != Some(breakdown.total) {
+                    changed += 1;
                 }
-                score
+                breakdown
             };
 
             // Update shared scores
             if let Ok(mut shared) = shared_scores.lock() {
-                shared.insert(package.pkg.clone(), risk_score);
+                shared.insert(package.pkg.clone(), breakdown.total);
+            }
+            if let Ok(mut shared) = shared_breakdowns.lock() {
+                shared.insert(package.pkg.clone(), breakdown);
             }
         }
 
         log::info!(
-            "IzzyRisk calculation complete: {} packages ({} cached, {} computed)",
+            "IzzyRisk calculation complete: {} packages ({} changed since last calculation)",
             total,
-            cache_hits,
-            cache_misses
+            changed
         );
 
         // Clear progress when done
@@ -339,3 +590,121 @@ pub fn calculate_all_risk_scores_async(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::AdbPackageInfoUser;
+
+    fn package(install_permissions: &[&str], users: Vec<AdbPackageInfoUser>) -> PackageFingerprint {
+        PackageFingerprint {
+            installPermissions: install_permissions.iter().map(|p| p.to_string()).collect(),
+            users,
+            ..PackageFingerprint::fixture("com.example.app", "", "")
+        }
+    }
+
+    #[test]
+    fn test_runtime_grant_state() {
+        let config = IzzyRiskConfig::default();
+        let pkg = package(
+            &["android.permission.INTERNET: granted=true"],
+            vec![
+                AdbPackageInfoUser::fixture(0, &[
+                    "android.permission.CAMERA: granted=true, flags=[ USER_SET ]",
+                    "android.permission.READ_SMS: granted=false, flags=[ USER_SET ]",
+                ]),
+                // A second user counts each permission once
+                AdbPackageInfoUser::fixture(0, &["android.permission.CAMERA: granted=true"]),
+            ],
+        );
+
+        let breakdown = explain_izzyrisk_with(&pkg, &config, &[]);
+        assert_eq!(breakdown.total, 4);
+        assert_eq!(
+            breakdown.contributions,
+            vec![RiskContribution {
+                reason: RiskReason::Permission {
+                    name: "CAMERA".to_string(),
+                    state: PermissionState::RuntimeGranted,
+                },
+                points: 4,
+            }]
+        );
+
+        // Denied runtime permissions can be given a share of their points
+        let config = IzzyRiskConfig {
+            denied_runtime_factor: 0.5,
+            ..IzzyRiskConfig::default()
+        };
+        assert_eq!(explain_izzyrisk_with(&pkg, &config, &[]).total, 6);
+    }
+
+    #[test]
+    fn test_combination_rules() {
+        let config: IzzyRiskConfig = serde_yaml::from_str(
+            "permissions:\n  READ_SMS: 3\n  com.example.permission.CUSTOM: 1\n\
+             combinations:\n  - name: sms exfiltration\n    permissions: [READ_SMS, INTERNET]\n    points: 10\n",
+        )
+        .unwrap();
+
+        let granted = package(
+            &[
+                "android.permission.INTERNET: granted=true",
+                "com.example.permission.CUSTOM: granted=true",
+            ],
+            vec![AdbPackageInfoUser::fixture(0, &["android.permission.READ_SMS: granted=true"])],
+        );
+        let breakdown = explain_izzyrisk_with(&granted, &config, &[]);
+        assert_eq!(breakdown.total, 14);
+        assert_eq!(
+            breakdown.contributions[0].reason,
+            RiskReason::Combination {
+                name: "sms exfiltration".to_string()
+            }
+        );
+
+        // The combination needs every permission granted
+        let denied = package(
+            &["android.permission.INTERNET: granted=true"],
+            vec![AdbPackageInfoUser::fixture(0, &["android.permission.READ_SMS: granted=false"])],
+        );
+        assert_eq!(explain_izzyrisk_with(&denied, &config, &[]).total, 0);
+    }
+
+    #[test]
+    fn test_service_combinations() {
+        let config = IzzyRiskConfig::default();
+        let pkg = package(
+            &["android.permission.INTERNET: granted=true"],
+            vec![AdbPackageInfoUser::fixture(0, &["android.permission.READ_SMS: granted=true"])],
+        );
+        let combinations = |signals: &[&str]| -> Vec<RiskReason> {
+            explain_izzyrisk_with(&pkg, &config, signals)
+                .contributions
+                .into_iter()
+                .map(|c| c.reason)
+                .filter(|r| matches!(r, RiskReason::Combination { .. }))
+                .collect()
+        };
+        assert!(combinations(&[]).is_empty());
+
+        let signals = enabled_service_signals(
+            &["com.example.app/.SpyService".to_string(), "com.other/.A11y".to_string()],
+            &["com.example.app/.Listener".to_string()],
+        );
+        assert_eq!(signals["com.example.app"], vec![ACCESSIBILITY_SIGNAL, NOTIFICATION_LISTENER_SIGNAL]);
+        assert_eq!(signals["com.other"], vec![ACCESSIBILITY_SIGNAL]);
+        assert_eq!(
+            combinations(&signals["com.example.app"]),
+            vec![
+                RiskReason::Combination {
+                    name: "SMS access with accessibility control".to_string()
+                },
+                RiskReason::Combination {
+                    name: "Notification reading".to_string()
+                },
+            ]
+        );
+    }
+}
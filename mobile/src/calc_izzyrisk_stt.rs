@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// IzzyRisk weights, loaded from the user-editable `izzyrisk.yaml` in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IzzyRiskConfig {
+    /// Points per permission, keyed by short name (CAMERA) or full name
+    #[serde(default)]
+    pub permissions: BTreeMap<String, i32>,
+    /// Share of the points counted for runtime permissions that are requested but not granted
+    #[serde(default)]
+    pub denied_runtime_factor: f32,
+    /// Extra points when all permissions of a combination are granted
+    #[serde(default)]
+    pub combinations: Vec<PermissionCombination>,
+}
+
+/// Dangerous permission combination, e.g. READ_SMS + INTERNET + BIND_ACCESSIBILITY_SERVICE
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCombination {
+    pub name: String,
+    pub permissions: Vec<String>,
+    pub points: i32,
+}
+
+/// How a permission is held by a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionState {
+    Install,
+    RuntimeGranted,
+    RuntimeDenied,
+}
+
+/// Why a package got some of its IzzyRisk points
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskReason {
+    Permission { name: String, state: PermissionState },
+    Combination { name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskContribution {
+    pub reason: RiskReason,
+    pub points: i32,
+}
+
+/// IzzyRisk score of a package with the contributions it is made of
+#[derive(Debug, Clone, Default)]
+pub struct IzzyRiskBreakdown {
+    pub total: i32,
+    /// Sorted by points, highest first
+    pub contributions: Vec<RiskContribution>,
+}
@@ -0,0 +1,270 @@
+use crate::api_koodous::{self, KoodousApk, KoodousError};
+use crate::calc_scanprovider::{
+    self, CachedReport, NormalizedResult, PollPolicy, ProviderError, RateLimitPolicy, RateLimiter,
+    RequestGate, ScanProvider, ScanTarget, Verdict,
+};
+use crate::db;
+use crate::db_koodous;
+use crate::models::KoodousResult;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub use crate::calc_koodous_stt::*;
+
+impl FileScanResult {
+    fn not_found(file_path: &str, sha256: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            sha256: sha256.to_string(),
+            verdict: "404 Not Found".to_string(),
+            app: None,
+            company: None,
+            rating: None,
+            tags: Vec::new(),
+            koodous_link: format!("https://koodous.com/apks/{}", sha256),
+        }
+    }
+
+    fn from_apk(file_path: &str, sha256: &str, apk: &KoodousApk) -> Self {
+        Self {
+            verdict: apk_verdict(apk).to_string(),
+            app: apk.app.clone(),
+            company: apk.company.clone(),
+            rating: apk.rating,
+            tags: apk.tags.clone(),
+            ..Self::not_found(file_path, sha256)
+        }
+    }
+
+    fn from_db_result(file_path: &str, r: &KoodousResult) -> Self {
+        Self {
+            verdict: r.verdict.clone(),
+            app: r.app.clone(),
+            company: r.company.clone(),
+            rating: r.rating,
+            tags: serde_json::from_str(&r.tags).unwrap_or_default(),
+            ..Self::not_found(file_path, &r.sha256)
+        }
+    }
+
+    /// Whether a Koodous detection rule matched this APK
+    pub fn is_detected(&self) -> bool {
+        self.verdict == "detected"
+    }
+}
+
+/// Detection outranks the trusted-certificate flag
+fn apk_verdict(apk: &KoodousApk) -> &'static str {
+    if apk.detected {
+        "detected"
+    } else if apk.trusted {
+        "trusted"
+    } else {
+        "undetected"
+    }
+}
+
+impl CalcKoodous {
+    pub fn from_db_results(db_results: Vec<KoodousResult>) -> Self {
+        let file_results = db_results
+            .iter()
+            .map(|r| FileScanResult::from_db_result(&r.file_path, r))
+            .collect();
+
+        Self { file_results }
+    }
+}
+
+impl From<KoodousError> for ProviderError {
+    fn from(err: KoodousError) -> Self {
+        match err {
+            KoodousError::NotFound => ProviderError::NotFound,
+            KoodousError::RateLimit { retry_after } => ProviderError::RateLimit { retry_after },
+            e => ProviderError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Koodous adapter for the scan pipeline in `calc_scanprovider` (lookup only)
+pub struct KoodousProvider {
+    pub api_key: String,
+    pub base_url: String,
+}
+
+impl KoodousProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            base_url: api_koodous::API_URL.to_string(),
+        }
+    }
+
+    fn store(&self, target: &ScanTarget, verdict: &str, apk: Option<KoodousApk>) {
+        // Save to database via queue
+        if let Err(e) = db_koodous::queue_upsert(
+            target.package_name.clone(),
+            target.file_path.clone(),
+            target.sha256.clone(),
+            verdict.to_string(),
+            apk,
+        ) {
+            log::error!("Failed to cache Koodous result for {}: {}", target.sha256, e);
+        }
+    }
+}
+
+impl ScanProvider for KoodousProvider {
+    type Report = FileScanResult;
+
+    fn name(&self) -> &'static str {
+        "Koodous"
+    }
+
+    /// Free API keys are throttled; stay around 10 requests per minute
+    fn rate_limit_policy(&self) -> RateLimitPolicy {
+        RateLimitPolicy {
+            windows: vec![(10, Duration::from_secs(60))],
+            min_interval: Duration::from_secs(2),
+            submit_backoff: None,
+            wait_counts_against_timeout: false,
+        }
+    }
+
+    fn poll_policy(&self) -> PollPolicy {
+        PollPolicy {
+            interval: Duration::ZERO,
+            max_attempts: Some(0),
+            defer_unfinished: false,
+            file_timeout: Duration::from_secs(60),
+        }
+    }
+
+    fn cached_report(&self, target: &ScanTarget) -> Option<CachedReport<FileScanResult>> {
+        let mut conn = db::establish_connection();
+        let cached = db_koodous::get_result_by_sha256(&mut conn, &target.sha256).ok()??;
+        // Cached 404s keep their verdict so they show up in the UI
+        Some(CachedReport::Report(FileScanResult::from_db_result(&target.file_path, &cached)))
+    }
+
+    fn lookup_hash(&self, target: &ScanTarget, gate: &RequestGate) -> Result<FileScanResult, ProviderError> {
+        gate.acquire()?;
+        let apk = api_koodous::get_apk_at(&self.base_url, &target.sha256, &self.api_key)?;
+        log::info!("Got Koodous result for {}", target.sha256);
+
+        let result = FileScanResult::from_apk(&target.file_path, &target.sha256, &apk);
+        self.store(target, &result.verdict, Some(apk));
+        Ok(result)
+    }
+
+    fn record_not_found(&self, target: &ScanTarget) {
+        self.store(target, "404 Not Found", None);
+    }
+
+    fn normalize(&self, report: &FileScanResult) -> NormalizedResult {
+        let verdict = match report.verdict.as_str() {
+            "detected" => Verdict::Malicious,
+            "trusted" | "undetected" => Verdict::Clean,
+            _ => Verdict::Unknown,
+        };
+
+        NormalizedResult {
+            provider: self.name().to_string(),
+            sha256: report.sha256.clone(),
+            verdict,
+            detections: None,
+            engines: None,
+            score: report.rating,
+            link: report.koodous_link.clone(),
+        }
+    }
+}
+
+/// Initialize scanner state by checking database cache
+pub fn init_scanner_state(package_names: &[String]) -> ScannerState {
+    let mut conn = db::establish_connection();
+    calc_scanprovider::init_scanner_state(package_names, |pkg_name| {
+        db_koodous::get_results_by_package(&mut conn, pkg_name)
+            .map(|cached| (!cached.is_empty()).then(|| CalcKoodous::from_db_results(cached)))
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Look up a package's file hashes on Koodous
+pub fn analyze_package(
+    package_name: &str,
+    hashes: Vec<(String, String)>,
+    state: &ScannerState,
+    rate_limiter: &SharedRateLimiter,
+    provider: &KoodousProvider,
+) {
+    calc_scanprovider::lookup_package(
+        provider,
+        package_name,
+        hashes,
+        state,
+        rate_limiter,
+        |t| FileScanResult::not_found(&t.file_path, &t.sha256),
+        |file_results| CalcKoodous { file_results },
+    );
+}
+
+/// Run Koodous lookups for a list of packages in a background thread.
+///
+/// # Returns
+/// Returns the scanner state and rate limiter for tracking progress
+pub fn run_koodous(
+    installed_packages: Vec<crate::adb::PackageFingerprint>,
+    device_serial: String,
+    api_key: String,
+    package_risk_scores: HashMap<String, i32>,
+    koodous_scan_progress: Arc<Mutex<Option<f32>>>,
+    koodous_scan_cancelled: Arc<Mutex<bool>>,
+) -> (ScannerState, SharedRateLimiter) {
+    let package_names: Vec<String> = installed_packages.iter().map(|p| p.pkg.clone()).collect();
+    let scanner_state = init_scanner_state(&package_names);
+
+    let provider = KoodousProvider::new(api_key);
+    let rate_limiter = RateLimiter::shared(provider.rate_limit_policy());
+
+    // Initialize progress
+    if let Ok(mut p) = koodous_scan_progress.lock() {
+        *p = Some(0.0);
+    }
+    if let Ok(mut cancelled) = koodous_scan_cancelled.lock() {
+        *cancelled = false;
+    }
+
+    log::info!(
+        "Starting Koodous lookup for {} packages",
+        installed_packages.len()
+    );
+
+    let scanner_state_clone = scanner_state.clone();
+    let rate_limiter_clone = rate_limiter.clone();
+
+    std::thread::spawn(move || {
+        calc_scanprovider::scan_installed_packages(
+            provider.name(),
+            installed_packages,
+            &device_serial,
+            &package_risk_scores,
+            &HashSet::new(),
+            &koodous_scan_progress,
+            &koodous_scan_cancelled,
+            |pkg_name| {
+                let s = scanner_state_clone.lock().unwrap();
+                matches!(s.get(pkg_name), Some(ScanStatus::Completed(_)))
+            },
+            |pkg_name, hashes| {
+                analyze_package(pkg_name, hashes, &scanner_state_clone, &rate_limiter_clone, &provider)
+            },
+        );
+
+        if let Ok(mut p) = koodous_scan_progress.lock() {
+            *p = None;
+        }
+    });
+
+    (scanner_state, rate_limiter)
+}
@@ -0,0 +1,28 @@
+/// Koodous lookup result for a single file
+#[derive(Debug, Clone)]
+pub struct FileScanResult {
+    pub file_path: String,
+    pub sha256: String,
+    /// "detected", "trusted", "undetected" or "404 Not Found"
+    pub verdict: String,
+    pub app: Option<String>,
+    pub company: Option<String>,
+    pub rating: Option<i32>,
+    pub tags: Vec<String>,
+    pub koodous_link: String,
+}
+
+/// Koodous lookup result for a package (may contain multiple files)
+#[derive(Debug, Clone, Default)]
+pub struct CalcKoodous {
+    pub file_results: Vec<FileScanResult>,
+}
+
+/// Status of a Koodous lookup for a package
+pub type ScanStatus = crate::calc_scanprovider_stt::ScanStatus<CalcKoodous>;
+
+/// Shared state for Koodous lookups
+pub type ScannerState = crate::calc_scanprovider_stt::ScannerState<CalcKoodous>;
+
+/// Shared rate limiter for Koodous API
+pub type SharedRateLimiter = crate::calc_scanprovider_stt::SharedRateLimiter;
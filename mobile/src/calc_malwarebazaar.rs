@@ -0,0 +1,258 @@
+use crate::api_malwarebazaar::{self, MalwareBazaarSample, MbError};
+use crate::calc_scanprovider::{
+    self, CachedReport, NormalizedResult, PollPolicy, ProviderError, RateLimitPolicy, RateLimiter,
+    RequestGate, ScanProvider, ScanTarget, Verdict,
+};
+use crate::db;
+use crate::db_malwarebazaar;
+use crate::models::MalwareBazaarResult;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub use crate::calc_malwarebazaar_stt::*;
+
+impl FileScanResult {
+    fn not_found(file_path: &str, sha256: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            sha256: sha256.to_string(),
+            verdict: "404 Not Found".to_string(),
+            signature: None,
+            file_type: None,
+            first_seen: None,
+            tags: Vec::new(),
+            mb_link: format!("https://bazaar.abuse.ch/sample/{}/", sha256),
+        }
+    }
+
+    fn from_sample(file_path: &str, sha256: &str, sample: &MalwareBazaarSample) -> Self {
+        Self {
+            verdict: "malicious".to_string(),
+            signature: sample.signature.clone(),
+            file_type: sample.file_type.clone(),
+            first_seen: sample.first_seen.clone(),
+            tags: sample.tags.clone().unwrap_or_default(),
+            ..Self::not_found(file_path, sha256)
+        }
+    }
+
+    fn from_db_result(file_path: &str, r: &MalwareBazaarResult) -> Self {
+        Self {
+            verdict: r.verdict.clone(),
+            signature: r.signature.clone(),
+            file_type: r.file_type.clone(),
+            first_seen: r.first_seen.clone(),
+            tags: serde_json::from_str(&r.tags).unwrap_or_default(),
+            ..Self::not_found(file_path, &r.sha256)
+        }
+    }
+
+    /// Whether MalwareBazaar lists this file as a malware sample
+    pub fn is_listed(&self) -> bool {
+        self.verdict == "malicious"
+    }
+}
+
+impl CalcMalwareBazaar {
+    pub fn from_db_results(db_results: Vec<MalwareBazaarResult>) -> Self {
+        let file_results = db_results
+            .iter()
+            .map(|r| FileScanResult::from_db_result(&r.file_path, r))
+            .collect();
+
+        Self { file_results }
+    }
+}
+
+impl From<MbError> for ProviderError {
+    fn from(err: MbError) -> Self {
+        match err {
+            MbError::NotFound => ProviderError::NotFound,
+            MbError::RateLimit { retry_after } => ProviderError::RateLimit { retry_after },
+            e => ProviderError::Other(e.to_string()),
+        }
+    }
+}
+
+/// MalwareBazaar adapter for the scan pipeline in `calc_scanprovider`.
+/// Lookup only: MalwareBazaar accepts uploads of malware samples, not unknown files.
+pub struct MalwareBazaarProvider {
+    pub api_key: String,
+    pub api_url: String,
+}
+
+impl MalwareBazaarProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            api_url: api_malwarebazaar::API_URL.to_string(),
+        }
+    }
+
+    fn store(&self, target: &ScanTarget, verdict: &str, sample: Option<MalwareBazaarSample>) {
+        // Save to database via queue
+        if let Err(e) = db_malwarebazaar::queue_upsert(
+            target.package_name.clone(),
+            target.file_path.clone(),
+            target.sha256.clone(),
+            verdict.to_string(),
+            sample,
+        ) {
+            log::error!("Failed to cache MalwareBazaar result for {}: {}", target.sha256, e);
+        }
+    }
+}
+
+impl ScanProvider for MalwareBazaarProvider {
+    type Report = FileScanResult;
+
+    fn name(&self) -> &'static str {
+        "MalwareBazaar"
+    }
+
+    /// No published quota; keep a short spacing to stay polite
+    fn rate_limit_policy(&self) -> RateLimitPolicy {
+        RateLimitPolicy {
+            windows: Vec::new(),
+            min_interval: Duration::from_secs(1),
+            submit_backoff: None,
+            wait_counts_against_timeout: false,
+        }
+    }
+
+    fn poll_policy(&self) -> PollPolicy {
+        PollPolicy {
+            interval: Duration::ZERO,
+            max_attempts: Some(0),
+            defer_unfinished: false,
+            file_timeout: Duration::from_secs(60),
+        }
+    }
+
+    fn cached_report(&self, target: &ScanTarget) -> Option<CachedReport<FileScanResult>> {
+        let mut conn = db::establish_connection();
+        let cached = db_malwarebazaar::get_result_by_sha256(&mut conn, &target.sha256).ok()??;
+        // Cached 404s keep their verdict so they show up in the UI
+        Some(CachedReport::Report(FileScanResult::from_db_result(&target.file_path, &cached)))
+    }
+
+    fn lookup_hash(&self, target: &ScanTarget, gate: &RequestGate) -> Result<FileScanResult, ProviderError> {
+        gate.acquire()?;
+        let sample = api_malwarebazaar::query_hash_at(&self.api_url, &target.sha256, &self.api_key)?;
+        log::info!("Got MalwareBazaar listing for {}", target.sha256);
+
+        let result = FileScanResult::from_sample(&target.file_path, &target.sha256, &sample);
+        self.store(target, &result.verdict, Some(sample));
+        Ok(result)
+    }
+
+    fn record_not_found(&self, target: &ScanTarget) {
+        self.store(target, "404 Not Found", None);
+    }
+
+    fn normalize(&self, report: &FileScanResult) -> NormalizedResult {
+        NormalizedResult {
+            provider: self.name().to_string(),
+            sha256: report.sha256.clone(),
+            verdict: if report.is_listed() {
+                Verdict::Malicious
+            } else {
+                Verdict::Clean
+            },
+            detections: None,
+            engines: None,
+            score: None,
+            link: report.mb_link.clone(),
+        }
+    }
+}
+
+/// Initialize scanner state by checking database cache
+pub fn init_scanner_state(package_names: &[String]) -> ScannerState {
+    let mut conn = db::establish_connection();
+    calc_scanprovider::init_scanner_state(package_names, |pkg_name| {
+        db_malwarebazaar::get_results_by_package(&mut conn, pkg_name)
+            .map(|cached| (!cached.is_empty()).then(|| CalcMalwareBazaar::from_db_results(cached)))
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Look up a package's file hashes on MalwareBazaar
+pub fn analyze_package(
+    package_name: &str,
+    hashes: Vec<(String, String)>,
+    state: &ScannerState,
+    rate_limiter: &SharedRateLimiter,
+    provider: &MalwareBazaarProvider,
+) {
+    calc_scanprovider::lookup_package(
+        provider,
+        package_name,
+        hashes,
+        state,
+        rate_limiter,
+        |t| FileScanResult::not_found(&t.file_path, &t.sha256),
+        |file_results| CalcMalwareBazaar { file_results },
+    );
+}
+
+/// Run MalwareBazaar lookups for a list of packages in a background thread.
+///
+/// # Returns
+/// Returns the scanner state and rate limiter for tracking progress
+pub fn run_malwarebazaar(
+    installed_packages: Vec<crate::adb::PackageFingerprint>,
+    device_serial: String,
+    api_key: String,
+    package_risk_scores: HashMap<String, i32>,
+    mb_scan_progress: Arc<Mutex<Option<f32>>>,
+    mb_scan_cancelled: Arc<Mutex<bool>>,
+) -> (ScannerState, SharedRateLimiter) {
+    let package_names: Vec<String> = installed_packages.iter().map(|p| p.pkg.clone()).collect();
+    let scanner_state = init_scanner_state(&package_names);
+
+    let provider = MalwareBazaarProvider::new(api_key);
+    let rate_limiter = RateLimiter::shared(provider.rate_limit_policy());
+
+    // Initialize progress
+    if let Ok(mut p) = mb_scan_progress.lock() {
+        *p = Some(0.0);
+    }
+    if let Ok(mut cancelled) = mb_scan_cancelled.lock() {
+        *cancelled = false;
+    }
+
+    log::info!(
+        "Starting MalwareBazaar lookup for {} packages",
+        installed_packages.len()
+    );
+
+    let scanner_state_clone = scanner_state.clone();
+    let rate_limiter_clone = rate_limiter.clone();
+
+    std::thread::spawn(move || {
+        calc_scanprovider::scan_installed_packages(
+            provider.name(),
+            installed_packages,
+            &device_serial,
+            &package_risk_scores,
+            &HashSet::new(),
+            &mb_scan_progress,
+            &mb_scan_cancelled,
+            |pkg_name| {
+                let s = scanner_state_clone.lock().unwrap();
+                matches!(s.get(pkg_name), Some(ScanStatus::Completed(_)))
+            },
+            |pkg_name, hashes| {
+                analyze_package(pkg_name, hashes, &scanner_state_clone, &rate_limiter_clone, &provider)
+            },
+        );
+
+        if let Ok(mut p) = mb_scan_progress.lock() {
+            *p = None;
+        }
+    });
+
+    (scanner_state, rate_limiter)
+}
@@ -0,0 +1,29 @@
+/// MalwareBazaar lookup result for a single file
+#[derive(Debug, Clone)]
+pub struct FileScanResult {
+    pub file_path: String,
+    pub sha256: String,
+    /// "malicious" when listed, "404 Not Found" otherwise
+    pub verdict: String,
+    /// Malware family
+    pub signature: Option<String>,
+    pub file_type: Option<String>,
+    pub first_seen: Option<String>,
+    pub tags: Vec<String>,
+    pub mb_link: String,
+}
+
+/// MalwareBazaar lookup result for a package (may contain multiple files)
+#[derive(Debug, Clone, Default)]
+pub struct CalcMalwareBazaar {
+    pub file_results: Vec<FileScanResult>,
+}
+
+/// Status of a MalwareBazaar lookup for a package
+pub type ScanStatus = crate::calc_scanprovider_stt::ScanStatus<CalcMalwareBazaar>;
+
+/// Shared state for MalwareBazaar lookups
+pub type ScannerState = crate::calc_scanprovider_stt::ScannerState<CalcMalwareBazaar>;
+
+/// Shared rate limiter for MalwareBazaar API
+pub type SharedRateLimiter = crate::calc_scanprovider_stt::SharedRateLimiter;
@@ -0,0 +1,670 @@
+pub use crate::calc_nativelibs_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::calc_scanrules::MAX_ENTRY_SIZE;
+use crate::shared_store_stt::get_shared_store;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Bump when the ELF parsing or vulnerability checks change, to invalidate cached inventories
+pub const INVENTORY_VERSION: i32 = 2;
+
+/// Separator between an APK path and an entry inside it ("base.apk!/lib/arm64-v8a/libfoo.so")
+const EMBEDDED_SEPARATOR: &str = "!/";
+
+const SHT_DYNAMIC: u32 = 6;
+const SHT_DYNSYM: u32 = 11;
+const DT_NULL: u64 = 0;
+const DT_NEEDED: u64 = 1;
+
+/// Imports that deserve a look in a native library
+const NOTABLE_IMPORTS: &[&str] = &[
+    "dlopen",
+    "android_dlopen_ext",
+    "ptrace",
+    "execve",
+    "execv",
+    "execvp",
+    "execl",
+    "execlp",
+    "system",
+    "popen",
+    "fork",
+    "kill",
+    "mprotect",
+    "inotify_add_watch",
+    "__system_property_get",
+    "socket",
+    "connect",
+];
+
+/// Version check of a bundled library: a regex capturing the embedded version, and per
+/// version prefix the first fixed version (`None` when the whole branch is end-of-life)
+struct VersionCheck {
+    library: &'static str,
+    pattern: &'static str,
+    branches: &'static [(&'static str, Option<&'static str>, &'static str)],
+}
+
+const VERSION_CHECKS: &[VersionCheck] = &[
+    VersionCheck {
+        library: "OpenSSL",
+        pattern: r"OpenSSL (\d+\.\d+\.\d+[a-z]?)[ -]",
+        branches: &[
+            ("1.0.", None, "OpenSSL 1.0.x is end-of-life with unpatched CVEs"),
+            ("1.1.0", None, "OpenSSL 1.1.0 is end-of-life with unpatched CVEs"),
+            ("1.1.1", Some("1.1.1w"), "CVE-2023-0286, CVE-2023-0215 and later OpenSSL 1.1.1 advisories"),
+            ("3.0.", Some("3.0.7"), "CVE-2022-3602 / CVE-2022-3786 (X.509 email buffer overflows)"),
+        ],
+    },
+    VersionCheck {
+        library: "zlib",
+        pattern: r"(?:deflate|inflate) (\d+\.\d+\.\d+(?:\.\d+)?) Copyright",
+        branches: &[("1.", Some("1.2.13"), "CVE-2022-37434 (inflate heap overflow), CVE-2018-25032")],
+    },
+    VersionCheck {
+        library: "libpng",
+        pattern: r"libpng version (\d+\.\d+\.\d+)",
+        branches: &[("1.", Some("1.6.37"), "CVE-2019-7317 (png_image_free use-after-free)")],
+    },
+    VersionCheck {
+        library: "libcurl",
+        pattern: r"libcurl/(\d+\.\d+\.\d+)",
+        branches: &[("", Some("8.4.0"), "CVE-2023-38545 (SOCKS5 heap overflow)")],
+    },
+];
+
+lazy_static::lazy_static! {
+    static ref VERSION_REGEXES: Vec<regex::bytes::Regex> = VERSION_CHECKS
+        .iter()
+        .map(|c| regex::bytes::Regex::new(c.pattern).expect("valid version pattern"))
+        .collect();
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Path of an entry inside an APK, as used for scan targets
+pub fn embedded_path(apk_path: &str, entry: &str) -> String {
+    format!("{}{}{}", apk_path, EMBEDDED_SEPARATOR, entry)
+}
+
+/// Split a scan target path into the APK path and the entry inside it
+pub fn split_embedded_path(path: &str) -> Option<(&str, &str)> {
+    path.split_once(EMBEDDED_SEPARATOR)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset.saturating_add(2))
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| format!("Unexpected end of ELF data at offset {}", offset))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset.saturating_add(4))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("Unexpected end of ELF data at offset {}", offset))
+}
+
+/// Address-sized value: 4 bytes in ELF32, 8 bytes in ELF64
+fn read_word(data: &[u8], offset: usize, is_64: bool) -> Result<u64, String> {
+    if !is_64 {
+        return read_u32(data, offset).map(u64::from);
+    }
+    data.get(offset..offset.saturating_add(8))
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .ok_or_else(|| format!("Unexpected end of ELF data at offset {}", offset))
+}
+
+/// `base + index * size`, failing instead of overflowing on values read from the file
+fn table_offset(base: usize, index: usize, size: usize) -> Result<usize, String> {
+    index
+        .checked_mul(size)
+        .and_then(|o| o.checked_add(base))
+        .ok_or_else(|| format!("ELF offset overflow at {} + {} * {}", base, index, size))
+}
+
+struct Section {
+    sh_type: u32,
+    offset: usize,
+    size: usize,
+    link: usize,
+}
+
+/// NUL-terminated string at `index` in a string table section
+fn section_string(data: &[u8], strtab: &Section, index: usize) -> Option<String> {
+    let start = strtab.offset.checked_add(index)?;
+    let end = strtab.offset.checked_add(strtab.size)?.min(data.len());
+    let bytes = data.get(start..end)?;
+    let len = bytes.iter().position(|b| *b == 0)?;
+    Some(String::from_utf8_lossy(&bytes[..len]).to_string())
+}
+
+/// Read the header, `DT_NEEDED` entries and dynamic symbols of a little-endian ELF file
+pub fn parse_elf(data: &[u8]) -> Result<ElfInfo, String> {
+    if data.len() < 0x34 || &data[0..4] != b"\x7fELF" {
+        return Err("Not an ELF file".to_string());
+    }
+    let is_64 = match data[4] {
+        1 => false,
+        2 => true,
+        class => return Err(format!("Unknown ELF class {}", class)),
+    };
+    if data[5] != 1 {
+        return Err("Big-endian ELF is not supported".to_string());
+    }
+
+    let machine = read_u16(data, 0x12)?;
+    let (shoff, shentsize, shnum) = if is_64 {
+        (read_word(data, 0x28, true)? as usize, read_u16(data, 0x3A)? as usize, read_u16(data, 0x3C)? as usize)
+    } else {
+        (read_word(data, 0x20, false)? as usize, read_u16(data, 0x2E)? as usize, read_u16(data, 0x30)? as usize)
+    };
+
+    let mut sections = Vec::with_capacity(shnum);
+    for i in 0..shnum {
+        let off = table_offset(shoff, i, shentsize)?;
+        sections.push(Section {
+            sh_type: read_u32(data, off.saturating_add(4))?,
+            offset: read_word(data, off.saturating_add(if is_64 { 0x18 } else { 0x10 }), is_64)? as usize,
+            size: read_word(data, off.saturating_add(if is_64 { 0x20 } else { 0x14 }), is_64)? as usize,
+            link: read_u32(data, off.saturating_add(if is_64 { 0x28 } else { 0x18 }))? as usize,
+        });
+    }
+
+    let mut info = ElfInfo {
+        machine,
+        bits: if is_64 { 64 } else { 32 },
+        ..Default::default()
+    };
+
+    for section in &sections {
+        let Some(strtab) = sections.get(section.link) else {
+            continue;
+        };
+        match section.sh_type {
+            SHT_DYNAMIC => {
+                let entsize = if is_64 { 16 } else { 8 };
+                for i in 0..section.size / entsize {
+                    let off = table_offset(section.offset, i, entsize)?;
+                    let tag = read_word(data, off, is_64)?;
+                    if tag == DT_NULL {
+                        break;
+                    }
+                    if tag == DT_NEEDED {
+                        let value = read_word(data, off.saturating_add(entsize / 2), is_64)? as usize;
+                        if let Some(name) = section_string(data, strtab, value) {
+                            info.needed.push(name);
+                        }
+                    }
+                }
+            }
+            SHT_DYNSYM => {
+                let entsize = if is_64 { 24 } else { 16 };
+                // Symbol 0 is the reserved null symbol
+                for i in 1..section.size / entsize {
+                    let off = table_offset(section.offset, i, entsize)?;
+                    let name_idx = read_u32(data, off)? as usize;
+                    let st_info = *data.get(off.saturating_add(if is_64 { 4 } else { 12 })).ok_or("Truncated symbol table")?;
+                    let shndx = read_u16(data, off.saturating_add(if is_64 { 6 } else { 14 }))?;
+                    let Some(name) = section_string(data, strtab, name_idx).filter(|n| !n.is_empty()) else {
+                        continue;
+                    };
+                    if shndx == 0 {
+                        info.imports.push(name);
+                    } else if st_info >> 4 != 0 {
+                        info.exports.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+pub fn elf_machine_name(machine: u16) -> &'static str {
+    match machine {
+        0x03 => "x86",
+        0x08 => "MIPS",
+        0x28 => "ARM",
+        0x3E => "x86-64",
+        0xB7 => "AArch64",
+        0xF3 => "RISC-V",
+        _ => "unknown",
+    }
+}
+
+/// Compare dotted versions numerically, with an optional letter suffix per part ("1.1.1k")
+fn version_less_than(version: &str, other: &str) -> bool {
+    let key = |v: &str| -> Vec<(u64, String)> {
+        v.split('.')
+            .map(|part| {
+                let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+                (digits.parse().unwrap_or(0), part[digits.len()..].to_string())
+            })
+            .collect()
+    };
+    key(version) < key(other)
+}
+
+/// Bundled libraries with known-vulnerable or end-of-life versions, from embedded version strings
+pub fn detect_vulnerable_components(data: &[u8]) -> Vec<VulnerableComponent> {
+    let mut found = Vec::new();
+    for (check, regex) in VERSION_CHECKS.iter().zip(VERSION_REGEXES.iter()) {
+        let mut versions: Vec<String> = regex
+            .captures_iter(data)
+            .filter_map(|c| c.get(1))
+            .map(|m| String::from_utf8_lossy(m.as_bytes()).to_string())
+            .collect();
+        versions.sort();
+        versions.dedup();
+        for version in versions {
+            let branch = check
+                .branches
+                .iter()
+                .find(|(prefix, _, _)| version.starts_with(prefix));
+            if let Some((_, fixed, advisory)) = branch {
+                if fixed.map_or(true, |fixed| version_less_than(&version, fixed)) {
+                    found.push(VulnerableComponent {
+                        library: check.library.to_string(),
+                        version: Some(version),
+                        advisory: advisory.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Bundled libraries worth a manual check that embed no version string. Informational only:
+/// most apps bundling libwebp ship a fixed version, so it is not reported as vulnerable.
+pub fn component_notes(exports: &[String]) -> Vec<String> {
+    let mut notes = Vec::new();
+    if exports.iter().any(|e| e == "WebPDecode" || e == "WebPDecodeRGBA") {
+        notes.push("Bundles libwebp; versions before 1.3.2 are affected by CVE-2023-4863".to_string());
+    }
+    notes
+}
+
+/// ABI directory of a `lib/<abi>/*.so` entry
+fn native_library_abi(entry: &str) -> Option<&str> {
+    let rest = entry.strip_prefix("lib/")?;
+    let (abi, file) = rest.split_once('/')?;
+    (file.ends_with(".so") && !file.contains('/')).then_some(abi)
+}
+
+/// Inventory one native library from its contents
+pub fn inspect_native_library(entry: &str, data: &[u8]) -> NativeLibrary {
+    let (arch, bits, needed, notable_imports, notes) = match parse_elf(data) {
+        Ok(elf) => {
+            let mut notable: Vec<String> = elf
+                .imports
+                .iter()
+                .filter(|i| NOTABLE_IMPORTS.contains(&i.as_str()))
+                .cloned()
+                .collect();
+            notable.sort();
+            notable.dedup();
+            (
+                elf_machine_name(elf.machine).to_string(),
+                elf.bits,
+                elf.needed,
+                notable,
+                component_notes(&elf.exports),
+            )
+        }
+        Err(e) => {
+            log::warn!("Failed to parse {}: {}", entry, e);
+            ("invalid ELF".to_string(), 0, Vec::new(), Vec::new(), Vec::new())
+        }
+    };
+    NativeLibrary {
+        apk_path: String::new(),
+        entry: entry.to_string(),
+        abi: native_library_abi(entry).unwrap_or_default().to_string(),
+        sha256: to_hex(&Sha256::digest(data)),
+        size: data.len() as u64,
+        arch,
+        bits,
+        needed,
+        notable_imports,
+        vulnerable: detect_vulnerable_components(data),
+        notes,
+    }
+}
+
+/// List and inspect every `lib/<abi>/*.so` in an APK
+pub fn inventory_apk(apk_path: &Path) -> Result<Vec<NativeLibrary>, String> {
+    let file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+
+    let entries: Vec<String> = archive
+        .file_names()
+        .filter(|n| native_library_abi(n).is_some())
+        .map(|n| n.to_string())
+        .collect();
+
+    let mut libraries = Vec::with_capacity(entries.len());
+    for name in entries {
+        let entry = archive
+            .by_name(&name)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        if entry.size() > MAX_ENTRY_SIZE {
+            log::warn!("Skipping {} in {:?}: {} bytes", name, apk_path, entry.size());
+            continue;
+        }
+        // The declared size comes from the zip header, so cap what is actually read too
+        let mut data = Vec::new();
+        entry
+            .take(MAX_ENTRY_SIZE)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        libraries.push(inspect_native_library(&name, &data));
+    }
+    libraries.sort_by(|a, b| a.entry.cmp(&b.entry));
+    Ok(libraries)
+}
+
+/// Extract one entry of a local APK into `dest_dir`, for submission to a scan provider
+pub fn extract_entry(apk_path: &Path, entry: &str, dest_dir: &Path) -> Result<PathBuf, String> {
+    let file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+    let mut zip_entry = archive
+        .by_name(entry)
+        .map_err(|e| format!("Failed to find {} in {:?}: {}", entry, apk_path, e))?;
+
+    let file_name = entry.rsplit('/').next().unwrap_or(entry);
+    let dest_path = dest_dir.join(format!(
+        "{}_{}",
+        apk_path.file_stem().and_then(|s| s.to_str()).unwrap_or("apk"),
+        file_name
+    ));
+    let mut dest = std::fs::File::create(&dest_path)
+        .map_err(|e| format!("Failed to create {:?}: {}", dest_path, e))?;
+    std::io::copy(&mut zip_entry, &mut dest).map_err(|e| format!("Failed to extract {}: {}", entry, e))?;
+    Ok(dest_path)
+}
+
+/// Native libraries of an APK from the cache, if it was inventoried with the current version
+pub fn cached_native_libraries(apk_sha256sum: &str) -> Option<Vec<NativeLibrary>> {
+    let cached = crate::db_package_cache::get_cached_apk_native_libs(apk_sha256sum, INVENTORY_VERSION)?;
+    serde_json::from_str(&cached.libs_json).ok()
+}
+
+/// `primaryCpuAbi` of a package from its `dumpsys package` block
+pub fn primary_cpu_abi(package: &PackageFingerprint) -> Option<String> {
+    crate::calc_provenance::dump_value(&package.dumpText, "primaryCpuAbi")
+}
+
+/// (scan target path, sha256) of the cached native libraries in a package's APKs.
+/// Only the package's primary ABI is included when it is known.
+pub fn native_library_hashes(package: &PackageFingerprint, apk_hashes: &[(String, String)]) -> Vec<(String, String)> {
+    let abi = primary_cpu_abi(package);
+    let abi = abi.as_deref();
+    apk_hashes
+        .iter()
+        .filter(|(path, _)| path.ends_with(".apk"))
+        .filter_map(|(path, sha256)| Some((path, cached_native_libraries(sha256)?)))
+        .flat_map(|(path, libraries)| {
+            libraries
+                .into_iter()
+                .filter(move |lib| abi.map_or(true, |abi| lib.abi == abi))
+                .map(move |lib| (embedded_path(path, &lib.entry), lib.sha256))
+        })
+        .collect()
+}
+
+/// Native libraries of one APK file, pulling and inspecting it only when no cached
+/// inventory exists for the same sha256sum
+fn native_libraries_for_apk(
+    device_serial: &str,
+    pkg_id: &str,
+    apk_path: &str,
+    apk_sha256sum: &str,
+    tmp_dir: &str,
+) -> Result<Vec<NativeLibrary>, String> {
+    if let Some(libraries) = cached_native_libraries(apk_sha256sum) {
+        return Ok(libraries);
+    }
+
+    let local_path = crate::adb::pull_file_to_temp(device_serial, apk_path, tmp_dir, pkg_id)
+        .map_err(|e| format!("Failed to pull {}: {}", apk_path, e))?;
+    let result = inventory_apk(Path::new(&local_path));
+    let _ = std::fs::remove_file(&local_path);
+    let libraries = result?;
+
+    match serde_json::to_string(&libraries) {
+        Ok(json) => {
+            if let Err(e) = crate::db_package_cache::upsert_apk_native_libs_cache(
+                apk_sha256sum,
+                INVENTORY_VERSION,
+                pkg_id,
+                &json,
+            ) {
+                log::error!("Failed to cache native libraries for {}: {}", pkg_id, e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize native libraries for {}: {}", pkg_id, e),
+    }
+
+    Ok(libraries)
+}
+
+/// Inventory native libraries of packages in a background thread.
+/// With `pull_missing` false only cached inventories are loaded (no APK is pulled).
+pub fn run_native_lib_scan_async(
+    installed_packages: Vec<PackageFingerprint>,
+    device_serial: String,
+    pull_missing: bool,
+    progress_clone: Arc<Mutex<Option<f32>>>,
+    cancelled_clone: Arc<Mutex<bool>>,
+) {
+    std::thread::spawn(move || {
+        let store = get_shared_store();
+
+        let tmp_dir = match crate::Config::new() {
+            Ok(config) => config.tmp_dir.to_string_lossy().to_string(),
+            Err(e) => {
+                log::error!("Config not available for native library scan: {}", e);
+                if let Ok(mut p) = progress_clone.lock() {
+                    *p = None;
+                }
+                return;
+            }
+        };
+
+        let finished = crate::calc_scanprovider::scan_package_apks(
+            &installed_packages,
+            &device_serial,
+            pull_missing,
+            &progress_clone,
+            &cancelled_clone,
+            |package, path, sha256| {
+                if pull_missing {
+                    native_libraries_for_apk(&device_serial, &package.pkg, path, sha256, &tmp_dir)
+                        .map_err(|e| log::warn!("Native library scan failed for {}: {}", package.pkg, e))
+                        .ok()
+                } else {
+                    cached_native_libraries(sha256)
+                }
+            },
+            |package, results| {
+                let package_libraries = results
+                    .into_iter()
+                    .flat_map(|(path, libraries)| {
+                        libraries.into_iter().map(move |lib| NativeLibrary {
+                            apk_path: path.to_string(),
+                            ..lib
+                        })
+                    })
+                    .collect();
+                store.set_native_libraries(package.pkg.clone(), package_libraries);
+            },
+        );
+        if !finished {
+            log::info!("Native library scan cancelled by user");
+        }
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = None;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal little-endian ELF64 with a .dynamic (DT_NEEDED) and .dynsym section
+    fn build_elf64(needed: &[&str], imports: &[&str], exports: &[&str]) -> Vec<u8> {
+        let mut strtab = vec![0u8];
+        let mut add_str = |s: &str| {
+            let off = strtab.len();
+            strtab.extend_from_slice(s.as_bytes());
+            strtab.push(0);
+            off as u64
+        };
+        let needed_offs: Vec<u64> = needed.iter().map(|s| add_str(s)).collect();
+        let mut symbols: Vec<(u64, u16, u8)> = imports.iter().map(|s| (add_str(s), 0u16, 0x12u8)).collect();
+        symbols.extend(exports.iter().map(|s| (add_str(s), 9u16, 0x12u8)));
+
+        let mut dynamic = Vec::new();
+        for off in &needed_offs {
+            dynamic.extend_from_slice(&DT_NEEDED.to_le_bytes());
+            dynamic.extend_from_slice(&off.to_le_bytes());
+        }
+        dynamic.extend_from_slice(&[0u8; 16]);
+
+        let mut dynsym = vec![0u8; 24];
+        for (name, shndx, info) in &symbols {
+            let mut sym = vec![0u8; 24];
+            sym[0..4].copy_from_slice(&(*name as u32).to_le_bytes());
+            sym[4] = *info;
+            sym[6..8].copy_from_slice(&shndx.to_le_bytes());
+            dynsym.extend_from_slice(&sym);
+        }
+
+        let strtab_off = 0x40;
+        let dynamic_off = strtab_off + strtab.len();
+        let dynsym_off = dynamic_off + dynamic.len();
+        let shoff = dynsym_off + dynsym.len();
+
+        let mut data = vec![0u8; 0x40];
+        data[0..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2;
+        data[5] = 1;
+        data[0x12..0x14].copy_from_slice(&0xB7u16.to_le_bytes());
+        data[0x28..0x30].copy_from_slice(&(shoff as u64).to_le_bytes());
+        data[0x3A..0x3C].copy_from_slice(&64u16.to_le_bytes());
+        data[0x3C..0x3E].copy_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&strtab);
+        data.extend_from_slice(&dynamic);
+        data.extend_from_slice(&dynsym);
+
+        // Section headers: null, .dynstr (3), .dynamic (6), .dynsym (11)
+        let sections = [
+            (0u32, 0usize, 0usize, 0u32),
+            (3, strtab_off, strtab.len(), 0),
+            (SHT_DYNAMIC, dynamic_off, dynamic.len(), 1),
+            (SHT_DYNSYM, dynsym_off, dynsym.len(), 1),
+        ];
+        for (sh_type, offset, size, link) in sections {
+            let mut sh = vec![0u8; 64];
+            sh[4..8].copy_from_slice(&sh_type.to_le_bytes());
+            sh[0x18..0x20].copy_from_slice(&(offset as u64).to_le_bytes());
+            sh[0x20..0x28].copy_from_slice(&(size as u64).to_le_bytes());
+            sh[0x28..0x2C].copy_from_slice(&link.to_le_bytes());
+            data.extend_from_slice(&sh);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_elf() {
+        let elf = build_elf64(&["libc.so", "libdl.so"], &["dlopen", "malloc"], &["JNI_OnLoad"]);
+        let info = parse_elf(&elf).unwrap();
+        assert_eq!(elf_machine_name(info.machine), "AArch64");
+        assert_eq!(info.bits, 64);
+        assert_eq!(info.needed, vec!["libc.so".to_string(), "libdl.so".to_string()]);
+        assert_eq!(info.imports, vec!["dlopen".to_string(), "malloc".to_string()]);
+        assert_eq!(info.exports, vec!["JNI_OnLoad".to_string()]);
+        assert!(parse_elf(b"PK\x03\x04").is_err());
+
+        // Hostile section header offset and sizes fail instead of overflowing
+        let mut hostile = elf.clone();
+        hostile[0x28..0x30].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(parse_elf(&hostile).is_err());
+        let mut hostile = elf.clone();
+        let shoff = u64::from_le_bytes(hostile[0x28..0x30].try_into().unwrap()) as usize;
+        // .dynamic section offset
+        hostile[shoff + 2 * 64 + 0x18..shoff + 2 * 64 + 0x20].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        assert!(parse_elf(&hostile).is_err());
+
+        let mut data = elf.clone();
+        data.extend_from_slice(b"\0OpenSSL 1.1.1k  25 Mar 2021\0");
+        let lib = inspect_native_library("lib/arm64-v8a/libnative.so", &data);
+        assert_eq!(lib.abi, "arm64-v8a");
+        assert_eq!(lib.arch, "AArch64");
+        assert_eq!(lib.notable_imports, vec!["dlopen".to_string()]);
+        assert_eq!(lib.vulnerable.len(), 1);
+        assert_eq!(lib.vulnerable[0].version.as_deref(), Some("1.1.1k"));
+        assert_eq!(lib.sha256.len(), 64);
+    }
+
+    #[test]
+    fn test_detect_vulnerable_components() {
+        let found = detect_vulnerable_components(
+            b"\0OpenSSL 3.0.12 24 Oct 2023\0 inflate 1.2.11 Copyright 1995-2017 Mark Adler\0libpng version 1.6.40\0",
+        );
+        let libraries: Vec<&str> = found.iter().map(|c| c.library.as_str()).collect();
+        assert_eq!(libraries, vec!["zlib"]);
+        assert_eq!(found[0].version.as_deref(), Some("1.2.11"));
+
+        // libwebp carries no version, so it is a note rather than a vulnerability
+        assert_eq!(component_notes(&["WebPDecode".to_string()]).len(), 1);
+        assert!(component_notes(&["JNI_OnLoad".to_string()]).is_empty());
+
+        assert!(version_less_than("1.1.1k", "1.1.1w"));
+        assert!(!version_less_than("1.1.1w", "1.1.1w"));
+        assert!(version_less_than("1.2.9", "1.2.13"));
+        assert!(!version_less_than("8.10.0", "8.4.0"));
+    }
+
+    #[test]
+    fn test_embedded_path() {
+        let path = embedded_path("/data/app/com.example-1/base.apk", "lib/arm64-v8a/libfoo.so");
+        assert_eq!(
+            split_embedded_path(&path),
+            Some(("/data/app/com.example-1/base.apk", "lib/arm64-v8a/libfoo.so"))
+        );
+        assert_eq!(split_embedded_path("/data/app/com.example-1/base.apk"), None);
+        assert_eq!(native_library_abi("lib/x86/libbar.so"), Some("x86"));
+        assert_eq!(native_library_abi("assets/lib/x86/libbar.so"), None);
+    }
+
+    #[test]
+    fn test_primary_cpu_abi_from_raw_dumpsys() {
+        let raw = "  Package [com.example.game] (2b1a0f9):
+    codePath=/data/app/~~Qw==/com.example.game-Rw==
+    legacyNativeLibraryDir=/data/app/~~Qw==/com.example.game-Rw==/lib
+    primaryCpuAbi=arm64-v8a
+    secondaryCpuAbi=armeabi-v7a
+    versionCode=3 minSdk=24 targetSdk=34
+    versionName=3.0
+    lastUpdateTime=2024-03-01 10:00:01
+";
+        let packages = crate::adb::parse_package_fingerprints(crate::adb::filter_fingerprint_lines(raw));
+        assert_eq!(packages.len(), 1);
+        assert_eq!(primary_cpu_abi(&packages[0]).as_deref(), Some("arm64-v8a"));
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Header, dependencies and symbols of an ELF shared object
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElfInfo {
+    /// `e_machine`
+    pub machine: u16,
+    /// 32 or 64
+    pub bits: u8,
+    /// `DT_NEEDED` libraries
+    pub needed: Vec<String>,
+    /// Undefined dynamic symbols
+    pub imports: Vec<String>,
+    /// Defined global dynamic symbols
+    pub exports: Vec<String>,
+}
+
+/// Bundled third-party library with a known-vulnerable or end-of-life version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VulnerableComponent {
+    pub library: String,
+    /// Version read from the library's strings, when it embeds one
+    pub version: Option<String>,
+    pub advisory: String,
+}
+
+/// Native library (`lib/<abi>/*.so`) inside an APK
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NativeLibrary {
+    /// Device path of the APK the library was found in; set when results are merged per package
+    #[serde(default)]
+    pub apk_path: String,
+    /// Entry name inside the APK (lib/arm64-v8a/libfoo.so)
+    pub entry: String,
+    pub abi: String,
+    pub sha256: String,
+    pub size: u64,
+    /// ELF machine ("AArch64", "ARM", ...), or "invalid ELF"
+    pub arch: String,
+    pub bits: u8,
+    pub needed: Vec<String>,
+    /// Imports worth a look (dynamic loading, process execution, anti-debugging, ...)
+    pub notable_imports: Vec<String>,
+    pub vulnerable: Vec<VulnerableComponent>,
+    /// Bundled components worth a manual check that carry no version (e.g. libwebp); informational
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
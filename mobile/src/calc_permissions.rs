@@ -0,0 +1,299 @@
+pub use crate::calc_permissions_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::models::PermissionJournalEntry;
+use crate::shared_store_stt::get_shared_store;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+/// Platform group of a runtime permission, by its short name
+pub fn permission_group(name: &str) -> PermissionGroup {
+    match name.rsplit('.').next().unwrap_or(name) {
+        "ACCESS_FINE_LOCATION" | "ACCESS_COARSE_LOCATION" | "ACCESS_BACKGROUND_LOCATION"
+        | "ACCESS_MEDIA_LOCATION" => PermissionGroup::Location,
+        "CAMERA" => PermissionGroup::Camera,
+        "RECORD_AUDIO" => PermissionGroup::Microphone,
+        "READ_CONTACTS" | "WRITE_CONTACTS" | "GET_ACCOUNTS" => PermissionGroup::Contacts,
+        "READ_CALENDAR" | "WRITE_CALENDAR" => PermissionGroup::Calendar,
+        "READ_PHONE_STATE" | "READ_PHONE_NUMBERS" | "CALL_PHONE" | "ANSWER_PHONE_CALLS" | "READ_CALL_LOG"
+        | "WRITE_CALL_LOG" | "PROCESS_OUTGOING_CALLS" | "ADD_VOICEMAIL" | "USE_SIP" | "ACCEPT_HANDOVER" => {
+            PermissionGroup::Phone
+        }
+        "SEND_SMS" | "RECEIVE_SMS" | "READ_SMS" | "RECEIVE_MMS" | "RECEIVE_WAP_PUSH" | "READ_CELL_BROADCASTS" => {
+            PermissionGroup::Sms
+        }
+        "READ_EXTERNAL_STORAGE" | "WRITE_EXTERNAL_STORAGE" | "READ_MEDIA_IMAGES" | "READ_MEDIA_VIDEO"
+        | "READ_MEDIA_AUDIO" | "READ_MEDIA_VISUAL_USER_SELECTED" => PermissionGroup::Storage,
+        "BODY_SENSORS" | "BODY_SENSORS_BACKGROUND" => PermissionGroup::BodySensors,
+        "ACTIVITY_RECOGNITION" => PermissionGroup::ActivityRecognition,
+        "BLUETOOTH_SCAN" | "BLUETOOTH_CONNECT" | "BLUETOOTH_ADVERTISE" | "NEARBY_WIFI_DEVICES" | "UWB_RANGING" => {
+            PermissionGroup::NearbyDevices
+        }
+        "POST_NOTIFICATIONS" => PermissionGroup::Notifications,
+        _ => PermissionGroup::Other,
+    }
+}
+
+/// Parse a runtime permission line from `dumpsys package`
+/// ("android.permission.CAMERA: granted=false, flags=[ USER_SET|SYSTEM_FIXED ]")
+/// into the permission name, whether it is granted and whether it is fixed
+pub fn parse_runtime_permission_line(line: &str) -> Option<(String, bool, bool)> {
+    let (name, attributes) = line.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let granted = attributes.contains("granted=true");
+    let fixed = attributes.contains("SYSTEM_FIXED") || attributes.contains("POLICY_FIXED");
+    Some((name.to_string(), granted, fixed))
+}
+
+/// Runtime permissions of a package for every user it is installed for
+pub fn runtime_permissions(package: &PackageFingerprint) -> Vec<RuntimePermission> {
+    package
+        .users
+        .iter()
+        .flat_map(|user| {
+            user.runtimePermissions.iter().filter_map(|line| {
+                let (name, granted, fixed) = parse_runtime_permission_line(line)?;
+                Some(RuntimePermission {
+                    pkg_id: package.pkg.clone(),
+                    user_id: user.userId,
+                    name,
+                    granted,
+                    fixed,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Runtime permissions of all packages for one user, optionally limited to a group
+pub fn collect_runtime_permissions(
+    packages: &[PackageFingerprint],
+    user_id: i32,
+    group: Option<PermissionGroup>,
+) -> Vec<RuntimePermission> {
+    packages
+        .iter()
+        .flat_map(runtime_permissions)
+        .filter(|p| p.user_id == user_id)
+        .filter(|p| group.map_or(true, |g| permission_group(&p.name) == g))
+        .collect()
+}
+
+/// Users that appear in the package dump, ascending
+pub fn user_ids(packages: &[PackageFingerprint]) -> Vec<i32> {
+    packages
+        .iter()
+        .flat_map(|p| p.users.iter().map(|u| u.userId))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Update the granted state of a runtime permission in the parsed package list.
+/// Returns the previous state, or `None` when the package does not request the permission for that user.
+pub fn set_permission_state(
+    packages: &mut [PackageFingerprint],
+    pkg_id: &str,
+    user_id: i32,
+    permission: &str,
+    granted: bool,
+) -> Option<bool> {
+    let user = packages
+        .iter_mut()
+        .find(|p| p.pkg == pkg_id)?
+        .users
+        .iter_mut()
+        .find(|u| u.userId == user_id)?;
+    let line = user
+        .runtimePermissions
+        .iter_mut()
+        .find(|line| line.split_once(':').map(|(name, _)| name.trim()) == Some(permission))?;
+    let previous = line.contains("granted=true");
+    let (from, to) = if granted {
+        ("granted=false", "granted=true")
+    } else {
+        ("granted=true", "granted=false")
+    };
+    *line = line.replacen(from, to, 1);
+    Some(previous)
+}
+
+fn run_pm(device: &str, pkg_id: &str, user_id: i32, permission: &str, grant: bool) -> std::io::Result<String> {
+    if grant {
+        crate::adb::grant_runtime_permission(device, pkg_id, permission, user_id)
+    } else {
+        crate::adb::revoke_runtime_permission(device, pkg_id, permission, user_id)
+    }
+}
+
+/// Grant or revoke a runtime permission, journal the change and update the shared package list
+pub fn apply_permission_change(device: &str, change: &PermissionChange) -> std::io::Result<String> {
+    let output = run_pm(device, &change.pkg_id, change.user_id, &change.permission, change.grant)?;
+
+    let previous = get_shared_store()
+        .update_installed_packages(|packages| {
+            set_permission_state(packages, &change.pkg_id, change.user_id, &change.permission, change.grant)
+        })
+        .flatten()
+        .unwrap_or(!change.grant);
+
+    if let Err(e) = crate::db_permission_journal::record_change(
+        device,
+        change.user_id,
+        &change.pkg_id,
+        &change.permission,
+        change.grant,
+        previous,
+    ) {
+        log::error!("Failed to journal permission change for {}: {}", change.pkg_id, e);
+    }
+    Ok(output)
+}
+
+/// Restore the state a journaled change replaced and mark the change as reverted
+pub fn revert_change(entry: &PermissionJournalEntry) -> std::io::Result<String> {
+    let output = run_pm(
+        &entry.device_serial,
+        &entry.pkg_id,
+        entry.user_id,
+        &entry.permission,
+        entry.previous_granted,
+    )?;
+
+    get_shared_store().update_installed_packages(|packages| {
+        set_permission_state(packages, &entry.pkg_id, entry.user_id, &entry.permission, entry.previous_granted)
+    });
+
+    if let Err(e) = crate::db_permission_journal::mark_reverted(entry.id) {
+        log::error!("Failed to mark permission change {} as reverted: {}", entry.id, e);
+    }
+    Ok(output)
+}
+
+/// Revert a journaled change in a background thread; the result holds the error text on failure
+pub fn run_revert_change_async(entry: PermissionJournalEntry, result: Arc<Mutex<Option<Result<(), String>>>>) {
+    std::thread::spawn(move || {
+        let outcome = match revert_change(&entry) {
+            Ok(_) => {
+                log::info!("Reverted permission change {} ({} {})", entry.id, entry.pkg_id, entry.permission);
+                Ok(())
+            }
+            Err(e) => {
+                log::error!("Failed to revert permission change {}: {}", entry.id, e);
+                Err(e.to_string())
+            }
+        };
+        if let Ok(mut r) = result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+/// Apply permission changes in a background thread
+pub fn run_permission_changes_async(
+    device: String,
+    changes: Vec<PermissionChange>,
+    result: Arc<Mutex<Option<PermissionBatchOutcome>>>,
+) {
+    std::thread::spawn(move || {
+        let mut outcome = PermissionBatchOutcome::default();
+        for change in &changes {
+            match apply_permission_change(&device, change) {
+                Ok(_) => outcome.succeeded += 1,
+                Err(e) => {
+                    log::error!(
+                        "Failed to {} {} for {}: {}",
+                        if change.grant { "grant" } else { "revoke" },
+                        change.permission,
+                        change.pkg_id,
+                        e
+                    );
+                    outcome.failures.push(format!("{} {}: {}", change.pkg_id, change.permission, e.to_string().trim()));
+                }
+            }
+        }
+        log::info!(
+            "Permission changes on {}: {} succeeded, {} failed",
+            device,
+            outcome.succeeded,
+            outcome.failures.len()
+        );
+        if let Ok(mut r) = result.lock() {
+            *r = Some(outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::AdbPackageInfoUser;
+
+    fn package(pkg: &str, users: Vec<AdbPackageInfoUser>) -> PackageFingerprint {
+        PackageFingerprint {
+            users,
+            ..PackageFingerprint::fixture(pkg, "", "")
+        }
+    }
+
+    #[test]
+    fn test_parse_runtime_permission_line() {
+        assert_eq!(
+            parse_runtime_permission_line("android.permission.CAMERA: granted=true, flags=[ USER_SET ]"),
+            Some(("android.permission.CAMERA".to_string(), true, false))
+        );
+        assert_eq!(
+            parse_runtime_permission_line(
+                "android.permission.ACCESS_FINE_LOCATION: granted=false, flags=[ SYSTEM_FIXED|GRANTED_BY_DEFAULT ]"
+            ),
+            Some(("android.permission.ACCESS_FINE_LOCATION".to_string(), false, true))
+        );
+        assert_eq!(parse_runtime_permission_line("no permission here"), None);
+    }
+
+    #[test]
+    fn test_collect_runtime_permissions() {
+        let packages = vec![
+            package(
+                "com.maps",
+                vec![
+                    AdbPackageInfoUser::fixture(0, &["android.permission.ACCESS_FINE_LOCATION: granted=true", "android.permission.CAMERA: granted=false"]),
+                    AdbPackageInfoUser::fixture(10, &["android.permission.ACCESS_FINE_LOCATION: granted=false"]),
+                ],
+            ),
+            package("com.notes", vec![AdbPackageInfoUser::fixture(0, &["android.permission.POST_NOTIFICATIONS: granted=true"])]),
+        ];
+
+        let location = collect_runtime_permissions(&packages, 0, Some(PermissionGroup::Location));
+        assert_eq!(location.len(), 1);
+        assert_eq!(location[0].pkg_id, "com.maps");
+        assert!(location[0].granted);
+
+        assert_eq!(collect_runtime_permissions(&packages, 0, None).len(), 3);
+        assert_eq!(collect_runtime_permissions(&packages, 10, None).len(), 1);
+        assert_eq!(user_ids(&packages), vec![0, 10]);
+    }
+
+    #[test]
+    fn test_set_permission_state() {
+        let mut packages = vec![package(
+            "com.maps",
+            vec![AdbPackageInfoUser::fixture(0, &["android.permission.ACCESS_FINE_LOCATION: granted=true, flags=[ USER_SET ]"])],
+        )];
+
+        assert_eq!(
+            set_permission_state(&mut packages, "com.maps", 0, "android.permission.ACCESS_FINE_LOCATION", false),
+            Some(true)
+        );
+        assert_eq!(
+            packages[0].users[0].runtimePermissions[0],
+            "android.permission.ACCESS_FINE_LOCATION: granted=false, flags=[ USER_SET ]"
+        );
+        assert_eq!(
+            set_permission_state(&mut packages, "com.maps", 10, "android.permission.ACCESS_FINE_LOCATION", true),
+            None
+        );
+    }
+}
@@ -0,0 +1,66 @@
+/// Platform permission group a runtime permission belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PermissionGroup {
+    Location,
+    Camera,
+    Microphone,
+    Contacts,
+    Calendar,
+    Phone,
+    Sms,
+    Storage,
+    BodySensors,
+    ActivityRecognition,
+    NearbyDevices,
+    Notifications,
+    /// Runtime permissions outside the platform groups (custom or newer permissions)
+    Other,
+}
+
+impl PermissionGroup {
+    /// All groups in the order they are offered as filters
+    pub const ALL: [PermissionGroup; 13] = [
+        PermissionGroup::Location,
+        PermissionGroup::Camera,
+        PermissionGroup::Microphone,
+        PermissionGroup::Contacts,
+        PermissionGroup::Calendar,
+        PermissionGroup::Phone,
+        PermissionGroup::Sms,
+        PermissionGroup::Storage,
+        PermissionGroup::BodySensors,
+        PermissionGroup::ActivityRecognition,
+        PermissionGroup::NearbyDevices,
+        PermissionGroup::Notifications,
+        PermissionGroup::Other,
+    ];
+}
+
+/// Runtime permission requested by a package, as reported for one user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimePermission {
+    pub pkg_id: String,
+    pub user_id: i32,
+    /// Full permission name (android.permission.CAMERA)
+    pub name: String,
+    pub granted: bool,
+    /// Set by the system or a device policy (SYSTEM_FIXED / POLICY_FIXED); `pm` cannot change it
+    pub fixed: bool,
+}
+
+/// Grant or revoke of one runtime permission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionChange {
+    pub pkg_id: String,
+    pub user_id: i32,
+    pub permission: String,
+    pub grant: bool,
+}
+
+/// Outcome of a batch of permission changes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionBatchOutcome {
+    pub succeeded: usize,
+    /// One line per failed change
+    pub failures: Vec<String>,
+}
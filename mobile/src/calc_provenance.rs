@@ -0,0 +1,307 @@
+pub use crate::calc_provenance_stt::*;
+use std::collections::HashMap;
+
+const PLAY_STORE: &[&str] = &["com.android.vending", "com.google.android.feedback"];
+
+const FDROID: &[&str] = &[
+    "org.fdroid.fdroid",
+    "org.fdroid.basic",
+    "org.fdroid.fdroid.privileged",
+    "com.looker.droidify",
+    "com.machiav3lli.fdroid",
+];
+
+const AURORA: &[&str] = &["com.aurora.store", "com.aurora.services"];
+
+const OTHER_STORES: &[&str] = &[
+    "com.sec.android.app.samsungapps",
+    "com.amazon.venezia",
+    "com.huawei.appmarket",
+    "com.xiaomi.market",
+    "com.xiaomi.mipicks",
+    "com.heytap.market",
+    "com.oppo.market",
+    "com.bbk.appstore",
+    "dev.imranr.obtainium",
+    "com.apkpure.aegon",
+    "com.uptodown",
+];
+
+const SHELL: &[&str] = &["com.android.shell", "adb"];
+
+/// Browsers and the downloads app, which open APKs fetched from the web
+const BROWSERS: &[&str] = &[
+    "com.android.chrome",
+    "com.chrome.beta",
+    "org.mozilla.firefox",
+    "org.mozilla.firefox_beta",
+    "org.mozilla.focus",
+    "com.opera.browser",
+    "com.opera.mini.native",
+    "com.brave.browser",
+    "com.microsoft.emmx",
+    "com.sec.android.app.sbrowser",
+    "com.UCMobile.intl",
+    "com.duckduckgo.mobile.android",
+    "com.vivaldi.browser",
+    "com.kiwibrowser.browser",
+    "com.mi.globalbrowser",
+    "com.android.browser",
+    "com.android.providers.downloads",
+    "com.android.providers.downloads.ui",
+];
+
+const MESSAGING: &[&str] = &[
+    "org.telegram.messenger",
+    "org.telegram.messenger.web",
+    "org.thoughtcrime.securesms",
+    "com.whatsapp",
+    "com.whatsapp.w4b",
+    "com.facebook.orca",
+    "com.discord",
+    "com.viber.voip",
+    "jp.naver.line.android",
+    "com.kakao.talk",
+    "com.tencent.mm",
+    "com.google.android.gm",
+    "com.microsoft.office.outlook",
+    "com.microsoft.teams",
+    "com.skype.raider",
+];
+
+/// `PackageInstaller.PACKAGE_SOURCE_DOWNLOADED_FILE`
+const PACKAGE_SOURCE_DOWNLOADED_FILE: i32 = 4;
+
+/// Value of a `key=value` line in a `dumpsys package` block; `null` and empty are `None`.
+/// Shared by every reader of install fields, e.g. `installerPackageName`.
+pub fn dump_value(dump_text: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}=", key);
+    dump_text
+        .lines()
+        .find_map(|l| l.trim().strip_prefix(prefix.as_str()).map(|v| v.trim().to_string()))
+        .filter(|v| !v.is_empty() && v != "null")
+}
+
+/// Classify where a package came from. The store that owns the install wins;
+/// otherwise the app that handed over the APK decides.
+pub fn classify_install_source(
+    is_system: bool,
+    installer: Option<&str>,
+    initiating: Option<&str>,
+    originating: Option<&str>,
+    package_source: Option<i32>,
+) -> InstallSource {
+    if let Some(installer) = installer {
+        if PLAY_STORE.contains(&installer) {
+            return InstallSource::PlayStore;
+        }
+        if FDROID.contains(&installer) {
+            return InstallSource::FDroid;
+        }
+        if AURORA.contains(&installer) {
+            return InstallSource::Aurora;
+        }
+        if OTHER_STORES.contains(&installer) {
+            return InstallSource::OtherStore;
+        }
+    }
+
+    let chain = [originating, initiating, installer];
+    let any_in = |list: &[&str]| chain.iter().flatten().any(|pkg| list.contains(pkg));
+    if any_in(MESSAGING) {
+        return InstallSource::Messaging;
+    }
+    if any_in(BROWSERS) {
+        return InstallSource::Browser;
+    }
+    if any_in(SHELL) {
+        return InstallSource::Adb;
+    }
+    if package_source == Some(PACKAGE_SOURCE_DOWNLOADED_FILE) {
+        return InstallSource::Browser;
+    }
+    if is_system && installer.is_none() {
+        return InstallSource::Preinstalled;
+    }
+    InstallSource::Unknown
+}
+
+/// Read install provenance fields from a `dumpsys package` block
+pub fn parse_provenance(package: &crate::adb::PackageFingerprint, label: Option<&String>) -> PackageProvenance {
+    let is_system = package.flags.contains("SYSTEM");
+    let installer = dump_value(&package.dumpText, "installerPackageName");
+    let initiating = dump_value(&package.dumpText, "initiatingPackageName");
+    let originating = dump_value(&package.dumpText, "originatingPackageName");
+    let package_source = dump_value(&package.dumpText, "packageSource").and_then(|v| v.parse().ok());
+    let source = classify_install_source(
+        is_system,
+        installer.as_deref(),
+        initiating.as_deref(),
+        originating.as_deref(),
+        package_source,
+    );
+    PackageProvenance {
+        pkg_id: package.pkg.clone(),
+        label: label.cloned(),
+        is_system,
+        installer,
+        initiating,
+        originating,
+        package_source,
+        source,
+    }
+}
+
+/// Provenance of every installed package, packages needing review first
+pub fn build_provenance(
+    packages: &[crate::adb::PackageFingerprint],
+    labels: &HashMap<String, String>,
+) -> Vec<PackageProvenance> {
+    let mut rows: Vec<PackageProvenance> = packages
+        .iter()
+        .map(|p| parse_provenance(p, labels.get(&p.pkg)))
+        .collect();
+    rows.sort_by(|a, b| {
+        b.source
+            .needs_review()
+            .cmp(&a.source.needs_review())
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.pkg_id.cmp(&b.pkg_id))
+    });
+    rows
+}
+
+/// Number of packages per source
+pub fn count_by_source(rows: &[PackageProvenance]) -> HashMap<InstallSource, usize> {
+    let mut counts = HashMap::new();
+    for row in rows {
+        *counts.entry(row.source).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adb::PackageFingerprint;
+
+    #[test]
+    fn test_dump_value() {
+        let dump = "  Package [com.example] (abc):\n    installerPackageName=org.fdroid.fdroid\n";
+        assert_eq!(dump_value(dump, "installerPackageName"), Some("org.fdroid.fdroid".to_string()));
+        assert_eq!(dump_value("    installerPackageName=null", "installerPackageName"), None);
+        assert_eq!(dump_value("", "installerPackageName"), None);
+    }
+
+    #[test]
+    fn test_classify_install_source() {
+        assert_eq!(
+            classify_install_source(false, Some("com.android.vending"), Some("com.android.vending"), None, Some(2)),
+            InstallSource::PlayStore
+        );
+        assert_eq!(
+            classify_install_source(false, Some("org.fdroid.fdroid"), None, None, None),
+            InstallSource::FDroid
+        );
+        assert_eq!(
+            classify_install_source(
+                false,
+                Some("com.google.android.packageinstaller"),
+                Some("com.google.android.packageinstaller"),
+                Some("org.telegram.messenger"),
+                Some(3)
+            ),
+            InstallSource::Messaging
+        );
+        assert_eq!(
+            classify_install_source(false, Some("com.android.chrome"), None, None, Some(4)),
+            InstallSource::Browser
+        );
+        assert_eq!(
+            classify_install_source(false, None, Some("com.android.shell"), None, None),
+            InstallSource::Adb
+        );
+        assert_eq!(
+            classify_install_source(false, Some("com.google.android.packageinstaller"), None, None, Some(4)),
+            InstallSource::Browser
+        );
+        assert_eq!(classify_install_source(true, None, None, None, None), InstallSource::Preinstalled);
+        assert_eq!(classify_install_source(false, None, None, None, None), InstallSource::Unknown);
+    }
+
+    #[test]
+    fn test_build_provenance() {
+        let packages = vec![
+            PackageFingerprint::fixture(
+                "com.example.store",
+                "[ HAS_CODE ]",
+                "    installerPackageName=com.android.vending\n    installerPackageUid=10100\n    initiatingPackageName=com.android.vending\n    originatingPackageName=null\n    packageSource=2\n",
+            ),
+            PackageFingerprint::fixture(
+                "com.example.apk",
+                "[ HAS_CODE ]",
+                "    installerPackageName=com.google.android.packageinstaller\n    initiatingPackageName=com.google.android.packageinstaller\n    originatingPackageName=com.android.chrome\n    packageSource=4\n",
+            ),
+            PackageFingerprint::fixture("com.android.settings", "[ SYSTEM HAS_CODE ]", "    installerPackageName=null\n"),
+        ];
+        let labels = HashMap::from([("com.example.apk".to_string(), "Example".to_string())]);
+        let rows = build_provenance(&packages, &labels);
+
+        assert_eq!(rows[0].pkg_id, "com.example.apk");
+        assert_eq!(rows[0].source, InstallSource::Browser);
+        assert_eq!(rows[0].originating.as_deref(), Some("com.android.chrome"));
+        assert_eq!(rows[0].package_source, Some(4));
+        assert_eq!(rows[0].label.as_deref(), Some("Example"));
+        assert_eq!(rows[1].source, InstallSource::PlayStore);
+        assert_eq!(rows[1].installer.as_deref(), Some("com.android.vending"));
+        assert_eq!(rows[2].source, InstallSource::Preinstalled);
+
+        let counts = count_by_source(&rows);
+        assert_eq!(counts.get(&InstallSource::Browser), Some(&1));
+        assert_eq!(counts.get(&InstallSource::Unknown), None);
+    }
+
+    #[test]
+    fn test_provenance_from_raw_dumpsys() {
+        // Raw `dumpsys package packages` output, filtered and parsed like the device path does
+        let raw = "Packages:
+  Package [com.example.apk] (8d3c2a1):
+    userId=10234
+    codePath=/data/app/~~Xw==/com.example.apk-Yw==
+    primaryCpuAbi=arm64-v8a
+    versionCode=42 minSdk=24 targetSdk=33
+    versionName=1.2.3
+    flags=[ HAS_CODE ALLOW_CLEAR_USER_DATA ]
+    timeStamp=2024-03-01 10:00:00
+    lastUpdateTime=2024-03-01 10:00:01
+    installerPackageName=com.google.android.packageinstaller
+    installerPackageUid=10050
+    initiatingPackageName=com.google.android.packageinstaller
+    originatingPackageName=com.android.chrome
+    packageSource=4
+    User 0: ceDataInode=1234 installed=true hidden=false suspended=false stopped=false enabled=0
+  Package [com.example.store] (5f4e3d2):
+    userId=10235
+    codePath=/data/app/~~Zw==/com.example.store-Vw==
+    versionCode=7 minSdk=24 targetSdk=34
+    versionName=7.0
+    flags=[ HAS_CODE ALLOW_CLEAR_USER_DATA ]
+    lastUpdateTime=2024-03-02 09:00:00
+    installerPackageName=com.android.vending
+    initiatingPackageName=com.android.vending
+    originatingPackageName=null
+    packageSource=2
+    User 0: ceDataInode=5678 installed=true hidden=false suspended=false stopped=false enabled=0
+";
+        let packages = crate::adb::parse_package_fingerprints(crate::adb::filter_fingerprint_lines(raw));
+        assert_eq!(packages.len(), 2);
+        let rows = build_provenance(&packages, &HashMap::new());
+
+        assert_eq!(rows[0].pkg_id, "com.example.apk");
+        assert_eq!(rows[0].source, InstallSource::Browser);
+        assert_eq!(rows[0].originating.as_deref(), Some("com.android.chrome"));
+        assert_eq!(rows[0].package_source, Some(4));
+        assert_eq!(rows[1].pkg_id, "com.example.store");
+        assert_eq!(rows[1].source, InstallSource::PlayStore);
+    }
+}
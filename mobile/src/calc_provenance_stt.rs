@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Where an installed package came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InstallSource {
+    PlayStore,
+    FDroid,
+    Aurora,
+    /// Vendor or third-party store (Galaxy Store, Amazon, Obtainium, ...)
+    OtherStore,
+    /// `adb install` / `pm install` from the shell
+    Adb,
+    /// APK downloaded and opened from a browser or the downloads app
+    Browser,
+    /// APK received as an attachment in a messaging or mail app
+    Messaging,
+    /// Preinstalled system package without an installer
+    Preinstalled,
+    Unknown,
+}
+
+impl InstallSource {
+    /// All sources in the order they are listed in the filters
+    pub const ALL: [InstallSource; 9] = [
+        InstallSource::PlayStore,
+        InstallSource::FDroid,
+        InstallSource::Aurora,
+        InstallSource::OtherStore,
+        InstallSource::Adb,
+        InstallSource::Browser,
+        InstallSource::Messaging,
+        InstallSource::Preinstalled,
+        InstallSource::Unknown,
+    ];
+
+    /// Sideloaded from a browser or messaging app; worth a manual review
+    pub fn needs_review(&self) -> bool {
+        matches!(self, InstallSource::Browser | InstallSource::Messaging)
+    }
+}
+
+/// Install provenance of one package, from its `dumpsys package` block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageProvenance {
+    pub pkg_id: String,
+    pub label: Option<String>,
+    pub is_system: bool,
+    /// Package that owns the install (`installerPackageName`)
+    pub installer: Option<String>,
+    /// Package that started the install session (`initiatingPackageName`)
+    pub initiating: Option<String>,
+    /// Package the APK was downloaded or opened from (`originatingPackageName`)
+    pub originating: Option<String>,
+    /// `PackageInstaller.PACKAGE_SOURCE_*` value (Android 13+)
+    pub package_source: Option<i32>,
+    pub source: InstallSource,
+}
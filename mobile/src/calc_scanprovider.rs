@@ -0,0 +1,1110 @@
+pub use crate::calc_scanprovider_stt::*;
+use crate::adb::{self, PackageFingerprint};
+use crate::models::PackageInfoCache;
+use egui_i18n::tr;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+impl RateLimiter {
+    pub fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            request_times: Vec::new(),
+            last_request: None,
+            rate_limit_until: None,
+            submit_rate_limit_until: None,
+            quota: None,
+        }
+    }
+
+    /// Count requests against a daily/monthly budget, starting from the usage stored for `provider`
+    pub fn with_quota(mut self, provider: &str, budget: QuotaBudget) -> Self {
+        self.quota = Some(QuotaUsage::load(provider, budget));
+        self
+    }
+
+    /// Requests left today within both the daily and monthly budget; None when unlimited
+    pub fn remaining_quota(&mut self) -> Option<u32> {
+        let quota = self.quota.as_mut()?;
+        quota.roll_over(&utc_day());
+        quota.remaining()
+    }
+
+    pub fn shared(policy: RateLimitPolicy) -> SharedRateLimiter {
+        Arc::new(Mutex::new(Self::new(policy)))
+    }
+
+    /// Check if we need to wait and return the duration, or None if a request may be made now
+    pub fn check_wait_needed(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+
+        // First check if we're in a global rate limit period (from 429 error)
+        if let Some(until) = self.rate_limit_until {
+            if now < until {
+                return Some(until.duration_since(now));
+            }
+            self.rate_limit_until = None;
+        }
+
+        let longest_window = self
+            .policy
+            .windows
+            .iter()
+            .map(|(_, window)| *window)
+            .max()
+            .unwrap_or(Duration::ZERO);
+        self.request_times
+            .retain(|&t| now.duration_since(t) < longest_window);
+
+        let mut wait = Duration::ZERO;
+        for &(max_requests, window) in &self.policy.windows {
+            let in_window: Vec<Instant> = self
+                .request_times
+                .iter()
+                .copied()
+                .filter(|&t| now.duration_since(t) < window)
+                .collect();
+            if max_requests > 0 && in_window.len() >= max_requests {
+                // The request that has to expire before a slot frees up
+                let blocking = in_window[in_window.len() - max_requests];
+                wait = wait.max(window.saturating_sub(now.duration_since(blocking)));
+            }
+        }
+
+        // Check minimum interval from last request
+        if let Some(last) = self.last_request {
+            wait = wait.max(self.policy.min_interval.saturating_sub(now.duration_since(last)));
+        }
+
+        if wait > Duration::ZERO {
+            Some(wait)
+        } else {
+            None
+        }
+    }
+
+    /// Same as `check_wait_needed`, also honouring a submission back-off
+    pub fn check_submit_wait_needed(&mut self) -> Option<Duration> {
+        if let Some(until) = self.submit_rate_limit_until {
+            let now = Instant::now();
+            if now < until {
+                return Some(until.duration_since(now));
+            }
+            self.submit_rate_limit_until = None;
+        }
+        self.check_wait_needed()
+    }
+
+    /// Record that a request was made
+    pub fn record_request(&mut self) {
+        let now = Instant::now();
+        self.last_request = Some(now);
+        self.request_times.push(now);
+
+        if let Some(quota) = self.quota.as_mut() {
+            quota.roll_over(&utc_day());
+            quota.record();
+            if let Err(e) = crate::db_api_quota::record_request(&quota.provider, &quota.day) {
+                log::error!("Failed to persist {} quota usage: {}", quota.provider, e);
+            }
+        }
+    }
+
+    /// Set global rate limit (for 429 errors) - all threads will wait until this time
+    pub fn set_rate_limit(&mut self, duration: Duration) {
+        let until = Instant::now() + duration;
+
+        // Only update if this extends the existing rate limit
+        if self.rate_limit_until.map_or(true, |current| current < until) {
+            log::warn!("Setting global rate limit for {:.2}s", duration.as_secs_f64());
+            self.rate_limit_until = Some(until);
+        }
+    }
+
+    /// Apply a 429 received on submission. Returns the fixed back-off if the policy has one,
+    /// otherwise the server's retry-after is applied to all requests.
+    pub fn set_submit_rate_limit(&mut self, retry_after: Duration) -> Option<Duration> {
+        let Some(backoff) = self.policy.submit_backoff else {
+            self.set_rate_limit(retry_after);
+            return None;
+        };
+        let until = Instant::now() + backoff;
+        if self.submit_rate_limit_until.map_or(true, |current| current < until) {
+            log::warn!("Setting submission rate limit for {}s", backoff.as_secs());
+            self.submit_rate_limit_until = Some(until);
+        }
+        Some(backoff)
+    }
+}
+
+impl QuotaUsage {
+    /// Usage stored for `provider` on the current UTC day and month
+    pub fn load(provider: &str, budget: QuotaBudget) -> Self {
+        let day = utc_day();
+        let (used_today, used_month) = crate::db_api_quota::get_usage(provider, &day);
+        Self {
+            provider: provider.to_string(),
+            budget,
+            day,
+            used_today,
+            used_month,
+        }
+    }
+
+    /// Reset the counters when the UTC day or month has changed
+    pub fn roll_over(&mut self, today: &str) {
+        if self.day == today {
+            return;
+        }
+        if self.day.get(..7) != today.get(..7) {
+            self.used_month = 0;
+        }
+        self.used_today = 0;
+        self.day = today.to_string();
+    }
+
+    pub fn record(&mut self) {
+        self.used_today += 1;
+        self.used_month += 1;
+    }
+
+    /// Requests left today within both budgets; None when both are unlimited
+    pub fn remaining(&self) -> Option<u32> {
+        let daily = (self.budget.per_day > 0)
+            .then(|| self.budget.per_day.saturating_sub(self.used_today));
+        let monthly = (self.budget.per_month > 0)
+            .then(|| self.budget.per_month.saturating_sub(self.used_month));
+        match (daily, monthly) {
+            (Some(d), Some(m)) => Some(d.min(m)),
+            (d, m) => d.or(m),
+        }
+    }
+}
+
+/// Current UTC day as "YYYY-MM-DD"; API quotas reset at 00:00 UTC
+pub fn utc_day() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+impl RequestGate<'_> {
+    fn wait(&self, submit: bool) -> Result<(), ProviderError> {
+        loop {
+            let wait = {
+                let mut limiter = self.limiter.lock().unwrap();
+                if limiter.remaining_quota() == Some(0) {
+                    return Err(ProviderError::QuotaExhausted);
+                }
+                let wait = if submit {
+                    limiter.check_submit_wait_needed()
+                } else {
+                    limiter.check_wait_needed()
+                };
+                // Reserve the slot while still holding the lock
+                if wait.is_none() {
+                    limiter.record_request();
+                }
+                wait
+            };
+
+            let Some(wait) = wait else {
+                return Ok(());
+            };
+            if let Some(deadline) = self.deadline {
+                if Instant::now() + wait > deadline {
+                    return Err(ProviderError::Timeout(format!(
+                        "Waited too long for rate limit: {:?}",
+                        wait
+                    )));
+                }
+            }
+
+            // Sleep in one second steps so the UI can show a countdown
+            (self.on_wait)(wait);
+            thread::sleep(wait.min(Duration::from_secs(1)));
+        }
+    }
+
+    /// Wait for the rate limit, then reserve a request slot
+    pub fn acquire(&self) -> Result<(), ProviderError> {
+        self.wait(false)
+    }
+
+    /// Wait for the rate limit and any submission back-off, then reserve a request slot
+    pub fn acquire_submit(&self) -> Result<(), ProviderError> {
+        self.wait(true)
+    }
+}
+
+/// Cached results fetched more recently than this are never re-queried,
+/// so a scan that is restarted does not repeat the same lookups
+pub const MIN_REQUERY_INTERVAL_SECS: i64 = 3600;
+
+impl FreshnessPolicy {
+    /// Whether a cached result should be looked up again.
+    /// `analyzed_at` is when the provider analyzed the file, `fetched_at` when we last asked.
+    pub fn is_stale(&self, analyzed_at: i64, fetched_at: i64, flagged: bool, now: i64) -> bool {
+        if now - fetched_at < MIN_REQUERY_INTERVAL_SECS {
+            return false;
+        }
+        if flagged && self.rescan_flagged {
+            return true;
+        }
+        self.max_age_days > 0 && now - analyzed_at > i64::from(self.max_age_days) * 86400
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Scan one file: local cache, hash lookup, then (if allowed) pull, submit and poll
+pub fn scan_file<P: ScanProvider>(
+    provider: &P,
+    target: &ScanTarget,
+    limiter: &SharedRateLimiter,
+    allow_submit: bool,
+    fetch: &dyn Fn(&ScanTarget) -> Result<PathBuf, String>,
+    on_status: &dyn Fn(String),
+) -> FileOutcome<P::Report> {
+    match provider.cached_report(target) {
+        Some(CachedReport::Report(report)) => {
+            log::debug!("Found cached {} result for {}", provider.name(), target.sha256);
+            return FileOutcome::Report(report);
+        }
+        Some(CachedReport::NotFound) => {
+            log::debug!("Found cached {} 404 for {}", provider.name(), target.sha256);
+            return FileOutcome::NotFound;
+        }
+        Some(CachedReport::Trusted) => {
+            log::debug!("Skipping {} lookup for allowlisted {}", provider.name(), target.sha256);
+            return FileOutcome::Trusted;
+        }
+        None => {}
+    }
+
+    let poll_policy = provider.poll_policy();
+    let deadline = Instant::now() + poll_policy.file_timeout;
+    let on_wait = |remaining: Duration| on_status(tr!("status-rate-limit", { secs: remaining.as_secs() }));
+    let gate = RequestGate {
+        limiter,
+        deadline: provider
+            .rate_limit_policy()
+            .wait_counts_against_timeout
+            .then_some(deadline),
+        on_wait: &on_wait,
+    };
+
+    on_status(tr!("status-checking-api"));
+    log::info!("Querying {} for SHA256: {}", provider.name(), target.sha256);
+    let lookup = loop {
+        match provider.lookup_hash(target, &gate) {
+            Err(ProviderError::RateLimit { retry_after }) => {
+                limiter
+                    .lock()
+                    .unwrap()
+                    .set_rate_limit(Duration::from_secs(retry_after));
+                if Instant::now() >= deadline {
+                    break Err(ProviderError::Timeout(tr!("error-timeout-rate-limit")));
+                }
+                // Retry once the rate limit has passed
+            }
+            other => break other,
+        }
+    };
+
+    match lookup {
+        Ok(report) => FileOutcome::Report(report),
+        Err(ProviderError::NotFound) if allow_submit => {
+            log::info!(
+                "File {} sha256 {} not found in {}, uploading",
+                target.file_path,
+                target.sha256,
+                provider.name()
+            );
+            submit_and_poll(provider, target, &gate, &poll_policy, deadline, fetch, on_status)
+        }
+        Err(ProviderError::NotFound) => {
+            log::info!(
+                "Sha256 {} for file {} not found in {} and upload is disabled",
+                target.sha256,
+                target.file_path,
+                provider.name()
+            );
+            provider.record_not_found(target);
+            FileOutcome::NotFound
+        }
+        Err(e) => {
+            log::error!(
+                "Error looking up {} in {} (pkg: {}, file: {}): {}",
+                target.sha256,
+                provider.name(),
+                target.package_name,
+                target.file_path,
+                e
+            );
+            FileOutcome::Error(e.to_string())
+        }
+    }
+}
+
+fn submit_and_poll<P: ScanProvider>(
+    provider: &P,
+    target: &ScanTarget,
+    gate: &RequestGate,
+    poll_policy: &PollPolicy,
+    deadline: Instant,
+    fetch: &dyn Fn(&ScanTarget) -> Result<PathBuf, String>,
+    on_status: &dyn Fn(String),
+) -> FileOutcome<P::Report> {
+    let file_path = &target.file_path;
+
+    // Skip if the path appears to be a directory (doesn't have a file extension or ends with /)
+    if file_path.ends_with('/') || !file_path.contains('.') {
+        log::warn!("Skipping directory or invalid path: {}", file_path);
+        return FileOutcome::Error(format!("Path is a directory, not a file: {}", file_path));
+    }
+
+    // Only upload APK and SO files (skip .prof, .dm, .art, etc.)
+    if !file_path.ends_with(".apk") && !file_path.ends_with(".so") {
+        log::info!(
+            "Skipping file for upload: {} (only .apk and .so files are uploaded)",
+            file_path
+        );
+        return FileOutcome::Skipped;
+    }
+
+    if Instant::now() > deadline {
+        return FileOutcome::Error("Timeout before pulling file".to_string());
+    }
+
+    on_status(tr!("status-pulling-file"));
+    let local_path = match fetch(target) {
+        Ok(path) => path,
+        Err(e) => {
+            log::error!("Failed to pull file {}: {}", file_path, e);
+            return FileOutcome::Error(format!("Failed to pull file: {}", e));
+        }
+    };
+
+    on_status(tr!("status-uploading"));
+    log::info!("Uploading file to {}: {}", provider.name(), local_path.display());
+    let submitted = provider.submit_file(target, &local_path, gate);
+    let _ = std::fs::remove_file(&local_path);
+
+    let submission_id = match submitted {
+        Ok(id) => id,
+        Err(ProviderError::RateLimit { retry_after }) => {
+            let backoff = gate
+                .limiter
+                .lock()
+                .unwrap()
+                .set_submit_rate_limit(Duration::from_secs(retry_after));
+            log::error!("{} upload rate limited (429)", provider.name());
+            return FileOutcome::SubmitRateLimited {
+                wait_until: backoff.map(|b| unix_now() + b.as_secs()),
+            };
+        }
+        Err(ProviderError::Timeout(e)) => return FileOutcome::Error(e),
+        Err(ProviderError::QuotaExhausted) => return FileOutcome::Error(ProviderError::QuotaExhausted.to_string()),
+        Err(e) => {
+            let message = format!("Failed to upload file: {}", e);
+            log::error!("{}", message);
+            provider.record_failure(target, FailureStage::Submit, &message);
+            return FileOutcome::Failed {
+                stage: FailureStage::Submit,
+                message,
+            };
+        }
+    };
+    log::info!(
+        "Uploaded file {} to {}, submission id: {}",
+        target.sha256,
+        provider.name(),
+        submission_id
+    );
+
+    let mut attempts = 0;
+    while Instant::now() < deadline {
+        if let Some(max_attempts) = poll_policy.max_attempts {
+            if attempts >= max_attempts {
+                break;
+            }
+        }
+        attempts += 1;
+        thread::sleep(poll_policy.interval);
+
+        match poll_submission(provider, target, &submission_id, gate) {
+            FileOutcome::Pending { .. } => {}
+            FileOutcome::Error(e) => log::warn!("Error polling {} analysis: {}", provider.name(), e),
+            outcome => return outcome,
+        }
+    }
+
+    if poll_policy.defer_unfinished {
+        log::info!(
+            "{} analysis {} is still running, will check back later",
+            provider.name(),
+            submission_id
+        );
+        FileOutcome::Pending { submission_id }
+    } else {
+        let message = format!(
+            "Timeout waiting for {} analysis to complete for package '{}' (sha256: {}, file: {})",
+            provider.name(),
+            target.package_name,
+            target.sha256,
+            file_path
+        );
+        log::error!("{}", message);
+        FileOutcome::Error(message)
+    }
+}
+
+/// Check a submission once. Rate limits are applied and reported as still pending.
+pub fn poll_submission<P: ScanProvider>(
+    provider: &P,
+    target: &ScanTarget,
+    submission_id: &str,
+    gate: &RequestGate,
+) -> FileOutcome<P::Report> {
+    match provider.poll(target, submission_id, gate) {
+        Ok(PollOutcome::Ready(report)) => FileOutcome::Report(report),
+        Ok(PollOutcome::Pending) => FileOutcome::Pending {
+            submission_id: submission_id.to_string(),
+        },
+        Ok(PollOutcome::Failed { stage, message }) => {
+            log::error!("{} analysis {} failed: {}", provider.name(), submission_id, message);
+            provider.record_failure(target, stage, &message);
+            FileOutcome::Failed { stage, message }
+        }
+        Err(ProviderError::RateLimit { retry_after }) => {
+            gate.limiter
+                .lock()
+                .unwrap()
+                .set_rate_limit(Duration::from_secs(retry_after));
+            FileOutcome::Pending {
+                submission_id: submission_id.to_string(),
+            }
+        }
+        Err(e) => FileOutcome::Error(e.to_string()),
+    }
+}
+
+/// Pull a file from the device into the temp directory for submission
+pub fn pull_for_submission(device_serial: &str, target: &ScanTarget) -> Result<PathBuf, String> {
+    let config = crate::Config::new().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&config.tmp_dir)
+        .map_err(|e| format!("Failed to create tmp directory: {}", e))?;
+    let tmp_dir = config.tmp_dir.to_str().ok_or("Invalid tmp directory path")?;
+
+    // Native library inside an APK: pull the APK and extract the library
+    if let Some((apk_path, entry)) = crate::calc_nativelibs::split_embedded_path(&target.file_path) {
+        let local_apk = adb::pull_file_to_temp(device_serial, apk_path, tmp_dir, &target.package_name)
+            .map_err(|e| e.to_string())?;
+        let extracted = crate::calc_nativelibs::extract_entry(std::path::Path::new(&local_apk), entry, &config.tmp_dir);
+        let _ = std::fs::remove_file(&local_apk);
+        return extracted;
+    }
+
+    // pull_file_to_temp handles both Android (base64 method) and non-Android (adb pull) platforms
+    let local_path = PathBuf::from(
+        adb::pull_file_to_temp(device_serial, &target.file_path, tmp_dir, &target.package_name)
+            .map_err(|e| e.to_string())?,
+    );
+    if !local_path.exists() {
+        return Err(format!(
+            "File was not pulled successfully: {} does not exist after pull",
+            local_path.display()
+        ));
+    }
+    Ok(local_path)
+}
+
+/// Initialize scanner state from cached results
+pub fn init_scanner_state<R>(
+    package_names: &[String],
+    mut load_cached: impl FnMut(&str) -> Result<Option<R>, String>,
+) -> ScannerState<R> {
+    let mut state = HashMap::new();
+
+    for pkg_name in package_names {
+        let status = match load_cached(pkg_name) {
+            Ok(Some(result)) => ScanStatus::Completed(result),
+            // No cached result, mark as pending
+            Ok(None) => ScanStatus::Pending,
+            Err(e) => {
+                log::error!("Error checking cache for {}: {}", pkg_name, e);
+                ScanStatus::Error(e)
+            }
+        };
+        state.insert(pkg_name.clone(), status);
+    }
+
+    Arc::new(Mutex::new(state))
+}
+
+/// Resolve (file path, sha256) pairs for a package. Prefers the package cache and
+/// re-hashes on the device when only a directory or invalid hashes are known.
+pub fn package_file_hashes(
+    device_serial: &str,
+    package: &PackageFingerprint,
+    cached: Option<&PackageInfoCache>,
+) -> Vec<(String, String)> {
+    let pkg_name = &package.pkg;
+    let mut paths_str = String::new();
+    let mut sha256sums_str = String::new();
+
+    if let Some(cached_pkg) = cached {
+        if let (Some(path), Some(sha256)) = (&cached_pkg.apk_path, &cached_pkg.apk_sha256sum) {
+            paths_str = path.clone();
+            sha256sums_str = sha256.clone();
+        }
+    }
+
+    if paths_str.is_empty() || sha256sums_str.is_empty() {
+        paths_str = package.codePath.clone();
+        sha256sums_str = package.pkgChecksum.clone();
+    }
+
+    if paths_str.is_empty() || sha256sums_str.is_empty() {
+        log::error!("Failed to get path and sha256 for package {}", pkg_name);
+        return Vec::new();
+    }
+
+    let needs_directory_scan = paths_str.split(' ').any(|p| !p.ends_with(".apk"));
+    let has_invalid_hashes = sha256sums_str.split(' ').any(|s| s.len() != 64);
+
+    if needs_directory_scan || has_invalid_hashes {
+        match adb::get_single_package_sha256sum(device_serial, pkg_name) {
+            Ok((new_paths, new_sha256sums)) if !new_paths.is_empty() && !new_sha256sums.is_empty() => {
+                paths_str = new_paths;
+                sha256sums_str = new_sha256sums;
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to get sha256sums for {}: {}", pkg_name, e),
+        }
+    }
+
+    let mut hashes = pair_hashes(&paths_str, &sha256sums_str);
+
+    // Native libraries inside the APKs, once their inventory is cached
+    let native_libraries = crate::calc_nativelibs::native_library_hashes(package, &hashes);
+    hashes.extend(native_libraries);
+    hashes
+}
+
+/// (path, sha256) pairs from space separated paths and sha256sums, skipping invalid hashes
+fn pair_hashes(paths: &str, sha256sums: &str) -> Vec<(String, String)> {
+    paths
+        .split(' ')
+        .zip(sha256sums.split(' '))
+        .filter(|(p, s)| !p.is_empty() && s.len() == 64)
+        .map(|(p, s)| (p.to_string(), s.to_string()))
+        .collect()
+}
+
+/// Number of files `package_file_hashes` is expected to return, without asking the device:
+/// the known APKs plus their cached native libraries, and at least one file per package
+pub fn estimated_file_count(package: &PackageFingerprint, cached: Option<&PackageInfoCache>) -> usize {
+    let apks = cached
+        .and_then(|c| Some(pair_hashes(c.apk_path.as_deref()?, c.apk_sha256sum.as_deref()?)))
+        .filter(|apks| !apks.is_empty())
+        .unwrap_or_else(|| pair_hashes(&package.codePath, &package.pkgChecksum));
+    if apks.is_empty() {
+        return 1;
+    }
+    apks.len() + crate::calc_nativelibs::native_library_hashes(package, &apks).len()
+}
+
+pub fn is_cancelled(cancelled: &Arc<Mutex<bool>>) -> bool {
+    cancelled.lock().map(|c| *c).unwrap_or(false)
+}
+
+/// Run a per-APK scan over the APKs of each package (tracker, rule and native library scans).
+/// APK paths and hashes come from the package cache, or from the device when `pull_missing`.
+/// `scan_apk(package, apk_path, sha256)` returns None when an APK has no result (not cached,
+/// or the scan failed); `on_package` only gets a package whose APKs all have one.
+/// Returns false when cancelled. Progress is left for the caller to clear.
+pub fn scan_package_apks<T>(
+    packages: &[PackageFingerprint],
+    device_serial: &str,
+    pull_missing: bool,
+    progress: &Arc<Mutex<Option<f32>>>,
+    cancelled: &Arc<Mutex<bool>>,
+    mut scan_apk: impl FnMut(&PackageFingerprint, &str, &str) -> Option<T>,
+    mut on_package: impl FnMut(&PackageFingerprint, Vec<(&str, T)>),
+) -> bool {
+    let cached_packages_map: HashMap<String, PackageInfoCache> =
+        crate::db_package_cache::get_all_cached_packages(device_serial)
+            .into_iter()
+            .map(|cp| (cp.pkg_id.clone(), cp))
+            .collect();
+
+    let total = packages.len();
+    for (i, package) in packages.iter().enumerate() {
+        if is_cancelled(cancelled) {
+            return false;
+        }
+        if let Ok(mut p) = progress.lock() {
+            *p = Some(i as f32 / total.max(1) as f32);
+        }
+
+        // APK paths and hashes: from the package cache, or from the device when pulling
+        let cached_files = cached_packages_map
+            .get(&package.pkg)
+            .and_then(|cp| Some((cp.apk_path.clone()?, cp.apk_sha256sum.clone()?)));
+        let files = match cached_files {
+            Some(files) => Some(files),
+            None if pull_missing => adb::get_single_package_sha256sum(device_serial, &package.pkg).ok(),
+            None => None,
+        };
+        let Some((paths_str, sha256sums_str)) = files else {
+            continue;
+        };
+
+        // Keep scanning after a miss so every APK gets cached
+        let mut results = Vec::new();
+        let mut complete = true;
+        for (path, sha256) in paths_str.split_whitespace().zip(sha256sums_str.split_whitespace()) {
+            if !path.ends_with(".apk") {
+                continue;
+            }
+            match scan_apk(package, path, sha256) {
+                Some(result) => results.push((path, result)),
+                None => complete = false,
+            }
+        }
+
+        if complete {
+            on_package(package, results);
+        }
+    }
+    true
+}
+
+/// Scan installed packages, `preferred` packages first and then most runtime
+/// permissions first, skipping packages that already have results.
+/// Progress is left for the caller to clear.
+pub fn scan_installed_packages(
+    provider_name: &str,
+    mut packages: Vec<PackageFingerprint>,
+    device_serial: &str,
+    package_risk_scores: &HashMap<String, i32>,
+    preferred: &HashSet<String>,
+    progress: &Arc<Mutex<Option<f32>>>,
+    cancelled: &Arc<Mutex<bool>>,
+    is_complete: impl Fn(&str) -> bool,
+    mut analyze: impl FnMut(&str, Vec<(String, String)>),
+) {
+    packages.sort_by(|a, b| {
+        let perms_a: usize = a.users.iter().map(|u| u.runtimePermissions.len()).sum();
+        let perms_b: usize = b.users.iter().map(|u| u.runtimePermissions.len()).sum();
+        preferred
+            .contains(&b.pkg)
+            .cmp(&preferred.contains(&a.pkg))
+            .then(perms_b.cmp(&perms_a))
+    });
+
+    let cached_packages_map: HashMap<String, PackageInfoCache> =
+        crate::db_package_cache::get_cached_packages_with_apk(device_serial)
+            .into_iter()
+            .map(|cp| (cp.pkg_id.clone(), cp))
+            .collect();
+
+    let total = packages.len();
+    let mut skipped_cached = 0usize;
+
+    for (i, package) in packages.iter().enumerate() {
+        if is_cancelled(cancelled) {
+            log::info!("{} scan cancelled by user", provider_name);
+            break;
+        }
+
+        if let Ok(mut p) = progress.lock() {
+            *p = Some(i as f32 / total as f32);
+        }
+
+        let pkg_name = &package.pkg;
+        if is_complete(pkg_name) {
+            skipped_cached += 1;
+            continue;
+        }
+
+        let hashes = package_file_hashes(device_serial, package, cached_packages_map.get(pkg_name));
+        if hashes.is_empty() {
+            continue;
+        }
+
+        log::info!(
+            "Analyzing package {} with {} files (Risk: {})",
+            pkg_name,
+            hashes.len(),
+            package_risk_scores.get(pkg_name).copied().unwrap_or(0)
+        );
+        analyze(pkg_name, hashes);
+    }
+
+    log::info!(
+        "{} scan complete: {} cached, {} processed",
+        provider_name,
+        skipped_cached,
+        total - skipped_cached
+    );
+}
+
+/// Look up every file of a package by hash, for providers that only answer
+/// hash queries. Files unknown to the provider are reported through `not_found`.
+pub fn lookup_package<P: ScanProvider, C>(
+    provider: &P,
+    package_name: &str,
+    hashes: Vec<(String, String)>,
+    state: &ScannerState<C>,
+    limiter: &SharedRateLimiter,
+    not_found: impl Fn(&ScanTarget) -> P::Report,
+    into_result: impl FnOnce(Vec<P::Report>) -> C,
+) {
+    // Skip package IDs with less than 2 domain levels (e.g., com.android)
+    if !crate::is_valid_package_id(package_name) {
+        log::debug!("Skipping {} lookup for invalid package ID: {}", provider.name(), package_name);
+        state.lock().unwrap().insert(
+            package_name.to_string(),
+            ScanStatus::Error("Invalid package ID (less than 2 domain levels)".to_string()),
+        );
+        return;
+    }
+
+    let total_files = hashes.len();
+    let mut reports = Vec::new();
+    let mut last_error: Option<String> = None;
+    let fetch = |_: &ScanTarget| Err(format!("{} does not accept submissions", provider.name()));
+
+    for (idx, (file_path, sha256)) in hashes.into_iter().enumerate() {
+        let target = ScanTarget {
+            package_name: package_name.to_string(),
+            file_path,
+            sha256,
+        };
+        let on_status = |operation: String| {
+            state.lock().unwrap().insert(
+                package_name.to_string(),
+                ScanStatus::Scanning {
+                    scanned: idx + 1,
+                    total: total_files,
+                    operation,
+                },
+            );
+        };
+
+        match scan_file(provider, &target, limiter, false, &fetch, &on_status) {
+            FileOutcome::Report(report) => reports.push(report),
+            FileOutcome::NotFound => reports.push(not_found(&target)),
+            FileOutcome::Error(e) => {
+                log::warn!("Skipping file {}: {}", target.file_path, e);
+                last_error = Some(e);
+            }
+            _ => log::warn!("Unexpected {} outcome for {}", provider.name(), target.file_path),
+        }
+    }
+
+    let status = match last_error {
+        Some(err) if reports.is_empty() => ScanStatus::Error(err),
+        _ => ScanStatus::Completed(into_result(reports)),
+    };
+    state.lock().unwrap().insert(package_name.to_string(), status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::path::Path;
+
+    fn policy(windows: Vec<(usize, Duration)>, min_interval: Duration) -> RateLimitPolicy {
+        RateLimitPolicy {
+            windows,
+            min_interval,
+            submit_backoff: None,
+            wait_counts_against_timeout: false,
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_windows() {
+        let mut limiter = RateLimiter::new(policy(vec![(2, Duration::from_secs(60))], Duration::ZERO));
+        assert_eq!(limiter.check_wait_needed(), None);
+        limiter.record_request();
+        assert_eq!(limiter.check_wait_needed(), None);
+        limiter.record_request();
+        let wait = limiter.check_wait_needed().unwrap();
+        assert!(wait > Duration::from_secs(59) && wait <= Duration::from_secs(60));
+
+        let mut limiter = RateLimiter::new(policy(Vec::new(), Duration::from_secs(5)));
+        limiter.record_request();
+        assert!(limiter.check_wait_needed().unwrap() <= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_rate_limiter_submit_backoff() {
+        let mut limiter = RateLimiter::new(RateLimitPolicy {
+            submit_backoff: Some(Duration::from_secs(86400)),
+            ..policy(Vec::new(), Duration::ZERO)
+        });
+        assert_eq!(
+            limiter.set_submit_rate_limit(Duration::from_secs(3)),
+            Some(Duration::from_secs(86400))
+        );
+        // Lookups are unaffected, submissions wait for the back-off
+        assert_eq!(limiter.check_wait_needed(), None);
+        assert!(limiter.check_submit_wait_needed().unwrap() > Duration::from_secs(86000));
+
+        let mut limiter = RateLimiter::new(policy(Vec::new(), Duration::ZERO));
+        assert_eq!(limiter.set_submit_rate_limit(Duration::from_secs(30)), None);
+        assert!(limiter.check_wait_needed().is_some());
+    }
+
+    /// In-memory provider: knows `known` hashes, accepts submissions that become ready after `polls_until_ready` polls
+    struct FakeProvider {
+        known: Vec<String>,
+        cached: Option<CachedReport<String>>,
+        polls_until_ready: Option<usize>,
+        defer: bool,
+        polls: Cell<usize>,
+        not_found_recorded: RefCell<Vec<String>>,
+    }
+
+    impl FakeProvider {
+        fn new(known: &[&str]) -> Self {
+            Self {
+                known: known.iter().map(|s| s.to_string()).collect(),
+                cached: None,
+                polls_until_ready: Some(1),
+                defer: false,
+                polls: Cell::new(0),
+                not_found_recorded: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ScanProvider for FakeProvider {
+        type Report = String;
+
+        fn name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn rate_limit_policy(&self) -> RateLimitPolicy {
+            policy(Vec::new(), Duration::ZERO)
+        }
+
+        fn poll_policy(&self) -> PollPolicy {
+            PollPolicy {
+                interval: Duration::ZERO,
+                max_attempts: Some(3),
+                defer_unfinished: self.defer,
+                file_timeout: Duration::from_secs(5),
+            }
+        }
+
+        fn cached_report(&self, _target: &ScanTarget) -> Option<CachedReport<String>> {
+            self.cached.clone()
+        }
+
+        fn lookup_hash(&self, target: &ScanTarget, gate: &RequestGate) -> Result<String, ProviderError> {
+            gate.acquire()?;
+            if self.known.contains(&target.sha256) {
+                Ok(format!("report:{}", target.sha256))
+            } else {
+                Err(ProviderError::NotFound)
+            }
+        }
+
+        fn submit_file(&self, target: &ScanTarget, _local_path: &Path, gate: &RequestGate) -> Result<String, ProviderError> {
+            gate.acquire_submit()?;
+            Ok(format!("job-{}", target.sha256))
+        }
+
+        fn poll(&self, target: &ScanTarget, _submission_id: &str, gate: &RequestGate) -> Result<PollOutcome<String>, ProviderError> {
+            gate.acquire()?;
+            self.polls.set(self.polls.get() + 1);
+            match self.polls_until_ready {
+                Some(n) if self.polls.get() >= n => Ok(PollOutcome::Ready(format!("analysis:{}", target.sha256))),
+                _ => Ok(PollOutcome::Pending),
+            }
+        }
+
+        fn record_not_found(&self, target: &ScanTarget) {
+            self.not_found_recorded.borrow_mut().push(target.sha256.clone());
+        }
+
+        fn normalize(&self, report: &String) -> NormalizedResult {
+            NormalizedResult {
+                provider: self.name().to_string(),
+                sha256: report.clone(),
+                verdict: Verdict::Unknown,
+                detections: None,
+                engines: None,
+                score: None,
+                link: String::new(),
+            }
+        }
+    }
+
+    fn target(path: &str, sha256: &str) -> ScanTarget {
+        ScanTarget {
+            package_name: "com.example.app".to_string(),
+            file_path: path.to_string(),
+            sha256: sha256.to_string(),
+        }
+    }
+
+    fn run(provider: &FakeProvider, target: &ScanTarget, allow_submit: bool) -> FileOutcome<String> {
+        let limiter = RateLimiter::shared(provider.rate_limit_policy());
+        let fetch = |_: &ScanTarget| -> Result<PathBuf, String> { Ok(PathBuf::from("/nonexistent/pulled.apk")) };
+        scan_file(provider, target, &limiter, allow_submit, &fetch, &|_| {})
+    }
+
+    #[test]
+    fn test_scan_file_lookup_and_cache() {
+        let provider = FakeProvider::new(&["aa"]);
+        assert_eq!(
+            run(&provider, &target("/data/app/base.apk", "aa"), false),
+            FileOutcome::Report("report:aa".to_string())
+        );
+
+        assert_eq!(run(&provider, &target("/data/app/base.apk", "bb"), false), FileOutcome::NotFound);
+        assert_eq!(*provider.not_found_recorded.borrow(), vec!["bb".to_string()]);
+
+        let mut cached = FakeProvider::new(&[]);
+        cached.cached = Some(CachedReport::Report("cached".to_string()));
+        assert_eq!(
+            run(&cached, &target("/data/app/base.apk", "cc"), true),
+            FileOutcome::Report("cached".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scan_file_submit_and_poll() {
+        let provider = FakeProvider::new(&[]);
+        assert_eq!(
+            run(&provider, &target("/data/app/base.apk", "dd"), true),
+            FileOutcome::Report("analysis:dd".to_string())
+        );
+
+        // Only APK and SO files are submitted
+        assert_eq!(run(&provider, &target("/data/app/base.dm", "ee"), true), FileOutcome::Skipped);
+
+        let mut deferred = FakeProvider::new(&[]);
+        deferred.polls_until_ready = None;
+        deferred.defer = true;
+        assert_eq!(
+            run(&deferred, &target("/data/app/lib.so", "ff"), true),
+            FileOutcome::Pending {
+                submission_id: "job-ff".to_string()
+            }
+        );
+        assert_eq!(deferred.polls.get(), 3);
+
+        deferred.defer = false;
+        assert!(matches!(
+            run(&deferred, &target("/data/app/lib.so", "ff"), true),
+            FileOutcome::Error(_)
+        ));
+    }
+
+    #[test]
+    fn test_lookup_package() {
+        let provider = FakeProvider::new(&["aa"]);
+        let limiter = RateLimiter::shared(provider.rate_limit_policy());
+        let state: ScannerState<Vec<String>> = Arc::new(Mutex::new(HashMap::new()));
+        let hashes = vec![
+            ("/data/app/base.apk".to_string(), "aa".to_string()),
+            ("/data/app/split.apk".to_string(), "bb".to_string()),
+        ];
+        lookup_package(&provider, "com.example.app", hashes, &state, &limiter, |t| format!("missing:{}", t.sha256), |r| r);
+
+        match state.lock().unwrap().get("com.example.app") {
+            Some(ScanStatus::Completed(reports)) => {
+                assert_eq!(reports, &vec!["report:aa".to_string(), "missing:bb".to_string()])
+            }
+            other => panic!("unexpected status: {:?}", other),
+        }
+        assert_eq!(provider.polls.get(), 0);
+
+        lookup_package(&provider, "android", Vec::new(), &state, &limiter, |_| String::new(), |r| r);
+        assert!(matches!(state.lock().unwrap().get("android"), Some(ScanStatus::Error(_))));
+    }
+
+    #[test]
+    fn test_freshness_policy() {
+        let day = 86400;
+        let now = 100 * day;
+        let policy = FreshnessPolicy {
+            max_age_days: 30,
+            rescan_flagged: true,
+        };
+
+        // Old analysis is re-queried, recent analysis is kept
+        assert!(policy.is_stale(now - 31 * day, now - 2 * day, false, now));
+        assert!(!policy.is_stale(now - 29 * day, now - 2 * day, false, now));
+        // Flagged files are always re-queried
+        assert!(policy.is_stale(now - day, now - day, true, now));
+        // ...unless they were fetched moments ago
+        assert!(!policy.is_stale(now - 31 * day, now - 60, true, now));
+
+        // Default policy keeps results forever
+        assert!(!FreshnessPolicy::default().is_stale(0, 0, true, now));
+    }
+
+    #[test]
+    fn test_quota_usage() {
+        let mut usage = QuotaUsage {
+            provider: "VirusTotal".to_string(),
+            budget: QuotaBudget {
+                per_day: 500,
+                per_month: 15_500,
+            },
+            day: "2026-02-27".to_string(),
+            used_today: 499,
+            used_month: 15_499,
+        };
+        assert_eq!(usage.remaining(), Some(1));
+        usage.record();
+        assert_eq!(usage.remaining(), Some(0));
+
+        // A new day resets the daily counter only
+        usage.roll_over("2026-02-28");
+        assert_eq!(usage.used_today, 0);
+        assert_eq!(usage.remaining(), Some(0));
+
+        // A new month resets both
+        usage.roll_over("2026-03-01");
+        assert_eq!(usage.remaining(), Some(500));
+
+        usage.budget = QuotaBudget::default();
+        assert_eq!(usage.remaining(), None);
+    }
+
+    #[test]
+    fn test_pair_hashes_and_estimate() {
+        let a = "a".repeat(64);
+        let b = "b".repeat(64);
+        let pairs = pair_hashes("/data/app/base.apk /data/app/split.apk /data/app/bad.apk", &format!("{} {} short", a, b));
+        assert_eq!(
+            pairs,
+            vec![
+                ("/data/app/base.apk".to_string(), a.clone()),
+                ("/data/app/split.apk".to_string(), b.clone())
+            ]
+        );
+
+        // A package without known hashes still costs one lookup
+        let package = PackageFingerprint::fixture("com.example.app", "", "");
+        assert_eq!(estimated_file_count(&package, None), 1);
+    }
+}
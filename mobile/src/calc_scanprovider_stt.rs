@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// File being scanned by a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanTarget {
+    pub package_name: String,
+    pub file_path: String,
+    pub sha256: String,
+}
+
+/// Request budget of a provider API
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    /// (max requests, window) pairs, e.g. 4 requests per 60 seconds
+    pub windows: Vec<(usize, Duration)>,
+    /// Minimum spacing between consecutive requests
+    pub min_interval: Duration,
+    /// Fixed back-off after a 429 on submission; `None` applies the server's retry-after globally
+    pub submit_backoff: Option<Duration>,
+    /// Whether waiting for the rate limit counts against the per-file timeout
+    pub wait_counts_against_timeout: bool,
+}
+
+/// How submitted files are followed up
+#[derive(Debug, Clone)]
+pub struct PollPolicy {
+    /// Delay before each poll
+    pub interval: Duration,
+    /// Maximum number of polls; `None` polls until the per-file timeout
+    pub max_attempts: Option<usize>,
+    /// Keep unfinished analyses as pending jobs instead of failing with a timeout
+    pub defer_unfinished: bool,
+    /// Time budget for a single file (lookup, upload and polling)
+    pub file_timeout: Duration,
+}
+
+/// Error returned by a provider request
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderError {
+    NotFound,
+    RateLimit { retry_after: u64 },
+    /// Gave up waiting for the rate limit within the per-file timeout
+    Timeout(String),
+    /// The daily or monthly request budget of the API key is used up
+    QuotaExhausted,
+    Other(String),
+}
+
+impl std::fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::NotFound => write!(f, "File not found"),
+            ProviderError::RateLimit { retry_after } => {
+                write!(f, "Rate limit exceeded, retry after {} seconds", retry_after)
+            }
+            ProviderError::QuotaExhausted => write!(f, "API request quota exhausted"),
+            ProviderError::Timeout(e) | ProviderError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Stage at which a submitted file failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureStage {
+    /// Upload was rejected or produced no report
+    Submit,
+    /// The provider's analysis itself failed
+    Analysis,
+}
+
+/// When locally cached results are looked up again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FreshnessPolicy {
+    /// Re-query results analyzed more than this many days ago; 0 keeps them forever
+    pub max_age_days: u32,
+    /// Re-query files flagged suspicious or malicious on every scan
+    pub rescan_flagged: bool,
+}
+
+/// Locally cached lookup result
+#[derive(Debug, Clone, PartialEq)]
+pub enum CachedReport<R> {
+    Report(R),
+    NotFound,
+    /// Hash is on the known-good allowlist
+    Trusted,
+}
+
+/// State of a submitted analysis
+#[derive(Debug, Clone, PartialEq)]
+pub enum PollOutcome<R> {
+    Ready(R),
+    Pending,
+    Failed { stage: FailureStage, message: String },
+}
+
+/// Outcome of scanning one file through a provider
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileOutcome<R> {
+    Report(R),
+    /// Unknown to the provider and not submitted
+    NotFound,
+    /// On the known-good hash allowlist; not looked up
+    Trusted,
+    /// Unknown to the provider and not a submittable file type (only .apk and .so are uploaded)
+    Skipped,
+    /// Submitted; analysis still running under this submission id
+    Pending { submission_id: String },
+    /// Submission refused by the rate limit; retry after this Unix timestamp if known
+    SubmitRateLimited { wait_until: Option<u64> },
+    /// Submission accepted but the analysis failed or produced no report
+    Failed { stage: FailureStage, message: String },
+    /// Lookup, pull or timeout error
+    Error(String),
+}
+
+/// Provider-independent verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Verdict {
+    Unknown,
+    Clean,
+    Suspicious,
+    Malicious,
+}
+
+/// Provider-independent summary of a file report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedResult {
+    pub provider: String,
+    pub sha256: String,
+    pub verdict: Verdict,
+    /// Engines flagging the file, for multi-engine providers
+    pub detections: Option<i32>,
+    /// Engines that analyzed the file, for multi-engine providers
+    pub engines: Option<i32>,
+    /// Provider threat score, if any
+    pub score: Option<i32>,
+    pub link: String,
+}
+
+/// A malware scanning service. Implementations only talk to the service and
+/// its result cache; rate limiting, pulling, submission and polling are
+/// driven by `calc_scanprovider::scan_file`.
+pub trait ScanProvider {
+    /// Per-file result shown in the UI
+    type Report: Clone;
+
+    fn name(&self) -> &'static str;
+    fn rate_limit_policy(&self) -> RateLimitPolicy;
+    fn poll_policy(&self) -> PollPolicy;
+
+    /// Look up a previous result in the local cache
+    fn cached_report(&self, target: &ScanTarget) -> Option<CachedReport<Self::Report>>;
+
+    /// Look up a file by hash. Call `gate.acquire()` before every HTTP request.
+    fn lookup_hash(&self, target: &ScanTarget, gate: &RequestGate) -> Result<Self::Report, ProviderError>;
+
+    /// Submit a pulled file, returning the submission id to poll.
+    /// Lookup-only providers keep the default.
+    fn submit_file(&self, _target: &ScanTarget, _local_path: &Path, _gate: &RequestGate) -> Result<String, ProviderError> {
+        Err(ProviderError::Other(format!("{} does not accept submissions", self.name())))
+    }
+
+    /// Check the state of a submission
+    fn poll(
+        &self,
+        _target: &ScanTarget,
+        _submission_id: &str,
+        _gate: &RequestGate,
+    ) -> Result<PollOutcome<Self::Report>, ProviderError> {
+        Err(ProviderError::Other(format!("{} does not accept submissions", self.name())))
+    }
+
+    /// Remember that the provider does not know this file
+    fn record_not_found(&self, _target: &ScanTarget) {}
+
+    /// Remember a failed submission
+    fn record_failure(&self, _target: &ScanTarget, _stage: FailureStage, _message: &str) {}
+
+    fn normalize(&self, report: &Self::Report) -> NormalizedResult;
+}
+
+/// Daily and monthly request budget of an API key; 0 means unlimited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuotaBudget {
+    pub per_day: u32,
+    pub per_month: u32,
+}
+
+/// Requests counted against a `QuotaBudget` for the current UTC day and month
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsage {
+    /// Provider name the usage is persisted under
+    pub provider: String,
+    pub budget: QuotaBudget,
+    /// UTC day the counters refer to ("YYYY-MM-DD")
+    pub day: String,
+    pub used_today: u32,
+    pub used_month: u32,
+}
+
+/// Rate limiter enforcing a provider's `RateLimitPolicy`
+pub struct RateLimiter {
+    pub policy: RateLimitPolicy,
+    pub request_times: Vec<Instant>,
+    pub last_request: Option<Instant>,
+    /// When a 429 is received, all threads wait until this time
+    pub rate_limit_until: Option<Instant>,
+    /// When a 429 is received on submission, uploads wait until this time
+    pub submit_rate_limit_until: Option<Instant>,
+    /// Persisted daily/monthly budget, if the provider has one
+    pub quota: Option<QuotaUsage>,
+}
+
+/// Shared rate limiter for a provider API
+pub type SharedRateLimiter = Arc<Mutex<RateLimiter>>;
+
+/// Rate-limited access to a provider API for one file
+pub struct RequestGate<'a> {
+    pub limiter: &'a SharedRateLimiter,
+    /// Give up waiting past this point
+    pub deadline: Option<Instant>,
+    /// Called with the remaining time while waiting for the rate limit
+    pub on_wait: &'a dyn Fn(Duration),
+}
+
+/// Scan status of a package (may contain multiple files)
+#[derive(Debug, Clone)]
+pub enum ScanStatus<R> {
+    Pending,
+    Scanning {
+        scanned: usize,
+        total: usize,
+        operation: String,
+    },
+    Completed(R),
+    Error(String),
+}
+
+/// Shared per-package scan state
+pub type ScannerState<R> = Arc<Mutex<HashMap<String, ScanStatus<R>>>>;
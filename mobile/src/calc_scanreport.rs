@@ -0,0 +1,687 @@
+pub use crate::calc_scanreport_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::calc_hybridanalysis_stt::CalcHybridAnalysis;
+use crate::calc_scanprovider_stt::{ScanStatus, ScannerState};
+use crate::calc_virustotal_stt::CalcVirustotal;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const INFORMATION_URI: &str = "https://github.com/nikescar/uad-shizuku";
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 4] = [
+        ReportFormat::Html,
+        ReportFormat::Json,
+        ReportFormat::Csv,
+        ReportFormat::Sarif,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReportFormat::Html => "HTML",
+            ReportFormat::Json => "JSON",
+            ReportFormat::Csv => "CSV",
+            ReportFormat::Sarif => "SARIF",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Sarif => "sarif",
+        }
+    }
+}
+
+/// Severity of a report finding, named after SARIF result levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FindingLevel {
+    Error,
+    Warning,
+}
+
+impl FindingLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            FindingLevel::Error => "error",
+            FindingLevel::Warning => "warning",
+        }
+    }
+}
+
+/// Rules reported in SARIF output: (id, short description)
+const RULES: [(&str, &str); 3] = [
+    ("vt-detection", "VirusTotal engines flag the file"),
+    ("ha-verdict", "Hybrid Analysis verdict is malicious or suspicious"),
+    ("stalkerware-ioc", "Package or signing certificate matches a stalkerware IoC"),
+];
+
+/// Completed results from a provider's scanner state, keyed by package name
+pub fn completed_results<R: Clone>(state: Option<&ScannerState<R>>) -> HashMap<String, R> {
+    let Some(state) = state else {
+        return HashMap::new();
+    };
+    let Ok(s) = state.lock() else {
+        return HashMap::new();
+    };
+    s.iter()
+        .filter_map(|(pkg, status)| match status {
+            ScanStatus::Completed(r) => Some((pkg.clone(), r.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Read device properties for the report header. Missing properties are left empty.
+pub fn collect_device_metadata(device: &str) -> DeviceMetadata {
+    let prop = |key: &str| crate::adb::get_prop(device, key).unwrap_or_default();
+    DeviceMetadata {
+        serial: device.to_string(),
+        manufacturer: prop("ro.product.manufacturer"),
+        model: prop("ro.product.model"),
+        android_version: prop("ro.build.version.release"),
+        sdk_level: prop("ro.build.version.sdk"),
+        build_fingerprint: prop("ro.build.fingerprint"),
+        security_patch: prop("ro.build.version.security_patch"),
+    }
+}
+
+/// Rows for one package: one per scanned file, or a single package row when nothing was scanned
+fn package_rows(
+    base: ScanReportRow,
+    vt: Option<&CalcVirustotal>,
+    ha: Option<&CalcHybridAnalysis>,
+) -> Vec<ScanReportRow> {
+    let mut files: Vec<(&str, &str)> = Vec::new();
+    let vt_files = vt.map(|r| r.file_results.as_slice()).unwrap_or_default();
+    let ha_files = ha.map(|r| r.file_results.as_slice()).unwrap_or_default();
+    for (path, sha256) in vt_files
+        .iter()
+        .map(|f| (f.file_path.as_str(), f.sha256.as_str()))
+        .chain(ha_files.iter().map(|f| (f.file_path.as_str(), f.sha256.as_str())))
+    {
+        if !files.iter().any(|(p, _)| *p == path) {
+            files.push((path, sha256));
+        }
+    }
+
+    if files.is_empty() {
+        return vec![base];
+    }
+
+    files
+        .into_iter()
+        .map(|(path, sha256)| {
+            let mut row = ScanReportRow {
+                file_path: Some(path.to_string()),
+                sha256: Some(sha256.to_string()),
+                ..base.clone()
+            };
+
+            if let Some(f) = vt_files.iter().find(|f| f.file_path == path) {
+                if f.not_found {
+                    row.vt_status = Some("not found".to_string());
+                } else if f.skipped {
+                    row.vt_status = Some("skipped".to_string());
+                } else if f.trusted {
+                    row.vt_status = Some("trusted (allowlist)".to_string());
+                } else if let Some(e) = &f.error {
+                    row.vt_status = Some(e.clone());
+                } else {
+                    row.vt_malicious = Some(f.malicious);
+                    row.vt_suspicious = Some(f.suspicious);
+                    row.vt_undetected = Some(f.undetected);
+                    row.vt_harmless = Some(f.harmless);
+                }
+            }
+
+            if let Some(f) = ha_files.iter().find(|f| f.file_path == path) {
+                row.ha_verdict = Some(f.verdict.clone());
+                row.ha_threat_score = f.threat_score;
+            }
+
+            row
+        })
+        .collect()
+}
+
+/// Combine scan results, risk scores and list metadata into a report
+pub fn build_scan_report(
+    device: DeviceMetadata,
+    packages: &[PackageFingerprint],
+    sources: &ReportSources,
+    generated_at: i64,
+) -> ScanReport {
+    let mut sorted: Vec<&PackageFingerprint> = packages.iter().collect();
+    sorted.sort_by(|a, b| a.pkg.cmp(&b.pkg));
+
+    let rows = sorted
+        .into_iter()
+        .flat_map(|package| {
+            let stalkerware_family = sources.stalkerware.as_ref().and_then(|indicators| {
+                indicators
+                    .get_family_name(&package.pkg)
+                    .or_else(|| {
+                        sources
+                            .certificates
+                            .get(&package.pkg)
+                            .and_then(|certs| indicators.get_family_by_certificates(certs))
+                    })
+                    .cloned()
+            });
+            let uad_entry = sources.uad_lists.as_ref().and_then(|lists| lists.apps.get(&package.pkg));
+
+            let base = ScanReportRow {
+                package_name: package.pkg.clone(),
+                is_system: package.flags.contains("SYSTEM"),
+                izzy_risk: sources.risk_scores.get(&package.pkg).copied(),
+                stalkerware_family,
+                installer: crate::calc_provenance::dump_value(&package.dumpText, "installerPackageName"),
+                uad_list: uad_entry.map(|e| e.list.clone()),
+                uad_removal: uad_entry.map(|e| e.removal.clone()),
+                ..Default::default()
+            };
+
+            package_rows(
+                base,
+                sources.vt_results.get(&package.pkg),
+                sources.ha_results.get(&package.pkg),
+            )
+        })
+        .collect();
+
+    ScanReport {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        device,
+        generated_at,
+        package_count: packages.len(),
+        rows,
+    }
+}
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// Findings worth flagging for a row: (rule id, level, message)
+fn row_findings(row: &ScanReportRow) -> Vec<(&'static str, FindingLevel, String)> {
+    let mut findings = Vec::new();
+
+    let malicious = row.vt_malicious.unwrap_or(0);
+    let suspicious = row.vt_suspicious.unwrap_or(0);
+    if malicious > 0 || suspicious > 0 {
+        let engines = malicious + suspicious + row.vt_undetected.unwrap_or(0) + row.vt_harmless.unwrap_or(0);
+        let level = if malicious > 0 { FindingLevel::Error } else { FindingLevel::Warning };
+        findings.push((
+            "vt-detection",
+            level,
+            format!(
+                "VirusTotal: {} malicious and {} suspicious out of {} engines",
+                malicious, suspicious, engines
+            ),
+        ));
+    }
+
+    if let Some(verdict) = &row.ha_verdict {
+        let level = match verdict.as_str() {
+            "malicious" => Some(FindingLevel::Error),
+            "suspicious" => Some(FindingLevel::Warning),
+            _ => None,
+        };
+        if let Some(level) = level {
+            let score = row
+                .ha_threat_score
+                .map(|s| format!(" (threat score {})", s))
+                .unwrap_or_default();
+            findings.push(("ha-verdict", level, format!("Hybrid Analysis verdict: {}{}", verdict, score)));
+        }
+    }
+
+    if let Some(family) = &row.stalkerware_family {
+        findings.push((
+            "stalkerware-ioc",
+            FindingLevel::Error,
+            format!("Matches stalkerware family {}", family),
+        ));
+    }
+
+    findings
+}
+
+/// Render a report in the given format
+pub fn render_report(report: &ScanReport, format: ReportFormat) -> Result<String, String> {
+    match format {
+        ReportFormat::Html => Ok(render_html(report)),
+        ReportFormat::Json => serde_json::to_string_pretty(report).map_err(|e| e.to_string()),
+        ReportFormat::Csv => Ok(render_csv(report)),
+        ReportFormat::Sarif => serde_json::to_string_pretty(&render_sarif(report)).map_err(|e| e.to_string()),
+    }
+}
+
+/// Quote a CSV field when needed. Leading formula characters are escaped so
+/// spreadsheet apps do not evaluate package-controlled strings.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn opt_num(value: Option<i32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn opt_str(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("")
+}
+
+const CSV_HEADER: &str = "device_serial,generated_at,package_name,file_path,sha256,is_system,\
+vt_malicious,vt_suspicious,vt_undetected,vt_harmless,vt_status,ha_verdict,ha_threat_score,\
+izzy_risk,stalkerware_family,installer,uad_list,uad_removal";
+
+fn render_csv(report: &ScanReport) -> String {
+    let generated_at = format_timestamp(report.generated_at);
+    let mut out = String::from(CSV_HEADER);
+    out.push_str("\r\n");
+    for row in &report.rows {
+        let fields = [
+            csv_field(&report.device.serial),
+            csv_field(&generated_at),
+            csv_field(&row.package_name),
+            csv_field(opt_str(&row.file_path)),
+            csv_field(opt_str(&row.sha256)),
+            row.is_system.to_string(),
+            opt_num(row.vt_malicious),
+            opt_num(row.vt_suspicious),
+            opt_num(row.vt_undetected),
+            opt_num(row.vt_harmless),
+            csv_field(opt_str(&row.vt_status)),
+            csv_field(opt_str(&row.ha_verdict)),
+            opt_num(row.ha_threat_score),
+            opt_num(row.izzy_risk),
+            csv_field(opt_str(&row.stalkerware_family)),
+            csv_field(opt_str(&row.installer)),
+            csv_field(opt_str(&row.uad_list)),
+            csv_field(opt_str(&row.uad_removal)),
+        ];
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn html_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:24px;color:#222}\
+table{border-collapse:collapse;font-size:13px}\
+th,td{border:1px solid #ccc;padding:4px 6px;text-align:left;vertical-align:top}\
+th{background:#eee}\
+td.mono{font-family:monospace;word-break:break-all}\
+tr.error{background:#fdecea}\
+tr.warning{background:#fff4e0}\
+dl{display:grid;grid-template-columns:max-content auto;gap:2px 12px}\
+dt{font-weight:bold}";
+
+fn render_html(report: &ScanReport) -> String {
+    let d = &report.device;
+    let flagged = report.rows.iter().filter(|r| !row_findings(r).is_empty()).count();
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+    out.push_str(&format!(
+        "<title>Scan report {}</title><style>{}</style></head><body>\n",
+        html_escape(&d.serial),
+        HTML_STYLE
+    ));
+    out.push_str("<h1>UAD-Shizuku scan report</h1>\n<dl>");
+    for (name, value) in [
+        ("Generated", format_timestamp(report.generated_at)),
+        ("Tool version", report.tool_version.clone()),
+        ("Device serial", d.serial.clone()),
+        ("Device", format!("{} {}", d.manufacturer, d.model)),
+        ("Android", format!("{} (SDK {})", d.android_version, d.sdk_level)),
+        ("Security patch", d.security_patch.clone()),
+        ("Build fingerprint", d.build_fingerprint.clone()),
+        ("Packages", report.package_count.to_string()),
+        ("Flagged rows", flagged.to_string()),
+    ] {
+        out.push_str(&format!("<dt>{}</dt><dd>{}</dd>", name, html_escape(&value)));
+    }
+    out.push_str("</dl>\n<table>\n<tr>");
+    for col in [
+        "Package", "File", "SHA-256", "System", "VT mal/sus/total", "HA verdict", "HA score", "IzzyRisk",
+        "Stalkerware", "Installer", "UAD list", "UAD removal", "Findings",
+    ] {
+        out.push_str(&format!("<th>{}</th>", col));
+    }
+    out.push_str("</tr>\n");
+
+    for row in &report.rows {
+        let findings = row_findings(row);
+        let class = if findings.iter().any(|f| f.1 == FindingLevel::Error) {
+            " class=\"error\""
+        } else if !findings.is_empty() {
+            " class=\"warning\""
+        } else {
+            ""
+        };
+        let vt = match (row.vt_malicious, row.vt_suspicious) {
+            (Some(m), Some(s)) => {
+                let total = m + s + row.vt_undetected.unwrap_or(0) + row.vt_harmless.unwrap_or(0);
+                format!("{}/{}/{}", m, s, total)
+            }
+            _ => opt_str(&row.vt_status).to_string(),
+        };
+        let findings_text: Vec<String> = findings.into_iter().map(|f| f.2).collect();
+        let cells = [
+            (row.package_name.clone(), ""),
+            (opt_str(&row.file_path).to_string(), " class=\"mono\""),
+            (opt_str(&row.sha256).to_string(), " class=\"mono\""),
+            (if row.is_system { "yes" } else { "" }.to_string(), ""),
+            (vt, ""),
+            (opt_str(&row.ha_verdict).to_string(), ""),
+            (opt_num(row.ha_threat_score), ""),
+            (opt_num(row.izzy_risk), ""),
+            (opt_str(&row.stalkerware_family).to_string(), ""),
+            (opt_str(&row.installer).to_string(), ""),
+            (opt_str(&row.uad_list).to_string(), ""),
+            (opt_str(&row.uad_removal).to_string(), ""),
+            (findings_text.join("; "), ""),
+        ];
+        out.push_str(&format!("<tr{}>", class));
+        for (value, attr) in cells {
+            out.push_str(&format!("<td{}>{}</td>", attr, html_escape(&value)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body></html>\n");
+    out
+}
+
+fn render_sarif(report: &ScanReport) -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = RULES
+        .iter()
+        .map(|(id, description)| json!({ "id": id, "shortDescription": { "text": description } }))
+        .collect();
+
+    let results: Vec<serde_json::Value> = report
+        .rows
+        .iter()
+        .flat_map(|row| {
+            let uri = row.file_path.clone().unwrap_or_else(|| row.package_name.clone());
+            row_findings(row).into_iter().map(move |(rule_id, level, message)| {
+                let mut artifact = json!({ "uri": uri });
+                if let Some(sha256) = &row.sha256 {
+                    artifact["hashes"] = json!({ "sha-256": sha256 });
+                }
+                json!({
+                    "ruleId": rule_id,
+                    "ruleIndex": RULES.iter().position(|(id, _)| *id == rule_id),
+                    "level": level.as_str(),
+                    "message": { "text": format!("{}: {}", row.package_name, message) },
+                    "locations": [{
+                        "physicalLocation": { "artifactLocation": artifact },
+                        "logicalLocations": [{ "name": row.package_name, "kind": "package" }],
+                    }],
+                    "properties": row,
+                })
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "uad-shizuku",
+                    "version": report.tool_version,
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "invocations": [{
+                "executionSuccessful": true,
+                "endTimeUtc": format_timestamp(report.generated_at),
+            }],
+            "properties": {
+                "device": report.device,
+                "packageCount": report.package_count,
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Render the report and write it to `dir` as `uad-shizuku-scan-<serial>-<time>.<ext>`
+pub fn write_report(report: &ScanReport, format: ReportFormat, dir: &Path) -> Result<PathBuf, String> {
+    let content = render_report(report, format)?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let serial: String = report
+        .device
+        .serial
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let time = chrono::DateTime::from_timestamp(report.generated_at, 0)
+        .map(|dt| dt.format("%Y%m%d-%H%M%S").to_string())
+        .unwrap_or_default();
+    let path = dir.join(format!("uad-shizuku-scan-{}-{}.{}", serial, time, format.extension()));
+
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Build and write a scan report for a device in a background thread
+pub fn export_scan_report_async(
+    device: String,
+    format: ReportFormat,
+    dir: PathBuf,
+    risk_scores: HashMap<String, i32>,
+    result: Arc<Mutex<Option<Result<PathBuf, String>>>>,
+) {
+    std::thread::spawn(move || {
+        let store = crate::shared_store_stt::get_shared_store();
+        let packages = store.get_installed_packages();
+        let written = if packages.is_empty() {
+            Err("No installed packages loaded for this device".to_string())
+        } else {
+            let sources = ReportSources {
+                vt_results: completed_results(store.get_vt_scanner_state().as_ref()),
+                ha_results: completed_results(store.get_ha_scanner_state().as_ref()),
+                risk_scores,
+                stalkerware: store.get_stalkerware_indicators(),
+                certificates: store.get_all_signing_certificates(),
+                uad_lists: store.get_uad_ng_lists(),
+            };
+            let generated_at = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            let report = build_scan_report(collect_device_metadata(&device), &packages, &sources, generated_at);
+            write_report(&report, format, &dir)
+        };
+        match &written {
+            Ok(path) => log::info!("Exported {} scan report to {}", format.label(), path.display()),
+            Err(e) => log::error!("Failed to export scan report: {}", e),
+        }
+        if let Ok(mut r) = result.lock() {
+            *r = Some(written);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc_hybridanalysis_stt::FileScanResult as HaFile;
+    use crate::calc_virustotal_stt::FileScanResult as VtFile;
+
+    fn vt_file(path: &str, sha256: &str, malicious: i32) -> VtFile {
+        VtFile {
+            file_path: path.to_string(),
+            sha256: sha256.to_string(),
+            malicious,
+            suspicious: 0,
+            undetected: 60,
+            harmless: 0,
+            dex_count: None,
+            reputation: 0,
+            vt_link: String::new(),
+            not_found: false,
+            skipped: false,
+            trusted: false,
+            error: None,
+            previous_detections: None,
+        }
+    }
+
+    fn sample_report() -> ScanReport {
+        let packages = vec![
+            PackageFingerprint::fixture(
+                "com.example.spy",
+                "[ HAS_CODE ALLOW_CLEAR_USER_DATA ]",
+                "    installerPackageName=com.android.vending\n",
+            ),
+            PackageFingerprint::fixture("com.android.settings", "[ SYSTEM HAS_CODE ]", "    installerPackageName=null\n"),
+        ];
+
+        let mut sources = ReportSources::default();
+        let mut vt = CalcVirustotal::default();
+        vt.file_results.push(vt_file("/data/app/spy/base.apk", "aa11", 3));
+        vt.file_results.push(vt_file("/data/app/spy/split_config.arm64_v8a.apk", "bb22", 0));
+        sources.vt_results.insert("com.example.spy".to_string(), vt);
+
+        let mut ha = CalcHybridAnalysis::default();
+        ha.file_results.push(HaFile {
+            file_path: "/data/app/spy/base.apk".to_string(),
+            sha256: "aa11".to_string(),
+            verdict: "malicious".to_string(),
+            threat_score: Some(88),
+            threat_level: Some(2),
+            classification_tags: Vec::new(),
+            total_signatures: None,
+            ha_link: String::new(),
+            wait_until: None,
+            job_id: None,
+            error_message: None,
+            previous_verdict: None,
+        });
+        sources.ha_results.insert("com.example.spy".to_string(), ha);
+        sources.risk_scores.insert("com.example.spy".to_string(), 42);
+
+        let device = DeviceMetadata {
+            serial: "emulator-5554".to_string(),
+            model: "Pixel 7".to_string(),
+            ..Default::default()
+        };
+        build_scan_report(device, &packages, &sources, 1_700_000_000)
+    }
+
+    #[test]
+    fn test_build_scan_report_rows() {
+        let report = sample_report();
+        assert_eq!(report.package_count, 2);
+        // Packages sorted by name; the unscanned package gets a single package row
+        assert_eq!(report.rows.len(), 3);
+        assert_eq!(report.rows[0].package_name, "com.android.settings");
+        assert!(report.rows[0].is_system);
+        assert_eq!(report.rows[0].file_path, None);
+        assert_eq!(report.rows[0].installer, None);
+
+        let base = &report.rows[1];
+        assert_eq!(base.file_path.as_deref(), Some("/data/app/spy/base.apk"));
+        assert_eq!(base.vt_malicious, Some(3));
+        assert_eq!(base.ha_verdict.as_deref(), Some("malicious"));
+        assert_eq!(base.ha_threat_score, Some(88));
+        assert_eq!(base.izzy_risk, Some(42));
+        assert_eq!(base.installer.as_deref(), Some("com.android.vending"));
+
+        let split = &report.rows[2];
+        assert_eq!(split.vt_malicious, Some(0));
+        assert_eq!(split.ha_verdict, None);
+        assert!(row_findings(split).is_empty());
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("=HYPERLINK(1)"), "'=HYPERLINK(1)");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let csv = render_report(&sample_report(), ReportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[2].starts_with("emulator-5554,2023-11-14T22:13:20Z,com.example.spy,/data/app/spy/base.apk,aa11,"));
+        assert_eq!(lines[2].split(',').count(), CSV_HEADER.split(',').count());
+    }
+
+    #[test]
+    fn test_render_html_escapes_values() {
+        let mut report = sample_report();
+        report.device.model = "<script>".to_string();
+        let html = render_report(&report, ReportFormat::Html).unwrap();
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<tr class=\"error\">"));
+    }
+
+    #[test]
+    fn test_render_sarif() {
+        let sarif: serde_json::Value =
+            serde_json::from_str(&render_report(&sample_report(), ReportFormat::Sarif).unwrap()).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["properties"]["device"]["serial"], "emulator-5554");
+        assert_eq!(run["invocations"][0]["endTimeUtc"], "2023-11-14T22:13:20Z");
+
+        let results = run["results"].as_array().unwrap();
+        let rule_ids: Vec<&str> = results.iter().map(|r| r["ruleId"].as_str().unwrap()).collect();
+        assert_eq!(rule_ids, vec!["vt-detection", "ha-verdict"]);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["hashes"]["sha-256"],
+            "aa11"
+        );
+    }
+
+    #[test]
+    fn test_render_json_round_trip() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render_report(&sample_report(), ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["generated_at"], 1_700_000_000);
+        assert_eq!(json["rows"].as_array().unwrap().len(), 3);
+    }
+}
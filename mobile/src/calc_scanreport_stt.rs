@@ -0,0 +1,79 @@
+use crate::calc_apksig_stt::SigningCertificate;
+use crate::calc_hybridanalysis_stt::CalcHybridAnalysis;
+use crate::calc_stalkerware_stt::StalkerwareIndicators;
+use crate::calc_virustotal_stt::CalcVirustotal;
+use crate::uad_shizuku_app::UadNgLists;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Output format for an exported scan report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Self-contained HTML page (inline styles, no external assets)
+    Html,
+    Json,
+    Csv,
+    /// SARIF 2.1.0 log for code-scanning and triage tools
+    Sarif,
+}
+
+/// Device the scan was run against
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeviceMetadata {
+    pub serial: String,
+    pub manufacturer: String,
+    pub model: String,
+    pub android_version: String,
+    pub sdk_level: String,
+    pub build_fingerprint: String,
+    pub security_patch: String,
+}
+
+/// One exported row: a package file, or the package itself when no file was scanned
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanReportRow {
+    pub package_name: String,
+    pub file_path: Option<String>,
+    pub sha256: Option<String>,
+    pub is_system: bool,
+    pub vt_malicious: Option<i32>,
+    pub vt_suspicious: Option<i32>,
+    pub vt_undetected: Option<i32>,
+    pub vt_harmless: Option<i32>,
+    /// "not found", "skipped" or the error message when VirusTotal has no counts
+    pub vt_status: Option<String>,
+    pub ha_verdict: Option<String>,
+    pub ha_threat_score: Option<i32>,
+    pub izzy_risk: Option<i32>,
+    /// Stalkerware family matched by package name or signing certificate
+    pub stalkerware_family: Option<String>,
+    /// Installer package recorded by the package manager
+    pub installer: Option<String>,
+    /// UAD list (e.g. "Google", "Oem") and removal category (e.g. "Recommended")
+    pub uad_list: Option<String>,
+    pub uad_removal: Option<String>,
+}
+
+/// Combined scan results for all installed packages of a device
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanReport {
+    pub tool_version: String,
+    pub device: DeviceMetadata,
+    /// Unix timestamp when the report was generated
+    pub generated_at: i64,
+    pub package_count: usize,
+    pub rows: Vec<ScanReportRow>,
+}
+
+/// Per-package data combined into a report, keyed by package name
+#[derive(Default)]
+pub struct ReportSources {
+    /// Completed VirusTotal scans
+    pub vt_results: HashMap<String, CalcVirustotal>,
+    /// Completed Hybrid Analysis scans
+    pub ha_results: HashMap<String, CalcHybridAnalysis>,
+    pub risk_scores: HashMap<String, i32>,
+    pub stalkerware: Option<StalkerwareIndicators>,
+    pub certificates: HashMap<String, Vec<SigningCertificate>>,
+    pub uad_lists: Option<UadNgLists>,
+}
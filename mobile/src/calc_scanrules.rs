@@ -0,0 +1,570 @@
+pub use crate::calc_scanrules_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::shared_store_stt::get_shared_store;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const CONFIG_FILE_NAME: &str = "scan_rules.yaml";
+
+const CONFIG_FILE_HEADER: &str = "\
+# Local scan rules evaluated against the contents of pulled APKs (DEX, resources, native libs).
+# Nothing is uploaded. Edit and run \"Scan Rules\" in the Scan tab to apply.
+#
+# rules:
+#   - name: Example_C2_Domain
+#     description: Hard-coded command and control host
+#     files: [\"classes*.dex\", \"lib/*.so\"]   # entries to search; all entries when omitted
+#     strings:
+#       - id: $domain
+#         text: evil.example.com
+#         nocase: true
+#         wide: true                          # also match UTF-16LE (resources.arsc)
+#       - id: $magic
+#         hex: \"DE AD ?? EF\"                  # ?? is a wildcard byte
+#       - id: $url
+#         regex: \"https?://[a-z0-9.-]+/gate\\\\.php\"
+#       - id: $payload
+#         file_name: \"assets/*.bin\"           # matches entry names, not contents
+#     condition: 2 of them                    # any of them | all of them | N of them
+";
+
+/// Larger entries are not read; file name patterns still apply to them
+pub const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Path of the user-editable rules file
+pub fn config_path() -> Option<PathBuf> {
+    crate::Config::new()
+        .ok()
+        .map(|config| config.config_dir.join(CONFIG_FILE_NAME))
+}
+
+/// Parse hex bytes with `??` wildcards ("6A 40 ?? 00" or "6a40??00")
+pub fn parse_hex_pattern(hex: &str) -> Result<Vec<Option<u8>>, String> {
+    let digits: String = hex.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return Err(format!("Invalid hex pattern \"{}\"", hex));
+    }
+    let bytes = (0..digits.len())
+        .step_by(2)
+        .map(|i| match &digits[i..i + 2] {
+            "??" => Ok(None),
+            pair => u8::from_str_radix(pair, 16)
+                .map(Some)
+                .map_err(|_| format!("Invalid hex byte \"{}\" in \"{}\"", pair, hex)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if bytes.iter().all(|b| b.is_none()) {
+        return Err(format!("Hex pattern \"{}\" has only wildcards", hex));
+    }
+    Ok(bytes)
+}
+
+fn parse_condition(condition: &str, pattern_count: usize) -> Result<RuleCondition, String> {
+    let normalized = condition.trim().to_ascii_lowercase();
+    let quantifier = normalized.strip_suffix("of them").unwrap_or(&normalized).trim();
+    match quantifier {
+        "any" => Ok(RuleCondition::Any),
+        "all" => Ok(RuleCondition::All),
+        n => match n.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= pattern_count => Ok(RuleCondition::AtLeast(n)),
+            _ => Err(format!("Invalid condition \"{}\"", condition)),
+        },
+    }
+}
+
+fn compile_pattern(def: &RulePatternDef, index: usize) -> Result<CompiledPattern, String> {
+    let id = if def.id.is_empty() {
+        format!("${}", index + 1)
+    } else {
+        def.id.clone()
+    };
+    let pattern = match (&def.text, &def.hex, &def.regex, &def.file_name) {
+        (Some(text), None, None, None) if !text.is_empty() => {
+            let mut alternatives = vec![text.as_bytes().to_vec()];
+            if def.wide {
+                alternatives.push(text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect());
+            }
+            RulePattern::Bytes {
+                alternatives,
+                nocase: def.nocase,
+            }
+        }
+        (None, Some(hex), None, None) => RulePattern::Hex(parse_hex_pattern(hex)?),
+        (None, None, Some(regex), None) => RulePattern::Regex(
+            regex::bytes::RegexBuilder::new(regex)
+                .case_insensitive(def.nocase)
+                .build()
+                .map_err(|e| format!("Invalid regex in {}: {}", id, e))?,
+        ),
+        (None, None, None, Some(glob)) if !glob.is_empty() => RulePattern::FileName(glob.clone()),
+        _ => return Err(format!("{} must set exactly one of text, hex, regex or file_name", id)),
+    };
+    Ok(CompiledPattern { id, pattern })
+}
+
+fn compile_rule(def: &RuleDef) -> Result<ScanRule, String> {
+    if def.strings.is_empty() {
+        return Err("no strings".to_string());
+    }
+    let patterns = def
+        .strings
+        .iter()
+        .enumerate()
+        .map(|(i, p)| compile_pattern(p, i))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, pattern) in patterns.iter().enumerate() {
+        if patterns[..i].iter().any(|p| p.id == pattern.id) {
+            return Err(format!("duplicate string id {}", pattern.id));
+        }
+    }
+    Ok(ScanRule {
+        name: def.name.clone(),
+        description: def.description.clone(),
+        files: def.files.clone(),
+        condition: parse_condition(&def.condition, patterns.len())?,
+        patterns,
+    })
+}
+
+/// Parse and compile the rules file
+pub fn parse_rules(yaml_content: &str) -> Result<ScanRules, String> {
+    let file: RulesFile =
+        serde_yaml::from_str(yaml_content).map_err(|e| format!("Failed to parse scan rules: {}", e))?;
+
+    let mut rules = Vec::with_capacity(file.rules.len());
+    for def in &file.rules {
+        if def.name.trim().is_empty() {
+            return Err("Scan rule without a name".to_string());
+        }
+        if rules.iter().any(|r: &ScanRule| r.name == def.name) {
+            return Err(format!("Duplicate scan rule {}", def.name));
+        }
+        rules.push(compile_rule(def).map_err(|e| format!("Scan rule {}: {}", def.name, e))?);
+    }
+
+    Ok(ScanRules {
+        rules,
+        content_md5: format!("{:x}", md5::compute(yaml_content.as_bytes())),
+    })
+}
+
+/// Load the rules file, writing an empty one with an example on first use
+pub fn load_rules() -> Result<ScanRules, String> {
+    let path = config_path().ok_or("Config not available")?;
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => parse_rules(&contents),
+        Err(_) => {
+            let contents = format!("{}rules: []\n", CONFIG_FILE_HEADER);
+            if let Err(e) = std::fs::write(&path, &contents) {
+                log::warn!("Failed to write default scan rules to {:?}: {}", path, e);
+            }
+            parse_rules(&contents)
+        }
+    }
+}
+
+/// Match an entry name against a glob where `*` is any run of characters and `?` one character
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let (p, n) = (pattern.as_bytes(), name.as_bytes());
+    let (mut pi, mut ni) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == b'?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == b'*' {
+            backtrack = Some((pi, ni));
+            pi += 1;
+        } else if let Some((star_pi, star_ni)) = backtrack {
+            pi = star_pi + 1;
+            ni = star_ni + 1;
+            backtrack = Some((star_pi, star_ni + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|c| *c == b'*')
+}
+
+fn find_bytes(data: &[u8], needle: &[u8], nocase: bool) -> bool {
+    if nocase {
+        data.windows(needle.len()).any(|w| w.eq_ignore_ascii_case(needle))
+    } else {
+        data.windows(needle.len()).any(|w| w == needle)
+    }
+}
+
+fn find_hex(data: &[u8], pattern: &[Option<u8>]) -> bool {
+    data.windows(pattern.len())
+        .any(|w| w.iter().zip(pattern).all(|(b, p)| p.map_or(true, |p| p == *b)))
+}
+
+fn content_found(pattern: &RulePattern, data: &[u8]) -> bool {
+    match pattern {
+        RulePattern::Bytes { alternatives, nocase } => alternatives.iter().any(|a| find_bytes(data, a, *nocase)),
+        RulePattern::Hex(bytes) => find_hex(data, bytes),
+        RulePattern::Regex(regex) => regex.is_match(data),
+        RulePattern::FileName(_) => false,
+    }
+}
+
+fn in_scope(rule: &ScanRule, name: &str) -> bool {
+    rule.files.is_empty() || rule.files.iter().any(|g| glob_match(g, name))
+}
+
+/// Whether any rule searches the contents of this entry
+fn needs_content(rules: &ScanRules, name: &str) -> bool {
+    rules.rules.iter().any(|r| {
+        in_scope(r, name) && r.patterns.iter().any(|p| !matches!(p.pattern, RulePattern::FileName(_)))
+    })
+}
+
+/// Pattern ids found per rule, with the entries they were found in
+type FoundPatterns = Vec<BTreeMap<String, Vec<String>>>;
+
+/// Evaluate every rule's patterns against one APK entry. `data` is `None`
+/// when the contents were not read; only file name patterns apply then.
+fn scan_entry(rules: &ScanRules, name: &str, data: Option<&[u8]>, found: &mut FoundPatterns) {
+    for (rule, found) in rules.rules.iter().zip(found.iter_mut()) {
+        let scoped_data = data.filter(|_| in_scope(rule, name));
+        for pattern in &rule.patterns {
+            let hit = match (&pattern.pattern, scoped_data) {
+                (RulePattern::FileName(glob), _) => glob_match(glob, name),
+                (content, Some(data)) => content_found(content, data),
+                (_, None) => false,
+            };
+            if hit {
+                found.entry(pattern.id.clone()).or_default().push(name.to_string());
+            }
+        }
+    }
+}
+
+/// Rules whose condition holds for the patterns found
+fn evaluate_rules(rules: &ScanRules, found: &FoundPatterns) -> Vec<RuleMatch> {
+    rules
+        .rules
+        .iter()
+        .zip(found)
+        .filter(|(rule, found)| match rule.condition {
+            RuleCondition::Any => !found.is_empty(),
+            RuleCondition::All => found.len() == rule.patterns.len(),
+            RuleCondition::AtLeast(n) => found.len() >= n,
+        })
+        .map(|(rule, found)| {
+            let mut files: Vec<String> = found.values().flatten().cloned().collect();
+            files.sort();
+            files.dedup();
+            RuleMatch {
+                rule: rule.name.clone(),
+                description: rule.description.clone(),
+                patterns: rule
+                    .patterns
+                    .iter()
+                    .filter(|p| found.contains_key(&p.id))
+                    .map(|p| p.id.clone())
+                    .collect(),
+                files,
+            }
+        })
+        .collect()
+}
+
+/// Evaluate rules against in-memory entries (name, contents)
+pub fn match_rules(entries: &[(String, Vec<u8>)], rules: &ScanRules) -> Vec<RuleMatch> {
+    let mut found: FoundPatterns = vec![BTreeMap::new(); rules.rules.len()];
+    for (name, data) in entries {
+        scan_entry(rules, name, Some(data), &mut found);
+    }
+    evaluate_rules(rules, &found)
+}
+
+/// Find every rule's patterns in the entries of an APK. Conditions are evaluated later,
+/// over all APKs of a package, so patterns split across APKs still satisfy a rule.
+fn scan_apk_patterns(apk_path: &Path, rules: &ScanRules) -> Result<FoundPatterns, String> {
+    let file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+
+    let mut found: FoundPatterns = vec![BTreeMap::new(); rules.rules.len()];
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read entry {} of {:?}: {}", i, apk_path, e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+
+        let mut data = None;
+        if needs_content(rules, &name) {
+            if entry.size() > MAX_ENTRY_SIZE {
+                log::warn!("Not searching {} in {:?}: {} bytes", name, apk_path, entry.size());
+            } else {
+                // The declared size comes from the APK, so cap the read as well
+                let mut buf = Vec::new();
+                (&mut entry)
+                    .take(MAX_ENTRY_SIZE)
+                    .read_to_end(&mut buf)
+                    .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+                data = Some(buf);
+            }
+        }
+        scan_entry(rules, &name, data.as_deref(), &mut found);
+    }
+
+    Ok(found)
+}
+
+/// Cached patterns found in an APK. Entries written before patterns were cached
+/// (rule matches) do not parse or have the wrong rule count, and are scanned again.
+fn get_cached_patterns(apk_sha256sum: &str, rules: &ScanRules) -> Option<FoundPatterns> {
+    let cached = crate::db_package_cache::get_cached_apk_rule_matches(apk_sha256sum, &rules.content_md5)?;
+    serde_json::from_str::<FoundPatterns>(&cached.matches_json)
+        .ok()
+        .filter(|found| found.len() == rules.rules.len())
+}
+
+/// Patterns found in one APK file, pulling and scanning it only when no cached result
+/// exists for the same sha256sum and rules file
+fn patterns_for_apk(
+    device_serial: &str,
+    pkg_id: &str,
+    apk_path: &str,
+    apk_sha256sum: &str,
+    rules: &ScanRules,
+    tmp_dir: &str,
+) -> Result<FoundPatterns, String> {
+    if let Some(found) = get_cached_patterns(apk_sha256sum, rules) {
+        return Ok(found);
+    }
+
+    let local_path = crate::adb::pull_file_to_temp(device_serial, apk_path, tmp_dir, pkg_id)
+        .map_err(|e| format!("Failed to pull {}: {}", apk_path, e))?;
+    let result = scan_apk_patterns(Path::new(&local_path), rules);
+    let _ = std::fs::remove_file(&local_path);
+    let found = result?;
+
+    match serde_json::to_string(&found) {
+        Ok(json) => {
+            if let Err(e) =
+                crate::db_package_cache::upsert_apk_rule_matches_cache(apk_sha256sum, &rules.content_md5, pkg_id, &json)
+            {
+                log::error!("Failed to cache rule matches for {}: {}", pkg_id, e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize rule matches for {}: {}", pkg_id, e),
+    }
+
+    Ok(found)
+}
+
+/// Add the patterns found in one APK of a package to the package's patterns
+fn merge_found_patterns(target: &mut FoundPatterns, found: FoundPatterns, apk_path: &str) {
+    let apk_name = apk_path.rsplit('/').next().unwrap_or(apk_path);
+    for (target, found) in target.iter_mut().zip(found) {
+        for (id, files) in found {
+            // Entry names are only unique within one APK of a split package
+            target
+                .entry(id)
+                .or_default()
+                .extend(files.into_iter().map(|file| format!("{}!{}", apk_name, file)));
+        }
+    }
+}
+
+/// Evaluate the rules file against packages in a background thread.
+/// With `pull_missing` false only cached results are loaded (no APK is pulled).
+pub fn run_rule_scan_async(
+    installed_packages: Vec<PackageFingerprint>,
+    device_serial: String,
+    pull_missing: bool,
+    progress_clone: Arc<Mutex<Option<f32>>>,
+    cancelled_clone: Arc<Mutex<bool>>,
+) {
+    std::thread::spawn(move || {
+        let store = get_shared_store();
+        let finish = || {
+            if let Ok(mut p) = progress_clone.lock() {
+                *p = None;
+            }
+        };
+
+        let rules = match load_rules() {
+            Ok(rules) => rules,
+            Err(e) => {
+                log::error!("Scan rules not loaded: {}", e);
+                store.set_scan_rules_status(Some(Err(e)));
+                finish();
+                return;
+            }
+        };
+        store.set_scan_rules_status(Some(Ok(rules.rules.len())));
+        if rules.rules.is_empty() {
+            log::info!("No scan rules defined, skipping rule scan");
+            finish();
+            return;
+        }
+
+        let tmp_dir = match crate::Config::new() {
+            Ok(config) => config.tmp_dir.to_string_lossy().to_string(),
+            Err(e) => {
+                log::error!("Config not available for rule scan: {}", e);
+                finish();
+                return;
+            }
+        };
+
+        let finished = crate::calc_scanprovider::scan_package_apks(
+            &installed_packages,
+            &device_serial,
+            pull_missing,
+            &progress_clone,
+            &cancelled_clone,
+            |package, path, sha256| {
+                if pull_missing {
+                    patterns_for_apk(&device_serial, &package.pkg, path, sha256, &rules, &tmp_dir)
+                        .map_err(|e| log::warn!("Rule scan failed for {}: {}", package.pkg, e))
+                        .ok()
+                } else {
+                    get_cached_patterns(sha256, &rules)
+                }
+            },
+            |package, results| {
+                // Conditions are evaluated over the patterns found in all APKs of the package
+                let mut package_found: FoundPatterns = vec![BTreeMap::new(); rules.rules.len()];
+                for (path, found) in results {
+                    merge_found_patterns(&mut package_found, found, path);
+                }
+                let mut package_matches = evaluate_rules(&rules, &package_found);
+                package_matches.sort_by(|a, b| a.rule.cmp(&b.rule));
+                store.set_rule_match_result(package.pkg.clone(), package_matches);
+            },
+        );
+        if !finished {
+            log::info!("Rule scan cancelled by user");
+        }
+
+        finish();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RULES: &str = r#"
+rules:
+  - name: C2_Domain
+    description: Hard-coded C2 host
+    files: ["classes*.dex"]
+    strings:
+      - id: $domain
+        text: EVIL.example.com
+        nocase: true
+  - name: Dropper
+    strings:
+      - id: $magic
+        hex: "DE AD ?? EF"
+      - id: $payload
+        file_name: "assets/*.bin"
+      - regex: "gate[0-9]+\\.php"
+    condition: 2 of them
+  - name: Wide_Marker
+    files: ["resources.arsc"]
+    strings:
+      - text: marker
+        wide: true
+    condition: all of them
+"#;
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = parse_rules(SAMPLE_RULES).unwrap();
+        assert_eq!(rules.rules.len(), 3);
+        assert_eq!(rules.rules[0].condition, RuleCondition::Any);
+        assert_eq!(rules.rules[1].condition, RuleCondition::AtLeast(2));
+        assert_eq!(rules.rules[1].patterns[2].id, "$3");
+        assert_eq!(rules.rules[2].condition, RuleCondition::All);
+        assert!(!rules.content_md5.is_empty());
+
+        assert_eq!(parse_hex_pattern("6a40??00").unwrap(), vec![Some(0x6a), Some(0x40), None, Some(0x00)]);
+        assert!(parse_hex_pattern("6A 4").is_err());
+        assert!(parse_hex_pattern("?? ??").is_err());
+
+        assert!(parse_rules("rules:\n  - name: Empty\n    strings: []\n").is_err());
+        assert!(parse_rules("rules:\n  - name: Both\n    strings:\n      - text: a\n        hex: \"61\"\n").is_err());
+        assert!(parse_rules("rules:\n  - name: Many\n    strings:\n      - text: a\n    condition: 2 of them\n").is_err());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("classes*.dex", "classes.dex"));
+        assert!(glob_match("classes*.dex", "classes12.dex"));
+        assert!(glob_match("lib/*.so", "lib/arm64-v8a/libfoo.so"));
+        assert!(glob_match("lib/?86/*", "lib/x86/libbar.so"));
+        assert!(!glob_match("classes*.dex", "assets/classes.dex.bak"));
+        assert!(!glob_match("assets/*.bin", "assets/payload.txt"));
+    }
+
+    #[test]
+    fn test_match_rules() {
+        let rules = parse_rules(SAMPLE_RULES).unwrap();
+        let wide: Vec<u8> = "marker".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let entries = vec![
+            ("classes.dex".to_string(), b"dex\n...https://evil.EXAMPLE.com/api".to_vec()),
+            ("assets/evil.example.com.txt".to_string(), b"evil.example.com".to_vec()),
+            ("lib/arm64-v8a/libx.so".to_string(), vec![0x00, 0xDE, 0xAD, 0x42, 0xEF, 0x00]),
+            ("assets/stage2.bin".to_string(), Vec::new()),
+            ("resources.arsc".to_string(), wide),
+        ];
+        let found = match_rules(&entries, &rules);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].rule, "C2_Domain");
+        assert_eq!(found[0].files, vec!["classes.dex".to_string()]);
+        assert_eq!(found[1].rule, "Dropper");
+        assert_eq!(found[1].patterns, vec!["$magic".to_string(), "$payload".to_string()]);
+        assert_eq!(
+            found[1].files,
+            vec!["assets/stage2.bin".to_string(), "lib/arm64-v8a/libx.so".to_string()]
+        );
+        assert_eq!(found[2].rule, "Wide_Marker");
+
+        let clean = vec![("classes.dex".to_string(), b"dex\nhello".to_vec())];
+        assert!(match_rules(&clean, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_condition_across_split_apks() {
+        let rules = parse_rules(SAMPLE_RULES).unwrap();
+        let scan = |entries: &[(&str, Vec<u8>)]| {
+            let mut found: FoundPatterns = vec![BTreeMap::new(); rules.rules.len()];
+            for (name, data) in entries {
+                scan_entry(&rules, name, Some(data), &mut found);
+            }
+            found
+        };
+        // Dropper needs 2 of its patterns; each split APK only has one
+        let base = scan(&[("lib/arm64-v8a/libx.so", vec![0xDE, 0xAD, 0x42, 0xEF])]);
+        let split = scan(&[("assets/stage2.bin", Vec::new())]);
+        assert!(evaluate_rules(&rules, &base).is_empty());
+        assert!(evaluate_rules(&rules, &split).is_empty());
+
+        let mut package: FoundPatterns = vec![BTreeMap::new(); rules.rules.len()];
+        merge_found_patterns(&mut package, base, "/data/app/com.example/base.apk");
+        merge_found_patterns(&mut package, split, "/data/app/com.example/split_config.apk");
+        let found = evaluate_rules(&rules, &package);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].rule, "Dropper");
+        assert_eq!(
+            found[0].files,
+            vec![
+                "base.apk!lib/arm64-v8a/libx.so".to_string(),
+                "split_config.apk!assets/stage2.bin".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// Pattern of a rule as written in the rules file.
+/// Exactly one of `text`, `hex`, `regex` or `file_name` is set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RulePatternDef {
+    /// Identifier shown in matches ($a, $c2_domain, ...); defaults to the pattern position
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Hex bytes with `??` wildcards ("6A 40 ?? 00")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// Glob matched against APK entry names (`*` any run of characters, `?` one character)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// Case-insensitive `text` and `regex`
+    #[serde(default)]
+    pub nocase: bool,
+    /// Also look for `text` encoded as UTF-16LE
+    #[serde(default)]
+    pub wide: bool,
+}
+
+/// Rule as written in the rules file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleDef {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Entry name globs the content patterns are searched in; all entries when empty
+    #[serde(default)]
+    pub files: Vec<String>,
+    pub strings: Vec<RulePatternDef>,
+    /// `any of them`, `all of them` or `N of them`
+    #[serde(default = "default_condition")]
+    pub condition: String,
+}
+
+fn default_condition() -> String {
+    "any of them".to_string()
+}
+
+/// Rules file contents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RulesFile {
+    #[serde(default)]
+    pub rules: Vec<RuleDef>,
+}
+
+/// How many patterns of a rule must be found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleCondition {
+    Any,
+    All,
+    AtLeast(usize),
+}
+
+/// Compiled pattern
+#[derive(Debug, Clone)]
+pub enum RulePattern {
+    /// Literal byte strings, any of which may match (plain and UTF-16LE for `wide`)
+    Bytes { alternatives: Vec<Vec<u8>>, nocase: bool },
+    /// Hex bytes, `None` is a `??` wildcard
+    Hex(Vec<Option<u8>>),
+    Regex(regex::bytes::Regex),
+    FileName(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledPattern {
+    pub id: String,
+    pub pattern: RulePattern,
+}
+
+/// Compiled rule ready to be evaluated against APK entries
+#[derive(Debug, Clone)]
+pub struct ScanRule {
+    pub name: String,
+    pub description: String,
+    pub files: Vec<String>,
+    pub patterns: Vec<CompiledPattern>,
+    pub condition: RuleCondition,
+}
+
+/// Loaded rule set
+#[derive(Debug, Clone, Default)]
+pub struct ScanRules {
+    pub rules: Vec<ScanRule>,
+    /// MD5 of the rules file, used to invalidate cached scan results
+    pub content_md5: String,
+}
+
+/// Rule that matched an APK
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub description: String,
+    /// Identifiers of the patterns that were found
+    pub patterns: Vec<String>,
+    /// APK entries the patterns were found in
+    pub files: Vec<String>,
+}
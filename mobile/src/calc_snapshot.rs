@@ -0,0 +1,194 @@
+pub use crate::calc_snapshot_stt::*;
+use crate::shared_store_stt::get_shared_store;
+use crate::uad_shizuku_app::UadNgLists;
+use std::path::Path;
+
+/// UAD-NG lists snapshot bundled at build time
+pub const EMBEDDED_UAD_LISTS: &str = include_str!("../resources/snapshots/uad_lists.json");
+/// Stalkerware IoC snapshot bundled at build time
+pub const EMBEDDED_STALKERWARE_IOC: &str = include_str!("../resources/snapshots/stalkerware_ioc.yaml");
+/// Exodus tracker signature snapshot bundled at build time
+pub const EMBEDDED_EXODUS_TRACKERS: &str = include_str!("../resources/snapshots/exodus_trackers.json");
+/// Date of the bundled snapshots (YYYY-MM-DD), empty when none was bundled
+pub const EMBEDDED_SNAPSHOT_DATE: &str = env!("UAD_SNAPSHOT_DATE");
+
+/// Parse the bundled snapshot date into a Unix timestamp
+pub fn embedded_snapshot_timestamp() -> Option<i64> {
+    parse_snapshot_date(EMBEDDED_SNAPSHOT_DATE)
+}
+
+fn parse_snapshot_date(date: &str) -> Option<i64> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc().timestamp())
+}
+
+fn embedded_content(kind: DatasetKind) -> &'static str {
+    match kind {
+        DatasetKind::UadLists => EMBEDDED_UAD_LISTS,
+        DatasetKind::StalkerwareIoc => EMBEDDED_STALKERWARE_IOC,
+        DatasetKind::ExodusTrackers => EMBEDDED_EXODUS_TRACKERS,
+    }
+}
+
+fn file_modified_timestamp(path: &Path) -> Option<i64> {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
+
+/// Pick the newer of the cached file and the bundled snapshot.
+/// The cache wins ties so a fresh download is never replaced by the snapshot.
+pub fn choose_dataset_source(cached_at: Option<i64>, embedded_at: Option<i64>) -> Option<DatasetSource> {
+    match (cached_at, embedded_at) {
+        (Some(cached), Some(embedded)) if embedded > cached => Some(DatasetSource::Embedded),
+        (Some(_), _) => Some(DatasetSource::Cache),
+        (None, Some(_)) => Some(DatasetSource::Embedded),
+        (None, None) => None,
+    }
+}
+
+/// Read the preferred copy of a dataset from the cache directory or the bundled snapshot
+pub fn load_dataset(kind: DatasetKind, cache_dir: &Path) -> Option<(String, DatasetStatus)> {
+    let cache_file_path = cache_dir.join(kind.cache_filename());
+    let cached_at = file_modified_timestamp(&cache_file_path);
+    let embedded_at = embedded_snapshot_timestamp();
+
+    match choose_dataset_source(cached_at, embedded_at)? {
+        DatasetSource::Cache => match std::fs::read_to_string(&cache_file_path) {
+            Ok(content) => Some((
+                content,
+                DatasetStatus {
+                    source: DatasetSource::Cache,
+                    updated_at: cached_at.unwrap_or_default(),
+                },
+            )),
+            Err(e) => {
+                log::error!("Failed to read {:?} from cache: {}", cache_file_path, e);
+                embedded_at.map(|updated_at| {
+                    (
+                        embedded_content(kind).to_string(),
+                        DatasetStatus { source: DatasetSource::Embedded, updated_at },
+                    )
+                })
+            }
+        },
+        DatasetSource::Embedded => {
+            log::info!(
+                "Using bundled {} snapshot from {}",
+                kind.cache_filename(),
+                EMBEDDED_SNAPSHOT_DATE
+            );
+            Some((
+                embedded_content(kind).to_string(),
+                DatasetStatus {
+                    source: DatasetSource::Embedded,
+                    updated_at: embedded_at.unwrap_or_default(),
+                },
+            ))
+        }
+    }
+}
+
+/// Parse UAD-NG lists content and publish it to the shared store.
+/// Returns the number of apps in the lists.
+pub fn apply_uad_lists(json_content: &str, status: DatasetStatus) -> Result<usize, String> {
+    let uad_lists = serde_json::from_str::<UadNgLists>(json_content)
+        .map_err(|e| format!("Failed to parse UAD lists JSON: {}", e))?;
+    let count = uad_lists.apps.len();
+    log::info!("Successfully parsed UAD lists with {} apps", count);
+
+    // Keep every distinct version so list changes can be diffed
+    match crate::db_uad_lists::insert_uad_lists_version_if_changed(json_content, count as i32) {
+        Ok(true) => log::info!("Stored new UAD lists version"),
+        Ok(false) => log::debug!("UAD lists unchanged since last stored version"),
+        Err(e) => log::error!("Failed to store UAD lists version: {}", e),
+    }
+
+    let shared_store = get_shared_store();
+    shared_store.set_uad_lists_diff(crate::calc_uadlists::load_latest_uad_lists_diff());
+    shared_store.set_uad_ng_lists(Some(uad_lists));
+    shared_store.set_uad_lists_status(Some(status));
+
+    Ok(count)
+}
+
+/// Parse stalkerware IoC content and publish it to the shared store.
+/// Returns the number of package indicators.
+pub fn apply_stalkerware_ioc(yaml_content: &str, status: DatasetStatus) -> Result<usize, String> {
+    let indicators = crate::calc_stalkerware::parse_stalkerware_yaml(yaml_content)?;
+    let count = indicators.package_names.len();
+    log::info!("Successfully parsed stalkerware IoC");
+
+    let shared_store = get_shared_store();
+    shared_store.set_stalkerware_indicators(Some(indicators));
+    shared_store.set_stalkerware_status(Some(status));
+
+    Ok(count)
+}
+
+/// Parse an Exodus tracker signature list and publish it to the shared store.
+/// Returns the number of trackers.
+pub fn apply_tracker_signatures(json_content: &str, status: DatasetStatus) -> Result<usize, String> {
+    let signatures = crate::calc_trackers::parse_tracker_signatures(json_content)?;
+    let count = signatures.trackers.len();
+    log::info!("Successfully parsed {} tracker signatures", count);
+
+    let shared_store = get_shared_store();
+    shared_store.set_tracker_signatures(Some(signatures));
+    shared_store.set_tracker_signatures_status(Some(status));
+
+    Ok(count)
+}
+
+/// Import a dataset file from disk: validate it, load it and store it in the cache
+/// so it is preferred over older downloads and the bundled snapshot.
+/// Returns the number of entries loaded.
+pub fn import_dataset(kind: DatasetKind, source_path: &Path, cache_dir: &Path) -> Result<usize, String> {
+    let content = std::fs::read_to_string(source_path)
+        .map_err(|e| format!("Failed to read {:?}: {}", source_path, e))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let status = DatasetStatus {
+        source: DatasetSource::Cache,
+        updated_at: now,
+    };
+
+    let count = match kind {
+        DatasetKind::UadLists => apply_uad_lists(&content, status)?,
+        DatasetKind::StalkerwareIoc => apply_stalkerware_ioc(&content, status)?,
+        DatasetKind::ExodusTrackers => apply_tracker_signatures(&content, status)?,
+    };
+
+    let cache_file_path = cache_dir.join(kind.cache_filename());
+    std::fs::write(&cache_file_path, &content)
+        .map_err(|e| format!("Failed to write {:?}: {}", cache_file_path, e))?;
+
+    log::info!("Imported {:?} from {:?} ({} entries)", kind, source_path, count);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_dataset_source() {
+        assert_eq!(choose_dataset_source(None, None), None);
+        assert_eq!(choose_dataset_source(Some(100), None), Some(DatasetSource::Cache));
+        assert_eq!(choose_dataset_source(None, Some(100)), Some(DatasetSource::Embedded));
+        assert_eq!(choose_dataset_source(Some(200), Some(100)), Some(DatasetSource::Cache));
+        assert_eq!(choose_dataset_source(Some(100), Some(200)), Some(DatasetSource::Embedded));
+        assert_eq!(choose_dataset_source(Some(100), Some(100)), Some(DatasetSource::Cache));
+    }
+
+    #[test]
+    fn test_parse_snapshot_date() {
+        assert_eq!(parse_snapshot_date("2026-02-01"), Some(1769904000));
+        assert_eq!(parse_snapshot_date("2026-02-01\n"), Some(1769904000));
+        assert_eq!(parse_snapshot_date(""), None);
+    }
+}
@@ -0,0 +1,35 @@
+/// Dataset that can be downloaded, bundled into the binary, or imported from disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetKind {
+    UadLists,
+    StalkerwareIoc,
+    ExodusTrackers,
+}
+
+impl DatasetKind {
+    /// File name used in the cache directory
+    pub fn cache_filename(&self) -> &'static str {
+        match self {
+            DatasetKind::UadLists => "uad_lists.json",
+            DatasetKind::StalkerwareIoc => "stalkerware_ioc.yaml",
+            DatasetKind::ExodusTrackers => "exodus_trackers.json",
+        }
+    }
+}
+
+/// Where the currently loaded dataset came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetSource {
+    /// Downloaded (or imported) file in the cache directory
+    Cache,
+    /// Snapshot embedded at build time
+    Embedded,
+}
+
+/// Origin and age of the currently loaded dataset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatasetStatus {
+    pub source: DatasetSource,
+    /// Cache file modification time or snapshot date (Unix timestamp)
+    pub updated_at: i64,
+}
@@ -16,7 +16,7 @@ pub struct StalkerwareEntry {
     /// Package names associated with this stalkerware
     #[serde(default)]
     pub packages: Vec<String>,
-    /// Optional: Certificate fingerprints (for future use)
+    /// Optional: Signing certificate fingerprints (SHA-1 or SHA-256 hex)
     #[serde(default)]
     pub certificates: Vec<String>,
     /// Optional: Associated websites (for future use)
@@ -31,12 +31,26 @@ pub fn parse_stalkerware_yaml(yaml_content: &str) -> Result<StalkerwareIndicator
 
     let mut package_names = HashSet::new();
     let mut package_to_family = HashMap::new();
+    let mut certificate_to_family = HashMap::new();
+    let mut name_to_family = HashMap::new();
 
     for entry in entries {
+        for name in std::iter::once(&entry.name).chain(entry.names.iter()) {
+            let normalized = normalize_name(name);
+            if !normalized.is_empty() {
+                name_to_family.insert(normalized, entry.name.clone());
+            }
+        }
         for package in entry.packages {
             package_names.insert(package.clone());
             package_to_family.insert(package, entry.name.clone());
         }
+        for certificate in entry.certificates {
+            let fingerprint = crate::calc_apksig::normalize_fingerprint(&certificate);
+            if fingerprint.len() == 40 || fingerprint.len() == 64 {
+                certificate_to_family.insert(fingerprint, entry.name.clone());
+            }
+        }
     }
 
     let now = std::time::SystemTime::now()
@@ -45,20 +59,324 @@ pub fn parse_stalkerware_yaml(yaml_content: &str) -> Result<StalkerwareIndicator
         .as_secs() as i64;
 
     log::info!(
-        "Parsed stalkerware IoC with {} package indicators",
-        package_names.len()
+        "Parsed stalkerware IoC with {} package indicators and {} certificate indicators",
+        package_names.len(),
+        certificate_to_family.len()
     );
 
     Ok(StalkerwareIndicators {
         package_names,
         package_to_family,
+        certificate_to_family,
+        name_to_family,
         last_updated: now,
     })
 }
 
+/// Parse a colon-separated component list from a secure setting
+/// (e.g. `enabled_accessibility_services`) into `pkg/class` entries
+pub fn parse_component_setting(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .split(':')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty() && *s != "null" && s.contains('/'))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Extract active device admin components from `dumpsys device_policy`.
+/// Handles both `ComponentInfo{pkg/class}` and `pkg/class:` section headers.
+pub fn parse_device_admins(dump: &str) -> Vec<String> {
+    let mut admins: Vec<String> = Vec::new();
+    for line in dump.lines() {
+        let trimmed = line.trim();
+        let mut rest = trimmed;
+        while let Some(start) = rest.find("ComponentInfo{") {
+            let after = &rest[start + "ComponentInfo{".len()..];
+            let Some(end) = after.find('}') else { break };
+            admins.push(after[..end].to_string());
+            rest = &after[end..];
+        }
+        if let Some(component) = trimmed.strip_suffix(':') {
+            if component.contains('/') && !component.contains(char::is_whitespace) {
+                admins.push(component.to_string());
+            }
+        }
+    }
+    admins.sort();
+    admins.dedup();
+    admins
+}
+
+/// Extract package names from `cmd package query-activities` output
+/// (either `--brief` component lines or `packageName=` fields)
+pub fn parse_launcher_packages(output: &str) -> HashSet<String> {
+    let mut packages = HashSet::new();
+    for token in output.split_whitespace() {
+        if let Some(pkg) = token.strip_prefix("packageName=") {
+            packages.insert(pkg.to_string());
+        } else if !token.contains('=') {
+            if let Some((pkg, class)) = token.split_once('/') {
+                if !pkg.is_empty() && !class.is_empty() && pkg.contains('.') {
+                    packages.insert(pkg.to_string());
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// Package name part of a `pkg/class` component
+fn component_package(component: &str) -> &str {
+    component.split('/').next().unwrap_or(component)
+}
+
+/// Collect accessibility, device admin, notification listener and launcher state from a device
+pub fn collect_device_signals(device: &str) -> DeviceSurveillanceSignals {
+    let secure_components = |key: &str| {
+        crate::adb::get_secure_setting(device, key)
+            .map(|v| parse_component_setting(&v))
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to read secure setting {}: {}", key, e);
+                Vec::new()
+            })
+    };
+
+    DeviceSurveillanceSignals {
+        accessibility_services: secure_components("enabled_accessibility_services"),
+        notification_listeners: secure_components("enabled_notification_listeners"),
+        device_admins: crate::adb::get_device_policy_dump(device)
+            .map(|d| parse_device_admins(&d))
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to read device policy: {}", e);
+                Vec::new()
+            }),
+        launcher_packages: crate::adb::query_launcher_activities(device)
+            .map(|o| parse_launcher_packages(&o))
+            .ok()
+            .filter(|p| !p.is_empty()),
+    }
+}
+
+/// Assess a single package. IoC matches apply to every package; label and capability
+/// heuristics only to user-installed apps, where they are meaningful.
+fn assess_package(
+    package: &crate::adb::PackageFingerprint,
+    indicators: &StalkerwareIndicators,
+    certificates: Option<&[crate::calc_apksig_stt::SigningCertificate]>,
+    label: Option<&String>,
+    signals: &DeviceSurveillanceSignals,
+) -> Option<StalkerwareFinding> {
+    let pkg_id = package.pkg.as_str();
+    let is_system = package.flags.contains("SYSTEM");
+    let mut evidence = Vec::new();
+
+    if let Some(family) = indicators.get_family_name(pkg_id) {
+        evidence.push(StalkerwareEvidence::PackageName { family: family.clone() });
+    }
+    if let Some(family) = certificates.and_then(|c| indicators.get_family_by_certificates(c)) {
+        evidence.push(StalkerwareEvidence::Certificate { family: family.clone() });
+    }
+    if let Some(label) = label.filter(|_| !is_system) {
+        if let Some(family) = indicators.get_family_by_label(label) {
+            evidence.push(StalkerwareEvidence::LabelMatch {
+                family: family.clone(),
+                label: label.clone(),
+            });
+        }
+    }
+
+    let owned = |components: &[String]| -> Vec<String> {
+        components
+            .iter()
+            .filter(|c| component_package(c) == pkg_id)
+            .cloned()
+            .collect()
+    };
+    let accessibility = owned(&signals.accessibility_services);
+    let admins = owned(&signals.device_admins);
+    let listeners = owned(&signals.notification_listeners);
+    let capability_kinds = [!accessibility.is_empty(), !admins.is_empty(), !listeners.is_empty()]
+        .iter()
+        .filter(|&&b| b)
+        .count();
+    let hidden_icon = !is_system
+        && signals
+            .launcher_packages
+            .as_ref()
+            .is_some_and(|launchers| !launchers.contains(pkg_id));
+
+    evidence.extend(accessibility.into_iter().map(|component| StalkerwareEvidence::AccessibilityService { component }));
+    evidence.extend(admins.into_iter().map(|component| StalkerwareEvidence::DeviceAdmin { component }));
+    evidence.extend(listeners.into_iter().map(|component| StalkerwareEvidence::NotificationListener { component }));
+    if hidden_icon {
+        evidence.push(StalkerwareEvidence::HiddenLauncherIcon);
+    }
+
+    let ioc_match = evidence.iter().any(|e| {
+        matches!(e, StalkerwareEvidence::PackageName { .. } | StalkerwareEvidence::Certificate { .. })
+    });
+    let label_match = evidence.iter().any(|e| matches!(e, StalkerwareEvidence::LabelMatch { .. }));
+    let has_signal = capability_kinds > 0 || hidden_icon;
+
+    let confidence = if ioc_match || (label_match && has_signal) {
+        StalkerwareConfidence::High
+    } else if label_match {
+        StalkerwareConfidence::Medium
+    } else if is_system {
+        return None;
+    } else if capability_kinds >= 2 || (capability_kinds == 1 && hidden_icon) {
+        StalkerwareConfidence::Medium
+    } else if capability_kinds == 1 {
+        StalkerwareConfidence::Low
+    } else {
+        return None;
+    };
+
+    let family = evidence.iter().find_map(|e| match e {
+        StalkerwareEvidence::PackageName { family }
+        | StalkerwareEvidence::Certificate { family }
+        | StalkerwareEvidence::LabelMatch { family, .. } => Some(family.clone()),
+        _ => None,
+    });
+
+    Some(StalkerwareFinding {
+        pkg_id: pkg_id.to_string(),
+        label: label.cloned(),
+        family,
+        is_system,
+        confidence,
+        evidence,
+    })
+}
+
+/// Combine IoC matches and device capability signals into a stalkerware report
+pub fn build_stalkerware_report(
+    device_serial: &str,
+    packages: &[crate::adb::PackageFingerprint],
+    indicators: &StalkerwareIndicators,
+    certificates: &HashMap<String, Vec<crate::calc_apksig_stt::SigningCertificate>>,
+    labels: &HashMap<String, String>,
+    signals: &DeviceSurveillanceSignals,
+) -> StalkerwareReport {
+    let mut findings: Vec<StalkerwareFinding> = packages
+        .iter()
+        .filter_map(|package| {
+            assess_package(
+                package,
+                indicators,
+                certificates.get(&package.pkg).map(|c| c.as_slice()),
+                labels.get(&package.pkg),
+                signals,
+            )
+        })
+        .collect();
+    findings.sort_by(|a, b| b.confidence.cmp(&a.confidence).then_with(|| a.pkg_id.cmp(&b.pkg_id)));
+
+    StalkerwareReport {
+        device_serial: device_serial.to_string(),
+        generated_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+        indicators_updated_at: indicators.last_updated,
+        scanned_packages: packages.len(),
+        findings,
+    }
+}
+
+/// Best known display label for each package: on-device label first, then store titles
+pub fn collect_package_labels(packages: &[crate::adb::PackageFingerprint]) -> HashMap<String, String> {
+    let store = crate::shared_store_stt::get_shared_store();
+    packages
+        .iter()
+        .filter_map(|p| {
+            let label = store
+                .get_cached_android_package_app(&p.pkg)
+                .map(|a| a.label)
+                .or_else(|| store.get_cached_google_play_app(&p.pkg).map(|a| a.title))
+                .or_else(|| store.get_cached_fdroid_app(&p.pkg).map(|a| a.title))
+                .or_else(|| store.get_cached_apkmirror_app(&p.pkg).map(|a| a.title))
+                .filter(|l| !l.trim().is_empty())?;
+            Some((p.pkg.clone(), label))
+        })
+        .collect()
+}
+
+/// Generate the stalkerware report for a device in a background thread
+pub fn run_stalkerware_report_async(
+    device: String,
+    result: std::sync::Arc<std::sync::Mutex<Option<Result<StalkerwareReport, String>>>>,
+) {
+    std::thread::spawn(move || {
+        let store = crate::shared_store_stt::get_shared_store();
+        let packages = store.get_installed_packages();
+        let report = if packages.is_empty() {
+            Err("No installed packages loaded for this device".to_string())
+        } else {
+            let indicators = store.get_stalkerware_indicators().unwrap_or_default();
+            let certificates = store.get_all_signing_certificates();
+            let labels = collect_package_labels(&packages);
+            let signals = collect_device_signals(&device);
+            let report = build_stalkerware_report(&device, &packages, &indicators, &certificates, &labels, &signals);
+            log::info!(
+                "Stalkerware report for {}: {} findings across {} packages",
+                device,
+                report.findings.len(),
+                report.scanned_packages
+            );
+            Ok(report)
+        };
+        if let Ok(mut r) = result.lock() {
+            *r = Some(report);
+        }
+    });
+}
+
+/// Plain-text rendering of a report, for saving as evidence before any removal
+pub fn format_report_text(report: &StalkerwareReport) -> String {
+    let format_ts = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    };
+    let mut out = format!(
+        "Stalkerware report\nDevice: {}\nGenerated: {}\nIndicators loaded: {}\nPackages scanned: {}\nFindings: {}\n",
+        report.device_serial,
+        format_ts(report.generated_at),
+        format_ts(report.indicators_updated_at),
+        report.scanned_packages,
+        report.findings.len()
+    );
+    for finding in &report.findings {
+        out.push_str(&format!(
+            "\n[{:?}] {}{}{}\n",
+            finding.confidence,
+            finding.pkg_id,
+            finding.label.as_ref().map(|l| format!(" ({})", l)).unwrap_or_default(),
+            finding.family.as_ref().map(|f| format!(" - family: {}", f)).unwrap_or_default()
+        ));
+        for evidence in &finding.evidence {
+            let line = match evidence {
+                StalkerwareEvidence::PackageName { family } => format!("package name listed in IoC ({})", family),
+                StalkerwareEvidence::Certificate { family } => format!("signing certificate listed in IoC ({})", family),
+                StalkerwareEvidence::LabelMatch { family, label } => format!("label \"{}\" matches {}", label, family),
+                StalkerwareEvidence::AccessibilityService { component } => format!("accessibility service: {}", component),
+                StalkerwareEvidence::DeviceAdmin { component } => format!("device admin: {}", component),
+                StalkerwareEvidence::NotificationListener { component } => format!("notification listener: {}", component),
+                StalkerwareEvidence::HiddenLauncherIcon => "no launcher icon".to_string(),
+            };
+            out.push_str(&format!("  - {}\n", line));
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adb::PackageFingerprint;
 
     #[test]
     fn test_parse_stalkerware_yaml() {
@@ -89,6 +407,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_certificate_matching() {
+        let yaml = r#"
+- name: CertStalker
+  packages:
+    - com.cert.stalker
+  certificates:
+    - "AB:CD:EF:01:23:45:67:89:AB:CD:EF:01:23:45:67:89:AB:CD:EF:01"
+    - not-a-fingerprint
+"#;
+
+        let indicators = parse_stalkerware_yaml(yaml).unwrap();
+        assert_eq!(indicators.certificate_to_family.len(), 1);
+
+        let repackaged = vec![crate::calc_apksig_stt::SigningCertificate {
+            schemes: vec![crate::calc_apksig_stt::SignatureScheme::V2],
+            sha1: "abcdef0123456789abcdef0123456789abcdef01".to_string(),
+            sha256: "00".repeat(32),
+            java_hash: "1".to_string(),
+        }];
+        assert_eq!(
+            indicators.get_family_by_certificates(&repackaged),
+            Some(&"CertStalker".to_string())
+        );
+        assert!(indicators.matches("com.renamed.app", Some(&repackaged)));
+        assert!(!indicators.matches("com.renamed.app", None));
+    }
+
+    #[test]
+    fn test_label_matching() {
+        let yaml = r#"
+- name: mSpy
+  names:
+    - "Update Service Pro"
+  packages:
+    - com.mspy.lite
+- name: Spy
+  packages:
+    - com.spy.app
+"#;
+        let indicators = parse_stalkerware_yaml(yaml).unwrap();
+        assert_eq!(indicators.get_family_by_label("mSpy"), Some(&"mSpy".to_string()));
+        assert_eq!(indicators.get_family_by_label("Update-Service PRO"), Some(&"mSpy".to_string()));
+        // Labels only match whole names
+        assert_eq!(indicators.get_family_by_label("My Update Service Pro 2"), None);
+        assert_eq!(indicators.get_family_by_label("Spy"), Some(&"Spy".to_string()));
+        assert_eq!(indicators.get_family_by_label("Spyder IDE"), None);
+    }
+
+    #[test]
+    fn test_parse_device_signals() {
+        assert_eq!(
+            parse_component_setting("com.a/.Svc:com.b/com.b.Other\n"),
+            vec!["com.a/.Svc".to_string(), "com.b/com.b.Other".to_string()]
+        );
+        assert!(parse_component_setting("null").is_empty());
+
+        let dump = "Current Device Policy Manager state:\n  Enabled Device Admins (User 0, provisioningState: 0):\n    com.evil/.AdminReceiver:\n      uid=10123\n    admin=ComponentInfo{com.old/com.old.Admin}\n";
+        assert_eq!(
+            parse_device_admins(dump),
+            vec!["com.evil/.AdminReceiver".to_string(), "com.old/com.old.Admin".to_string()]
+        );
+
+        let launchers = parse_launcher_packages("com.android.settings/.Settings\npriority=0 match=0x108000\npackageName=com.other.app\n");
+        assert!(launchers.contains("com.android.settings"));
+        assert!(launchers.contains("com.other.app"));
+        assert_eq!(launchers.len(), 2);
+    }
+
+    #[test]
+    fn test_build_stalkerware_report() {
+        let yaml = r#"
+- name: KnownFamily
+  names: ["System Sync Helper"]
+  packages:
+    - com.known.stalker
+"#;
+        let indicators = parse_stalkerware_yaml(yaml).unwrap();
+        let packages = vec![
+            PackageFingerprint::fixture("com.known.stalker", "[ HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.renamed.stalker", "[ HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.hidden.watcher", "[ HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.password.manager", "[ HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.android.systemui", "[ SYSTEM HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.plain.app", "[ HAS_CODE ]", ""),
+            PackageFingerprint::fixture("com.vendor.sync", "[ SYSTEM HAS_CODE ]", ""),
+        ];
+        let labels = HashMap::from([
+            ("com.renamed.stalker".to_string(), "System Sync Helper".to_string()),
+            // Label heuristics skip system packages
+            ("com.vendor.sync".to_string(), "System Sync Helper".to_string()),
+        ]);
+        let signals = DeviceSurveillanceSignals {
+            accessibility_services: vec![
+                "com.hidden.watcher/.A11y".to_string(),
+                "com.password.manager/.Autofill".to_string(),
+                "com.android.systemui/.Svc".to_string(),
+            ],
+            device_admins: vec!["com.hidden.watcher/.Admin".to_string()],
+            notification_listeners: vec![],
+            launcher_packages: Some(HashSet::from([
+                "com.renamed.stalker".to_string(),
+                "com.password.manager".to_string(),
+                "com.plain.app".to_string(),
+            ])),
+        };
+
+        let report = build_stalkerware_report("serial", &packages, &indicators, &HashMap::new(), &labels, &signals);
+        let confidence = |pkg: &str| report.findings.iter().find(|f| f.pkg_id == pkg).map(|f| f.confidence);
+
+        assert_eq!(confidence("com.known.stalker"), Some(StalkerwareConfidence::High));
+        assert_eq!(confidence("com.renamed.stalker"), Some(StalkerwareConfidence::Medium));
+        assert_eq!(confidence("com.hidden.watcher"), Some(StalkerwareConfidence::Medium));
+        assert_eq!(confidence("com.password.manager"), Some(StalkerwareConfidence::Low));
+        assert_eq!(confidence("com.android.systemui"), None);
+        assert_eq!(confidence("com.plain.app"), None);
+        assert_eq!(confidence("com.vendor.sync"), None);
+        assert_eq!(report.findings[0].confidence, StalkerwareConfidence::High);
+
+        let watcher = report.findings.iter().find(|f| f.pkg_id == "com.hidden.watcher").unwrap();
+        assert!(watcher.has_device_admin());
+        assert!(watcher.evidence.contains(&StalkerwareEvidence::HiddenLauncherIcon));
+        assert!(format_report_text(&report).contains("device admin: com.hidden.watcher/.Admin"));
+    }
+
     #[test]
     fn test_is_stalkerware() {
         let mut indicators = StalkerwareIndicators::new();
@@ -1,3 +1,5 @@
+use crate::calc_apksig_stt::SigningCertificate;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 /// Stalkerware indicators data structure for package detection
@@ -7,6 +9,10 @@ pub struct StalkerwareIndicators {
     pub package_names: HashSet<String>,
     /// Map package name to stalkerware family name
     pub package_to_family: HashMap<String, String>,
+    /// Map normalized certificate fingerprint (lowercase hex SHA-1 or SHA-256) to family name
+    pub certificate_to_family: HashMap<String, String>,
+    /// Map normalized family name or alias (lowercase alphanumerics) to family name
+    pub name_to_family: HashMap<String, String>,
     /// When the indicators were last updated (Unix timestamp)
     pub last_updated: i64,
 }
@@ -16,6 +22,8 @@ impl StalkerwareIndicators {
         Self {
             package_names: HashSet::new(),
             package_to_family: HashMap::new(),
+            certificate_to_family: HashMap::new(),
+            name_to_family: HashMap::new(),
             last_updated: 0,
         }
     }
@@ -29,6 +37,33 @@ impl StalkerwareIndicators {
     pub fn get_family_name(&self, package_id: &str) -> Option<&String> {
         self.package_to_family.get(package_id)
     }
+
+    /// Get stalkerware family name if any signing certificate is a known stalkerware certificate.
+    /// Catches repackaged stalkerware installed under a new package name.
+    pub fn get_family_by_certificates(&self, certificates: &[SigningCertificate]) -> Option<&String> {
+        certificates.iter().find_map(|cert| {
+            self.certificate_to_family
+                .get(&cert.sha1)
+                .or_else(|| self.certificate_to_family.get(&cert.sha256))
+        })
+    }
+
+    /// Get stalkerware family name if an app label equals a known family name or alias,
+    /// ignoring case and punctuation. Labels that only contain a name do not match,
+    /// so generic aliases ("Update Service") do not flag unrelated apps.
+    pub fn get_family_by_label(&self, label: &str) -> Option<&String> {
+        let normalized = normalize_name(label);
+        if normalized.is_empty() {
+            return None;
+        }
+        self.name_to_family.get(&normalized)
+    }
+
+    /// Check if a package is stalkerware by package name or signing certificate
+    pub fn matches(&self, package_id: &str, certificates: Option<&[SigningCertificate]>) -> bool {
+        self.is_stalkerware(package_id)
+            || certificates.is_some_and(|certs| self.get_family_by_certificates(certs).is_some())
+    }
 }
 
 impl Default for StalkerwareIndicators {
@@ -36,3 +71,96 @@ impl Default for StalkerwareIndicators {
         Self::new()
     }
 }
+
+/// Normalize a family name or app label for comparison (lowercase alphanumerics only)
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// How strongly a finding points to stalkerware
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum StalkerwareConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// Single piece of evidence behind a stalkerware finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StalkerwareEvidence {
+    /// Package name is listed in the IoC database
+    PackageName { family: String },
+    /// Signing certificate is listed in the IoC database
+    Certificate { family: String },
+    /// App label matches a family name or alias
+    LabelMatch { family: String, label: String },
+    /// Enabled accessibility service (can read the screen and input)
+    AccessibilityService { component: String },
+    /// Active device administrator (can resist uninstall, lock or wipe the device)
+    DeviceAdmin { component: String },
+    /// Enabled notification listener (can read incoming messages)
+    NotificationListener { component: String },
+    /// Installed user app without a launcher icon
+    HiddenLauncherIcon,
+}
+
+impl StalkerwareEvidence {
+    /// Whether this evidence comes from the IoC database rather than a capability heuristic
+    pub fn is_indicator(&self) -> bool {
+        matches!(
+            self,
+            StalkerwareEvidence::PackageName { .. }
+                | StalkerwareEvidence::Certificate { .. }
+                | StalkerwareEvidence::LabelMatch { .. }
+        )
+    }
+}
+
+/// Stalkerware assessment for one package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StalkerwareFinding {
+    pub pkg_id: String,
+    pub label: Option<String>,
+    pub family: Option<String>,
+    pub is_system: bool,
+    pub confidence: StalkerwareConfidence,
+    pub evidence: Vec<StalkerwareEvidence>,
+}
+
+impl StalkerwareFinding {
+    /// Device admin must be deactivated before the package can be uninstalled
+    pub fn has_device_admin(&self) -> bool {
+        self.evidence
+            .iter()
+            .any(|e| matches!(e, StalkerwareEvidence::DeviceAdmin { .. }))
+    }
+}
+
+/// Surveillance-relevant device state collected over adb
+#[derive(Debug, Clone, Default)]
+pub struct DeviceSurveillanceSignals {
+    /// Enabled accessibility service components (`pkg/class`)
+    pub accessibility_services: Vec<String>,
+    /// Active device admin components (`pkg/class`)
+    pub device_admins: Vec<String>,
+    /// Enabled notification listener components (`pkg/class`)
+    pub notification_listeners: Vec<String>,
+    /// Packages that expose a launcher activity; `None` if the query failed
+    pub launcher_packages: Option<HashSet<String>>,
+}
+
+/// Per-device stalkerware report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StalkerwareReport {
+    pub device_serial: String,
+    /// When the report was generated (Unix timestamp)
+    pub generated_at: i64,
+    /// When the IoC database was loaded (Unix timestamp)
+    pub indicators_updated_at: i64,
+    pub scanned_packages: usize,
+    /// Findings sorted by confidence, highest first
+    pub findings: Vec<StalkerwareFinding>,
+}
@@ -0,0 +1,400 @@
+pub use crate::calc_trackers_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::shared_store_stt::get_shared_store;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Tracker entry in the Exodus `api/trackers` JSON
+#[derive(Debug, Deserialize)]
+struct ExodusTracker {
+    #[serde(default)]
+    id: i64,
+    name: String,
+    #[serde(default)]
+    code_signature: String,
+    #[serde(default)]
+    website: String,
+    #[serde(default)]
+    categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExodusTrackerList {
+    trackers: HashMap<String, ExodusTracker>,
+}
+
+/// Turn an Exodus `code_signature` regex ("com.foo.|com\\.bar\\.ads") into plain class prefixes
+fn code_signature_prefixes(code_signature: &str) -> Vec<String> {
+    code_signature
+        .split('|')
+        .filter_map(|part| {
+            let unescaped = part.trim().trim_start_matches('^').replace('\\', "");
+            // Cut at the first regex metacharacter; what remains is a literal prefix
+            let literal: String = unescaped
+                .chars()
+                .take_while(|c| !matches!(c, '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '$'))
+                .collect();
+            // Very short prefixes ("a.", "com.") would match nearly every app
+            if literal.len() < 5 {
+                None
+            } else {
+                Some(literal)
+            }
+        })
+        .collect()
+}
+
+/// Parse the Exodus tracker signature JSON
+pub fn parse_tracker_signatures(json_content: &str) -> Result<TrackerSignatures, String> {
+    let list: ExodusTrackerList = serde_json::from_str(json_content)
+        .map_err(|e| format!("Failed to parse tracker signatures: {}", e))?;
+
+    let mut trackers: Vec<TrackerSignature> = list
+        .trackers
+        .into_iter()
+        .map(|(key, t)| TrackerSignature {
+            id: if t.id != 0 { t.id } else { key.parse().unwrap_or(0) },
+            name: t.name,
+            categories: t.categories,
+            website: t.website,
+            code_prefixes: code_signature_prefixes(&t.code_signature),
+        })
+        .filter(|t| !t.code_prefixes.is_empty())
+        .collect();
+    trackers.sort_by_key(|t| t.id);
+
+    Ok(TrackerSignatures {
+        trackers,
+        content_md5: format!("{:x}", md5::compute(json_content.as_bytes())),
+    })
+}
+
+/// `base + index * size`, failing instead of overflowing on values read from the file
+fn table_offset(base: usize, index: usize, size: usize) -> Result<usize, String> {
+    index
+        .checked_mul(size)
+        .and_then(|o| o.checked_add(base))
+        .ok_or_else(|| format!("DEX offset overflow at {} + {} * {}", base, index, size))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset.saturating_add(4))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("Unexpected end of DEX data at offset {}", offset))
+}
+
+fn read_uleb128(data: &[u8], pos: &mut usize) -> Result<u32, String> {
+    let mut result: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *data.get(*pos).ok_or("Unexpected end of DEX string data")?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err("Invalid uleb128 value".to_string())
+}
+
+/// List the classes defined in a DEX file as Java names (com.example.Foo)
+pub fn dex_class_names(data: &[u8]) -> Result<Vec<String>, String> {
+    if data.len() < 0x70 || &data[0..4] != b"dex\n" {
+        return Err("Not a DEX file".to_string());
+    }
+
+    let string_ids_size = read_u32(data, 0x38)? as usize;
+    let string_ids_off = read_u32(data, 0x3C)? as usize;
+    let type_ids_size = read_u32(data, 0x40)? as usize;
+    let type_ids_off = read_u32(data, 0x44)? as usize;
+    let class_defs_size = read_u32(data, 0x60)? as usize;
+    let class_defs_off = read_u32(data, 0x64)? as usize;
+
+    // Each class_def_item is 32 bytes, so a larger count is corrupt (or hostile)
+    if class_defs_size > data.len() / 32 {
+        return Err(format!("Invalid class_defs_size {}", class_defs_size));
+    }
+
+    let mut names = Vec::with_capacity(class_defs_size);
+    for i in 0..class_defs_size {
+        let class_idx = read_u32(data, table_offset(class_defs_off, i, 32)?)? as usize;
+        if class_idx >= type_ids_size {
+            return Err(format!("Invalid class type index {}", class_idx));
+        }
+        let descriptor_idx = read_u32(data, table_offset(type_ids_off, class_idx, 4)?)? as usize;
+        if descriptor_idx >= string_ids_size {
+            return Err(format!("Invalid descriptor string index {}", descriptor_idx));
+        }
+        let mut pos = read_u32(data, table_offset(string_ids_off, descriptor_idx, 4)?)? as usize;
+        let _utf16_size = read_uleb128(data, &mut pos)?;
+        let end = data[pos.min(data.len())..]
+            .iter()
+            .position(|b| *b == 0)
+            .map(|p| pos + p)
+            .ok_or("Unterminated DEX string")?;
+
+        let descriptor = String::from_utf8_lossy(&data[pos..end]);
+        if let Some(class) = descriptor.strip_prefix('L').and_then(|d| d.strip_suffix(';')) {
+            names.push(class.replace('/', "."));
+        }
+    }
+
+    Ok(names)
+}
+
+/// Match class names against the tracker signatures
+pub fn match_trackers(class_names: &[String], signatures: &TrackerSignatures) -> Vec<TrackerMatch> {
+    let mut sorted: Vec<&str> = class_names.iter().map(|s| s.as_str()).collect();
+    sorted.sort_unstable();
+
+    let has_prefix = |prefix: &str| {
+        let idx = sorted.partition_point(|c| *c < prefix);
+        sorted.get(idx).is_some_and(|c| c.starts_with(prefix))
+    };
+
+    signatures
+        .trackers
+        .iter()
+        .filter(|t| t.code_prefixes.iter().any(|p| has_prefix(p)))
+        .map(|t| TrackerMatch {
+            id: t.id,
+            name: t.name.clone(),
+            categories: t.categories.clone(),
+            website: t.website.clone(),
+        })
+        .collect()
+}
+
+/// Scan every classes*.dex in an APK for tracker classes
+pub fn scan_apk_trackers(apk_path: &Path, signatures: &TrackerSignatures) -> Result<Vec<TrackerMatch>, String> {
+    let file = std::fs::File::open(apk_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", apk_path, e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read APK {:?}: {}", apk_path, e))?;
+
+    let dex_names: Vec<String> = archive
+        .file_names()
+        .filter(|n| n.starts_with("classes") && n.ends_with(".dex") && !n.contains('/'))
+        .map(|n| n.to_string())
+        .collect();
+
+    let mut class_names = Vec::new();
+    for dex_name in dex_names {
+        let mut entry = archive
+            .by_name(&dex_name)
+            .map_err(|e| format!("Failed to read {}: {}", dex_name, e))?;
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read {}: {}", dex_name, e))?;
+        match dex_class_names(&data) {
+            Ok(names) => class_names.extend(names),
+            Err(e) => log::warn!("Skipping {} in {:?}: {}", dex_name, apk_path, e),
+        }
+    }
+
+    Ok(match_trackers(&class_names, signatures))
+}
+
+fn get_cached_trackers(apk_sha256sum: &str, signatures: &TrackerSignatures) -> Option<Vec<TrackerMatch>> {
+    let cached = crate::db_package_cache::get_cached_apk_trackers(apk_sha256sum, &signatures.content_md5)?;
+    serde_json::from_str(&cached.trackers_json).ok()
+}
+
+/// Trackers of one APK file, pulling and scanning it only when no cached result
+/// exists for the same sha256sum and signature database
+fn trackers_for_apk(
+    device_serial: &str,
+    pkg_id: &str,
+    apk_path: &str,
+    apk_sha256sum: &str,
+    signatures: &TrackerSignatures,
+    tmp_dir: &str,
+) -> Result<Vec<TrackerMatch>, String> {
+    if let Some(trackers) = get_cached_trackers(apk_sha256sum, signatures) {
+        return Ok(trackers);
+    }
+
+    let local_path = crate::adb::pull_file_to_temp(device_serial, apk_path, tmp_dir, pkg_id)
+        .map_err(|e| format!("Failed to pull {}: {}", apk_path, e))?;
+    let result = scan_apk_trackers(Path::new(&local_path), signatures);
+    let _ = std::fs::remove_file(&local_path);
+    let trackers = result?;
+
+    match serde_json::to_string(&trackers) {
+        Ok(json) => {
+            if let Err(e) = crate::db_package_cache::upsert_apk_trackers_cache(
+                apk_sha256sum,
+                &signatures.content_md5,
+                pkg_id,
+                &json,
+            ) {
+                log::error!("Failed to cache trackers for {}: {}", pkg_id, e);
+            }
+        }
+        Err(e) => log::error!("Failed to serialize trackers for {}: {}", pkg_id, e),
+    }
+
+    Ok(trackers)
+}
+
+fn merge_trackers(target: &mut Vec<TrackerMatch>, found: Vec<TrackerMatch>) {
+    for tracker in found {
+        if !target.iter().any(|t| t.id == tracker.id) {
+            target.push(tracker);
+        }
+    }
+}
+
+/// Scan packages for trackers in a background thread.
+/// With `pull_missing` false only cached results are loaded (no APK is pulled).
+pub fn run_tracker_scan_async(
+    installed_packages: Vec<PackageFingerprint>,
+    device_serial: String,
+    pull_missing: bool,
+    progress_clone: Arc<Mutex<Option<f32>>>,
+    cancelled_clone: Arc<Mutex<bool>>,
+) {
+    std::thread::spawn(move || {
+        let store = get_shared_store();
+        let Some(signatures) = store.get_tracker_signatures() else {
+            log::warn!("Tracker signatures not loaded, skipping tracker scan");
+            if let Ok(mut p) = progress_clone.lock() {
+                *p = None;
+            }
+            return;
+        };
+
+        let tmp_dir = match crate::Config::new() {
+            Ok(config) => config.tmp_dir.to_string_lossy().to_string(),
+            Err(e) => {
+                log::error!("Config not available for tracker scan: {}", e);
+                if let Ok(mut p) = progress_clone.lock() {
+                    *p = None;
+                }
+                return;
+            }
+        };
+
+        let finished = crate::calc_scanprovider::scan_package_apks(
+            &installed_packages,
+            &device_serial,
+            pull_missing,
+            &progress_clone,
+            &cancelled_clone,
+            |package, path, sha256| {
+                if pull_missing {
+                    trackers_for_apk(&device_serial, &package.pkg, path, sha256, &signatures, &tmp_dir)
+                        .map_err(|e| log::warn!("Tracker scan failed for {}: {}", package.pkg, e))
+                        .ok()
+                } else {
+                    get_cached_trackers(sha256, &signatures)
+                }
+            },
+            |package, results| {
+                let mut package_trackers = Vec::new();
+                for (_, trackers) in results {
+                    merge_trackers(&mut package_trackers, trackers);
+                }
+                package_trackers.sort_by(|a, b| a.name.cmp(&b.name));
+                store.set_tracker_result(package.pkg.clone(), package_trackers);
+            },
+        );
+        if !finished {
+            log::info!("Tracker scan cancelled by user");
+        }
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = None;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_SIGNATURES: &str = r#"{
+        "trackers": {
+            "1": {"id": 1, "name": "Google AdMob", "code_signature": "com.google.android.gms.ads.|com.google.ads.", "website": "https://admob.google.com", "categories": ["Advertisement"]},
+            "2": {"id": 2, "name": "Facebook Analytics", "code_signature": "com\\.facebook\\.appevents", "website": "", "categories": ["Analytics"]},
+            "3": {"id": 3, "name": "Too Broad", "code_signature": "com.", "website": "", "categories": []}
+        }
+    }"#;
+
+    /// Build a minimal DEX file defining the given type descriptors
+    fn build_dex(descriptors: &[&str]) -> Vec<u8> {
+        let n = descriptors.len();
+        let string_ids_off = 0x70;
+        let type_ids_off = string_ids_off + n * 4;
+        let class_defs_off = type_ids_off + n * 4;
+        let data_off = class_defs_off + n * 32;
+
+        let mut data = vec![0u8; data_off];
+        data[0..8].copy_from_slice(b"dex\n035\0");
+        let put = |data: &mut Vec<u8>, off: usize, v: u32| data[off..off + 4].copy_from_slice(&v.to_le_bytes());
+        put(&mut data, 0x38, n as u32);
+        put(&mut data, 0x3C, string_ids_off as u32);
+        put(&mut data, 0x40, n as u32);
+        put(&mut data, 0x44, type_ids_off as u32);
+        put(&mut data, 0x60, n as u32);
+        put(&mut data, 0x64, class_defs_off as u32);
+
+        for (i, d) in descriptors.iter().enumerate() {
+            let off = data.len();
+            data.push(d.len() as u8); // uleb128 utf16 size
+            data.extend_from_slice(d.as_bytes());
+            data.push(0);
+            put(&mut data, string_ids_off + i * 4, off as u32);
+            put(&mut data, type_ids_off + i * 4, i as u32);
+            put(&mut data, class_defs_off + i * 32, i as u32);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_tracker_signatures() {
+        let signatures = parse_tracker_signatures(SAMPLE_SIGNATURES).unwrap();
+        assert_eq!(signatures.trackers.len(), 2);
+        assert_eq!(
+            signatures.trackers[0].code_prefixes,
+            vec!["com.google.android.gms.ads.".to_string(), "com.google.ads.".to_string()]
+        );
+        assert_eq!(signatures.trackers[1].code_prefixes, vec!["com.facebook.appevents".to_string()]);
+        assert!(!signatures.content_md5.is_empty());
+    }
+
+    #[test]
+    fn test_dex_class_names() {
+        let dex = build_dex(&["Lcom/example/Main;", "Lcom/google/ads/AdView;"]);
+        assert_eq!(
+            dex_class_names(&dex).unwrap(),
+            vec!["com.example.Main".to_string(), "com.google.ads.AdView".to_string()]
+        );
+        assert!(dex_class_names(b"PK\x03\x04").is_err());
+
+        // Hostile header sizes and offsets fail instead of allocating or overflowing
+        let mut hostile = dex.clone();
+        hostile[0x60..0x64].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(dex_class_names(&hostile).is_err());
+        let mut hostile = dex;
+        hostile[0x64..0x68].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(dex_class_names(&hostile).is_err());
+    }
+
+    #[test]
+    fn test_match_trackers() {
+        let signatures = parse_tracker_signatures(SAMPLE_SIGNATURES).unwrap();
+        let classes = vec![
+            "com.example.Main".to_string(),
+            "com.google.ads.AdView".to_string(),
+            "com.facebook.login.LoginManager".to_string(),
+        ];
+        let found = match_trackers(&classes, &signatures);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "Google AdMob");
+        assert_eq!(found[0].categories, vec!["Advertisement".to_string()]);
+    }
+}
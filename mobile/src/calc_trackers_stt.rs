@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Tracker definition from the Exodus signature database
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerSignature {
+    pub id: i64,
+    pub name: String,
+    pub categories: Vec<String>,
+    pub website: String,
+    /// Java class name prefixes derived from the Exodus `code_signature`
+    pub code_prefixes: Vec<String>,
+}
+
+/// Loaded tracker signature database
+#[derive(Debug, Clone, Default)]
+pub struct TrackerSignatures {
+    pub trackers: Vec<TrackerSignature>,
+    /// MD5 of the signature file, used to invalidate cached scan results
+    pub content_md5: String,
+}
+
+/// Tracker found in a package's DEX files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackerMatch {
+    pub id: i64,
+    pub name: String,
+    pub categories: Vec<String>,
+    pub website: String,
+}
@@ -0,0 +1,206 @@
+pub use crate::calc_uadlists_stt::*;
+use crate::adb::PackageFingerprint;
+use crate::models::UadPackageAction;
+use crate::uad_shizuku_app::UadNgLists;
+use std::collections::HashSet;
+
+/// Compare two versions of the UAD-NG lists
+pub fn diff_uad_ng_lists(old: &UadNgLists, new: &UadNgLists) -> UadListsDiff {
+    let mut diff = UadListsDiff::default();
+
+    for (pkg_id, entry) in &new.apps {
+        match old.apps.get(pkg_id) {
+            None => diff.added.push(UadListsAddedEntry {
+                pkg_id: pkg_id.clone(),
+                list: entry.list.clone(),
+                removal: entry.removal.clone(),
+                description: entry.description.clone(),
+            }),
+            Some(old_entry) if old_entry.removal != entry.removal => {
+                diff.removal_changed.push(UadRemovalChange {
+                    pkg_id: pkg_id.clone(),
+                    old_removal: old_entry.removal.clone(),
+                    new_removal: entry.removal.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    for pkg_id in old.apps.keys() {
+        if !new.apps.contains_key(pkg_id) {
+            diff.removed.push(pkg_id.clone());
+        }
+    }
+
+    diff.added.sort_by(|a, b| a.pkg_id.cmp(&b.pkg_id));
+    diff.removed.sort();
+    diff.removal_changed.sort_by(|a, b| a.pkg_id.cmp(&b.pkg_id));
+
+    diff
+}
+
+/// Build the diff between the two most recently stored list versions
+pub fn load_latest_uad_lists_diff() -> Option<UadListsDiff> {
+    let versions = crate::db_uad_lists::get_latest_uad_lists_versions(2);
+    let (newer, older) = match versions.as_slice() {
+        [newer, older] => (newer, older),
+        _ => return None,
+    };
+
+    let parse = |raw: &str| match serde_json::from_str::<UadNgLists>(raw) {
+        Ok(lists) => Some(lists),
+        Err(e) => {
+            log::error!("Failed to parse stored UAD lists version: {}", e);
+            None
+        }
+    };
+
+    let mut diff = diff_uad_ng_lists(&parse(&older.raw_json)?, &parse(&newer.raw_json)?);
+    diff.old_fetched_at = older.fetched_at as i64;
+    diff.new_fetched_at = newer.fetched_at as i64;
+
+    log::info!(
+        "UAD lists changelog: {} added, {} removed, {} category changes",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.removal_changed.len()
+    );
+
+    Some(diff)
+}
+
+/// Find packages on the device whose removal category differs from the one
+/// recorded when the user last uninstalled, disabled or enabled them
+pub fn flag_changed_since_action(
+    actions: &[UadPackageAction],
+    lists: &UadNgLists,
+    installed_packages: &[PackageFingerprint],
+) -> Vec<UadActionFlag> {
+    let on_device: HashSet<&str> = installed_packages.iter().map(|p| p.pkg.as_str()).collect();
+
+    let mut flags: Vec<UadActionFlag> = actions
+        .iter()
+        .filter(|action| on_device.contains(action.pkg_id.as_str()))
+        .filter_map(|action| {
+            let current_removal = lists
+                .apps
+                .get(&action.pkg_id)
+                .map(|entry| entry.removal.clone())
+                .unwrap_or_else(|| "Unknown".to_string());
+            if current_removal == action.removal {
+                return None;
+            }
+            Some(UadActionFlag {
+                pkg_id: action.pkg_id.clone(),
+                action: action.action.clone(),
+                removal_at_action: action.removal.clone(),
+                current_removal,
+                acted_at: action.acted_at as i64,
+            })
+        })
+        .collect();
+
+    flags.sort_by(|a, b| a.pkg_id.cmp(&b.pkg_id));
+    flags
+}
+
+/// Remember the removal category a package had when the user acted on it
+pub fn record_package_action(pkg_id: &str, device_serial: &str, action: &str) {
+    let removal = crate::shared_store_stt::get_shared_store()
+        .get_uad_ng_lists()
+        .and_then(|lists| lists.apps.get(pkg_id).map(|entry| entry.removal.clone()))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    if let Err(e) =
+        crate::db_uad_lists::upsert_uad_package_action(pkg_id, device_serial, action, &removal)
+    {
+        log::error!("Failed to record {} action for {}: {}", action, pkg_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uad_shizuku_app::AppEntry;
+    use std::collections::HashMap;
+
+    fn entry(removal: &str) -> AppEntry {
+        AppEntry {
+            list: "Oem".to_string(),
+            description: String::new(),
+            dependencies: Vec::new(),
+            needed_by: Vec::new(),
+            labels: Vec::new(),
+            removal: removal.to_string(),
+        }
+    }
+
+    fn lists(entries: &[(&str, &str)]) -> UadNgLists {
+        let apps: HashMap<String, AppEntry> = entries
+            .iter()
+            .map(|(pkg, removal)| (pkg.to_string(), entry(removal)))
+            .collect();
+        UadNgLists { apps }
+    }
+
+    fn action(pkg: &str, removal: &str) -> UadPackageAction {
+        UadPackageAction {
+            id: 0,
+            pkg_id: pkg.to_string(),
+            device_serial: "dev".to_string(),
+            action: "disable".to_string(),
+            removal: removal.to_string(),
+            acted_at: 100,
+        }
+    }
+
+    #[test]
+    fn test_diff_uad_ng_lists() {
+        let old = lists(&[("com.a", "Recommended"), ("com.b", "Advanced"), ("com.gone", "Expert")]);
+        let new = lists(&[("com.a", "Expert"), ("com.b", "Advanced"), ("com.new", "Recommended")]);
+
+        let diff = diff_uad_ng_lists(&old, &new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].pkg_id, "com.new");
+        assert_eq!(diff.removed, vec!["com.gone".to_string()]);
+        assert_eq!(
+            diff.removal_changed,
+            vec![UadRemovalChange {
+                pkg_id: "com.a".to_string(),
+                old_removal: "Recommended".to_string(),
+                new_removal: "Expert".to_string(),
+            }]
+        );
+        assert_eq!(diff.change_count(), 3);
+    }
+
+    #[test]
+    fn test_diff_identical_lists_is_empty() {
+        let old = lists(&[("com.a", "Recommended")]);
+        assert!(diff_uad_ng_lists(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_flag_changed_since_action() {
+        let current = lists(&[("com.a", "Expert"), ("com.b", "Advanced")]);
+        let actions = vec![
+            action("com.a", "Recommended"),
+            action("com.b", "Advanced"),
+            action("com.c", "Recommended"),
+            action("com.not.on.device", "Recommended"),
+        ];
+        let installed: Vec<PackageFingerprint> = ["com.a", "com.b", "com.c"]
+            .iter()
+            .map(|pkg| PackageFingerprint::fixture(pkg, "", ""))
+            .collect();
+
+        let flags = flag_changed_since_action(&actions, &current, &installed);
+        assert_eq!(flags.len(), 2);
+        assert_eq!(flags[0].pkg_id, "com.a");
+        assert_eq!(flags[0].removal_at_action, "Recommended");
+        assert_eq!(flags[0].current_removal, "Expert");
+        assert_eq!(flags[1].pkg_id, "com.c");
+        assert_eq!(flags[1].current_removal, "Unknown");
+    }
+}
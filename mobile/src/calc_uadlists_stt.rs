@@ -0,0 +1,52 @@
+/// Package newly added to the UAD-NG lists
+#[derive(Debug, Clone, PartialEq)]
+pub struct UadListsAddedEntry {
+    pub pkg_id: String,
+    pub list: String,
+    pub removal: String,
+    pub description: String,
+}
+
+/// Package whose `removal` category changed between two list versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct UadRemovalChange {
+    pub pkg_id: String,
+    pub old_removal: String,
+    pub new_removal: String,
+}
+
+/// Difference between two downloaded versions of the UAD-NG lists
+#[derive(Debug, Clone, Default)]
+pub struct UadListsDiff {
+    /// When the older version was fetched (Unix timestamp)
+    pub old_fetched_at: i64,
+    /// When the newer version was fetched (Unix timestamp)
+    pub new_fetched_at: i64,
+    pub added: Vec<UadListsAddedEntry>,
+    pub removed: Vec<String>,
+    pub removal_changed: Vec<UadRemovalChange>,
+}
+
+impl UadListsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.removal_changed.is_empty()
+    }
+
+    pub fn change_count(&self) -> usize {
+        self.added.len() + self.removed.len() + self.removal_changed.len()
+    }
+}
+
+/// Installed package whose recommendation changed since the user last acted on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct UadActionFlag {
+    pub pkg_id: String,
+    /// Last action taken ("uninstall", "disable" or "enable")
+    pub action: String,
+    /// Removal category at the time of the action
+    pub removal_at_action: String,
+    /// Removal category in the current lists
+    pub current_removal: String,
+    /// When the action was taken (Unix timestamp)
+    pub acted_at: i64,
+}
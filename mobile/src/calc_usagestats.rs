@@ -0,0 +1,374 @@
+pub use crate::calc_usagestats_stt::*;
+use crate::adb_stt::PackageFingerprint;
+use crate::uad_shizuku_app::UadNgLists;
+use std::collections::{HashMap, HashSet};
+
+/// Timestamps before this are placeholders for "never" (the epoch, printed as 1970-01-01)
+const MIN_VALID_TIMESTAMP: i64 = 365 * 24 * 3600;
+
+/// Interval of a section header ("In-memory daily stats", "Weekly stats:")
+fn section_interval(line: &str) -> Option<UsageInterval> {
+    let lower = line.trim().trim_end_matches(':').to_lowercase();
+    if line.contains('=') || !lower.ends_with("stats") {
+        return None;
+    }
+    [
+        ("daily", UsageInterval::Daily),
+        ("weekly", UsageInterval::Weekly),
+        ("monthly", UsageInterval::Monthly),
+        ("yearly", UsageInterval::Yearly),
+    ]
+    .into_iter()
+    .find(|(name, _)| lower.contains(name))
+    .map(|(_, interval)| interval)
+}
+
+/// Split `key=value key="quoted value"` pairs
+fn parse_pairs(line: &str) -> Vec<(&str, &str)> {
+    let mut pairs = Vec::new();
+    let mut rest = line.trim();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let after = &rest[eq + 1..];
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => match after.find(' ') {
+                Some(end) => (&after[..end], &after[end..]),
+                None => (after, ""),
+            },
+        };
+        pairs.push((key, value));
+        rest = remaining.trim_start();
+    }
+    pairs
+}
+
+/// Duration in seconds from "H:MM:SS", "MM:SS" or a plain millisecond count (compact dumps)
+pub fn parse_duration_secs(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if !value.contains(':') {
+        return value.parse::<u64>().ok().map(|ms| ms / 1000);
+    }
+    value
+        .split(':')
+        .try_fold(0u64, |total, part| part.trim().parse::<u64>().ok().map(|n| total * 60 + n))
+}
+
+/// Offset in seconds from a `date +%z` value ("+0900", "-0330")
+pub fn parse_utc_offset(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Unix timestamp from a device local "2024-05-01 10:00:00" (shifted by the device's
+/// `utc_offset` in seconds) or a millisecond count; `None` for never
+pub fn parse_timestamp(value: &str, utc_offset: i64) -> Option<i64> {
+    let value = value.trim();
+    let timestamp = match value.parse::<i64>() {
+        Ok(ms) => ms / 1000,
+        Err(_) => {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()?
+                .and_utc()
+                .timestamp()
+                - utc_offset
+        }
+    };
+    (timestamp >= MIN_VALID_TIMESTAMP).then_some(timestamp)
+}
+
+/// Parse `dumpsys usagestats` output into per-package usage.
+///
+/// Package lines ("package=com.example totalTimeUsed="01:02:03" lastTimeUsed="2024-05-01 10:00:00"
+/// appLaunchCount=3") are grouped by the interval section they appear in. The first section of
+/// each interval is the current one and gives the foreground times; later sections (history)
+/// only contribute to the last use. Each `user=` block starts over, so the current sections
+/// of every user count. Local times are converted with the device's `utc_offset` in seconds.
+pub fn parse_usage_stats(output: &str, utc_offset: i64) -> HashMap<String, PackageUsage> {
+    let mut usage: HashMap<String, PackageUsage> = HashMap::new();
+    let mut seen_intervals: HashSet<UsageInterval> = HashSet::new();
+    // Interval of the current section, and whether it is the first one of that interval
+    let mut section: Option<(UsageInterval, bool)> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("user=") {
+            seen_intervals.clear();
+            section = None;
+            continue;
+        }
+        if let Some(interval) = section_interval(trimmed) {
+            section = Some((interval, seen_intervals.insert(interval)));
+            continue;
+        }
+        if !trimmed.starts_with("package=") {
+            continue;
+        }
+        let Some((interval, current)) = section else {
+            continue;
+        };
+
+        let pairs = parse_pairs(trimmed);
+        let value = |key: &str| pairs.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        let Some(pkg_id) = value("package") else {
+            continue;
+        };
+        let entry = usage.entry(pkg_id.to_string()).or_insert_with(|| PackageUsage {
+            pkg_id: pkg_id.to_string(),
+            ..Default::default()
+        });
+
+        if let Some(last_used) = value("lastTimeUsed").and_then(|v| parse_timestamp(v, utc_offset)) {
+            entry.last_used = Some(entry.last_used.map_or(last_used, |t| t.max(last_used)));
+        }
+        if !current {
+            continue;
+        }
+        // Summed across users, since a package can be used in more than one profile
+        let foreground = value("totalTimeUsed").and_then(parse_duration_secs).unwrap_or(0);
+        match interval {
+            UsageInterval::Daily => entry.foreground_day += foreground,
+            UsageInterval::Weekly => entry.foreground_week += foreground,
+            UsageInterval::Monthly => {
+                entry.foreground_month += foreground;
+                entry.launch_count += value("appLaunchCount").and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+            }
+            UsageInterval::Yearly => {}
+        }
+    }
+
+    usage
+}
+
+/// Whether a package was not used within `days` of `now`. Packages missing from
+/// the usage stats count as unused.
+pub fn is_unused_for(usage: Option<&PackageUsage>, now: i64, days: i64) -> bool {
+    let Some(usage) = usage else {
+        return true;
+    };
+    let recently_used = usage.last_used.is_some_and(|t| t >= now - days * 24 * 3600);
+    !recently_used && usage.foreground_month == 0
+}
+
+/// Whether an unused package may be offered as a debloat selection: system packages
+/// and packages UAD lists as Unsafe are left for the user to pick by hand.
+pub fn is_selection_candidate(package: &PackageFingerprint, removal: Option<&str>) -> bool {
+    !package.flags.contains("SYSTEM") && removal != Some("Unsafe")
+}
+
+/// Usage table rows for the installed packages; packages missing from the stats were never used
+pub fn build_usage_rows(
+    usage: &HashMap<String, PackageUsage>,
+    installed: &[PackageFingerprint],
+    lists: Option<&UadNgLists>,
+    now: i64,
+    unused_days: i64,
+) -> Vec<UsageRow> {
+    installed
+        .iter()
+        .map(|p| {
+            let package_usage = usage.get(&p.pkg);
+            let unused = is_unused_for(package_usage, now, unused_days);
+            let removal = lists.and_then(|l| l.apps.get(&p.pkg)).map(|app| app.removal.as_str());
+            UsageRow {
+                usage: package_usage.cloned().unwrap_or_else(|| PackageUsage {
+                    pkg_id: p.pkg.clone(),
+                    ..Default::default()
+                }),
+                unused,
+                candidate: unused && is_selection_candidate(p, removal),
+            }
+        })
+        .collect()
+}
+
+/// Short human readable duration ("2h 05m", "12m", "40s")
+pub fn format_duration(secs: u64) -> String {
+    match secs {
+        0 => "-".to_string(),
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h {:02}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+/// Read and parse the usage stats of a device
+pub fn load_usage_stats(device: &str) -> Result<HashMap<String, PackageUsage>, String> {
+    let output = crate::adb::usagestats_history(device).map_err(|e| e.to_string().trim().to_string())?;
+    let utc_offset = match crate::adb::get_utc_offset(device) {
+        Ok(value) => parse_utc_offset(&value).unwrap_or_else(|| {
+            log::warn!("Unexpected device UTC offset {:?}, reading usage times as UTC", value);
+            0
+        }),
+        Err(e) => {
+            log::warn!("Failed to read device UTC offset, reading usage times as UTC: {}", e);
+            0
+        }
+    };
+    Ok(parse_usage_stats(&output, utc_offset))
+}
+
+/// Load the usage stats in a background thread
+pub fn run_usage_stats_load_async(device: String, result: UsageStatsResult) {
+    std::thread::spawn(move || {
+        let stats = load_usage_stats(&device);
+        match &stats {
+            Ok(stats) => log::info!("Loaded usage stats of {} packages", stats.len()),
+            Err(e) => log::error!("Failed to read usage stats: {}", e),
+        }
+        if let Ok(mut r) = result.lock() {
+            *r = Some(stats);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "user=0\n\
+        \x20 Last 24 hour events (timeRange=\"May 1, 2024 10:00 - May 2, 2024 10:00\")\n\
+        \x20   time=\"2024-05-02 09:00:00\" type=ACTIVITY_RESUMED package=com.example.chat\n\
+        \x20 In-memory daily stats\n\
+        \x20   timeRange=\"May 2, 2024 00:00 - 10:00\"\n\
+        \x20   packages\n\
+        \x20     package=com.example.chat totalTimeUsed=\"12:30\" lastTimeUsed=\"2024-05-02 09:00:00\" appLaunchCount=2\n\
+        \x20 In-memory weekly stats\n\
+        \x20   packages\n\
+        \x20     package=com.example.chat totalTimeUsed=\"1:02:03\" lastTimeUsed=\"2024-05-02 09:00:00\" appLaunchCount=9\n\
+        \x20     package=com.example.news totalTimeUsed=\"00:00\" lastTimeUsed=\"1970-01-01 00:00:00\" appLaunchCount=0\n\
+        \x20 In-memory monthly stats\n\
+        \x20   packages\n\
+        \x20     package=com.example.chat totalTimeUsed=\"5:00:00\" lastTimeUsed=\"2024-05-02 09:00:00\" appLaunchCount=40\n\
+        \x20     package=com.example.news totalTimeUsed=\"00:00\" lastTimeUsed=\"1970-01-01 00:00:00\" appLaunchCount=0\n\
+        \x20 Yearly stats:\n\
+        \x20   packages\n\
+        \x20     package=com.example.news totalTimeUsed=\"2:00\" lastTimeUsed=\"2023-11-20 18:00:00\" appLaunchCount=1\n";
+
+    #[test]
+    fn test_parse_usage_stats() {
+        let usage = parse_usage_stats(DUMP, 0);
+        assert_eq!(usage.len(), 2);
+
+        let chat = &usage["com.example.chat"];
+        assert_eq!(chat.foreground_day, 12 * 60 + 30);
+        assert_eq!(chat.foreground_week, 3723);
+        assert_eq!(chat.foreground_month, 5 * 3600);
+        assert_eq!(chat.launch_count, 40);
+        assert_eq!(chat.last_used, parse_timestamp("2024-05-02 09:00:00", 0));
+
+        let news = &usage["com.example.news"];
+        assert_eq!(news.foreground_month, 0);
+        assert_eq!(news.last_used, parse_timestamp("2023-11-20 18:00:00", 0));
+    }
+
+    #[test]
+    fn test_is_unused_for() {
+        let usage = parse_usage_stats(DUMP, 0);
+        let now = parse_timestamp("2024-05-10 00:00:00", 0).unwrap();
+        assert!(!is_unused_for(usage.get("com.example.chat"), now, 30));
+        assert!(is_unused_for(usage.get("com.example.news"), now, 30));
+        assert!(is_unused_for(None, now, 30));
+    }
+
+    #[test]
+    fn test_build_usage_rows() {
+        use crate::uad_shizuku_app::AppEntry;
+        let usage = parse_usage_stats(DUMP, 0);
+        let now = parse_timestamp("2024-05-10 00:00:00", 0).unwrap();
+        let installed = vec![
+            PackageFingerprint::fixture("com.example.chat", "", ""),
+            PackageFingerprint::fixture("com.example.news", "", ""),
+            PackageFingerprint::fixture("com.vendor.unused", "SYSTEM HAS_CODE", ""),
+            PackageFingerprint::fixture("com.example.keyboard", "", ""),
+        ];
+        let entry = |removal: &str| AppEntry {
+            list: "Misc".to_string(),
+            description: String::new(),
+            dependencies: Vec::new(),
+            needed_by: Vec::new(),
+            labels: Vec::new(),
+            removal: removal.to_string(),
+        };
+        let lists = UadNgLists {
+            apps: [
+                ("com.example.news".to_string(), entry("Recommended")),
+                ("com.example.keyboard".to_string(), entry("Unsafe")),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let rows = build_usage_rows(&usage, &installed, Some(&lists), now, 30);
+        let flags: Vec<(&str, bool, bool)> =
+            rows.iter().map(|r| (r.usage.pkg_id.as_str(), r.unused, r.candidate)).collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("com.example.chat", false, false),
+                ("com.example.news", true, true),
+                ("com.vendor.unused", true, false),
+                ("com.example.keyboard", true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_and_timestamp() {
+        assert_eq!(parse_duration_secs("01:02:03"), Some(3723));
+        assert_eq!(parse_duration_secs("05:03"), Some(303));
+        assert_eq!(parse_duration_secs("90500"), Some(90));
+        assert_eq!(parse_timestamp("1970-01-01 00:00:00", 0), None);
+        assert_eq!(parse_timestamp("1714554000000", 0), Some(1714554000));
+        assert_eq!(format_duration(3723), "1h 02m");
+    }
+
+    #[test]
+    fn test_device_local_times() {
+        assert_eq!(parse_utc_offset("+0900"), Some(9 * 3600));
+        assert_eq!(parse_utc_offset("-0330"), Some(-(3 * 3600 + 30 * 60)));
+        assert_eq!(parse_utc_offset("KST"), None);
+
+        // 09:00 in UTC+9 is midnight UTC; millisecond counts are already UTC
+        assert_eq!(parse_timestamp("2024-05-02 09:00:00", 9 * 3600), parse_timestamp("2024-05-02 00:00:00", 0));
+        assert_eq!(parse_timestamp("1714554000000", 9 * 3600), Some(1714554000));
+
+        let usage = parse_usage_stats(DUMP, 9 * 3600);
+        assert_eq!(usage["com.example.chat"].last_used, parse_timestamp("2024-05-02 00:00:00", 0));
+    }
+
+    #[test]
+    fn test_parse_usage_stats_per_user() {
+        let dump = format!(
+            "{}user=10\n\
+            \x20 In-memory daily stats\n\
+            \x20   packages\n\
+            \x20     package=com.example.chat totalTimeUsed=\"02:00\" lastTimeUsed=\"2024-05-03 08:00:00\" appLaunchCount=1\n\
+            \x20 In-memory monthly stats\n\
+            \x20   packages\n\
+            \x20     package=com.example.work totalTimeUsed=\"30:00\" lastTimeUsed=\"2024-05-03 08:00:00\" appLaunchCount=3\n",
+            DUMP
+        );
+        let usage = parse_usage_stats(&dump, 0);
+
+        // The sections of user 10 are current ones, not history of user 0
+        let chat = &usage["com.example.chat"];
+        assert_eq!(chat.foreground_day, 12 * 60 + 30 + 2 * 60);
+        assert_eq!(chat.last_used, parse_timestamp("2024-05-03 08:00:00", 0));
+        let work = &usage["com.example.work"];
+        assert_eq!(work.foreground_month, 30 * 60);
+        assert_eq!(work.launch_count, 3);
+    }
+}
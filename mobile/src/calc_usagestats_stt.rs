@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Aggregation interval of `dumpsys usagestats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageInterval {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Usage of one package, aggregated from `dumpsys usagestats`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageUsage {
+    pub pkg_id: String,
+    /// Last use as a unix timestamp; `None` when never used
+    pub last_used: Option<i64>,
+    /// Foreground seconds in the current daily, weekly and monthly buckets: today,
+    /// this week and this month so far, not rolling 1/7/30 day windows
+    pub foreground_day: u64,
+    pub foreground_week: u64,
+    pub foreground_month: u64,
+    /// Launches in the current monthly interval
+    pub launch_count: u64,
+}
+
+/// Row of the usage table, built once per loaded report
+#[derive(Debug, Clone)]
+pub struct UsageRow {
+    pub usage: PackageUsage,
+    /// Not used within the unused threshold
+    pub unused: bool,
+    /// Unused and safe to offer as a debloat selection
+    pub candidate: bool,
+}
+
+/// Parsed usage stats per package; `Err` holds the adb error
+pub type UsageStatsResult = Arc<Mutex<Option<Result<HashMap<String, PackageUsage>, String>>>>;
@@ -1,16 +1,19 @@
-use crate::adb;
-use crate::api_virustotal::{self, VtError};
+use crate::api_virustotal::{self, VirusTotalResponse, VtError};
+use crate::calc_scanprovider::{
+    self, CachedReport, FileOutcome, FreshnessPolicy, NormalizedResult, PollOutcome, PollPolicy,
+    ProviderError, QuotaBudget, RateLimitPolicy, RateLimiter, RequestGate, ScanProvider, ScanTarget,
+    Verdict,
+};
 use crate::db;
+use crate::db_hash_allowlist;
 use crate::db_virustotal;
 use crate::is_valid_package_id;
 use crate::models::VirusTotalResult;
-use crate::Config;
 use egui_i18n::tr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 pub use crate::calc_virustotal_stt::*;
 
@@ -18,27 +21,75 @@ impl FileScanResult {
     pub fn total(&self) -> i32 {
         self.malicious + self.suspicious + self.undetected + self.harmless
     }
+
+    /// Empty result for a file without a report
+    fn without_report(file_path: &str, sha256: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            sha256: sha256.to_string(),
+            malicious: 0,
+            suspicious: 0,
+            undetected: 0,
+            harmless: 0,
+            dex_count: None,
+            reputation: 0,
+            vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
+            not_found: false,
+            skipped: false,
+            trusted: false,
+            error: None,
+            previous_detections: None,
+        }
+    }
+
+    /// Whether detections differ from the previous lookup of this file
+    pub fn detections_changed(&self) -> bool {
+        self.previous_detections
+            .is_some_and(|previous| previous != (self.malicious, self.suspicious))
+    }
+
+    fn from_response(file_path: &str, sha256: &str, response: &VirusTotalResponse) -> Self {
+        let attributes = &response.data.attributes;
+        let stats = attributes.last_analysis_stats.as_ref();
+        let dex_count = attributes
+            .androguard
+            .as_ref()
+            .and_then(|a| a.risk_indicator.as_ref())
+            .and_then(|r| r.apk.as_ref())
+            .and_then(|a| a.dex);
+
+        Self {
+            malicious: stats.map_or(0, |s| s.malicious),
+            suspicious: stats.map_or(0, |s| s.suspicious),
+            undetected: stats.map_or(0, |s| s.undetected),
+            harmless: stats.map_or(0, |s| s.harmless),
+            dex_count,
+            reputation: attributes.reputation,
+            ..Self::without_report(file_path, sha256)
+        }
+    }
+
+    fn from_db_result(file_path: &str, r: &VirusTotalResult) -> Self {
+        Self {
+            malicious: r.malicious,
+            suspicious: r.suspicious,
+            undetected: r.undetected,
+            harmless: r.harmless,
+            dex_count: r.dex_count,
+            reputation: r.reputation,
+            not_found: r.raw_response.contains("404 Not Found"),
+            previous_detections: r.previous_malicious.zip(r.previous_suspicious),
+            ..Self::without_report(file_path, &r.sha256)
+        }
+    }
 }
 
 impl CalcVirustotal {
     pub fn from_db_results(db_results: Vec<VirusTotalResult>) -> Self {
         let files_attempted = db_results.len();
         let file_results = db_results
-            .into_iter()
-            .map(|r| FileScanResult {
-                file_path: r.file_path.clone(),
-                sha256: r.sha256.clone(),
-                malicious: r.malicious,
-                suspicious: r.suspicious,
-                undetected: r.undetected,
-                harmless: r.harmless,
-                dex_count: r.dex_count,
-                reputation: r.reputation,
-                vt_link: format!("https://www.virustotal.com/gui/file/{}", r.sha256),
-                not_found: r.raw_response.contains("404 Not Found"),
-                skipped: false,
-                error: None,
-            })
+            .iter()
+            .map(|r| FileScanResult::from_db_result(&r.file_path, r))
             .collect();
 
         Self {
@@ -49,159 +100,304 @@ impl CalcVirustotal {
     }
 }
 
-impl RateLimiter {
-    pub fn new(max_requests: usize, time_window: Duration, min_interval: Duration) -> Self {
-        Self {
-            request_times: Vec::new(),
-            max_requests,
-            time_window,
-            min_interval,
-            rate_limit_until: None,
+impl From<VtError> for ProviderError {
+    fn from(err: VtError) -> Self {
+        match err {
+            VtError::NotFound => ProviderError::NotFound,
+            VtError::RateLimit { retry_after } => ProviderError::RateLimit { retry_after },
+            e => ProviderError::Other(e.to_string()),
         }
     }
+}
 
-    /// Wait if necessary to respect rate limits, then record the request
-    /// Returns duration waited, or None if no wait was needed
-    fn check_wait_needed(&mut self) -> Option<Duration> {
-        let now = Instant::now();
-        let mut wait_duration = Duration::ZERO;
+/// Sort rank of an engine category: detections first
+fn engine_category_rank(category: &str) -> u8 {
+    match category {
+        "malicious" => 0,
+        "suspicious" => 1,
+        "harmless" | "undetected" => 2,
+        _ => 3,
+    }
+}
 
-        // First check if we're in a global rate limit period (from 429 error)
-        if let Some(until) = self.rate_limit_until {
-            if now < until {
-                wait_duration = until.duration_since(now);
-                log::info!("Global rate limit active, waiting {:?}", wait_duration);
-                return Some(wait_duration);
-            } else {
-                // Rate limit period has passed, clear it
-                self.rate_limit_until = None;
+/// Parse engine verdicts, threat names and rule hits from a stored file report.
+/// Returns None for cached 404s and responses that cannot be parsed.
+pub fn parse_report_details(raw_response: &str) -> Option<VtReportDetails> {
+    let response: VirusTotalResponse = serde_json::from_str(raw_response).ok()?;
+    let attributes = response.data.attributes;
+    if attributes.status.as_deref() == Some("404 Not Found") {
+        return None;
+    }
+
+    let mut engines: Vec<api_virustotal::EngineResult> = attributes
+        .last_analysis_results
+        .into_iter()
+        .map(|(name, mut engine)| {
+            if engine.engine_name.is_empty() {
+                engine.engine_name = name;
             }
-        }
+            engine
+        })
+        .collect();
+    engines.sort_by(|a, b| {
+        engine_category_rank(&a.category)
+            .cmp(&engine_category_rank(&b.category))
+            .then_with(|| a.engine_name.to_lowercase().cmp(&b.engine_name.to_lowercase()))
+    });
 
-        // Remove requests outside the time window
-        self.request_times
-            .retain(|&time| now.duration_since(time) < self.time_window);
+    let (suggested_threat_label, mut popular_threat_names) = match attributes.popular_threat_classification {
+        Some(c) => (
+            c.suggested_threat_label,
+            c.popular_threat_name.into_iter().map(|t| (t.value, t.count)).collect(),
+        ),
+        None => (None, Vec::new()),
+    };
+    popular_threat_names.sort_by(|a: &(String, u32), b| b.1.cmp(&a.1));
+
+    Some(VtReportDetails {
+        engines,
+        suggested_threat_label,
+        popular_threat_names,
+        yara_hits: attributes.crowdsourced_yara_results,
+        sigma_hits: attributes.sigma_analysis_results,
+        first_submission_date: attributes.first_submission_date,
+        names: attributes.names,
+    })
+}
 
-        // Check minimum interval from last request
-        if let Some(&last) = self.request_times.last() {
-            let since_last = now.duration_since(last);
-            if since_last < self.min_interval {
-                let interval_wait = self.min_interval - since_last;
-                if interval_wait > wait_duration {
-                    wait_duration = interval_wait;
-                }
-            }
-        }
+/// Load the report details of a file from the cached raw response (no API call)
+pub fn load_report_details(sha256: &str) -> Option<VtReportDetails> {
+    let mut conn = db::establish_connection();
+    let cached = db_virustotal::get_result_by_sha256(&mut conn, sha256).ok()??;
+    parse_report_details(&cached.raw_response)
+}
 
-        // If we've hit the limit, wait until the oldest request expires
-        if self.request_times.len() >= self.max_requests {
-            if let Some(&oldest) = self.request_times.first() {
-                let limit_wait = self.time_window.saturating_sub(now.duration_since(oldest));
-                if limit_wait > wait_duration {
-                    wait_duration = limit_wait;
-                }
-            }
+/// Request limits of the free public API key
+pub const PUBLIC_REQUESTS_PER_MINUTE: u32 = 4;
+pub const PUBLIC_REQUESTS_PER_DAY: u32 = 500;
+pub const PUBLIC_REQUESTS_PER_MONTH: u32 = 15_500;
+
+/// VirusTotal adapter for the scan pipeline in `calc_scanprovider`
+pub struct VirusTotalProvider {
+    pub api_key: String,
+    pub freshness: FreshnessPolicy,
+    /// Per-minute request limit of the API key's tier
+    pub requests_per_minute: u32,
+    /// Daily/monthly request budget of the API key's tier
+    pub budget: QuotaBudget,
+}
+
+impl VirusTotalProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            freshness: FreshnessPolicy::default(),
+            requests_per_minute: PUBLIC_REQUESTS_PER_MINUTE,
+            budget: QuotaBudget {
+                per_day: PUBLIC_REQUESTS_PER_DAY,
+                per_month: PUBLIC_REQUESTS_PER_MONTH,
+            },
         }
+    }
 
-        if wait_duration > Duration::ZERO {
-            Some(wait_duration)
+    /// Rate limiter for this provider, counting requests against the persisted quota
+    pub fn rate_limiter(&self) -> SharedRateLimiter {
+        Arc::new(Mutex::new(
+            RateLimiter::new(self.rate_limit_policy()).with_quota(self.name(), self.budget),
+        ))
+    }
+
+    /// Whether a cached result is due for a re-query under the freshness policy
+    fn is_stale(&self, r: &VirusTotalResult) -> bool {
+        // 404s carry no analysis date; age them from when they were recorded
+        let analyzed_at = if r.raw_response.contains("404 Not Found") {
+            r.updated_at
         } else {
-            None
+            r.last_analysis_date
+        };
+        self.freshness.is_stale(
+            i64::from(analyzed_at),
+            i64::from(r.updated_at),
+            r.malicious > 0 || r.suspicious > 0,
+            calc_scanprovider::unix_now() as i64,
+        )
+    }
+
+    /// Whether any cached file of a package is due for a re-query
+    pub fn has_stale_results(&self, package_name: &str) -> bool {
+        let mut conn = db::establish_connection();
+        db_virustotal::get_results_by_package(&mut conn, package_name)
+            .map(|results| results.iter().any(|r| self.is_stale(r)))
+            .unwrap_or(false)
+    }
+
+    fn store(&self, target: &ScanTarget, response: VirusTotalResponse) {
+        // Save to database via queue
+        if let Err(e) = db_virustotal::queue_upsert(
+            target.package_name.clone(),
+            target.file_path.clone(),
+            target.sha256.clone(),
+            response,
+        ) {
+            log::error!("Failed to cache VirusTotal result for {}: {}", target.sha256, e);
         }
     }
+}
+
+impl ScanProvider for VirusTotalProvider {
+    type Report = FileScanResult;
 
-    fn record_request(&mut self) {
-        self.request_times.push(Instant::now());
-        // Remove oldest if we exceed max_requests (though logic above should handle it, this keeps vector size bounded if logic changes)
-        if self.request_times.len() > self.max_requests * 2 {
-            self.request_times.remove(0);
+    fn name(&self) -> &'static str {
+        "VirusTotal"
+    }
+
+    /// Public API: 4 requests per minute
+    fn rate_limit_policy(&self) -> RateLimitPolicy {
+        let per_minute = self.requests_per_minute.max(1);
+        RateLimitPolicy {
+            windows: vec![(per_minute as usize, Duration::from_secs(60))],
+            // Spread requests out, but never below what the tier allows per minute
+            min_interval: Duration::from_secs(5).min(Duration::from_millis(60_000 / u64::from(per_minute))),
+            submit_backoff: None,
+            wait_counts_against_timeout: false,
         }
     }
 
-    /// Wait if necessary to respect rate limits, then record the request
-    #[allow(dead_code)]
-    fn wait_if_needed(&mut self) {
-        if let Some(duration) = self.check_wait_needed() {
-            log::debug!("Sleeping for {:?}", duration);
-            thread::sleep(duration);
+    fn poll_policy(&self) -> PollPolicy {
+        PollPolicy {
+            interval: Duration::from_secs(10),
+            max_attempts: None,
+            defer_unfinished: false,
+            file_timeout: Duration::from_secs(60),
         }
+    }
 
-        // Record this request
-        self.record_request();
+    fn cached_report(&self, target: &ScanTarget) -> Option<CachedReport<FileScanResult>> {
+        let mut conn = db::establish_connection();
+        let cached = db_virustotal::get_result_by_sha256(&mut conn, &target.sha256).ok().flatten();
+        // A local detection always wins over the allowlist
+        let flagged = cached.as_ref().is_some_and(|c| c.malicious > 0 || c.suspicious > 0);
+        if !flagged && db_hash_allowlist::contains(&mut conn, &target.sha256) {
+            return Some(CachedReport::Trusted);
+        }
+        let cached = cached?;
+        if self.is_stale(&cached) {
+            log::debug!("Cached VirusTotal result for {} is stale", target.sha256);
+            return None;
+        }
+        if cached.raw_response.contains("404 Not Found") {
+            Some(CachedReport::NotFound)
+        } else {
+            Some(CachedReport::Report(FileScanResult::from_db_result(&target.file_path, &cached)))
+        }
     }
 
-    /// Set global rate limit (for 429 errors) - all threads will wait until this time
-    fn set_rate_limit(&mut self, duration: Duration) {
-        let until = Instant::now() + duration;
+    fn lookup_hash(&self, target: &ScanTarget, gate: &RequestGate) -> Result<FileScanResult, ProviderError> {
+        gate.acquire()?;
+        let response = api_virustotal::get_file_report(&target.sha256, &self.api_key)?;
+        log::info!("Got VirusTotal report for {}", target.sha256);
 
-        // Only update if this extends the existing rate limit
-        if self.rate_limit_until.is_none() || self.rate_limit_until.unwrap() < until {
-            log::warn!(
-                "Setting global rate limit for {:.2}s (until {:.2}s from now)",
-                duration.as_secs_f64(),
-                duration.as_secs_f64()
-            );
-            self.rate_limit_until = Some(until);
-            // Clear request times since we're resetting
-            self.request_times.clear();
+        let mut result = FileScanResult::from_response(&target.file_path, &target.sha256, &response);
+        let mut conn = db::establish_connection();
+        if let Ok(Some(previous)) = db_virustotal::get_result_by_sha256(&mut conn, &target.sha256) {
+            result.previous_detections = Some((previous.malicious, previous.suspicious));
         }
+        self.store(target, response);
+        Ok(result)
     }
 
-    /// Get the number of available requests in the current time window
-    fn available_requests(&self) -> usize {
-        let now = Instant::now();
+    fn submit_file(&self, _target: &ScanTarget, local_path: &Path, gate: &RequestGate) -> Result<String, ProviderError> {
+        gate.acquire_submit()?;
+        let upload_response = api_virustotal::upload_file_smart(local_path, &self.api_key)?;
+        Ok(upload_response.data.id)
+    }
 
-        // If in global rate limit, no requests available
-        if let Some(until) = self.rate_limit_until {
-            if now < until {
-                return 0;
-            }
+    fn poll(
+        &self,
+        target: &ScanTarget,
+        submission_id: &str,
+        gate: &RequestGate,
+    ) -> Result<PollOutcome<FileScanResult>, ProviderError> {
+        gate.acquire()?;
+        log::info!("Retrieving analysis results for: {}", submission_id);
+        let response = api_virustotal::get_analysis(submission_id, &self.api_key)?;
+
+        let status = response
+            .data
+            .attributes
+            .status
+            .as_deref()
+            .unwrap_or("unknown");
+        if status != "completed" {
+            log::debug!("Analysis status: {}", status);
+            return Ok(PollOutcome::Pending);
         }
 
-        // Count requests within the time window
-        let recent_requests = self
-            .request_times
-            .iter()
-            .filter(|&&time| now.duration_since(time) < self.time_window)
-            .count();
+        log::info!("Got analysis result for {}", target.sha256);
+        let result = FileScanResult::from_response(&target.file_path, &target.sha256, &response);
+        self.store(target, response);
+        Ok(PollOutcome::Ready(result))
+    }
 
-        self.max_requests.saturating_sub(recent_requests)
+    fn record_not_found(&self, target: &ScanTarget) {
+        // Cache 404 so the file is not looked up again
+        let not_found_response = api_virustotal::VirusTotalResponse {
+            data: api_virustotal::VirusTotalData {
+                id: target.sha256.clone(),
+                data_type: "file".to_string(),
+                attributes: api_virustotal::VirusTotalAttributes {
+                    last_analysis_date: Some(0),
+                    last_analysis_stats: Some(api_virustotal::LastAnalysisStats {
+                        malicious: 0,
+                        suspicious: 0,
+                        undetected: 0,
+                        harmless: 0,
+                        timeout: 0,
+                        confirmed_timeout: 0,
+                        failure: 0,
+                        type_unsupported: 0,
+                    }),
+                    status: Some("404 Not Found".to_string()),
+                    ..Default::default()
+                },
+            },
+        };
+        self.store(target, not_found_response);
     }
-}
 
-/// Initialize scanner state by checking database cache
-pub fn init_scanner_state(package_names: &[String]) -> ScannerState {
-    let state = Arc::new(Mutex::new(HashMap::new()));
-    let mut conn = db::establish_connection();
+    fn normalize(&self, report: &FileScanResult) -> NormalizedResult {
+        let verdict = if report.trusted {
+            Verdict::Clean
+        } else if report.not_found || report.skipped || report.error.is_some() {
+            Verdict::Unknown
+        } else if report.malicious > 0 {
+            Verdict::Malicious
+        } else if report.suspicious > 0 {
+            Verdict::Suspicious
+        } else {
+            Verdict::Clean
+        };
 
-    for pkg_name in package_names {
-        // Check if we have cached results
-        match db_virustotal::get_results_by_package(&mut conn, pkg_name) {
-            Ok(cached_results) if !cached_results.is_empty() => {
-                let result = CalcVirustotal::from_db_results(cached_results);
-                state
-                    .lock()
-                    .unwrap()
-                    .insert(pkg_name.clone(), ScanStatus::Completed(result));
-            }
-            Ok(_) => {
-                // No cached result, mark as pending
-                state
-                    .lock()
-                    .unwrap()
-                    .insert(pkg_name.clone(), ScanStatus::Pending);
-            }
-            Err(e) => {
-                log::error!("Error checking cache for {}: {}", pkg_name, e);
-                state
-                    .lock()
-                    .unwrap()
-                    .insert(pkg_name.clone(), ScanStatus::Error(e.to_string()));
-            }
+        NormalizedResult {
+            provider: self.name().to_string(),
+            sha256: report.sha256.clone(),
+            verdict,
+            detections: Some(report.malicious + report.suspicious),
+            engines: Some(report.total()),
+            score: None,
+            link: report.vt_link.clone(),
         }
     }
+}
 
-    state
+/// Initialize scanner state by checking database cache
+pub fn init_scanner_state(package_names: &[String]) -> ScannerState {
+    let mut conn = db::establish_connection();
+    calc_scanprovider::init_scanner_state(package_names, |pkg_name| {
+        db_virustotal::get_results_by_package(&mut conn, pkg_name)
+            .map(|cached| (!cached.is_empty()).then(|| CalcVirustotal::from_db_results(cached)))
+            .map_err(|e| e.to_string())
+    })
 }
 
 /// Analyze hashes using VirusTotal API
@@ -210,7 +406,7 @@ pub fn analyze_package(
     hashes: Vec<(String, String)>,
     state: &ScannerState,
     rate_limiter: &SharedRateLimiter,
-    api_key: &str,
+    provider: &VirusTotalProvider,
     device_serial: &str,
     allow_upload: bool,
     repaint_signal: &Option<Arc<dyn Fn() + Send + Sync>>,
@@ -229,18 +425,12 @@ pub fn analyze_package(
         return Ok(());
     }
 
-    let config = Config::new()?;
-    let mut conn = db::establish_connection();
     let total_files = hashes.len();
 
     let mut file_results = Vec::new();
     let mut last_error: Option<String> = None;
-    let mut last_was_cache = false;
     let mut files_skipped_invalid_hash = 0usize;
 
-    // Timeout duration for this file
-    let timeout_duration = Duration::from_secs(60);
-
     for (idx, (file_path, sha256)) in hashes.iter().enumerate() {
         // Validate SHA256 hash length (must be 64 hex characters)
         if sha256.len() != 64 {
@@ -254,568 +444,54 @@ pub fn analyze_package(
             continue;
         }
 
-        let start_time = Instant::now();
-        let max_wait_time = start_time + timeout_duration;
-
-        // Check database cache first
-        if let Ok(Some(cached)) = db_virustotal::get_result_by_sha256(&mut conn, sha256) {
-            log::debug!("Found cached result for {}", sha256);
-
-            // Check if it's a cached 404
-            if cached.raw_response.contains("404 Not Found") {
-                log::info!("Found cached 404 for {}", sha256);
-                file_results.push(FileScanResult {
-                    file_path: file_path.clone(),
-                    sha256: cached.sha256.clone(),
-                    malicious: 0,
-                    suspicious: 0,
-                    undetected: 0,
-                    harmless: 0,
-                    dex_count: None,
-                    reputation: 0,
-                    vt_link: format!("https://www.virustotal.com/gui/file/{}", cached.sha256),
-                    not_found: true,
-                    skipped: false,
-                    error: None,
-                });
-                last_was_cache = true;
-                continue;
-            }
-
-            // Update status - using cached result
-            {
-                let mut s = state.lock().unwrap();
-                s.insert(
-                    package_name.to_string(),
-                    ScanStatus::Scanning {
-                        scanned: idx + 1,
-                        total: total_files,
-                        operation: tr!("status-checking-api"),
-                    },
-                );
-            }
-            if let Some(signal) = repaint_signal {
-                signal();
-            }
-
-            file_results.push(FileScanResult {
-                file_path: file_path.clone(),
-                sha256: cached.sha256.clone(),
-                malicious: cached.malicious,
-                suspicious: cached.suspicious,
-                undetected: cached.undetected,
-                harmless: cached.harmless,
-                dex_count: cached.dex_count,
-                reputation: cached.reputation,
-                vt_link: format!("https://www.virustotal.com/gui/file/{}", cached.sha256),
-                not_found: false,
-                skipped: false,
-                error: None,
-            });
-            last_was_cache = true;
-            continue;
-        }
-
-        // Update status - checking API
-        {
-            let mut s = state.lock().unwrap();
-            s.insert(
+        let target = ScanTarget {
+            package_name: package_name.to_string(),
+            file_path: file_path.clone(),
+            sha256: sha256.clone(),
+        };
+        let on_status = |operation: String| {
+            state.lock().unwrap().insert(
                 package_name.to_string(),
                 ScanStatus::Scanning {
                     scanned: idx + 1,
                     total: total_files,
-                    operation: "Checking API".to_string(),
+                    operation,
                 },
             );
-        }
-        if let Some(signal) = repaint_signal {
-            signal();
-        }
-
-        // Not in cache, query VirusTotal API
-
-        // Wait for rate limit with timeout
-        loop {
-            // if Instant::now() > max_wait_time {
-            //     last_error = Some(tr!("error-timeout-rate-limit"));
-            //     break;
-            // }
-
-            let wait_needed = {
-                let mut limiter = rate_limiter.lock().unwrap();
-                if last_was_cache && limiter.rate_limit_until.is_none() {
-                    None
-                } else {
-                    limiter.check_wait_needed()
-                }
-            };
-
-            if let Some(duration) = wait_needed {
-                thread::sleep(duration);
-            } else {
-                break;
-            }
-        }
-        if last_error.is_some() {
-            log::warn!("Skipping {} due to timeout", file_path);
-            continue;
-        }
-
-        last_was_cache = false;
-
-        // Record request
-        {
-            let mut limiter = rate_limiter.lock().unwrap();
-            limiter.record_request();
-        }
-
-        log::info!("Querying VirusTotal API for SHA256: {}", sha256);
-        match api_virustotal::get_file_report(sha256, api_key) {
-            Ok(response) => {
-                let available_after = rate_limiter.lock().unwrap().available_requests();
-                log::info!(
-                    "Got VirusTotal report for {}",
-                    sha256
-                );
-
-                // Save to database via queue
-                db_virustotal::queue_upsert(
-                    package_name.to_string(),
-                    file_path.clone(),
-                    sha256.clone(),
-                    response.clone(),
-                )?;
-
-                let default_stats = api_virustotal::LastAnalysisStats {
-                    malicious: 0,
-                    suspicious: 0,
-                    undetected: 0,
-                    harmless: 0,
-                    timeout: 0,
-                    confirmed_timeout: 0,
-                    failure: 0,
-                    type_unsupported: 0,
-                };
-                let stats = response
-                    .data
-                    .attributes
-                    .last_analysis_stats
-                    .as_ref()
-                    .unwrap_or(&default_stats);
-                let dex_count = response
-                    .data
-                    .attributes
-                    .androguard
-                    .as_ref()
-                    .and_then(|a| a.risk_indicator.as_ref())
-                    .and_then(|r| r.apk.as_ref())
-                    .and_then(|a| a.dex);
-
-                file_results.push(FileScanResult {
-                    file_path: file_path.clone(),
-                    sha256: sha256.clone(),
-                    malicious: stats.malicious,
-                    suspicious: stats.suspicious,
-                    undetected: stats.undetected,
-                    harmless: stats.harmless,
-                    dex_count,
-                    reputation: response.data.attributes.reputation,
-                    vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                    not_found: false,
-                    skipped: false,
-                    error: None,
-                });
-            }
-            Err(VtError::NotFound) if allow_upload => {
-                log::info!(
-                    "File {} sha256 {} not found in VirusTotal, uploading",
-                    file_path,
-                    sha256
-                );
-
-                // Update status - pulling file
-                {
-                    let mut s = state.lock().unwrap();
-                    s.insert(
-                        package_name.to_string(),
-                        ScanStatus::Scanning {
-                            scanned: idx + 1,
-                            total: total_files,
-                            operation: tr!("status-pulling-file"),
-                        },
-                    );
-                }
-                if let Some(signal) = repaint_signal {
-                    signal();
-                }
-
-                if Instant::now() > max_wait_time {
-                    last_error = Some("Timeout before pulling file".to_string());
-                    continue;
-                }
-
-                // Pull file to temp directory
-                // Note: This requires ADB to be available. If it's not, this will fail.
-
-                // Skip if the path appears to be a directory (doesn't have a file extension or ends with /)
-                if file_path.ends_with('/') || !file_path.contains('.') {
-                    log::warn!("Skipping directory or invalid path: {}", file_path);
-                    let err_msg = format!("Path is a directory, not a file: {}", file_path);
-                    file_results.push(FileScanResult {
-                        file_path: file_path.clone(),
-                        sha256: sha256.clone(),
-                        malicious: 0,
-                        suspicious: 0,
-                        undetected: 0,
-                        harmless: 0,
-                        dex_count: None,
-                        reputation: 0,
-                        vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                        not_found: false,
-                        skipped: false,
-                        error: Some(err_msg.clone()),
-                    });
-                    last_error = Some(err_msg);
-                    continue;
-                }
-
-                // Only upload APK and SO files to VirusTotal (skip .prof, .dm, .art, etc.)
-                if !file_path.ends_with(".apk") && !file_path.ends_with(".so") {
-                    log::info!(
-                        "Skipping file for upload: {} (only .apk and .so files are uploaded)",
-                        file_path
-                    );
-                    file_results.push(FileScanResult {
-                        file_path: file_path.clone(),
-                        sha256: sha256.clone(),
-                        malicious: 0,
-                        suspicious: 0,
-                        undetected: 0,
-                        harmless: 0,
-                        dex_count: None,
-                        reputation: 0,
-                        vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                        not_found: false,
-                        skipped: true,
-                        error: None,
-                    });
-                    continue;
-                }
-
-                let tmp_dir_str = config.tmp_dir.to_str().ok_or("Invalid tmp directory path")?;
-                let expected_filename = format!("{}.apk", package_name.replace('.', "_"));
-                let local_path = config.tmp_dir.join(&expected_filename);
-
-                // pull_file_to_temp handles both Android (base64 method) and non-Android (adb pull) platforms
-                let pull_result =
-                    adb::pull_file_to_temp(device_serial, file_path, tmp_dir_str, package_name);
-                match pull_result {
-                    Ok(tmp_file) => {
-                        // Update status - uploading
-                        {
-                            let mut s = state.lock().unwrap();
-                            s.insert(
-                                package_name.to_string(),
-                                ScanStatus::Scanning {
-                                    scanned: idx + 1,
-                                    total: total_files,
-                                    operation: tr!("status-uploading"),
-                                },
-                            );
-                        }
-                        if let Some(signal) = repaint_signal {
-                            signal();
-                        }
-
-                        // Upload file
-                        // Wait for rate limit
-                        loop {
-                            // if Instant::now() > max_wait_time {
-                            //     last_error = Some(tr!("error-timeout-rate-limit"));
-                            //     break;
-                            // }
-                            let wait_needed = {
-                                let mut limiter = rate_limiter.lock().unwrap();
-                                limiter.check_wait_needed()
-                            };
-                            if let Some(duration) = wait_needed {
-                                thread::sleep(duration);
-                            } else {
-                                break;
-                            }
-                        }
-                        if last_error.is_some() {
-                            let _ = std::fs::remove_file(&tmp_file);
-                            log::warn!("Skipping upload {} due to timeout", tmp_file);
-                            continue;
-                        }
-
-                        {
-                            let mut limiter = rate_limiter.lock().unwrap();
-                            limiter.record_request();
-                        }
-
-                        log::info!("Uploading file to VirusTotal: {}", tmp_file);
-                        match api_virustotal::upload_file_smart(Path::new(&tmp_file), api_key) {
-                            Ok(upload_response) => {
-                                let available_after =
-                                    rate_limiter.lock().unwrap().available_requests();
-                                log::info!("Uploaded file {}, analysis ID: {}", sha256, upload_response.data.id);
-
-                                // Poll for results until timeout
-                                log::info!("Waiting for analysis to complete...");
-
-                                let mut analysis_result = None;
-                                while Instant::now() < max_wait_time {
-                                    // Wait a bit before polling
-                                    thread::sleep(Duration::from_secs(10));
-
-                                    // Check rate limit for polling
-                                    let mut can_poll = false;
-                                    {
-                                        let mut limiter = rate_limiter.lock().unwrap();
-                                        if limiter.check_wait_needed().is_none() {
-                                            limiter.record_request();
-                                            can_poll = true;
-                                        }
-                                    }
-
-                                    if can_poll {
-                                        log::info!(
-                                            "Retrieving analysis results for: {}",
-                                            upload_response.data.id
-                                        );
-                                        match api_virustotal::get_analysis(
-                                            &upload_response.data.id,
-                                            api_key,
-                                        ) {
-                                            Ok(response) => {
-                                                // Check status
-                                                let status = response
-                                                    .data
-                                                    .attributes
-                                                    .status
-                                                    .as_deref()
-                                                    .unwrap_or("unknown");
-                                                if status == "completed" {
-                                                    analysis_result = Some(response);
-                                                    break;
-                                                } else {
-                                                    log::debug!("Analysis status: {}", status);
-                                                }
-                                            }
-                                            Err(VtError::RateLimit { retry_after }) => {
-                                                rate_limiter.lock().unwrap().set_rate_limit(
-                                                    Duration::from_secs(retry_after),
-                                                );
-                                            }
-                                            Err(e) => {
-                                                log::warn!("Error polling analysis: {}", e);
-                                            }
-                                        }
-                                    }
-                                }
-
-                                if let Some(response) = analysis_result {
-                                    let available_after =
-                                        rate_limiter.lock().unwrap().available_requests();
-                                    log::info!(
-                                        "Got analysis result for {}",
-                                        sha256
-                                    );
-
-                                    // Save to database via queue
-                                    db_virustotal::queue_upsert(
-                                        package_name.to_string(),
-                                        file_path.clone(),
-                                        sha256.clone(),
-                                        response.clone(),
-                                    )?;
-
-                                    let default_stats = api_virustotal::LastAnalysisStats {
-                                        malicious: 0,
-                                        suspicious: 0,
-                                        undetected: 0,
-                                        harmless: 0,
-                                        timeout: 0,
-                                        confirmed_timeout: 0,
-                                        failure: 0,
-                                        type_unsupported: 0,
-                                    };
-                                    let stats = response
-                                        .data
-                                        .attributes
-                                        .last_analysis_stats
-                                        .as_ref()
-                                        .unwrap_or(&default_stats);
-                                    let dex_count = response
-                                        .data
-                                        .attributes
-                                        .androguard
-                                        .as_ref()
-                                        .and_then(|a| a.risk_indicator.as_ref())
-                                        .and_then(|r| r.apk.as_ref())
-                                        .and_then(|a| a.dex);
-
-                                    file_results.push(FileScanResult {
-                                        file_path: file_path.clone(),
-                                        sha256: sha256.clone(),
-                                        malicious: stats.malicious,
-                                        suspicious: stats.suspicious,
-                                        undetected: stats.undetected,
-                                        harmless: stats.harmless,
-                                        dex_count,
-                                        reputation: response.data.attributes.reputation,
-                                        vt_link: format!(
-                                            "https://www.virustotal.com/gui/file/{}",
-                                            sha256
-                                        ),
-                                        not_found: false,
-                                        skipped: false,
-                                        error: None,
-                                    });
-                                } else {
-                                    let err_msg = format!(
-                                        "Timeout waiting for VirusTotal analysis to complete for package '{}' (sha256: {}, file: {})",
-                                        package_name, sha256, file_path
-                                    );
-                                    log::error!("{}", err_msg);
-                                    last_error = Some(err_msg);
-                                }
-                            }
-                            Err(VtError::RateLimit { retry_after }) => {
-                                rate_limiter
-                                    .lock()
-                                    .unwrap()
-                                    .set_rate_limit(Duration::from_secs(retry_after));
-                            }
-                            Err(e) => {
-                                let err_msg = format!("Failed to upload file: {}", e);
-                                log::error!("Failed to upload file: {}", e);
-                                file_results.push(FileScanResult {
-                                    file_path: file_path.clone(),
-                                    sha256: sha256.clone(),
-                                    malicious: 0,
-                                    suspicious: 0,
-                                    undetected: 0,
-                                    harmless: 0,
-                                    dex_count: None,
-                                    reputation: 0,
-                                    vt_link: format!(
-                                        "https://www.virustotal.com/gui/file/{}",
-                                        sha256
-                                    ),
-                                    not_found: false,
-                                    skipped: false,
-                                    error: Some(err_msg.clone()),
-                                });
-                                last_error = Some(err_msg);
-                            }
-                        }
-
-                        // Clean up temp file
-                        let _ = std::fs::remove_file(&tmp_file);
-                    }
-                    Err(e) => {
-                        let err_msg = format!("Failed to pull file: {}", e);
-                        log::error!("Failed to pull file: {}", e);
-                        file_results.push(FileScanResult {
-                            file_path: file_path.clone(),
-                            sha256: sha256.clone(),
-                            malicious: 0,
-                            suspicious: 0,
-                            undetected: 0,
-                            harmless: 0,
-                            dex_count: None,
-                            reputation: 0,
-                            vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                            not_found: false,
-                            skipped: false,
-                            error: Some(err_msg.clone()),
-                        });
-                        last_error = Some(err_msg);
-                    }
-                }
+            if let Some(signal) = repaint_signal {
+                signal();
             }
-            Err(VtError::NotFound) => {
-                log::warn!(
-                    "Sha256 {} for file {} not found in VirusTotal and upload is disabled",
-                    sha256,
-                    file_path
-                );
-
-                // Cache 404
-                let not_found_response = api_virustotal::VirusTotalResponse {
-                    data: api_virustotal::VirusTotalData {
-                        id: sha256.to_string(),
-                        data_type: "file".to_string(),
-                        attributes: api_virustotal::VirusTotalAttributes {
-                            last_analysis_date: Some(0),
-                            last_analysis_stats: Some(api_virustotal::LastAnalysisStats {
-                                malicious: 0,
-                                suspicious: 0,
-                                undetected: 0,
-                                harmless: 0,
-                                timeout: 0,
-                                confirmed_timeout: 0,
-                                failure: 0,
-                                type_unsupported: 0,
-                            }),
-                            reputation: 0,
-                            androguard: None,
-                            status: Some("404 Not Found".to_string()),
-                        },
-                    },
-                };
-
-                if let Err(e) = db_virustotal::queue_upsert(
-                    package_name.to_string(),
-                    file_path.clone(),
-                    sha256.clone(),
-                    not_found_response,
-                ) {
-                    log::error!("Failed to cache 404 for {}: {}", sha256, e);
-                }
-
+        };
+        let fetch = |t: &ScanTarget| calc_scanprovider::pull_for_submission(device_serial, t);
+
+        match calc_scanprovider::scan_file(provider, &target, rate_limiter, allow_upload, &fetch, &on_status) {
+            FileOutcome::Report(result) => file_results.push(result),
+            FileOutcome::NotFound => file_results.push(FileScanResult {
+                not_found: true,
+                ..FileScanResult::without_report(file_path, sha256)
+            }),
+            FileOutcome::Skipped => file_results.push(FileScanResult {
+                skipped: true,
+                ..FileScanResult::without_report(file_path, sha256)
+            }),
+            FileOutcome::Trusted => file_results.push(FileScanResult {
+                trusted: true,
+                ..FileScanResult::without_report(file_path, sha256)
+            }),
+            // Uploads are retried on the next scan
+            FileOutcome::SubmitRateLimited { .. } => {}
+            FileOutcome::Pending { submission_id } => {
+                let err_msg = format!("Analysis {} did not complete in time", submission_id);
                 file_results.push(FileScanResult {
-                    file_path: file_path.clone(),
-                    sha256: sha256.clone(),
-                    malicious: 0,
-                    suspicious: 0,
-                    undetected: 0,
-                    harmless: 0,
-                    dex_count: None,
-                    reputation: 0,
-                    vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                    not_found: true,
-                    skipped: false,
-                    error: None,
+                    error: Some(err_msg.clone()),
+                    ..FileScanResult::without_report(file_path, sha256)
                 });
+                last_error = Some(err_msg);
             }
-            Err(VtError::RateLimit { retry_after }) => {
-                rate_limiter
-                    .lock()
-                    .unwrap()
-                    .set_rate_limit(Duration::from_secs(retry_after));
-                // Retry this file
-                // (In a production system, we'd want a more sophisticated retry mechanism)
-            }
-            Err(e) => {
-                let err_msg = e.to_string();
-                log::error!("Error getting VirusTotal report for {}: {}", sha256, e);
+            FileOutcome::Failed { message: err_msg, .. } | FileOutcome::Error(err_msg) => {
                 file_results.push(FileScanResult {
-                    file_path: file_path.clone(),
-                    sha256: sha256.clone(),
-                    malicious: 0,
-                    suspicious: 0,
-                    undetected: 0,
-                    harmless: 0,
-                    dex_count: None,
-                    reputation: 0,
-                    vt_link: format!("https://www.virustotal.com/gui/file/{}", sha256),
-                    not_found: false,
-                    skipped: false,
                     error: Some(err_msg.clone()),
+                    ..FileScanResult::without_report(file_path, sha256)
                 });
                 last_error = Some(err_msg);
             }
@@ -824,11 +500,11 @@ pub fn analyze_package(
 
     // Update final status
     let mut s = state.lock().unwrap();
-    if file_results.is_empty() {
-        if let Some(err) = last_error {
+    match last_error {
+        Some(err) if file_results.is_empty() => {
             s.insert(package_name.to_string(), ScanStatus::Error(err));
-        } else {
-            // No results but no specific error
+        }
+        _ => {
             let result = CalcVirustotal {
                 file_results,
                 files_attempted: total_files,
@@ -836,13 +512,6 @@ pub fn analyze_package(
             };
             s.insert(package_name.to_string(), ScanStatus::Completed(result));
         }
-    } else {
-        let result = CalcVirustotal {
-            file_results,
-            files_attempted: total_files,
-            files_skipped_invalid_hash,
-        };
-        s.insert(package_name.to_string(), ScanStatus::Completed(result));
     }
     if let Some(signal) = repaint_signal {
         signal();
@@ -851,6 +520,46 @@ pub fn analyze_package(
     Ok(())
 }
 
+/// Packages to scan first when fewer requests are left than the files of the packages still
+/// to scan (APKs plus native libraries): the selected and non-system ones. Empty otherwise.
+fn quota_preferred_packages(
+    installed_packages: &[crate::adb::PackageFingerprint],
+    device_serial: &str,
+    scanner_state: &ScannerState,
+    remaining: Option<u32>,
+    selected_packages: &HashSet<String>,
+) -> HashSet<String> {
+    let Some(remaining) = remaining else {
+        return HashSet::new();
+    };
+    let cached: HashMap<String, crate::models::PackageInfoCache> =
+        crate::db_package_cache::get_cached_packages_with_apk(device_serial)
+            .into_iter()
+            .map(|cp| (cp.pkg_id.clone(), cp))
+            .collect();
+    let estimated_files: usize = {
+        let s = scanner_state.lock().unwrap();
+        installed_packages
+            .iter()
+            .filter(|p| !matches!(s.get(&p.pkg), Some(ScanStatus::Completed(_))))
+            .map(|p| calc_scanprovider::estimated_file_count(p, cached.get(&p.pkg)))
+            .sum()
+    };
+    if remaining as usize >= estimated_files {
+        return HashSet::new();
+    }
+    log::info!(
+        "VirusTotal quota is tight ({} requests left for about {} files), prioritizing selected and user apps",
+        remaining,
+        estimated_files
+    );
+    installed_packages
+        .iter()
+        .filter(|p| selected_packages.contains(&p.pkg) || !p.flags.contains("SYSTEM"))
+        .map(|p| p.pkg.clone())
+        .collect()
+}
+
 /// Run VirusTotal scanning for a list of packages in a background thread.
 /// This function initializes the scanner state and spawns a background thread
 /// to scan all packages using the VirusTotal API.
@@ -858,9 +567,10 @@ pub fn analyze_package(
 /// # Arguments
 /// * `installed_packages` - List of packages to scan
 /// * `device_serial` - Device serial number for ADB operations
-/// * `api_key` - VirusTotal API key
+/// * `provider` - VirusTotal provider with API key and freshness policy
 /// * `virustotal_submit_enabled` - Whether to submit unknown files to VirusTotal
 /// * `package_risk_scores` - Risk scores for sorting packages by priority
+/// * `selected_packages` - Packages selected by the user, scanned first when the quota is tight
 /// * `vt_scan_progress` - Shared progress value for UI updates
 /// * `vt_scan_cancelled` - Shared cancellation flag
 ///
@@ -869,20 +579,17 @@ pub fn analyze_package(
 pub fn run_virustotal(
     installed_packages: Vec<crate::adb::PackageFingerprint>,
     device_serial: String,
-    api_key: String,
+    provider: VirusTotalProvider,
     virustotal_submit_enabled: bool,
     package_risk_scores: HashMap<String, i32>,
+    selected_packages: HashSet<String>,
     vt_scan_progress: Arc<Mutex<Option<f32>>>,
     vt_scan_cancelled: Arc<Mutex<bool>>,
 ) -> (ScannerState, SharedRateLimiter) {
     let package_names: Vec<String> = installed_packages.iter().map(|p| p.pkg.clone()).collect();
     let scanner_state = init_scanner_state(&package_names);
 
-    let rate_limiter = Arc::new(Mutex::new(RateLimiter::new(
-        4,
-        Duration::from_secs(60),
-        Duration::from_secs(5),
-    )));
+    let rate_limiter = provider.rate_limiter();
 
     // Initialize progress
     if let Ok(mut p) = vt_scan_progress.lock() {
@@ -899,150 +606,97 @@ pub fn run_virustotal(
 
     let scanner_state_clone = scanner_state.clone();
     let rate_limiter_clone = rate_limiter.clone();
-    let vt_scan_progress_clone = vt_scan_progress;
-    let vt_scan_cancelled_clone = vt_scan_cancelled;
 
     std::thread::spawn(move || {
-        let mut packages = installed_packages;
-        packages.sort_by(|a, b| {
-            let perms_a: usize = a.users.iter().map(|u| u.runtimePermissions.len()).sum();
-            let perms_b: usize = b.users.iter().map(|u| u.runtimePermissions.len()).sum();
-            perms_b.cmp(&perms_a)
-        });
-
-        let cached_packages =
-            crate::db_package_cache::get_cached_packages_with_apk(&device_serial);
-
-        let mut cached_packages_map: HashMap<String, crate::models::PackageInfoCache> =
-            HashMap::new();
-        for cp in cached_packages {
-            cached_packages_map.insert(cp.pkg_id.clone(), cp);
-        }
-
-        let total = packages.len();
-        let mut skipped_cached = 0usize;
-
-        for (i, package) in packages.iter().enumerate() {
-            if let Ok(cancelled) = vt_scan_cancelled_clone.lock() {
-                if *cancelled {
-                    log::info!("VirusTotal scan cancelled by user");
-                    break;
-                }
-            }
-
-            if let Ok(mut p) = vt_scan_progress_clone.lock() {
-                *p = Some(i as f32 / total as f32);
-            }
-
-            let pkg_name = &package.pkg;
-
-            {
-                let s = scanner_state_clone.lock().unwrap();
-                if matches!(s.get(pkg_name), Some(ScanStatus::Completed(_))) {
-                    skipped_cached += 1;
-                    continue;
-                }
-            }
-
-            let mut paths_str = String::new();
-            let mut sha256sums_str = String::new();
-
-            if let Some(cached_pkg) = cached_packages_map.get(pkg_name) {
-                if let (Some(path), Some(sha256)) =
-                    (&cached_pkg.apk_path, &cached_pkg.apk_sha256sum)
-                {
-                    paths_str = path.clone();
-                    sha256sums_str = sha256.clone();
+        let remaining = rate_limiter_clone.lock().unwrap().remaining_quota();
+        let preferred = quota_preferred_packages(
+            &installed_packages,
+            &device_serial,
+            &scanner_state_clone,
+            remaining,
+            &selected_packages,
+        );
+        calc_scanprovider::scan_installed_packages(
+            provider.name(),
+            installed_packages,
+            &device_serial,
+            &package_risk_scores,
+            &preferred,
+            &vt_scan_progress,
+            &vt_scan_cancelled,
+            |pkg_name| {
+                let completed = {
+                    let s = scanner_state_clone.lock().unwrap();
+                    matches!(s.get(pkg_name), Some(ScanStatus::Completed(_)))
+                };
+                completed && !provider.has_stale_results(pkg_name)
+            },
+            |pkg_name, hashes| {
+                // Leave the remaining packages pending once the quota is used up
+                if rate_limiter_clone.lock().unwrap().remaining_quota() == Some(0) {
+                    log::debug!("VirusTotal quota exhausted, skipping {}", pkg_name);
+                    return;
                 }
-            }
-
-            if paths_str.is_empty() || sha256sums_str.is_empty() {
-                paths_str = package.codePath.clone();
-                sha256sums_str = package.pkgChecksum.clone();
-            }
-
-            if !paths_str.is_empty() && !sha256sums_str.is_empty() {
-                let paths: Vec<&str> = paths_str.split(' ').collect();
-                let sha256sums: Vec<&str> = sha256sums_str.split(' ').collect();
-                let needs_directory_scan = paths.iter().any(|p| !p.ends_with(".apk"));
-                let has_invalid_hashes = sha256sums.iter().any(|s| s.len() != 64);
-
-                let (final_paths_str, final_sha256sums_str) =
-                    if needs_directory_scan || has_invalid_hashes {
-                        match crate::adb::get_single_package_sha256sum(&device_serial, pkg_name) {
-                            Ok((new_paths, new_sha256sums)) => {
-                                if !new_paths.is_empty() && !new_sha256sums.is_empty() {
-                                    (new_paths, new_sha256sums)
-                                } else if !has_invalid_hashes {
-                                    (paths_str.clone(), sha256sums_str.clone())
-                                } else {
-                                    (String::new(), String::new())
-                                }
-                            }
-                            Err(e) => {
-                                if !has_invalid_hashes {
-                                    log::warn!(
-                                        "Failed to get sha256sums for {}: {}, using cached values",
-                                        pkg_name,
-                                        e
-                                    );
-                                    (paths_str.clone(), sha256sums_str.clone())
-                                } else {
-                                    log::warn!(
-                                        "Failed to get sha256sums for {}: {}, skipping",
-                                        pkg_name,
-                                        e
-                                    );
-                                    (String::new(), String::new())
-                                }
-                            }
-                        }
-                    } else {
-                        (paths_str.clone(), sha256sums_str.clone())
-                    };
-
-                let final_paths: Vec<&str> = final_paths_str.split(' ').collect();
-                let final_sha256sums: Vec<&str> = final_sha256sums_str.split(' ').collect();
-
-                let hashes: Vec<(String, String)> = final_paths
-                    .iter()
-                    .zip(final_sha256sums.iter())
-                    .filter(|(p, s)| !p.is_empty() && s.len() == 64)
-                    .map(|(p, s)| (p.to_string(), s.to_string()))
-                    .collect();
-
-                log::info!(
-                    "Analyzing package {} with {} files (Risk: {})",
-                    pkg_name,
-                    hashes.len(),
-                    package_risk_scores.get(pkg_name).copied().unwrap_or(0)
-                );
-
                 if let Err(e) = analyze_package(
                     pkg_name,
                     hashes,
                     &scanner_state_clone,
                     &rate_limiter_clone,
-                    &api_key,
+                    &provider,
                     &device_serial,
                     virustotal_submit_enabled,
                     &None,
                 ) {
                     log::error!("Error analyzing package {}: {}", pkg_name, e);
                 }
-            }
-        }
-
-        log::info!(
-            "VirusTotal scan complete: {} cached, {} processed",
-            skipped_cached,
-            total - skipped_cached
+            },
         );
 
-        if let Ok(mut p) = vt_scan_progress_clone.lock() {
+        if let Ok(mut p) = vt_scan_progress.lock() {
             *p = None;
         }
     });
 
     (scanner_state, rate_limiter)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_report_details() {
+        let raw = r#"{"data":{"id":"aa11","type":"file","attributes":{
+            "last_analysis_stats":{"malicious":1,"suspicious":1,"undetected":1,"harmless":0,"timeout":0,
+                "confirmed-timeout":0,"failure":0,"type-unsupported":1},
+            "last_analysis_results":{
+                "Zeta":{"category":"undetected","engine_name":"Zeta","result":null},
+                "Avast":{"category":"suspicious","engine_name":"Avast","result":"Android:Evo-gen"},
+                "ESET-NOD32":{"category":"malicious","engine_name":"ESET-NOD32","result":"Android/Spy.Agent"},
+                "Bkav":{"category":"type-unsupported","result":null}},
+            "popular_threat_classification":{"suggested_threat_label":"trojan.spyagent",
+                "popular_threat_name":[{"value":"evo","count":2},{"value":"spyagent","count":5}]},
+            "crowdsourced_yara_results":[{"rule_name":"android_spy","ruleset_name":"mobile","author":"x"}],
+            "sigma_analysis_results":[{"rule_title":"Reads contacts","rule_level":"high"}],
+            "first_submission_date":1600000000,
+            "names":["base.apk","spy.apk"]}}}"#;
+
+        let details = parse_report_details(raw).unwrap();
+        let engines: Vec<&str> = details.engines.iter().map(|e| e.engine_name.as_str()).collect();
+        assert_eq!(engines, vec!["ESET-NOD32", "Avast", "Zeta", "Bkav"]);
+        assert_eq!(details.engines[0].result.as_deref(), Some("Android/Spy.Agent"));
+        assert_eq!(details.suggested_threat_label.as_deref(), Some("trojan.spyagent"));
+        assert_eq!(details.popular_threat_names[0], ("spyagent".to_string(), 5));
+        assert_eq!(details.yara_hits[0].rule_name, "android_spy");
+        assert_eq!(details.sigma_hits[0].rule_level, "high");
+        assert_eq!(details.first_submission_date, Some(1_600_000_000));
+        assert_eq!(details.names, vec!["base.apk", "spy.apk"]);
+    }
+
+    #[test]
+    fn test_parse_report_details_not_found() {
+        let raw = r#"{"data":{"id":"aa11","type":"file","attributes":{"status":"404 Not Found"}}}"#;
+        assert!(parse_report_details(raw).is_none());
+        assert!(parse_report_details("not json").is_none());
+    }
+}
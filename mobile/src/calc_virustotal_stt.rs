@@ -1,6 +1,5 @@
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use crate::api_virustotal_stt::{EngineResult, SigmaResult, YaraResult};
+use std::time::Instant;
 
 /// Status of a VirusTotal scan for a file
 #[derive(Debug, Clone)]
@@ -32,21 +31,28 @@ pub struct FileScanResult {
     pub not_found: bool,
     /// True if the file was skipped (not an APK or SO file)
     pub skipped: bool,
+    /// True if the hash is on the known-good allowlist and was not looked up
+    pub trusted: bool,
     /// Error message if scanning failed for this specific file
     pub error: Option<String>,
+    /// (malicious, suspicious) before the latest re-query, if the file was looked up before
+    pub previous_detections: Option<(i32, i32)>,
 }
 
-/// Status of a VirusTotal scan for a package (may contain multiple files)
-#[derive(Debug, Clone)]
-pub enum ScanStatus {
-    Pending,
-    Scanning {
-        scanned: usize,
-        total: usize,
-        operation: String,
-    },
-    Completed(CalcVirustotal),
-    Error(String),
+/// Per-engine detail of a cached VirusTotal report, parsed from the stored raw response
+#[derive(Debug, Clone, Default)]
+pub struct VtReportDetails {
+    /// Engine verdicts, detections first
+    pub engines: Vec<EngineResult>,
+    pub suggested_threat_label: Option<String>,
+    /// Popular threat names with vote counts, most common first
+    pub popular_threat_names: Vec<(String, u32)>,
+    pub yara_hits: Vec<YaraResult>,
+    pub sigma_hits: Vec<SigmaResult>,
+    /// Unix timestamp of the first submission to VirusTotal
+    pub first_submission_date: Option<i64>,
+    /// File names the sample is known under
+    pub names: Vec<String>,
 }
 
 /// Result of a VirusTotal scan for a package (may contain multiple files)
@@ -69,18 +75,11 @@ impl Default for CalcVirustotal {
     }
 }
 
+/// Status of a VirusTotal scan for a package (may contain multiple files)
+pub type ScanStatus = crate::calc_scanprovider_stt::ScanStatus<CalcVirustotal>;
+
 /// Shared state for VirusTotal scanning
-pub type ScannerState = Arc<Mutex<HashMap<String, ScanStatus>>>;
+pub type ScannerState = crate::calc_scanprovider_stt::ScannerState<CalcVirustotal>;
 
 /// Shared rate limiter for VirusTotal API
-pub type SharedRateLimiter = Arc<Mutex<RateLimiter>>;
-
-/// Rate limiter for VirusTotal API (4 requests per minute with 5 seconds minimum interval)
-pub struct RateLimiter {
-    pub request_times: Vec<Instant>,
-    pub max_requests: usize,
-    pub time_window: Duration,
-    pub min_interval: Duration,
-    /// When a 429 is received, all threads should wait until this time
-    pub rate_limit_until: Option<Instant>,
-}
+pub type SharedRateLimiter = crate::calc_scanprovider_stt::SharedRateLimiter;
@@ -168,6 +168,44 @@ pub fn flush_hybridanalysis() {
     }
 }
 
+/// Flush (delete all records from) the malwarebazaar_results table
+pub fn flush_malwarebazaar() {
+    let connection = &mut establish_connection();
+    match diesel::sql_query("DELETE FROM malwarebazaar_results").execute(connection) {
+        Ok(count) => {
+            #[cfg(not(target_family = "wasm"))]
+            log::info!("Flushed malwarebazaar_results table. Deleted {} rows.", count);
+            #[cfg(target_family = "wasm")]
+            console_log!("Flushed malwarebazaar_results table. Deleted {} rows.", count);
+        }
+        Err(e) => {
+            #[cfg(not(target_family = "wasm"))]
+            log::error!("Failed to flush malwarebazaar_results table: {}", e);
+            #[cfg(target_family = "wasm")]
+            console_log!("Failed to flush malwarebazaar_results table: {}", e);
+        }
+    }
+}
+
+/// Flush (delete all records from) the koodous_results table
+pub fn flush_koodous() {
+    let connection = &mut establish_connection();
+    match diesel::sql_query("DELETE FROM koodous_results").execute(connection) {
+        Ok(count) => {
+            #[cfg(not(target_family = "wasm"))]
+            log::info!("Flushed koodous_results table. Deleted {} rows.", count);
+            #[cfg(target_family = "wasm")]
+            console_log!("Flushed koodous_results table. Deleted {} rows.", count);
+        }
+        Err(e) => {
+            #[cfg(not(target_family = "wasm"))]
+            log::error!("Failed to flush koodous_results table: {}", e);
+            #[cfg(target_family = "wasm")]
+            console_log!("Failed to flush koodous_results table: {}", e);
+        }
+    }
+}
+
 /// Flush (delete all records from) the google_play_apps table
 pub fn flush_googleplay() {
     let connection = &mut establish_connection();
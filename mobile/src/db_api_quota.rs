@@ -0,0 +1,51 @@
+use crate::db::establish_connection;
+use crate::models::{ApiQuotaUsage, NewApiQuotaUsage};
+use crate::schema::api_quota_usage;
+use diesel::prelude::*;
+
+/// Requests made by `provider` on `day` ("YYYY-MM-DD") and in the month of `day`
+pub fn get_usage(provider: &str, day: &str) -> (u32, u32) {
+    let mut conn = establish_connection();
+    let month_prefix = format!("{}%", day.get(..8).unwrap_or(day));
+
+    let rows = api_quota_usage::table
+        .filter(api_quota_usage::provider.eq(provider))
+        .filter(api_quota_usage::day.like(&month_prefix))
+        .load::<ApiQuotaUsage>(&mut conn)
+        .unwrap_or_default();
+
+    let used_today = rows
+        .iter()
+        .filter(|r| r.day == day)
+        .map(|r| r.requests.max(0) as u32)
+        .sum();
+    let used_month = rows.iter().map(|r| r.requests.max(0) as u32).sum();
+    (used_today, used_month)
+}
+
+/// Count one request made by `provider` on `day`
+pub fn record_request(provider: &str, day: &str) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+
+    let existing = api_quota_usage::table
+        .filter(api_quota_usage::provider.eq(provider))
+        .filter(api_quota_usage::day.eq(day))
+        .first::<ApiQuotaUsage>(&mut conn)
+        .optional()?;
+
+    if let Some(usage) = existing {
+        diesel::update(api_quota_usage::table.find(usage.id))
+            .set(api_quota_usage::requests.eq(usage.requests + 1))
+            .execute(&mut conn)?;
+    } else {
+        diesel::insert_into(api_quota_usage::table)
+            .values(&NewApiQuotaUsage {
+                provider,
+                day,
+                requests: 1,
+            })
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
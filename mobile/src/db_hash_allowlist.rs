@@ -0,0 +1,203 @@
+use crate::db::establish_connection;
+use crate::models::{HashAllowlistEntry, NewHashAllowlistEntry};
+use crate::schema::hash_allowlist;
+use diesel::prelude::*;
+use std::collections::HashSet;
+
+/// Allowlist entry added from this installation's own clean scan results
+pub const SOURCE_LOCAL: &str = "local";
+/// Allowlist entry imported from a shared file
+pub const SOURCE_IMPORT: &str = "import";
+
+/// Minimum engines that must have analyzed a VirusTotal-clean file before it is trusted
+pub const MIN_CLEAN_ENGINES: i32 = 20;
+
+fn now() -> i32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32
+}
+
+/// Parse an allowlist file: one sha256 per line, optionally followed by a label.
+/// Blank lines and `#` comments are ignored; invalid hashes are skipped.
+pub fn parse_allowlist(text: &str) -> Vec<(String, Option<String>)> {
+    text.lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|l| !l.is_empty())
+        .filter_map(|l| {
+            let (hash, label) = match l.split_once(char::is_whitespace) {
+                Some((hash, label)) => (hash, Some(label.trim().to_string()).filter(|s| !s.is_empty())),
+                None => (l, None),
+            };
+            let hash = hash.to_ascii_lowercase();
+            (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then_some((hash, label))
+        })
+        .collect()
+}
+
+/// Whether a sha256 is on the allowlist
+pub fn contains(conn: &mut SqliteConnection, sha256: &str) -> bool {
+    hash_allowlist::table
+        .find(sha256.to_ascii_lowercase())
+        .first::<HashAllowlistEntry>(conn)
+        .optional()
+        .ok()
+        .flatten()
+        .is_some()
+}
+
+pub fn count() -> i64 {
+    let mut conn = establish_connection();
+    hash_allowlist::table.count().get_result(&mut conn).unwrap_or(0)
+}
+
+/// Add hashes, keeping existing entries untouched. Returns the number of new entries.
+pub fn insert_hashes(entries: &[(String, Option<String>)], source: &str) -> Result<usize, diesel::result::Error> {
+    let mut conn = establish_connection();
+    let added_at = now();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let mut inserted = 0;
+        for (sha256, label) in entries {
+            inserted += diesel::insert_or_ignore_into(hash_allowlist::table)
+                .values(&NewHashAllowlistEntry {
+                    sha256,
+                    label: label.as_deref(),
+                    source,
+                    added_at,
+                })
+                .execute(conn)?;
+        }
+        Ok(inserted)
+    })
+}
+
+/// Hashes flagged malicious or suspicious by VirusTotal or Hybrid Analysis on this installation
+fn flagged_hashes(conn: &mut SqliteConnection) -> Result<HashSet<String>, diesel::result::Error> {
+    use crate::schema::{hybridanalysis_results, virustotal_results};
+    let flagged_vt: Vec<String> = virustotal_results::table
+        .filter(
+            virustotal_results::malicious
+                .gt(0)
+                .or(virustotal_results::suspicious.gt(0)),
+        )
+        .select(virustotal_results::sha256)
+        .load(conn)?;
+    let flagged_ha: Vec<String> = hybridanalysis_results::table
+        .filter(hybridanalysis_results::verdict.eq_any(vec!["malicious", "suspicious"]))
+        .select(hybridanalysis_results::sha256)
+        .load(conn)?;
+    Ok(flagged_vt
+        .into_iter()
+        .chain(flagged_ha)
+        .map(|sha256| sha256.to_ascii_lowercase())
+        .collect())
+}
+
+/// Add every hash this installation has seen as clean: no VirusTotal detections
+/// from at least `MIN_CLEAN_ENGINES` engines, or whitelisted by Hybrid Analysis.
+/// Hashes flagged by either provider are never added.
+pub fn add_clean_results() -> Result<usize, diesel::result::Error> {
+    use crate::schema::{hybridanalysis_results, virustotal_results};
+    let mut conn = establish_connection();
+    let flagged = flagged_hashes(&mut conn)?;
+
+    let clean_vt: Vec<(String, String)> = virustotal_results::table
+        .filter(virustotal_results::malicious.eq(0))
+        .filter(virustotal_results::suspicious.eq(0))
+        .filter((virustotal_results::harmless + virustotal_results::undetected).ge(MIN_CLEAN_ENGINES))
+        .select((virustotal_results::sha256, virustotal_results::package_name))
+        .load(&mut conn)?;
+    let clean_ha: Vec<(String, String)> = hybridanalysis_results::table
+        .filter(hybridanalysis_results::verdict.eq("whitelisted"))
+        .select((hybridanalysis_results::sha256, hybridanalysis_results::package_name))
+        .load(&mut conn)?;
+
+    let entries: Vec<(String, Option<String>)> = clean_vt
+        .into_iter()
+        .chain(clean_ha)
+        .map(|(sha256, package_name)| (sha256.to_ascii_lowercase(), Some(package_name)))
+        .filter(|(sha256, _)| !flagged.contains(sha256))
+        .collect();
+    insert_hashes(&entries, SOURCE_LOCAL)
+}
+
+/// Drop entries whose hash is in `flagged`. Returns the kept entries and the refused hashes.
+pub fn split_flagged(
+    entries: Vec<(String, Option<String>)>,
+    flagged: &HashSet<String>,
+) -> (Vec<(String, Option<String>)>, Vec<String>) {
+    let (refused, kept): (Vec<_>, Vec<_>) = entries.into_iter().partition(|(sha256, _)| flagged.contains(sha256));
+    (kept, refused.into_iter().map(|(sha256, _)| sha256).collect())
+}
+
+/// Import a shared allowlist file, refusing hashes this installation has flagged.
+/// Returns the number of new entries and the number of refused hashes.
+pub fn import_file(path: &std::path::Path) -> Result<(usize, usize), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let entries = parse_allowlist(&text);
+    if entries.is_empty() {
+        return Err(format!("No sha256 hashes found in {}", path.display()));
+    }
+    let flagged = flagged_hashes(&mut establish_connection()).map_err(|e| e.to_string())?;
+    let (entries, refused) = split_flagged(entries, &flagged);
+    for sha256 in &refused {
+        log::warn!("Not importing {} into the allowlist: flagged by a local scan", sha256);
+    }
+    let added = insert_hashes(&entries, SOURCE_IMPORT).map_err(|e| e.to_string())?;
+    Ok((added, refused.len()))
+}
+
+/// Write the allowlist in the format read by `import_file`. Returns the number of entries.
+pub fn export_file(path: &std::path::Path) -> Result<usize, String> {
+    let mut conn = establish_connection();
+    let entries = hash_allowlist::table
+        .order(hash_allowlist::sha256)
+        .load::<HashAllowlistEntry>(&mut conn)
+        .map_err(|e| e.to_string())?;
+    let mut text = String::from("# sha256 label\n");
+    for entry in &entries {
+        match &entry.label {
+            Some(label) => text.push_str(&format!("{} {}\n", entry.sha256, label)),
+            None => text.push_str(&format!("{}\n", entry.sha256)),
+        }
+    }
+    std::fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(entries.len())
+}
+
+pub fn clear() -> Result<usize, diesel::result::Error> {
+    let mut conn = establish_connection();
+    diesel::delete(hash_allowlist::table).execute(&mut conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_allowlist() {
+        let hash = "6f2ca352440a0027b9f8ed014d40a1557df2b4b3d3e3fc06e574cc02ead982aa";
+        let text = format!(
+            "# shared allowlist\n{}  com.linkedin.android\n\n{}\nnot-a-hash\n{}  # trailing comment\n",
+            hash.to_uppercase(),
+            hash,
+            &hash[..63]
+        );
+        let entries = parse_allowlist(&text);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], (hash.to_string(), Some("com.linkedin.android".to_string())));
+        assert_eq!(entries[1], (hash.to_string(), None));
+    }
+
+    #[test]
+    fn test_split_flagged() {
+        let clean = "6f2ca352440a0027b9f8ed014d40a1557df2b4b3d3e3fc06e574cc02ead982aa".to_string();
+        let bad = "a".repeat(64);
+        let flagged: HashSet<String> = [bad.clone()].into_iter().collect();
+        let entries = vec![(clean.clone(), None), (bad.clone(), Some("x".to_string()))];
+        let (kept, refused) = split_flagged(entries, &flagged);
+        assert_eq!(kept, vec![(clean, None)]);
+        assert_eq!(refused, vec![bad]);
+    }
+}
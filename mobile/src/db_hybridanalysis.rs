@@ -192,6 +192,9 @@ fn upsert_result_with_error(
                 dsl::tags.eq(&tags_json),
                 dsl::raw_response.eq(&raw_response),
                 dsl::error_message.eq(&error_msg),
+                // Keep what the previous lookup said so changed verdicts can be highlighted
+                dsl::previous_verdict.eq(Some(&existing_record.verdict)),
+                dsl::previous_threat_score.eq(existing_record.threat_score),
                 dsl::updated_at.eq(std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
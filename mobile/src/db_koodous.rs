@@ -0,0 +1,211 @@
+// https://docs.koodous.com/api/
+
+use crate::api_koodous::KoodousApk;
+use crate::models::{KoodousResult, NewKoodousResult};
+use crate::schema::koodous_results;
+use diesel::prelude::*;
+use std::error::Error;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+lazy_static::lazy_static! {
+    static ref UPSERT_QUEUE: Arc<Mutex<Option<Sender<UpsertTask>>>> = Arc::new(Mutex::new(None));
+}
+
+struct UpsertTask {
+    package_name: String,
+    file_path: String,
+    sha256: String,
+    verdict: String,
+    /// `None` for APKs Koodous does not know
+    apk: Option<KoodousApk>,
+}
+
+/// Initialize the upsert queue worker thread
+pub fn init_upsert_queue() {
+    let mut queue_lock = UPSERT_QUEUE.lock().unwrap();
+    if queue_lock.is_some() {
+        return; // Already initialized
+    }
+
+    let (tx, rx): (Sender<UpsertTask>, Receiver<UpsertTask>) = channel();
+    *queue_lock = Some(tx);
+    drop(queue_lock);
+
+    // Spawn worker thread
+    thread::spawn(move || {
+        log::info!("Koodous upsert queue worker started");
+        let mut conn = crate::db::establish_connection();
+
+        for task in rx {
+            match upsert_result(
+                &mut conn,
+                &task.package_name,
+                &task.file_path,
+                &task.sha256,
+                &task.verdict,
+                task.apk.as_ref(),
+            ) {
+                Ok(_) => {
+                    log::debug!(
+                        "Successfully upserted Koodous result for {} ({})",
+                        task.package_name,
+                        task.file_path
+                    );
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to upsert Koodous result for {} ({}): {}",
+                        task.package_name,
+                        task.file_path,
+                        e
+                    );
+                }
+            }
+        }
+
+        log::info!("Koodous upsert queue worker stopped");
+    });
+}
+
+/// Queue an upsert operation (non-blocking)
+pub fn queue_upsert(
+    package_name: String,
+    file_path: String,
+    sha256: String,
+    verdict: String,
+    apk: Option<KoodousApk>,
+) -> Result<(), Box<dyn Error>> {
+    let queue_lock = UPSERT_QUEUE.lock().unwrap();
+    if let Some(ref tx) = *queue_lock {
+        let task = UpsertTask {
+            package_name,
+            file_path,
+            sha256,
+            verdict,
+            apk,
+        };
+        tx.send(task).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+        Ok(())
+    } else {
+        Err("Upsert queue not initialized".into())
+    }
+}
+
+/// Get all Koodous results for a package by package name
+pub fn get_results_by_package(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+) -> Result<Vec<KoodousResult>, Box<dyn Error>> {
+    use crate::schema::koodous_results::dsl;
+
+    let results = dsl::koodous_results
+        .filter(dsl::package_name.eq(package_name))
+        .load::<KoodousResult>(conn)?;
+
+    Ok(results)
+}
+
+/// Get Koodous result from database by SHA256
+pub fn get_result_by_sha256(
+    conn: &mut SqliteConnection,
+    sha256: &str,
+) -> Result<Option<KoodousResult>, Box<dyn Error>> {
+    use crate::schema::koodous_results::dsl;
+
+    let result = dsl::koodous_results
+        .filter(dsl::sha256.eq(sha256))
+        .first::<KoodousResult>(conn)
+        .optional()?;
+
+    Ok(result)
+}
+
+/// Insert or update the result for a package file
+pub fn upsert_result(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+    file_path: &str,
+    sha256: &str,
+    verdict: &str,
+    apk: Option<&KoodousApk>,
+) -> Result<KoodousResult, Box<dyn Error>> {
+    use crate::schema::koodous_results::dsl;
+
+    let tags_json = serde_json::to_string(&apk.map(|a| a.tags.clone()).unwrap_or_default())?;
+    let raw_response = match apk {
+        Some(a) => serde_json::to_string(a)?,
+        None => "{}".to_string(),
+    };
+    let app = apk.and_then(|a| a.app.as_deref());
+    let company = apk.and_then(|a| a.company.as_deref());
+    let rating = apk.and_then(|a| a.rating);
+    let detected = apk.is_some_and(|a| a.detected);
+    let trusted = apk.is_some_and(|a| a.trusted);
+
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = dsl::koodous_results
+        .filter(dsl::package_name.eq(package_name))
+        .filter(dsl::file_path.eq(file_path))
+        .filter(dsl::sha256.eq(sha256))
+        .first::<KoodousResult>(conn)
+        .optional()?;
+
+    if let Some(existing_record) = existing {
+        diesel::update(dsl::koodous_results.find(existing_record.id))
+            .set((
+                dsl::verdict.eq(verdict),
+                dsl::app.eq(app),
+                dsl::company.eq(company),
+                dsl::rating.eq(rating),
+                dsl::detected.eq(detected),
+                dsl::trusted.eq(trusted),
+                dsl::tags.eq(&tags_json),
+                dsl::raw_response.eq(&raw_response),
+                dsl::updated_at.eq(current_time),
+            ))
+            .execute(conn)?;
+
+        Ok(dsl::koodous_results.find(existing_record.id).first(conn)?)
+    } else {
+        let new_result = NewKoodousResult {
+            package_name,
+            file_path,
+            sha256,
+            verdict,
+            app,
+            company,
+            rating,
+            detected,
+            trusted,
+            tags: &tags_json,
+            raw_response: &raw_response,
+            created_at: current_time,
+            updated_at: current_time,
+        };
+
+        diesel::insert_into(koodous_results::table)
+            .values(&new_result)
+            .execute(conn)?;
+
+        Ok(dsl::koodous_results.order(dsl::id.desc()).first(conn)?)
+    }
+}
+
+/// Delete all Koodous results for a package by package name
+pub fn delete_results_by_package(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+) -> Result<usize, Box<dyn Error>> {
+    use crate::schema::koodous_results::dsl;
+
+    let deleted = diesel::delete(dsl::koodous_results.filter(dsl::package_name.eq(package_name)))
+        .execute(conn)?;
+
+    Ok(deleted)
+}
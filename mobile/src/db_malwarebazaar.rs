@@ -0,0 +1,210 @@
+// https://bazaar.abuse.ch/api/
+
+use crate::api_malwarebazaar::MalwareBazaarSample;
+use crate::models::{MalwareBazaarResult, NewMalwareBazaarResult};
+use crate::schema::malwarebazaar_results;
+use diesel::prelude::*;
+use std::error::Error;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+lazy_static::lazy_static! {
+    static ref UPSERT_QUEUE: Arc<Mutex<Option<Sender<UpsertTask>>>> = Arc::new(Mutex::new(None));
+}
+
+struct UpsertTask {
+    package_name: String,
+    file_path: String,
+    sha256: String,
+    verdict: String,
+    /// `None` for hashes MalwareBazaar does not list
+    sample: Option<MalwareBazaarSample>,
+}
+
+/// Initialize the upsert queue worker thread
+pub fn init_upsert_queue() {
+    let mut queue_lock = UPSERT_QUEUE.lock().unwrap();
+    if queue_lock.is_some() {
+        return; // Already initialized
+    }
+
+    let (tx, rx): (Sender<UpsertTask>, Receiver<UpsertTask>) = channel();
+    *queue_lock = Some(tx);
+    drop(queue_lock);
+
+    // Spawn worker thread
+    thread::spawn(move || {
+        log::info!("MalwareBazaar upsert queue worker started");
+        let mut conn = crate::db::establish_connection();
+
+        for task in rx {
+            match upsert_result(
+                &mut conn,
+                &task.package_name,
+                &task.file_path,
+                &task.sha256,
+                &task.verdict,
+                task.sample.as_ref(),
+            ) {
+                Ok(_) => {
+                    log::debug!(
+                        "Successfully upserted MalwareBazaar result for {} ({})",
+                        task.package_name,
+                        task.file_path
+                    );
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to upsert MalwareBazaar result for {} ({}): {}",
+                        task.package_name,
+                        task.file_path,
+                        e
+                    );
+                }
+            }
+        }
+
+        log::info!("MalwareBazaar upsert queue worker stopped");
+    });
+}
+
+/// Queue an upsert operation (non-blocking)
+pub fn queue_upsert(
+    package_name: String,
+    file_path: String,
+    sha256: String,
+    verdict: String,
+    sample: Option<MalwareBazaarSample>,
+) -> Result<(), Box<dyn Error>> {
+    let queue_lock = UPSERT_QUEUE.lock().unwrap();
+    if let Some(ref tx) = *queue_lock {
+        let task = UpsertTask {
+            package_name,
+            file_path,
+            sha256,
+            verdict,
+            sample,
+        };
+        tx.send(task).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+        Ok(())
+    } else {
+        Err("Upsert queue not initialized".into())
+    }
+}
+
+/// Get all MalwareBazaar results for a package by package name
+pub fn get_results_by_package(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+) -> Result<Vec<MalwareBazaarResult>, Box<dyn Error>> {
+    use crate::schema::malwarebazaar_results::dsl;
+
+    let results = dsl::malwarebazaar_results
+        .filter(dsl::package_name.eq(package_name))
+        .load::<MalwareBazaarResult>(conn)?;
+
+    Ok(results)
+}
+
+/// Get MalwareBazaar result from database by SHA256
+pub fn get_result_by_sha256(
+    conn: &mut SqliteConnection,
+    sha256: &str,
+) -> Result<Option<MalwareBazaarResult>, Box<dyn Error>> {
+    use crate::schema::malwarebazaar_results::dsl;
+
+    let result = dsl::malwarebazaar_results
+        .filter(dsl::sha256.eq(sha256))
+        .first::<MalwareBazaarResult>(conn)
+        .optional()?;
+
+    Ok(result)
+}
+
+/// Insert or update the result for a package file
+pub fn upsert_result(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+    file_path: &str,
+    sha256: &str,
+    verdict: &str,
+    sample: Option<&MalwareBazaarSample>,
+) -> Result<MalwareBazaarResult, Box<dyn Error>> {
+    use crate::schema::malwarebazaar_results::dsl;
+
+    let tags_json = serde_json::to_string(&sample.and_then(|s| s.tags.clone()).unwrap_or_default())?;
+    let raw_response = match sample {
+        Some(s) => serde_json::to_string(s)?,
+        None => "{}".to_string(),
+    };
+    let signature = sample.and_then(|s| s.signature.as_deref());
+    let file_type = sample.and_then(|s| s.file_type.as_deref());
+    let first_seen = sample.and_then(|s| s.first_seen.as_deref());
+
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = dsl::malwarebazaar_results
+        .filter(dsl::package_name.eq(package_name))
+        .filter(dsl::file_path.eq(file_path))
+        .filter(dsl::sha256.eq(sha256))
+        .first::<MalwareBazaarResult>(conn)
+        .optional()?;
+
+    if let Some(existing_record) = existing {
+        diesel::update(dsl::malwarebazaar_results.find(existing_record.id))
+            .set((
+                dsl::verdict.eq(verdict),
+                dsl::signature.eq(signature),
+                dsl::file_type.eq(file_type),
+                dsl::first_seen.eq(first_seen),
+                dsl::tags.eq(&tags_json),
+                dsl::raw_response.eq(&raw_response),
+                dsl::updated_at.eq(current_time),
+            ))
+            .execute(conn)?;
+
+        Ok(dsl::malwarebazaar_results
+            .find(existing_record.id)
+            .first(conn)?)
+    } else {
+        let new_result = NewMalwareBazaarResult {
+            package_name,
+            file_path,
+            sha256,
+            verdict,
+            signature,
+            file_type,
+            first_seen,
+            tags: &tags_json,
+            raw_response: &raw_response,
+            created_at: current_time,
+            updated_at: current_time,
+        };
+
+        diesel::insert_into(malwarebazaar_results::table)
+            .values(&new_result)
+            .execute(conn)?;
+
+        Ok(dsl::malwarebazaar_results
+            .order(dsl::id.desc())
+            .first(conn)?)
+    }
+}
+
+/// Delete all MalwareBazaar results for a package by package name
+pub fn delete_results_by_package(
+    conn: &mut SqliteConnection,
+    package_name: &str,
+) -> Result<usize, Box<dyn Error>> {
+    use crate::schema::malwarebazaar_results::dsl;
+
+    let deleted =
+        diesel::delete(dsl::malwarebazaar_results.filter(dsl::package_name.eq(package_name)))
+            .execute(conn)?;
+
+    Ok(deleted)
+}
@@ -1,6 +1,13 @@
 use crate::db::establish_connection;
-use crate::models::{NewPackageInfoCache, PackageInfoCache};
-use crate::schema::package_info_cache;
+use crate::models::{
+    ApkManifestCache, ApkNativeLibCache, ApkRuleCache, ApkSigningCertCache, ApkTrackerCache, NewApkManifestCache,
+    NewApkNativeLibCache, NewApkRuleCache, NewApkSigningCertCache, NewApkTrackerCache, NewPackageInfoCache,
+    PackageInfoCache,
+};
+use crate::schema::{
+    apk_manifest_cache, apk_native_lib_cache, apk_rule_cache, apk_signing_cert_cache, apk_tracker_cache,
+    package_info_cache,
+};
 use diesel::prelude::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -195,3 +202,277 @@ pub fn cleanup_stale_cache(
     )
     .execute(&mut conn)
 }
+
+/// Get the cached parsed manifest of an APK by its sha256sum
+pub fn get_cached_apk_manifest(apk_sha256sum: &str) -> Option<ApkManifestCache> {
+    let mut conn = establish_connection();
+
+    apk_manifest_cache::table
+        .filter(apk_manifest_cache::apk_sha256sum.eq(apk_sha256sum))
+        .first::<ApkManifestCache>(&mut conn)
+        .ok()
+}
+
+/// Insert or replace the parsed manifest of an APK
+pub fn upsert_apk_manifest_cache(
+    apk_sha256sum: &str,
+    pkg_id: &str,
+    manifest_json: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    if let Some(existing) = get_cached_apk_manifest(apk_sha256sum) {
+        diesel::update(apk_manifest_cache::table.find(existing.id))
+            .set((
+                apk_manifest_cache::pkg_id.eq(pkg_id),
+                apk_manifest_cache::manifest_json.eq(manifest_json),
+                apk_manifest_cache::created_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_entry = NewApkManifestCache {
+            apk_sha256sum,
+            pkg_id,
+            manifest_json,
+            created_at: now,
+        };
+
+        diesel::insert_into(apk_manifest_cache::table)
+            .values(&new_entry)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+/// Get the cached tracker scan result of an APK, only if it was produced
+/// with the given signature database
+pub fn get_cached_apk_trackers(apk_sha256sum: &str, signatures_md5: &str) -> Option<ApkTrackerCache> {
+    let mut conn = establish_connection();
+
+    apk_tracker_cache::table
+        .filter(apk_tracker_cache::apk_sha256sum.eq(apk_sha256sum))
+        .filter(apk_tracker_cache::signatures_md5.eq(signatures_md5))
+        .first::<ApkTrackerCache>(&mut conn)
+        .ok()
+}
+
+/// Insert or replace the tracker scan result of an APK
+pub fn upsert_apk_trackers_cache(
+    apk_sha256sum: &str,
+    signatures_md5: &str,
+    pkg_id: &str,
+    trackers_json: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = apk_tracker_cache::table
+        .filter(apk_tracker_cache::apk_sha256sum.eq(apk_sha256sum))
+        .first::<ApkTrackerCache>(&mut conn)
+        .ok();
+
+    if let Some(existing) = existing {
+        diesel::update(apk_tracker_cache::table.find(existing.id))
+            .set((
+                apk_tracker_cache::signatures_md5.eq(signatures_md5),
+                apk_tracker_cache::pkg_id.eq(pkg_id),
+                apk_tracker_cache::trackers_json.eq(trackers_json),
+                apk_tracker_cache::created_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_entry = NewApkTrackerCache {
+            apk_sha256sum,
+            signatures_md5,
+            pkg_id,
+            trackers_json,
+            created_at: now,
+        };
+
+        diesel::insert_into(apk_tracker_cache::table)
+            .values(&new_entry)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+/// Get the cached scan rule results of an APK (patterns found per rule), only if
+/// they were produced with the given rules file
+pub fn get_cached_apk_rule_matches(apk_sha256sum: &str, rules_md5: &str) -> Option<ApkRuleCache> {
+    let mut conn = establish_connection();
+
+    apk_rule_cache::table
+        .filter(apk_rule_cache::apk_sha256sum.eq(apk_sha256sum))
+        .filter(apk_rule_cache::rules_md5.eq(rules_md5))
+        .first::<ApkRuleCache>(&mut conn)
+        .ok()
+}
+
+/// Insert or replace the scan rule results of an APK
+pub fn upsert_apk_rule_matches_cache(
+    apk_sha256sum: &str,
+    rules_md5: &str,
+    pkg_id: &str,
+    matches_json: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = apk_rule_cache::table
+        .filter(apk_rule_cache::apk_sha256sum.eq(apk_sha256sum))
+        .first::<ApkRuleCache>(&mut conn)
+        .ok();
+
+    if let Some(existing) = existing {
+        diesel::update(apk_rule_cache::table.find(existing.id))
+            .set((
+                apk_rule_cache::rules_md5.eq(rules_md5),
+                apk_rule_cache::pkg_id.eq(pkg_id),
+                apk_rule_cache::matches_json.eq(matches_json),
+                apk_rule_cache::created_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_entry = NewApkRuleCache {
+            apk_sha256sum,
+            rules_md5,
+            pkg_id,
+            matches_json,
+            created_at: now,
+        };
+
+        diesel::insert_into(apk_rule_cache::table)
+            .values(&new_entry)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+/// Get the cached native library inventory of an APK, only if it was produced
+/// by the given inventory version
+pub fn get_cached_apk_native_libs(apk_sha256sum: &str, inventory_version: i32) -> Option<ApkNativeLibCache> {
+    let mut conn = establish_connection();
+
+    apk_native_lib_cache::table
+        .filter(apk_native_lib_cache::apk_sha256sum.eq(apk_sha256sum))
+        .filter(apk_native_lib_cache::inventory_version.eq(inventory_version))
+        .first::<ApkNativeLibCache>(&mut conn)
+        .ok()
+}
+
+/// Insert or replace the native library inventory of an APK
+pub fn upsert_apk_native_libs_cache(
+    apk_sha256sum: &str,
+    inventory_version: i32,
+    pkg_id: &str,
+    libs_json: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = apk_native_lib_cache::table
+        .filter(apk_native_lib_cache::apk_sha256sum.eq(apk_sha256sum))
+        .first::<ApkNativeLibCache>(&mut conn)
+        .ok();
+
+    if let Some(existing) = existing {
+        diesel::update(apk_native_lib_cache::table.find(existing.id))
+            .set((
+                apk_native_lib_cache::inventory_version.eq(inventory_version),
+                apk_native_lib_cache::pkg_id.eq(pkg_id),
+                apk_native_lib_cache::libs_json.eq(libs_json),
+                apk_native_lib_cache::created_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_entry = NewApkNativeLibCache {
+            apk_sha256sum,
+            inventory_version,
+            pkg_id,
+            libs_json,
+            created_at: now,
+        };
+
+        diesel::insert_into(apk_native_lib_cache::table)
+            .values(&new_entry)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+/// Get the cached signing certificates of an APK by its sha256sum
+pub fn get_cached_apk_signing_certs(apk_sha256sum: &str) -> Option<ApkSigningCertCache> {
+    let mut conn = establish_connection();
+
+    apk_signing_cert_cache::table
+        .filter(apk_signing_cert_cache::apk_sha256sum.eq(apk_sha256sum))
+        .first::<ApkSigningCertCache>(&mut conn)
+        .ok()
+}
+
+/// Get cached signing certificates of any APK signed with the same certificates,
+/// identified by the space separated `dumpsys package` signature hashes
+pub fn get_signing_certs_by_signature_hashes(signature_hashes: &str) -> Option<ApkSigningCertCache> {
+    let mut conn = establish_connection();
+
+    apk_signing_cert_cache::table
+        .filter(apk_signing_cert_cache::signature_hashes.eq(signature_hashes))
+        .first::<ApkSigningCertCache>(&mut conn)
+        .ok()
+}
+
+/// Insert or replace the signing certificates of an APK
+pub fn upsert_apk_signing_certs_cache(
+    apk_sha256sum: &str,
+    pkg_id: &str,
+    signature_hashes: &str,
+    certificates_json: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    if let Some(existing) = get_cached_apk_signing_certs(apk_sha256sum) {
+        diesel::update(apk_signing_cert_cache::table.find(existing.id))
+            .set((
+                apk_signing_cert_cache::pkg_id.eq(pkg_id),
+                apk_signing_cert_cache::signature_hashes.eq(signature_hashes),
+                apk_signing_cert_cache::certificates_json.eq(certificates_json),
+                apk_signing_cert_cache::created_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_entry = NewApkSigningCertCache {
+            apk_sha256sum,
+            pkg_id,
+            signature_hashes,
+            certificates_json,
+            created_at: now,
+        };
+
+        diesel::insert_into(apk_signing_cert_cache::table)
+            .values(&new_entry)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::db::establish_connection;
+use crate::models::{NewPermissionJournalEntry, PermissionJournalEntry};
+use crate::schema::permission_journal;
+use diesel::prelude::*;
+
+fn now() -> i32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32
+}
+
+/// Record a runtime permission change made on a device
+pub fn record_change(
+    device_serial: &str,
+    user_id: i32,
+    pkg_id: &str,
+    permission: &str,
+    granted: bool,
+    previous_granted: bool,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+
+    diesel::insert_into(permission_journal::table)
+        .values(&NewPermissionJournalEntry {
+            device_serial,
+            user_id,
+            pkg_id,
+            permission,
+            granted,
+            previous_granted,
+            changed_at: now(),
+        })
+        .execute(&mut conn)?;
+
+    Ok(())
+}
+
+/// Get the most recent permission changes for a device, newest first
+pub fn get_journal(device_serial: &str, limit: i64) -> Vec<PermissionJournalEntry> {
+    let mut conn = establish_connection();
+
+    permission_journal::table
+        .filter(permission_journal::device_serial.eq(device_serial))
+        .order(permission_journal::changed_at.desc())
+        .then_order_by(permission_journal::id.desc())
+        .limit(limit)
+        .load::<PermissionJournalEntry>(&mut conn)
+        .unwrap_or_default()
+}
+
+/// Mark a change as reverted
+pub fn mark_reverted(id: i32) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+
+    diesel::update(permission_journal::table.find(id))
+        .set(permission_journal::reverted_at.eq(Some(now())))
+        .execute(&mut conn)?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::db::establish_connection;
+use crate::models::{NewUadListsVersion, NewUadPackageAction, UadListsVersion, UadPackageAction};
+use crate::schema::{uad_lists_versions, uad_package_actions};
+use diesel::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of UAD lists versions kept; each holds the full lists JSON
+pub const MAX_STORED_VERSIONS: i64 = 5;
+
+/// Get the most recently fetched UAD lists versions, newest first
+pub fn get_latest_uad_lists_versions(limit: i64) -> Vec<UadListsVersion> {
+    let mut conn = establish_connection();
+
+    uad_lists_versions::table
+        .order(uad_lists_versions::fetched_at.desc())
+        .then_order_by(uad_lists_versions::id.desc())
+        .limit(limit)
+        .load::<UadListsVersion>(&mut conn)
+        .unwrap_or_default()
+}
+
+/// Store a downloaded UAD lists file unless it matches the newest stored version,
+/// then drop versions beyond the newest `MAX_STORED_VERSIONS`.
+/// Returns true when a new version was inserted.
+pub fn insert_uad_lists_version_if_changed(
+    raw_json: &str,
+    app_count: i32,
+) -> Result<bool, diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let content_md5 = format!("{:x}", md5::compute(raw_json.as_bytes()));
+
+    // Only the newest version counts, so content reverting to an older version (A -> B -> A) is recorded
+    let newest_md5 = uad_lists_versions::table
+        .order(uad_lists_versions::fetched_at.desc())
+        .then_order_by(uad_lists_versions::id.desc())
+        .select(uad_lists_versions::content_md5)
+        .first::<String>(&mut conn)
+        .optional()?;
+
+    if newest_md5.as_deref() == Some(content_md5.as_str()) {
+        return Ok(false);
+    }
+
+    let new_version = NewUadListsVersion {
+        content_md5: &content_md5,
+        app_count,
+        raw_json,
+        fetched_at: now,
+    };
+
+    // content_md5 is unique: an older copy of the same content makes way for the new row
+    diesel::delete(uad_lists_versions::table.filter(uad_lists_versions::content_md5.eq(&content_md5)))
+        .execute(&mut conn)?;
+    diesel::insert_into(uad_lists_versions::table)
+        .values(&new_version)
+        .execute(&mut conn)?;
+
+    let kept_ids: Vec<i32> = uad_lists_versions::table
+        .order(uad_lists_versions::fetched_at.desc())
+        .then_order_by(uad_lists_versions::id.desc())
+        .limit(MAX_STORED_VERSIONS)
+        .select(uad_lists_versions::id)
+        .load(&mut conn)?;
+    let pruned = diesel::delete(uad_lists_versions::table.filter(uad_lists_versions::id.ne_all(kept_ids)))
+        .execute(&mut conn)?;
+    if pruned > 0 {
+        log::debug!("Pruned {} old UAD lists versions", pruned);
+    }
+
+    Ok(true)
+}
+
+/// Record the last debloat action taken on a package together with its UAD removal category
+pub fn upsert_uad_package_action(
+    pkg_id: &str,
+    device_serial: &str,
+    action: &str,
+    removal: &str,
+) -> Result<(), diesel::result::Error> {
+    let mut conn = establish_connection();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i32;
+
+    let existing = uad_package_actions::table
+        .filter(uad_package_actions::pkg_id.eq(pkg_id))
+        .filter(uad_package_actions::device_serial.eq(device_serial))
+        .first::<UadPackageAction>(&mut conn)
+        .optional()?;
+
+    if let Some(existing_action) = existing {
+        diesel::update(uad_package_actions::table.find(existing_action.id))
+            .set((
+                uad_package_actions::action.eq(action),
+                uad_package_actions::removal.eq(removal),
+                uad_package_actions::acted_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+    } else {
+        let new_action = NewUadPackageAction {
+            pkg_id,
+            device_serial,
+            action,
+            removal,
+            acted_at: now,
+        };
+
+        diesel::insert_into(uad_package_actions::table)
+            .values(&new_action)
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+/// Get all recorded debloat actions for a device
+pub fn get_uad_package_actions(device_serial: &str) -> Vec<UadPackageAction> {
+    let mut conn = establish_connection();
+
+    uad_package_actions::table
+        .filter(uad_package_actions::device_serial.eq(device_serial))
+        .load::<UadPackageAction>(&mut conn)
+        .unwrap_or_default()
+}
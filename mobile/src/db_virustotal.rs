@@ -191,6 +191,9 @@ fn upsert_result_internal(
                 dsl::dex_count.eq(dex_count),
                 dsl::reputation.eq(vt_response.data.attributes.reputation),
                 dsl::raw_response.eq(&raw_response),
+                // Keep what the previous lookup said so changed detections can be highlighted
+                dsl::previous_malicious.eq(Some(existing_record.malicious)),
+                dsl::previous_suspicious.eq(Some(existing_record.suspicious)),
                 dsl::updated_at.eq(std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
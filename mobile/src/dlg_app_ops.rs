@@ -0,0 +1,298 @@
+pub use crate::dlg_app_ops_stt::*;
+use crate::calc_appops_stt::{AppOpChange, AppOpEntry, AppOpMode};
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+use std::collections::BTreeSet;
+
+impl DlgAppOps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the editor for one or more packages and start reading their ops
+    pub fn open(&mut self, device: String, packages: Vec<String>) {
+        if packages.is_empty() {
+            return;
+        }
+        self.current_package = packages.first().cloned();
+        self.packages = packages;
+        self.device = Some(device);
+        self.action_result = None;
+        self.open = true;
+        self.refresh();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh(&mut self) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.results.lock() {
+            r.clear();
+        }
+        crate::calc_appops::run_app_ops_load_async(device, self.packages.clone(), self.results.clone());
+    }
+
+    fn start_changes(&mut self, changes: Vec<AppOpChange>) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if changes.is_empty() {
+            return;
+        }
+        if let Ok(mut r) = self.batch_result.lock() {
+            *r = None;
+        }
+        self.batch_running = true;
+        self.action_result = None;
+        crate::calc_appops::run_app_op_changes_async(
+            device,
+            changes,
+            self.batch_result.clone(),
+            self.results.clone(),
+        );
+    }
+
+    fn mode_color(mode: &str) -> egui::Color32 {
+        match AppOpMode::parse(mode) {
+            Some(AppOpMode::Allow) => egui::Color32::from_rgb(255, 152, 0),
+            Some(AppOpMode::Ignore) | Some(AppOpMode::Deny) => egui::Color32::from_rgb(56, 142, 60),
+            _ => egui::Color32::GRAY,
+        }
+    }
+
+    /// Render an op row; returns the mode picked for it
+    fn render_op(ui: &mut egui::Ui, entry: &AppOpEntry, enabled: bool) -> Option<AppOpMode> {
+        let mut picked = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(&entry.op).strong());
+            if entry.uid_mode {
+                ui.label(egui::RichText::new(tr!("appops-uid-mode")).small().weak())
+                    .on_hover_text(tr!("appops-uid-mode-desc"));
+            }
+            ui.label(egui::RichText::new(&entry.mode).color(Self::mode_color(&entry.mode)));
+            for mode in AppOpMode::ALL {
+                let selected = entry.mode == mode.as_str();
+                if ui
+                    .add_enabled(enabled && !selected, egui::Button::selectable(selected, mode.as_str()).small())
+                    .clicked()
+                {
+                    picked = Some(mode);
+                }
+            }
+        });
+        let mut details = Vec::new();
+        if let Some(time) = &entry.last_access {
+            details.push(tr!("appops-last-access", { time: time.clone() }));
+        }
+        if let Some(duration) = &entry.duration {
+            details.push(tr!("appops-duration", { duration: duration.clone() }));
+        }
+        if let Some(time) = &entry.last_reject {
+            details.push(tr!("appops-last-reject", { time: time.clone() }));
+        }
+        if !details.is_empty() {
+            ui.label(egui::RichText::new(format!("  {}", details.join("  "))).small().weak());
+        }
+        picked
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        if self.batch_running {
+            let finished = self.batch_result.lock().ok().and_then(|mut r| r.take());
+            match finished {
+                Some(outcome) => {
+                    self.batch_running = false;
+                    self.action_result = Some(if outcome.failures.is_empty() {
+                        (true, tr!("appops-changes-done", { count: outcome.succeeded }))
+                    } else {
+                        (
+                            false,
+                            format!(
+                                "{}\n{}",
+                                tr!("appops-changes-failed", {
+                                    count: outcome.failures.len(),
+                                    succeeded: outcome.succeeded
+                                }),
+                                outcome.failures.join("\n")
+                            ),
+                        )
+                    });
+                }
+                None => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            }
+        }
+
+        let results = self.results.lock().map(|r| r.clone()).unwrap_or_default();
+        let loading = results.len() < self.packages.len();
+        if loading {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+
+        let mut close_clicked = false;
+        let mut refresh_clicked = false;
+        let mut changes: Vec<AppOpChange> = Vec::new();
+
+        egui::Window::new(tr!("appops-title"))
+            .id(egui::Id::new("app_ops_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("appops-title"));
+                ui.add_space(8.0);
+
+                // Batch: one op and mode for every package the editor was opened for
+                let known_ops: BTreeSet<String> = crate::calc_appops::PRIVACY_OPS
+                    .iter()
+                    .map(|op| op.to_string())
+                    .chain(results.values().flatten().flatten().map(|e| e.op.clone()))
+                    .collect();
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr!("appops-batch"));
+                    egui::ComboBox::from_id_salt("app_ops_batch_op")
+                        .selected_text(&self.batch_op)
+                        .show_ui(ui, |ui| {
+                            for op in &known_ops {
+                                ui.selectable_value(&mut self.batch_op, op.clone(), op);
+                            }
+                        });
+                    egui::ComboBox::from_id_salt("app_ops_batch_mode")
+                        .selected_text(self.batch_mode.as_str())
+                        .show_ui(ui, |ui| {
+                            for mode in AppOpMode::ALL {
+                                ui.selectable_value(&mut self.batch_mode, mode, mode.as_str());
+                            }
+                        });
+                    if ui
+                        .add_enabled(
+                            !self.batch_running,
+                            MaterialButton::filled(tr!("appops-apply", { count: self.packages.len() })).small(),
+                        )
+                        .clicked()
+                    {
+                        changes = self
+                            .packages
+                            .iter()
+                            .map(|pkg_id| AppOpChange {
+                                pkg_id: pkg_id.clone(),
+                                op: self.batch_op.clone(),
+                                mode: self.batch_mode,
+                                uid_mode: false,
+                            })
+                            .collect();
+                    }
+                    if self.batch_running {
+                        ui.spinner();
+                        ui.label(tr!("appops-applying"));
+                    }
+                });
+
+                if let Some((ok, message)) = &self.action_result {
+                    let color = if *ok {
+                        egui::Color32::from_rgb(56, 142, 60)
+                    } else {
+                        egui::Color32::from_rgb(211, 47, 47)
+                    };
+                    ui.colored_label(color, message);
+                }
+                ui.separator();
+
+                ui.horizontal_wrapped(|ui| {
+                    if self.packages.len() > 1 {
+                        egui::ComboBox::from_id_salt("app_ops_package")
+                            .selected_text(self.current_package.clone().unwrap_or_default())
+                            .show_ui(ui, |ui| {
+                                for pkg_id in &self.packages {
+                                    ui.selectable_value(&mut self.current_package, Some(pkg_id.clone()), pkg_id);
+                                }
+                            });
+                    } else if let Some(pkg_id) = &self.current_package {
+                        ui.label(egui::RichText::new(pkg_id).strong());
+                    }
+                    ui.checkbox(&mut self.used_only, tr!("appops-used-only"));
+                });
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("app_ops_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        let Some(pkg_id) = self.current_package.clone() else {
+                            return;
+                        };
+                        let entries = match results.get(&pkg_id) {
+                            None => {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.label(tr!("appops-loading"));
+                                });
+                                return;
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e);
+                                return;
+                            }
+                            Some(Ok(entries)) => entries,
+                        };
+                        let entries: Vec<&AppOpEntry> = entries
+                            .iter()
+                            .filter(|e| !self.used_only || e.last_access.is_some() || e.last_reject.is_some())
+                            .collect();
+                        if entries.is_empty() {
+                            ui.label(tr!("appops-none"));
+                        }
+                        for entry in entries {
+                            if let Some(mode) = Self::render_op(ui, entry, !self.batch_running) {
+                                changes = vec![AppOpChange {
+                                    pkg_id: pkg_id.clone(),
+                                    op: entry.op.clone(),
+                                    mode,
+                                    uid_mode: entry.uid_mode,
+                                }];
+                            }
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    if ui
+                        .add_enabled(!loading && !self.batch_running, MaterialButton::outlined(tr!("refresh")))
+                        .clicked()
+                    {
+                        refresh_clicked = true;
+                    }
+                });
+            });
+
+        if !changes.is_empty() {
+            self.start_changes(changes);
+        } else if refresh_clicked {
+            self.refresh();
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
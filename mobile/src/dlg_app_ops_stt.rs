@@ -0,0 +1,42 @@
+use crate::calc_appops_stt::{AppOpBatchOutcome, AppOpMode, AppOpsResults};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgAppOps {
+    pub open: bool,
+    pub device: Option<String>,
+    /// Packages the editor was opened for
+    pub packages: Vec<String>,
+    /// Package whose ops are listed
+    pub current_package: Option<String>,
+    /// Ops loaded in the background per package
+    pub results: AppOpsResults,
+    /// Op set on every package by the batch action
+    pub batch_op: String,
+    pub batch_mode: AppOpMode,
+    /// Outcome of the running change; `None` while applying
+    pub batch_result: Arc<Mutex<Option<AppOpBatchOutcome>>>,
+    pub batch_running: bool,
+    /// Outcome of the last change (success, message)
+    pub action_result: Option<(bool, String)>,
+    /// Show only ops that were accessed or rejected
+    pub used_only: bool,
+}
+
+impl Default for DlgAppOps {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            packages: Vec::new(),
+            current_package: None,
+            results: Arc::new(Mutex::new(HashMap::new())),
+            batch_op: "RUN_ANY_IN_BACKGROUND".to_string(),
+            batch_mode: AppOpMode::Ignore,
+            batch_result: Arc::new(Mutex::new(None)),
+            batch_running: false,
+            action_result: None,
+            used_only: false,
+        }
+    }
+}
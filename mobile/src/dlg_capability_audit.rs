@@ -0,0 +1,249 @@
+pub use crate::dlg_capability_audit_stt::*;
+use crate::calc_capabilities_stt::{CapabilityGrant, CapabilityKind};
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+/// Action requested from a grant row
+enum GrantAction {
+    Revoke(CapabilityGrant),
+    Disable(CapabilityGrant),
+}
+
+impl DlgCapabilityAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the dialog and start auditing the device
+    pub fn open(&mut self, device: String) {
+        self.device = Some(device);
+        self.open = true;
+        self.action_result = None;
+        self.refresh();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh(&mut self) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.result.lock() {
+            *r = None;
+        }
+        crate::calc_capabilities::run_capability_audit_async(device, self.result.clone());
+    }
+
+    fn kind_title(kind: CapabilityKind) -> String {
+        match kind {
+            CapabilityKind::Accessibility => tr!("capability-accessibility"),
+            CapabilityKind::DeviceAdmin => tr!("capability-device-admin"),
+            CapabilityKind::NotificationListener => tr!("capability-notification-listener"),
+            CapabilityKind::Vpn => tr!("capability-vpn"),
+            CapabilityKind::SmsRole => tr!("capability-sms-role"),
+            CapabilityKind::DialerRole => tr!("capability-dialer-role"),
+            CapabilityKind::UsageStats => tr!("capability-usage-stats"),
+            CapabilityKind::Overlay => tr!("capability-overlay"),
+            CapabilityKind::InstallUnknownSources => tr!("capability-install-unknown"),
+        }
+    }
+
+    fn kind_desc(kind: CapabilityKind) -> String {
+        match kind {
+            CapabilityKind::Accessibility => tr!("capability-accessibility-desc"),
+            CapabilityKind::DeviceAdmin => tr!("capability-device-admin-desc"),
+            CapabilityKind::NotificationListener => tr!("capability-notification-listener-desc"),
+            CapabilityKind::Vpn => tr!("capability-vpn-desc"),
+            CapabilityKind::SmsRole => tr!("capability-sms-role-desc"),
+            CapabilityKind::DialerRole => tr!("capability-dialer-role-desc"),
+            CapabilityKind::UsageStats => tr!("capability-usage-stats-desc"),
+            CapabilityKind::Overlay => tr!("capability-overlay-desc"),
+            CapabilityKind::InstallUnknownSources => tr!("capability-install-unknown-desc"),
+        }
+    }
+
+    fn render_grant(&mut self, ui: &mut egui::Ui, grant: &CapabilityGrant) -> Option<GrantAction> {
+        let mut action = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui.link(egui::RichText::new(&grant.pkg_id).strong()).clicked() {
+                self.open_package = Some(grant.pkg_id.clone());
+            }
+            if let Some(label) = &grant.label {
+                ui.label(format!("({})", label));
+            }
+            if grant.is_system {
+                ui.label(egui::RichText::new(tr!("capability-system")).weak());
+            }
+            if grant.always_on {
+                ui.label(
+                    egui::RichText::new(tr!("capability-always-on")).color(egui::Color32::from_rgb(255, 152, 0)),
+                );
+            }
+            if ui
+                .add(MaterialButton::outlined(tr!("capability-revoke")).small())
+                .on_hover_text(tr!("capability-revoke-desc"))
+                .clicked()
+            {
+                action = Some(GrantAction::Revoke(grant.clone()));
+            }
+            if ui
+                .add(MaterialButton::outlined(tr!("capability-disable-app")).small())
+                .on_hover_text(tr!("capability-disable-app-desc"))
+                .clicked()
+            {
+                action = Some(GrantAction::Disable(grant.clone()));
+            }
+        });
+        if let Some(component) = &grant.component {
+            ui.label(egui::RichText::new(format!("  {}", component)).small().weak());
+        }
+        action
+    }
+
+    fn run_action(&mut self, action: GrantAction) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        let (grant, result) = match action {
+            GrantAction::Revoke(grant) => {
+                let result = crate::calc_capabilities::revoke_capability(&device, &grant);
+                (grant, result)
+            }
+            GrantAction::Disable(grant) => {
+                let result = crate::adb::disable_app_current_user(&grant.pkg_id, &device, None);
+                (grant, result)
+            }
+        };
+        self.action_result = Some(match result {
+            Ok(_) => {
+                log::info!("Capability action on {} ({:?}) succeeded", grant.pkg_id, grant.kind);
+                (true, tr!("capability-action-done", { package: grant.pkg_id.clone() }))
+            }
+            Err(e) if grant.kind == CapabilityKind::DeviceAdmin && e.kind() == std::io::ErrorKind::PermissionDenied => {
+                log::warn!("Device admin of {} can only be removed in Settings: {}", grant.pkg_id, e);
+                (false, tr!("capability-device-admin-manual", { package: grant.pkg_id.clone() }))
+            }
+            Err(e) => {
+                log::error!("Capability action on {} ({:?}) failed: {}", grant.pkg_id, grant.kind, e);
+                (false, tr!("capability-action-failed", { package: grant.pkg_id.clone(), error: e.to_string() }))
+            }
+        });
+        self.refresh();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let result = self.result.lock().ok().and_then(|r| r.clone());
+        if result.is_none() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+
+        let mut close_clicked = false;
+        let mut refresh_clicked = false;
+        let mut action: Option<GrantAction> = None;
+
+        egui::Window::new(tr!("capability-audit-title"))
+            .id(egui::Id::new("capability_audit_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("capability-audit-title"));
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("capability_audit_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        let audit = match &result {
+                            None => {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.label(tr!("capability-audit-generating"));
+                                });
+                                return;
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e);
+                                return;
+                            }
+                            Some(Ok(audit)) => audit,
+                        };
+
+                        ui.label(tr!("capability-audit-summary", {
+                            device: audit.device_serial,
+                            count: audit.grants.len()
+                        }));
+                        if !audit.errors.is_empty() {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 152, 0),
+                                tr!("capability-audit-partial", { count: audit.errors.len() }),
+                            )
+                            .on_hover_text(audit.errors.join("\n"));
+                        }
+                        if let Some((ok, message)) = &self.action_result {
+                            let color = if *ok {
+                                egui::Color32::from_rgb(56, 142, 60)
+                            } else {
+                                egui::Color32::from_rgb(211, 47, 47)
+                            };
+                            ui.colored_label(color, message);
+                        }
+                        ui.add_space(8.0);
+
+                        for kind in CapabilityKind::ALL {
+                            let grants: Vec<&CapabilityGrant> = audit.grants_of(kind).collect();
+                            ui.label(egui::RichText::new(format!("{} ({})", Self::kind_title(kind), grants.len())).strong())
+                                .on_hover_text(Self::kind_desc(kind));
+                            if grants.is_empty() {
+                                ui.label(egui::RichText::new(format!("  {}", tr!("capability-none"))).weak());
+                            }
+                            for grant in grants {
+                                if let Some(a) = self.render_grant(ui, grant) {
+                                    action = Some(a);
+                                }
+                            }
+                            ui.add_space(6.0);
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    if ui
+                        .add_enabled(result.is_some(), MaterialButton::outlined(tr!("refresh")))
+                        .clicked()
+                    {
+                        refresh_clicked = true;
+                    }
+                });
+            });
+
+        if let Some(action) = action {
+            self.run_action(action);
+        } else if refresh_clicked {
+            self.refresh();
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
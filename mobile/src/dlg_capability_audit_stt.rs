@@ -0,0 +1,25 @@
+use crate::calc_capabilities_stt::CapabilityAudit;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgCapabilityAudit {
+    pub open: bool,
+    pub device: Option<String>,
+    /// Audit generated in the background; `None` while generating
+    pub result: Arc<Mutex<Option<Result<CapabilityAudit, String>>>>,
+    /// Outcome of the last revoke/disable action (success, message)
+    pub action_result: Option<(bool, String)>,
+    /// Package whose details were requested; taken by the owning tab
+    pub open_package: Option<String>,
+}
+
+impl Default for DlgCapabilityAudit {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            result: Arc::new(Mutex::new(None)),
+            action_result: None,
+            open_package: None,
+        }
+    }
+}
@@ -0,0 +1,304 @@
+pub use crate::dlg_components_stt::*;
+use crate::calc_components_stt::{AppComponent, ComponentChange, ComponentKind};
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+impl DlgComponents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the browser for one or more packages and start reading their components
+    pub fn open(&mut self, device: String, packages: Vec<String>) {
+        if packages.is_empty() {
+            return;
+        }
+        self.current_package = packages.first().cloned();
+        self.packages = packages;
+        self.device = Some(device);
+        self.action_result = None;
+        self.open = true;
+        self.refresh();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh(&mut self) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.results.lock() {
+            r.clear();
+        }
+        crate::calc_components::run_components_load_async(device, self.packages.clone(), self.results.clone());
+    }
+
+    fn start_changes(&mut self, changes: Vec<ComponentChange>) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if changes.is_empty() {
+            return;
+        }
+        if let Ok(mut r) = self.batch_result.lock() {
+            *r = None;
+        }
+        self.batch_running = true;
+        self.action_result = None;
+        crate::calc_components::run_component_changes_async(
+            device,
+            changes,
+            self.batch_result.clone(),
+            self.results.clone(),
+        );
+    }
+
+    fn kind_label(kind: ComponentKind) -> String {
+        match kind {
+            ComponentKind::Activity => tr!("components-activities"),
+            ComponentKind::Service => tr!("components-services"),
+            ComponentKind::Receiver => tr!("components-receivers"),
+            ComponentKind::Provider => tr!("components-providers"),
+        }
+    }
+
+    /// Render a component row; returns the requested enabled state when its button is clicked
+    fn render_component(ui: &mut egui::Ui, component: &AppComponent, enabled: bool) -> Option<bool> {
+        let mut toggled = None;
+        ui.horizontal_wrapped(|ui| {
+            let name = egui::RichText::new(&component.name);
+            ui.label(if component.enabled { name } else { name.strikethrough().weak() });
+            if component.exported {
+                ui.label(egui::RichText::new(tr!("components-exported")).small().color(egui::Color32::from_rgb(255, 152, 0)));
+            }
+            if let Some(tracker) = &component.tracker {
+                ui.label(
+                    egui::RichText::new(tr!("components-tracker", { name: tracker.clone() }))
+                        .small()
+                        .color(egui::Color32::from_rgb(211, 47, 47)),
+                );
+            }
+            let (label, enable) = if component.enabled {
+                (tr!("disable"), false)
+            } else {
+                (tr!("enable"), true)
+            };
+            if ui.add_enabled(enabled, egui::Button::new(label).small()).clicked() {
+                toggled = Some(enable);
+            }
+        });
+        toggled
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        if self.batch_running {
+            let finished = self.batch_result.lock().ok().and_then(|mut r| r.take());
+            match finished {
+                Some(outcome) => {
+                    self.batch_running = false;
+                    self.action_result = Some(if outcome.failures.is_empty() {
+                        (true, tr!("components-changes-done", { count: outcome.succeeded }))
+                    } else {
+                        (
+                            false,
+                            format!(
+                                "{}\n{}",
+                                tr!("components-changes-failed", {
+                                    count: outcome.failures.len(),
+                                    succeeded: outcome.succeeded
+                                }),
+                                outcome.failures.join("\n")
+                            ),
+                        )
+                    });
+                }
+                None => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            }
+        }
+
+        let results = self.results.lock().map(|r| r.clone()).unwrap_or_default();
+        let loading = results.len() < self.packages.len();
+        if loading {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+
+        // Enabled tracker components of every package the browser was opened for
+        let tracker_changes: Vec<ComponentChange> = results
+            .iter()
+            .filter_map(|(pkg_id, r)| r.as_ref().ok().map(|components| (pkg_id, components)))
+            .flat_map(|(pkg_id, components)| {
+                components
+                    .iter()
+                    .filter(|c| c.enabled && c.tracker.is_some())
+                    .map(move |c| ComponentChange {
+                        pkg_id: pkg_id.clone(),
+                        component: c.name.clone(),
+                        enable: false,
+                    })
+            })
+            .collect();
+        let has_signatures = crate::shared_store_stt::get_shared_store().has_tracker_signatures();
+
+        let mut close_clicked = false;
+        let mut refresh_clicked = false;
+        let mut changes: Vec<ComponentChange> = Vec::new();
+
+        egui::Window::new(tr!("components-title"))
+            .id(egui::Id::new("components_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("components-title"));
+                ui.add_space(8.0);
+
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .add_enabled(
+                            !loading && !self.batch_running && !tracker_changes.is_empty(),
+                            MaterialButton::filled(tr!("components-disable-trackers", {
+                                count: tracker_changes.len()
+                            }))
+                            .small(),
+                        )
+                        .on_hover_text(tr!("components-disable-trackers-desc"))
+                        .clicked()
+                    {
+                        changes = tracker_changes.clone();
+                    }
+                    if self.batch_running {
+                        ui.spinner();
+                        ui.label(tr!("components-applying"));
+                    }
+                });
+                if !has_signatures {
+                    ui.label(egui::RichText::new(tr!("components-no-signatures")).small().weak());
+                }
+
+                if let Some((ok, message)) = &self.action_result {
+                    let color = if *ok {
+                        egui::Color32::from_rgb(56, 142, 60)
+                    } else {
+                        egui::Color32::from_rgb(211, 47, 47)
+                    };
+                    ui.colored_label(color, message);
+                }
+                ui.separator();
+
+                ui.horizontal_wrapped(|ui| {
+                    if self.packages.len() > 1 {
+                        egui::ComboBox::from_id_salt("components_package")
+                            .selected_text(self.current_package.clone().unwrap_or_default())
+                            .show_ui(ui, |ui| {
+                                for pkg_id in &self.packages {
+                                    ui.selectable_value(&mut self.current_package, Some(pkg_id.clone()), pkg_id);
+                                }
+                            });
+                    } else if let Some(pkg_id) = &self.current_package {
+                        ui.label(egui::RichText::new(pkg_id).strong());
+                    }
+                    ui.label(tr!("filter"));
+                    ui.add(egui::TextEdit::singleline(&mut self.search_query).desired_width(200.0));
+                    ui.checkbox(&mut self.trackers_only, tr!("components-trackers-only"));
+                });
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("components_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        let Some(pkg_id) = self.current_package.clone() else {
+                            return;
+                        };
+                        let components = match results.get(&pkg_id) {
+                            None => {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.label(tr!("components-loading"));
+                                });
+                                return;
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e);
+                                return;
+                            }
+                            Some(Ok(components)) => components,
+                        };
+                        let query = self.search_query.to_lowercase();
+                        let visible: Vec<&AppComponent> = components
+                            .iter()
+                            .filter(|c| !self.trackers_only || c.tracker.is_some())
+                            .filter(|c| query.is_empty() || c.name.to_lowercase().contains(&query))
+                            .collect();
+                        if visible.is_empty() {
+                            ui.label(tr!("components-none"));
+                        }
+
+                        for kind in ComponentKind::ALL {
+                            let of_kind: Vec<&AppComponent> = visible.iter().copied().filter(|c| c.kind == kind).collect();
+                            if of_kind.is_empty() {
+                                continue;
+                            }
+                            let disabled_count = of_kind.iter().filter(|c| !c.enabled).count();
+                            egui::CollapsingHeader::new(tr!("components-kind-header", {
+                                kind: Self::kind_label(kind),
+                                count: of_kind.len(),
+                                disabled: disabled_count
+                            }))
+                            .id_salt(("components_kind", &pkg_id, kind))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for component in of_kind {
+                                    if let Some(enable) = Self::render_component(ui, component, !self.batch_running) {
+                                        changes = vec![ComponentChange {
+                                            pkg_id: pkg_id.clone(),
+                                            component: component.name.clone(),
+                                            enable,
+                                        }];
+                                    }
+                                }
+                            });
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    if ui
+                        .add_enabled(!loading && !self.batch_running, MaterialButton::outlined(tr!("refresh")))
+                        .clicked()
+                    {
+                        refresh_clicked = true;
+                    }
+                });
+            });
+
+        if !changes.is_empty() {
+            self.start_changes(changes);
+        } else if refresh_clicked {
+            self.refresh();
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::calc_components_stt::{ComponentBatchOutcome, ComponentsResults};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgComponents {
+    pub open: bool,
+    pub device: Option<String>,
+    /// Packages the browser was opened for
+    pub packages: Vec<String>,
+    /// Package whose components are listed
+    pub current_package: Option<String>,
+    /// Components loaded in the background per package
+    pub results: ComponentsResults,
+    /// Case-insensitive filter on the class name
+    pub search_query: String,
+    /// Show only components matching a tracker signature
+    pub trackers_only: bool,
+    /// Outcome of the running change; `None` while applying
+    pub batch_result: Arc<Mutex<Option<ComponentBatchOutcome>>>,
+    pub batch_running: bool,
+    /// Outcome of the last change (success, message)
+    pub action_result: Option<(bool, String)>,
+}
+
+impl Default for DlgComponents {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            packages: Vec::new(),
+            current_package: None,
+            results: Arc::new(Mutex::new(HashMap::new())),
+            search_query: String::new(),
+            trackers_only: false,
+            batch_result: Arc::new(Mutex::new(None)),
+            batch_running: false,
+            action_result: None,
+        }
+    }
+}
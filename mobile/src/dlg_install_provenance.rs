@@ -0,0 +1,169 @@
+pub use crate::dlg_install_provenance_stt::*;
+use crate::calc_provenance_stt::{InstallSource, PackageProvenance};
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+impl DlgInstallProvenance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the dialog and classify the currently loaded packages
+    pub fn open(&mut self) {
+        self.refresh();
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh(&mut self) {
+        let store = crate::shared_store_stt::get_shared_store();
+        let packages = store.get_installed_packages();
+        let labels = crate::calc_stalkerware::collect_package_labels(&packages);
+        self.rows = crate::calc_provenance::build_provenance(&packages, &labels);
+    }
+
+    fn source_text(source: InstallSource) -> String {
+        match source {
+            InstallSource::PlayStore => tr!("provenance-play-store"),
+            InstallSource::FDroid => tr!("provenance-fdroid"),
+            InstallSource::Aurora => tr!("provenance-aurora"),
+            InstallSource::OtherStore => tr!("provenance-other-store"),
+            InstallSource::Adb => tr!("provenance-adb"),
+            InstallSource::Browser => tr!("provenance-browser"),
+            InstallSource::Messaging => tr!("provenance-messaging"),
+            InstallSource::Preinstalled => tr!("provenance-preinstalled"),
+            InstallSource::Unknown => tr!("provenance-unknown"),
+        }
+    }
+
+    fn render_row(&mut self, ui: &mut egui::Ui, row: &PackageProvenance) {
+        ui.horizontal_wrapped(|ui| {
+            let source = egui::RichText::new(Self::source_text(row.source)).strong();
+            if row.source.needs_review() {
+                ui.label(source.color(egui::Color32::from_rgb(255, 152, 0)))
+                    .on_hover_text(tr!("provenance-review-desc"));
+            } else {
+                ui.label(source);
+            }
+            if ui.link(&row.pkg_id).clicked() {
+                self.open_package = Some(row.pkg_id.clone());
+            }
+            if let Some(label) = &row.label {
+                ui.label(format!("({})", label));
+            }
+        });
+        let chain: Vec<String> = [
+            ("installer", &row.installer),
+            ("initiating", &row.initiating),
+            ("originating", &row.originating),
+        ]
+        .iter()
+        .filter_map(|(name, pkg)| pkg.as_ref().map(|p| format!("{}={}", name, p)))
+        .collect();
+        if !chain.is_empty() {
+            ui.label(egui::RichText::new(format!("  {}", chain.join("  "))).small().weak());
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let mut close_clicked = false;
+        let mut refresh_clicked = false;
+
+        egui::Window::new(tr!("provenance-title"))
+            .id(egui::Id::new("install_provenance_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("provenance-title"));
+                ui.add_space(8.0);
+
+                let counts = crate::calc_provenance::count_by_source(&self.rows);
+                let review_count: usize = InstallSource::ALL
+                    .iter()
+                    .filter(|s| s.needs_review())
+                    .map(|s| counts.get(s).copied().unwrap_or(0))
+                    .sum();
+
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .selectable_label(self.source_filter.is_none(), tr!("provenance-all", { count: self.rows.len() }))
+                        .clicked()
+                    {
+                        self.source_filter = None;
+                    }
+                    for source in InstallSource::ALL {
+                        let count = counts.get(&source).copied().unwrap_or(0);
+                        if count == 0 {
+                            continue;
+                        }
+                        if ui
+                            .selectable_label(
+                                self.source_filter == Some(source),
+                                format!("{} ({})", Self::source_text(source), count),
+                            )
+                            .clicked()
+                        {
+                            self.source_filter = Some(source);
+                        }
+                    }
+                });
+                ui.checkbox(&mut self.review_only, tr!("provenance-review-only", { count: review_count }));
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+                let rows: Vec<PackageProvenance> = self
+                    .rows
+                    .iter()
+                    .filter(|r| self.source_filter.map_or(true, |s| s == r.source))
+                    .filter(|r| !self.review_only || r.source.needs_review())
+                    .cloned()
+                    .collect();
+
+                egui::ScrollArea::both()
+                    .id_salt("install_provenance_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        if self.rows.is_empty() {
+                            ui.label(tr!("provenance-no-packages"));
+                        }
+                        for row in &rows {
+                            self.render_row(ui, row);
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    if ui.add(MaterialButton::outlined(tr!("refresh"))).clicked() {
+                        refresh_clicked = true;
+                    }
+                });
+            });
+
+        if refresh_clicked {
+            self.refresh();
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
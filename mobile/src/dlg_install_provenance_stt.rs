@@ -0,0 +1,25 @@
+use crate::calc_provenance_stt::{InstallSource, PackageProvenance};
+
+pub struct DlgInstallProvenance {
+    pub open: bool,
+    /// Provenance of the installed packages, computed on open
+    pub rows: Vec<PackageProvenance>,
+    /// Show only one source; `None` shows all
+    pub source_filter: Option<InstallSource>,
+    /// Show only packages sideloaded from browsers or messaging apps
+    pub review_only: bool,
+    /// Package whose details were requested; taken by the owning tab
+    pub open_package: Option<String>,
+}
+
+impl Default for DlgInstallProvenance {
+    fn default() -> Self {
+        Self {
+            open: false,
+            rows: Vec::new(),
+            source_filter: None,
+            review_only: false,
+            open_package: None,
+        }
+    }
+}
@@ -1,4 +1,9 @@
 use crate::adb::PackageFingerprint;
+use crate::calc_apksig_stt::SignatureScheme;
+use crate::calc_axml_stt::{ManifestComponent, ManifestLoadState};
+use crate::calc_hybridanalysis_stt::HaReportDetails;
+use crate::calc_permissions_stt::PermissionChange;
+use crate::calc_virustotal_stt::VtReportDetails;
 use crate::shared_store_stt::get_shared_store;
 use crate::uad_shizuku_app::UadNgLists;
 pub use crate::dlg_package_details_stt::*;
@@ -13,6 +18,9 @@ impl DlgPackageDetails {
     pub fn open(&mut self, package_index: usize) {
         self.selected_package_index = Some(package_index);
         self.selected_tab = 0;
+        self.vt_details.clear();
+        self.ha_details.clear();
+        self.permission_result = None;
         self.open = true;
     }
 
@@ -25,6 +33,7 @@ impl DlgPackageDetails {
         ctx: &egui::Context,
         installed_packages: &[PackageFingerprint],
         uad_ng_lists: &Option<UadNgLists>,
+        device_serial: Option<&str>,
     ) {
         if !self.open {
             return;
@@ -54,6 +63,17 @@ impl DlgPackageDetails {
             let ha_state = store.get_ha_scanner_state();
             ha_state.and_then(|state| state.lock().ok().and_then(|s| s.get(pkg_id).cloned())).is_some()
         };
+        let has_malwarebazaar = {
+            let mb_state = store.get_mb_scanner_state();
+            mb_state.and_then(|state| state.lock().ok().and_then(|s| s.get(pkg_id).cloned())).is_some()
+        };
+        let has_koodous = {
+            let koodous_state = store.get_koodous_scanner_state();
+            koodous_state.and_then(|state| state.lock().ok().and_then(|s| s.get(pkg_id).cloned())).is_some()
+        };
+        let has_trackers = store.get_tracker_result(pkg_id).is_some();
+        let has_permissions = !crate::calc_permissions::runtime_permissions(package).is_empty();
+        let has_nativelibs = store.get_native_libraries(pkg_id).is_some();
 
         let mut close_clicked = false;
 
@@ -73,8 +93,12 @@ impl DlgPackageDetails {
                 // Build tab labels dynamically
                 let mut tabs = tabs_primary(&mut self.selected_tab)
                     .id_salt("package_details_tabs")
-                    .tab("pkg");
+                    .tab("pkg")
+                    .tab("manifest");
 
+                if has_permissions {
+                    tabs = tabs.tab("permissions");
+                }
                 if has_uad {
                     tabs = tabs.tab("uad");
                 }
@@ -93,6 +117,18 @@ impl DlgPackageDetails {
                 if has_hybridanalysis {
                     tabs = tabs.tab("hybridanalysis");
                 }
+                if has_malwarebazaar {
+                    tabs = tabs.tab("malwarebazaar");
+                }
+                if has_koodous {
+                    tabs = tabs.tab("koodous");
+                }
+                if has_trackers {
+                    tabs = tabs.tab("trackers");
+                }
+                if has_nativelibs {
+                    tabs = tabs.tab("nativelibs");
+                }
 
                 ui.add(tabs);
                 ui.add_space(10.0);
@@ -103,7 +139,7 @@ impl DlgPackageDetails {
                 let mut tab_index = 0;
                 let mut selected_tab_type = "pkg";
                 
-                for tab_type in ["pkg", "uad", "googleplay", "fdroid", "apkmirror", "virustotal", "hybridanalysis"] {
+                for tab_type in ["pkg", "manifest", "permissions", "uad", "googleplay", "fdroid", "apkmirror", "virustotal", "hybridanalysis", "malwarebazaar", "koodous", "trackers", "nativelibs"] {
                     match tab_type {
                         "pkg" => {
                             if self.selected_tab == tab_index {
@@ -112,6 +148,20 @@ impl DlgPackageDetails {
                             }
                             tab_index += 1;
                         }
+                        "manifest" => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "manifest";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
+                        "permissions" if has_permissions => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "permissions";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
                         "uad" if has_uad => {
                             if self.selected_tab == tab_index {
                                 selected_tab_type = "uad";
@@ -154,6 +204,34 @@ impl DlgPackageDetails {
                             }
                             tab_index += 1;
                         }
+                        "malwarebazaar" if has_malwarebazaar => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "malwarebazaar";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
+                        "koodous" if has_koodous => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "koodous";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
+                        "trackers" if has_trackers => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "trackers";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
+                        "nativelibs" if has_nativelibs => {
+                            if self.selected_tab == tab_index {
+                                selected_tab_type = "nativelibs";
+                                break;
+                            }
+                            tab_index += 1;
+                        }
                         _ => {}
                     }
                 }
@@ -164,12 +242,18 @@ impl DlgPackageDetails {
                     .show(ui, |ui| {
                         match selected_tab_type {
                             "pkg" => self.render_pkg_tab(ui, package),
+                            "manifest" => self.render_manifest_tab(ui, pkg_id, device_serial),
+                            "permissions" => self.render_permissions_tab(ui, package, device_serial),
                             "uad" => self.render_uad_tab(ui, pkg_id, uad_ng_lists),
                             "googleplay" => self.render_googleplay_tab(ui, pkg_id),
                             "fdroid" => self.render_fdroid_tab(ui, pkg_id),
                             "apkmirror" => self.render_apkmirror_tab(ui, pkg_id),
                             "virustotal" => self.render_virustotal_tab(ui, pkg_id),
                             "hybridanalysis" => self.render_hybridanalysis_tab(ui, pkg_id),
+                            "malwarebazaar" => self.render_malwarebazaar_tab(ui, pkg_id),
+                            "koodous" => self.render_koodous_tab(ui, pkg_id),
+                            "trackers" => self.render_trackers_tab(ui, pkg_id),
+                            "nativelibs" => self.render_nativelibs_tab(ui, pkg_id),
                             _ => {}
                         }
                     });
@@ -333,6 +417,178 @@ impl DlgPackageDetails {
                 }
             }
         }
+
+        let store = get_shared_store();
+        if let Some(certificates) = store.get_signing_certificates(&package.pkg) {
+            ui.add_space(8.0);
+            ui.separator();
+            ui.label(format!("Signing Certificates ({}):", certificates.len()));
+
+            if let Some(family) = store
+                .get_stalkerware_indicators()
+                .and_then(|ind| ind.get_family_by_certificates(&certificates).cloned())
+            {
+                ui.colored_label(
+                    egui::Color32::from_rgb(211, 47, 47),
+                    format!("Signed with a known stalkerware certificate: {}", family),
+                );
+            }
+
+            for certificate in &certificates {
+                let schemes: Vec<&str> = certificate
+                    .schemes
+                    .iter()
+                    .map(|s| match s {
+                        SignatureScheme::V1 => "v1",
+                        SignatureScheme::V2 => "v2",
+                        SignatureScheme::V3 => "v3",
+                    })
+                    .collect();
+                ui.add_space(4.0);
+                ui.label(format!("  • Schemes: {}", schemes.join(", ")));
+                ui.horizontal(|ui| {
+                    ui.label("    SHA-1:");
+                    ui.add(egui::Label::new(&certificate.sha1).wrap());
+                });
+                ui.horizontal(|ui| {
+                    ui.label("    SHA-256:");
+                    ui.add(egui::Label::new(&certificate.sha256).wrap());
+                });
+            }
+        }
+    }
+
+    fn render_manifest_tab(&self, ui: &mut egui::Ui, pkg_id: &str, device_serial: Option<&str>) {
+        ui.heading("AndroidManifest.xml");
+        ui.add_space(4.0);
+
+        let state = get_shared_store().get_apk_manifest_state(pkg_id);
+        let manifest = match state {
+            Some(ManifestLoadState::Loaded(manifest)) => manifest,
+            Some(ManifestLoadState::Loading) => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Pulling and parsing APK...");
+                });
+                return;
+            }
+            other => {
+                if let Some(ManifestLoadState::Failed(error)) = other {
+                    ui.colored_label(egui::Color32::from_rgb(244, 67, 54), format!("Error: {}", error));
+                    ui.add_space(4.0);
+                }
+                ui.label("Pull the base APK from the device and parse its manifest.");
+                ui.add_space(4.0);
+                match device_serial {
+                    Some(device) => {
+                        if ui.add(MaterialButton::filled("Analyze APK")).clicked() {
+                            crate::calc_axml::spawn_manifest_analysis(
+                                device.to_string(),
+                                pkg_id.to_string(),
+                                ui.ctx().clone(),
+                            );
+                        }
+                    }
+                    None => {
+                        ui.label("No device selected.");
+                    }
+                }
+                return;
+            }
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Package:");
+            ui.label(&manifest.package);
+        });
+        if let Some(version_name) = &manifest.version_name {
+            ui.horizontal(|ui| {
+                ui.label("Version Name:");
+                ui.label(version_name);
+            });
+        }
+        if let Some(version_code) = manifest.version_code {
+            ui.horizontal(|ui| {
+                ui.label("Version Code:");
+                ui.label(version_code.to_string());
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Min SDK:");
+            ui.label(manifest.min_sdk.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()));
+            ui.add_space(8.0);
+            ui.label("Target SDK:");
+            ui.label(manifest.target_sdk.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()));
+        });
+
+        let flag_to_string = |value: Option<bool>| match value {
+            Some(true) => "Yes",
+            Some(false) => "No",
+            None => "Not set",
+        };
+        ui.horizontal(|ui| {
+            ui.label("Debuggable:");
+            ui.label(flag_to_string(manifest.debuggable));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Allow Backup:");
+            ui.label(flag_to_string(manifest.allow_backup));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Uses Cleartext Traffic:");
+            ui.label(flag_to_string(manifest.uses_cleartext_traffic));
+        });
+        ui.add_space(8.0);
+
+        if !manifest.permissions.is_empty() {
+            ui.label(format!("Requested Permissions ({}):", manifest.permissions.len()));
+            for perm in &manifest.permissions {
+                ui.add(egui::Label::new(format!("  • {}", perm)).wrap());
+            }
+            ui.add_space(8.0);
+        }
+
+        for (title, components) in [
+            ("Activities", &manifest.activities),
+            ("Services", &manifest.services),
+            ("Receivers", &manifest.receivers),
+            ("Providers", &manifest.providers),
+        ] {
+            if components.is_empty() {
+                continue;
+            }
+            let exported_count = components.iter().filter(|c| c.is_exported()).count();
+            ui.collapsing(
+                format!("{} ({}, {} exported)", title, components.len(), exported_count),
+                |ui| {
+                    for component in components {
+                        Self::render_manifest_component(ui, component);
+                    }
+                },
+            );
+        }
+    }
+
+    fn render_manifest_component(ui: &mut egui::Ui, component: &ManifestComponent) {
+        let exported = if component.is_exported() {
+            egui::RichText::new(" [exported]").color(egui::Color32::from_rgb(255, 152, 0))
+        } else {
+            egui::RichText::new("")
+        };
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("  • {}", component.name));
+            ui.label(exported);
+        });
+        if let Some(permission) = &component.permission {
+            ui.add(egui::Label::new(format!("      permission: {}", permission)).wrap());
+        }
+        for filter in &component.intent_filters {
+            let mut parts: Vec<String> = Vec::new();
+            parts.extend(filter.actions.iter().cloned());
+            parts.extend(filter.categories.iter().cloned());
+            parts.extend(filter.data_schemes.iter().map(|s| format!("{}://", s)));
+            ui.add(egui::Label::new(format!("      intent-filter: {}", parts.join(", "))).wrap());
+        }
     }
 
     fn render_uad_tab(&self, ui: &mut egui::Ui, pkg_id: &str, uad_ng_lists: &Option<UadNgLists>) {
@@ -575,7 +831,7 @@ impl DlgPackageDetails {
         }
     }
 
-    fn render_virustotal_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+    fn render_virustotal_tab(&mut self, ui: &mut egui::Ui, pkg_id: &str) {
         let store = get_shared_store();
         let vt_state = store.get_vt_scanner_state();
         
@@ -633,6 +889,21 @@ impl DlgPackageDetails {
                                 
                                 if let Some(error) = &file_result.error {
                                     ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
+                                } else if file_result.trusted {
+                                    ui.label("Trusted (allowlist): this hash is known to be clean and was not looked up.");
+                                } else if !file_result.not_found && !file_result.skipped {
+                                    let details = self
+                                        .vt_details
+                                        .entry(file_result.sha256.clone())
+                                        .or_insert_with(|| crate::calc_virustotal::load_report_details(&file_result.sha256));
+                                    ui.push_id(&file_result.sha256, |ui| match details {
+                                        Some(details) if !details.engines.is_empty() => {
+                                            Self::render_virustotal_details(ui, details)
+                                        }
+                                        _ => {
+                                            ui.label("No engine details cached for this file. Refresh the scan to fetch them.");
+                                        }
+                                    });
                                 }
                                 ui.add_space(4.0);
                             }
@@ -658,7 +929,260 @@ impl DlgPackageDetails {
         ui.label("No VirusTotal scan results available for this package");
     }
 
-    fn render_hybridanalysis_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+    fn render_virustotal_details(ui: &mut egui::Ui, details: &VtReportDetails) {
+        if let Some(ts) = details.first_submission_date {
+            ui.horizontal(|ui| {
+                ui.label("First Submission:");
+                ui.label(
+                    chrono::DateTime::from_timestamp(ts, 0)
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                        .unwrap_or_else(|| ts.to_string()),
+                );
+            });
+        }
+        if let Some(label) = &details.suggested_threat_label {
+            ui.horizontal(|ui| {
+                ui.label("Threat Label:");
+                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), label);
+            });
+        }
+        if !details.popular_threat_names.is_empty() {
+            let names: Vec<String> = details
+                .popular_threat_names
+                .iter()
+                .map(|(name, count)| format!("{} ({})", name, count))
+                .collect();
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Popular Threat Names:");
+                ui.label(names.join(", "));
+            });
+        }
+        if !details.names.is_empty() {
+            ui.collapsing(format!("Known Names ({})", details.names.len()), |ui| {
+                for name in &details.names {
+                    ui.add(egui::Label::new(format!("  • {}", name)).wrap());
+                }
+            });
+        }
+
+        if !details.yara_hits.is_empty() {
+            ui.collapsing(format!("Crowdsourced YARA ({})", details.yara_hits.len()), |ui| {
+                for hit in &details.yara_hits {
+                    ui.label(egui::RichText::new(format!("{} ({})", hit.rule_name, hit.ruleset_name)).strong());
+                    if let Some(description) = &hit.description {
+                        ui.add(egui::Label::new(format!("      {}", description)).wrap());
+                    }
+                    if let Some(author) = &hit.author {
+                        ui.label(format!("      author: {}", author));
+                    }
+                }
+            });
+        }
+        if !details.sigma_hits.is_empty() {
+            ui.collapsing(format!("Sigma Rules ({})", details.sigma_hits.len()), |ui| {
+                for hit in &details.sigma_hits {
+                    let color = match hit.rule_level.as_str() {
+                        "critical" | "high" => egui::Color32::from_rgb(211, 47, 47),
+                        "medium" => egui::Color32::from_rgb(255, 152, 0),
+                        _ => egui::Color32::GRAY,
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        ui.colored_label(color, format!("[{}]", hit.rule_level));
+                        ui.label(&hit.rule_title);
+                    });
+                    if let Some(description) = &hit.rule_description {
+                        ui.add(egui::Label::new(format!("      {}", description)).wrap());
+                    }
+                }
+            });
+        }
+
+        if !details.engines.is_empty() {
+            let detected = details
+                .engines
+                .iter()
+                .filter(|e| e.category == "malicious" || e.category == "suspicious")
+                .count();
+            ui.collapsing(
+                format!("Engines ({} of {} detected)", detected, details.engines.len()),
+                |ui| {
+                    egui::Grid::new("vt_engine_grid").striped(true).num_columns(3).show(ui, |ui| {
+                        ui.label(egui::RichText::new("Engine").strong());
+                        ui.label(egui::RichText::new("Category").strong());
+                        ui.label(egui::RichText::new("Result").strong());
+                        ui.end_row();
+                        for engine in &details.engines {
+                            let color = match engine.category.as_str() {
+                                "malicious" => egui::Color32::from_rgb(211, 47, 47),
+                                "suspicious" => egui::Color32::from_rgb(255, 152, 0),
+                                _ => ui.visuals().text_color(),
+                            };
+                            ui.label(&engine.engine_name);
+                            ui.colored_label(color, &engine.category);
+                            ui.label(engine.result.as_deref().unwrap_or("-"));
+                            ui.end_row();
+                        }
+                    });
+                },
+            );
+        }
+    }
+
+    fn render_trackers_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+        let Some(trackers) = get_shared_store().get_tracker_result(pkg_id) else {
+            return;
+        };
+
+        ui.heading("Trackers");
+        ui.add_space(4.0);
+        ui.label("Detected offline from DEX class names (Exodus Privacy signatures).");
+        ui.add_space(8.0);
+
+        if trackers.is_empty() {
+            ui.label("No known trackers found.");
+            return;
+        }
+
+        ui.label(format!("Trackers found: {}", trackers.len()));
+        for tracker in &trackers {
+            ui.separator();
+            ui.label(egui::RichText::new(&tracker.name).strong());
+            if !tracker.categories.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label("Categories:");
+                    ui.label(tracker.categories.join(", "));
+                });
+            }
+            if !tracker.website.is_empty() {
+                ui.hyperlink_to(&tracker.website, &tracker.website);
+            }
+            ui.hyperlink_to(
+                "Exodus Privacy",
+                format!("https://reports.exodus-privacy.eu.org/en/trackers/{}/", tracker.id),
+            );
+        }
+    }
+
+    fn render_permissions_tab(&mut self, ui: &mut egui::Ui, package: &PackageFingerprint, device_serial: Option<&str>) {
+        ui.heading("Runtime Permissions");
+        ui.add_space(4.0);
+        ui.label("Grant or revoke runtime permissions per user. Changes are journaled and can be reverted from the permission manager.");
+        ui.add_space(8.0);
+
+        if let Some((ok, message)) = &self.permission_result {
+            let color = if *ok {
+                egui::Color32::from_rgb(56, 142, 60)
+            } else {
+                egui::Color32::from_rgb(211, 47, 47)
+            };
+            ui.colored_label(color, message);
+            ui.add_space(4.0);
+        }
+
+        let mut change: Option<PermissionChange> = None;
+        let mut current_user: Option<i32> = None;
+        for permission in crate::calc_permissions::runtime_permissions(package) {
+            if current_user != Some(permission.user_id) {
+                current_user = Some(permission.user_id);
+                ui.separator();
+                ui.label(egui::RichText::new(format!("User {}", permission.user_id)).strong());
+            }
+            ui.horizontal_wrapped(|ui| {
+                if permission.granted {
+                    ui.colored_label(egui::Color32::from_rgb(255, 152, 0), "Granted");
+                } else {
+                    ui.label(egui::RichText::new("Denied").weak());
+                }
+                ui.label(&permission.name);
+                if permission.fixed {
+                    ui.label(egui::RichText::new("fixed by system or policy").small().weak());
+                    return;
+                }
+                let (text, grant) = if permission.granted { ("Revoke", false) } else { ("Grant", true) };
+                if ui
+                    .add_enabled(device_serial.is_some(), MaterialButton::outlined(text).small())
+                    .clicked()
+                {
+                    change = Some(PermissionChange {
+                        pkg_id: permission.pkg_id.clone(),
+                        user_id: permission.user_id,
+                        permission: permission.name.clone(),
+                        grant,
+                    });
+                }
+            });
+        }
+
+        if let (Some(change), Some(device)) = (change, device_serial) {
+            let verb = if change.grant { "grant" } else { "revoke" };
+            self.permission_result = Some(match crate::calc_permissions::apply_permission_change(device, &change) {
+                Ok(_) => (true, format!("{}: {} done", change.permission, verb)),
+                Err(e) => (false, format!("Failed to {} {}: {}", verb, change.permission, e.to_string().trim())),
+            });
+        }
+    }
+
+    fn render_nativelibs_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+        let Some(libraries) = get_shared_store().get_native_libraries(pkg_id) else {
+            return;
+        };
+
+        ui.heading("Native Libraries");
+        ui.add_space(4.0);
+        ui.label("Shared objects bundled under lib/<abi>/ in the package's APKs.");
+        ui.add_space(8.0);
+
+        if libraries.is_empty() {
+            ui.label("No native libraries found.");
+            return;
+        }
+
+        ui.label(format!("Libraries found: {}", libraries.len()));
+        for lib in &libraries {
+            ui.separator();
+            ui.label(egui::RichText::new(&lib.entry).strong());
+            ui.horizontal(|ui| {
+                ui.label("APK:");
+                ui.add(egui::Label::new(&lib.apk_path).wrap());
+            });
+            ui.horizontal(|ui| {
+                ui.label("Architecture:");
+                ui.label(format!("{} ({}-bit, {})", lib.arch, lib.bits, lib.abi));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Size:");
+                ui.label(format!("{} bytes", lib.size));
+            });
+            ui.horizontal(|ui| {
+                ui.label("SHA256:");
+                ui.add(egui::Label::new(&lib.sha256).wrap());
+            });
+            if !lib.needed.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label("Needed:");
+                    ui.add(egui::Label::new(lib.needed.join(", ")).wrap());
+                });
+            }
+            if !lib.notable_imports.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label("Notable imports:");
+                    ui.add(egui::Label::new(lib.notable_imports.join(", ")).wrap());
+                });
+            }
+            for vuln in &lib.vulnerable {
+                let version = vuln.version.as_deref().unwrap_or("unknown version");
+                ui.colored_label(
+                    egui::Color32::from_rgb(211, 47, 47),
+                    format!("{} {}: {}", vuln.library, version, vuln.advisory),
+                );
+            }
+            for note in &lib.notes {
+                ui.colored_label(egui::Color32::from_rgb(255, 152, 0), note);
+            }
+        }
+    }
+
+    fn render_hybridanalysis_tab(&mut self, ui: &mut egui::Ui, pkg_id: &str) {
         let store = get_shared_store();
         let ha_state = store.get_ha_scanner_state();
         
@@ -722,6 +1246,17 @@ impl DlgPackageDetails {
                                 
                                 if let Some(error) = &file_result.error_message {
                                     ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
+                                } else if file_result.threat_score.is_some() || file_result.total_signatures.is_some() {
+                                    let details = self
+                                        .ha_details
+                                        .entry(file_result.sha256.clone())
+                                        .or_insert_with(|| crate::calc_hybridanalysis::load_report_details(&file_result.sha256));
+                                    ui.push_id(&file_result.sha256, |ui| match details {
+                                        Some(details) => Self::render_hybridanalysis_details(ui, details),
+                                        None => {
+                                            ui.label("No report details cached for this file. Refresh the scan to fetch them.");
+                                        }
+                                    });
                                 }
                                 ui.add_space(4.0);
                             }
@@ -747,6 +1282,280 @@ impl DlgPackageDetails {
         ui.label("No Hybrid Analysis scan results available for this package");
     }
 
+    fn render_hybridanalysis_details(ui: &mut egui::Ui, details: &HaReportDetails) {
+        if !details.environment_description.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Environment:");
+                ui.label(&details.environment_description);
+            });
+        }
+
+        if details.signatures.is_empty() && details.mitre_attcks.is_empty() && details.hosts.is_empty() {
+            ui.label("No signature details cached for this file. Refresh the scan to fetch them.");
+        }
+
+        if !details.signatures.is_empty() {
+            ui.collapsing(format!("Signatures ({})", details.signatures.len()), |ui| {
+                for sig in &details.signatures {
+                    let (color, level) = match sig.threat_level {
+                        Some(2) => (egui::Color32::from_rgb(211, 47, 47), "malicious"),
+                        Some(1) => (egui::Color32::from_rgb(255, 152, 0), "suspicious"),
+                        _ => (egui::Color32::GRAY, "informative"),
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        ui.colored_label(color, format!("[{}]", sig.threat_level_human.as_deref().unwrap_or(level)));
+                        ui.label(egui::RichText::new(&sig.name).strong());
+                        if let Some(category) = &sig.category {
+                            ui.label(format!("({})", category));
+                        }
+                        if let Some(attck_id) = &sig.attck_id {
+                            match &sig.attck_id_wiki {
+                                Some(wiki) => ui.hyperlink_to(attck_id, wiki),
+                                None => ui.label(attck_id),
+                            };
+                        }
+                    });
+                    if let Some(description) = &sig.description {
+                        ui.add(egui::Label::new(format!("      {}", description)).wrap());
+                    }
+                }
+            });
+        }
+
+        if !details.mitre_attcks.is_empty() {
+            ui.collapsing(format!("MITRE ATT&CK ({})", details.mitre_attcks.len()), |ui| {
+                egui::Grid::new("ha_mitre_grid").striped(true).num_columns(3).show(ui, |ui| {
+                    ui.label(egui::RichText::new("Tactic").strong());
+                    ui.label(egui::RichText::new("Technique").strong());
+                    ui.label(egui::RichText::new("ID").strong());
+                    ui.end_row();
+                    for attack in &details.mitre_attcks {
+                        ui.label(attack.tactic.as_deref().unwrap_or("-"));
+                        ui.label(attack.technique.as_deref().unwrap_or("-"));
+                        match (&attack.attck_id, &attack.attck_id_wiki) {
+                            (Some(id), Some(wiki)) => ui.hyperlink_to(id, wiki),
+                            (Some(id), None) => ui.label(id),
+                            _ => ui.label("-"),
+                        };
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+
+        if !details.hosts.is_empty() {
+            ui.collapsing(format!("Contacted Hosts ({})", details.hosts.len()), |ui| {
+                for host in &details.hosts {
+                    ui.label(format!("  • {}", host));
+                }
+            });
+        }
+
+        if !details.extracted_files.is_empty() {
+            ui.collapsing(format!("Dropped Files ({})", details.extracted_files.len()), |ui| {
+                for file in &details.extracted_files {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(egui::RichText::new(&file.name).strong());
+                        if let Some(size) = file.file_size {
+                            ui.label(format!("{} bytes", size));
+                        }
+                        if !file.type_tags.is_empty() {
+                            ui.label(format!("[{}]", file.type_tags.join(", ")));
+                        }
+                        if let Some(threat) = &file.threat_level_readable {
+                            ui.label(threat);
+                        }
+                    });
+                    if let Some(sha256) = &file.sha256 {
+                        ui.add(egui::Label::new(format!("      {}", sha256)).wrap());
+                    }
+                }
+            });
+        }
+    }
+
+    fn render_malwarebazaar_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+        let store = get_shared_store();
+        let mb_state = store.get_mb_scanner_state();
+
+        if let Some(state) = mb_state {
+            if let Ok(scanner_state) = state.lock() {
+                if let Some(scan_status) = scanner_state.get(pkg_id) {
+                    ui.heading("MalwareBazaar Lookup Results");
+                    ui.add_space(4.0);
+
+                    match scan_status {
+                        crate::calc_malwarebazaar_stt::ScanStatus::Completed(result) => {
+                            ui.label(format!("Files looked up: {}", result.file_results.len()));
+                            ui.add_space(8.0);
+
+                            for file_result in &result.file_results {
+                                ui.separator();
+                                ui.label(format!("File: {}", file_result.file_path));
+
+                                ui.horizontal(|ui| {
+                                    ui.label("SHA256:");
+                                    ui.add(egui::Label::new(&file_result.sha256).wrap());
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Verdict:");
+                                    if file_result.is_listed() {
+                                        ui.colored_label(egui::Color32::RED, &file_result.verdict);
+                                    } else {
+                                        ui.label(&file_result.verdict);
+                                    }
+                                });
+
+                                if let Some(signature) = &file_result.signature {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Signature:");
+                                        ui.label(signature);
+                                    });
+                                }
+
+                                if let Some(file_type) = &file_result.file_type {
+                                    ui.horizontal(|ui| {
+                                        ui.label("File Type:");
+                                        ui.label(file_type);
+                                    });
+                                }
+
+                                if let Some(first_seen) = &file_result.first_seen {
+                                    ui.horizontal(|ui| {
+                                        ui.label("First Seen:");
+                                        ui.label(first_seen);
+                                    });
+                                }
+
+                                if !file_result.tags.is_empty() {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Tags:");
+                                        ui.label(file_result.tags.join(", "));
+                                    });
+                                }
+
+                                if file_result.is_listed() {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Report Link:");
+                                        ui.hyperlink_to("View on MalwareBazaar", &file_result.mb_link);
+                                    });
+                                }
+                                ui.add_space(4.0);
+                            }
+                            return;
+                        }
+                        crate::calc_malwarebazaar_stt::ScanStatus::Scanning { scanned, total, operation } => {
+                            ui.label(format!("Scanning: {} / {} ({})", scanned, total, operation));
+                            return;
+                        }
+                        crate::calc_malwarebazaar_stt::ScanStatus::Pending => {
+                            ui.label("Lookup pending...");
+                            return;
+                        }
+                        crate::calc_malwarebazaar_stt::ScanStatus::Error(err) => {
+                            ui.colored_label(egui::Color32::RED, format!("Lookup error: {}", err));
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
+        ui.label("No MalwareBazaar results available for this package");
+    }
+
+    fn render_koodous_tab(&self, ui: &mut egui::Ui, pkg_id: &str) {
+        let store = get_shared_store();
+        let koodous_state = store.get_koodous_scanner_state();
+
+        if let Some(state) = koodous_state {
+            if let Ok(scanner_state) = state.lock() {
+                if let Some(scan_status) = scanner_state.get(pkg_id) {
+                    ui.heading("Koodous Lookup Results");
+                    ui.add_space(4.0);
+
+                    match scan_status {
+                        crate::calc_koodous_stt::ScanStatus::Completed(result) => {
+                            ui.label(format!("Files looked up: {}", result.file_results.len()));
+                            ui.add_space(8.0);
+
+                            for file_result in &result.file_results {
+                                ui.separator();
+                                ui.label(format!("File: {}", file_result.file_path));
+
+                                ui.horizontal(|ui| {
+                                    ui.label("SHA256:");
+                                    ui.add(egui::Label::new(&file_result.sha256).wrap());
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Verdict:");
+                                    if file_result.is_detected() {
+                                        ui.colored_label(egui::Color32::RED, &file_result.verdict);
+                                    } else {
+                                        ui.label(&file_result.verdict);
+                                    }
+                                });
+
+                                if let Some(app) = &file_result.app {
+                                    ui.horizontal(|ui| {
+                                        ui.label("App:");
+                                        ui.label(app);
+                                    });
+                                }
+
+                                if let Some(company) = &file_result.company {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Company:");
+                                        ui.label(company);
+                                    });
+                                }
+
+                                if let Some(rating) = file_result.rating {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Rating:");
+                                        ui.label(format!("{}", rating));
+                                    });
+                                }
+
+                                if !file_result.tags.is_empty() {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Tags:");
+                                        ui.label(file_result.tags.join(", "));
+                                    });
+                                }
+
+                                if file_result.verdict != "404 Not Found" {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Report Link:");
+                                        ui.hyperlink_to("View on Koodous", &file_result.koodous_link);
+                                    });
+                                }
+                                ui.add_space(4.0);
+                            }
+                            return;
+                        }
+                        crate::calc_koodous_stt::ScanStatus::Scanning { scanned, total, operation } => {
+                            ui.label(format!("Scanning: {} / {} ({})", scanned, total, operation));
+                            return;
+                        }
+                        crate::calc_koodous_stt::ScanStatus::Pending => {
+                            ui.label("Lookup pending...");
+                            return;
+                        }
+                        crate::calc_koodous_stt::ScanStatus::Error(err) => {
+                            ui.colored_label(egui::Color32::RED, format!("Lookup error: {}", err));
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
+        ui.label("No Koodous results available for this package");
+    }
+
     fn enabled_to_string(enabled: i32) -> &'static str {
         match enabled {
             0 => "DEFAULT",
@@ -1,8 +1,18 @@
+use crate::calc_hybridanalysis_stt::HaReportDetails;
+use crate::calc_virustotal_stt::VtReportDetails;
+use std::collections::HashMap;
+
 #[derive(Debug, Clone)]
 pub struct DlgPackageDetails {
     pub open: bool,
     pub selected_package_index: Option<usize>,
     pub selected_tab: usize,
+    /// VirusTotal report details parsed from the cached raw response, keyed by sha256
+    pub vt_details: HashMap<String, Option<VtReportDetails>>,
+    /// Hybrid Analysis report details parsed from the cached report summary, keyed by sha256
+    pub ha_details: HashMap<String, Option<HaReportDetails>>,
+    /// Outcome of the last grant/revoke from the permissions tab (success, message)
+    pub permission_result: Option<(bool, String)>,
 }
 
 impl Default for DlgPackageDetails {
@@ -11,6 +21,9 @@ impl Default for DlgPackageDetails {
             open: false,
             selected_package_index: None,
             selected_tab: 0,
+            vt_details: HashMap::new(),
+            ha_details: HashMap::new(),
+            permission_result: None,
         }
     }
 }
@@ -0,0 +1,383 @@
+pub use crate::dlg_permission_manager_stt::*;
+use crate::calc_permissions_stt::{PermissionChange, PermissionGroup, RuntimePermission};
+use crate::models::PermissionJournalEntry;
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+/// Number of journal entries listed
+const JOURNAL_LIMIT: i64 = 200;
+
+impl DlgPermissionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the dialog for a device
+    pub fn open(&mut self, device: String) {
+        self.device = Some(device);
+        self.open = true;
+        self.selected.clear();
+        self.action_result = None;
+        let packages = crate::shared_store_stt::get_shared_store().get_installed_packages();
+        let users = crate::calc_permissions::user_ids(&packages);
+        if !users.contains(&self.user_id) {
+            self.user_id = users.first().copied().unwrap_or(0);
+        }
+        self.refresh_journal();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh_journal(&mut self) {
+        self.journal = match &self.device {
+            Some(device) => crate::db_permission_journal::get_journal(device, JOURNAL_LIMIT),
+            None => Vec::new(),
+        };
+    }
+
+    pub fn group_text(group: PermissionGroup) -> String {
+        match group {
+            PermissionGroup::Location => tr!("permission-group-location"),
+            PermissionGroup::Camera => tr!("permission-group-camera"),
+            PermissionGroup::Microphone => tr!("permission-group-microphone"),
+            PermissionGroup::Contacts => tr!("permission-group-contacts"),
+            PermissionGroup::Calendar => tr!("permission-group-calendar"),
+            PermissionGroup::Phone => tr!("permission-group-phone"),
+            PermissionGroup::Sms => tr!("permission-group-sms"),
+            PermissionGroup::Storage => tr!("permission-group-storage"),
+            PermissionGroup::BodySensors => tr!("permission-group-body-sensors"),
+            PermissionGroup::ActivityRecognition => tr!("permission-group-activity"),
+            PermissionGroup::NearbyDevices => tr!("permission-group-nearby"),
+            PermissionGroup::Notifications => tr!("permission-group-notifications"),
+            PermissionGroup::Other => tr!("permission-group-other"),
+        }
+    }
+
+    fn format_timestamp(ts: i64) -> String {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    }
+
+    /// Apply a grant or revoke to the selected rows that are not already in that state
+    fn start_batch(&mut self, rows: &[RuntimePermission], grant: bool) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        let changes: Vec<PermissionChange> = rows
+            .iter()
+            .filter(|p| self.selected.contains(&(p.pkg_id.clone(), p.name.clone())))
+            .filter(|p| p.granted != grant && !p.fixed)
+            .map(|p| PermissionChange {
+                pkg_id: p.pkg_id.clone(),
+                user_id: p.user_id,
+                permission: p.name.clone(),
+                grant,
+            })
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+        if let Ok(mut r) = self.batch_result.lock() {
+            *r = None;
+        }
+        self.batch_running = true;
+        self.action_result = None;
+        crate::calc_permissions::run_permission_changes_async(device, changes, self.batch_result.clone());
+    }
+
+    fn start_revert(&mut self, entry: PermissionJournalEntry) {
+        if let Ok(mut r) = self.revert_result.lock() {
+            *r = None;
+        }
+        self.action_result = None;
+        crate::calc_permissions::run_revert_change_async(entry.clone(), self.revert_result.clone());
+        self.reverting = Some(entry);
+    }
+
+    fn render_row(&mut self, ui: &mut egui::Ui, permission: &RuntimePermission) {
+        ui.horizontal_wrapped(|ui| {
+            let key = (permission.pkg_id.clone(), permission.name.clone());
+            let mut checked = self.selected.contains(&key);
+            if ui.add_enabled(!permission.fixed, egui::Checkbox::without_text(&mut checked)).changed() {
+                if checked {
+                    self.selected.insert(key);
+                } else {
+                    self.selected.remove(&key);
+                }
+            }
+            if permission.granted {
+                ui.label(egui::RichText::new(tr!("permission-granted")).color(egui::Color32::from_rgb(255, 152, 0)));
+            } else {
+                ui.label(egui::RichText::new(tr!("permission-denied")).weak());
+            }
+            if ui.link(egui::RichText::new(&permission.pkg_id).strong()).clicked() {
+                self.open_package = Some(permission.pkg_id.clone());
+            }
+            ui.label(permission.name.rsplit('.').next().unwrap_or(&permission.name))
+                .on_hover_text(&permission.name);
+            if permission.fixed {
+                ui.label(egui::RichText::new(tr!("permission-fixed")).small().weak())
+                    .on_hover_text(tr!("permission-fixed-desc"));
+            }
+        });
+    }
+
+    fn render_journal(&mut self, ui: &mut egui::Ui) -> Option<PermissionJournalEntry> {
+        let mut revert = None;
+        if self.journal.is_empty() {
+            ui.label(egui::RichText::new(tr!("permission-journal-empty")).weak());
+        }
+        for entry in &self.journal {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(egui::RichText::new(Self::format_timestamp(entry.changed_at as i64)).small().weak());
+                ui.label(if entry.granted {
+                    tr!("permission-journal-granted")
+                } else {
+                    tr!("permission-journal-revoked")
+                });
+                ui.label(egui::RichText::new(&entry.pkg_id).strong());
+                ui.label(entry.permission.rsplit('.').next().unwrap_or(&entry.permission))
+                    .on_hover_text(&entry.permission);
+                ui.label(egui::RichText::new(tr!("permission-user", { user: entry.user_id })).small().weak());
+                if entry.reverted_at.is_some() {
+                    ui.label(egui::RichText::new(tr!("permission-reverted")).small().weak());
+                } else if ui
+                    .add_enabled(
+                        !self.batch_running && self.reverting.is_none(),
+                        MaterialButton::outlined(tr!("permission-revert")).small(),
+                    )
+                    .clicked()
+                {
+                    revert = Some(entry.clone());
+                }
+            });
+        }
+        revert
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        if self.batch_running {
+            let finished = self.batch_result.lock().ok().and_then(|mut r| r.take());
+            match finished {
+                Some(outcome) => {
+                    self.batch_running = false;
+                    self.selected.clear();
+                    self.action_result = Some(if outcome.failures.is_empty() {
+                        (true, tr!("permission-batch-done", { count: outcome.succeeded }))
+                    } else {
+                        (
+                            false,
+                            format!(
+                                "{}\n{}",
+                                tr!("permission-batch-failed", {
+                                    count: outcome.failures.len(),
+                                    succeeded: outcome.succeeded
+                                }),
+                                outcome.failures.join("\n")
+                            ),
+                        )
+                    });
+                    self.refresh_journal();
+                }
+                None => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            }
+        }
+        if let Some(entry) = &self.reverting {
+            let finished = self.revert_result.lock().ok().and_then(|mut r| r.take());
+            match finished {
+                Some(outcome) => {
+                    let package = entry.pkg_id.clone();
+                    self.action_result = Some(match outcome {
+                        Ok(()) => (true, tr!("permission-revert-done", { package: package })),
+                        Err(error) => (false, tr!("permission-revert-failed", { package: package, error: error })),
+                    });
+                    self.reverting = None;
+                    self.refresh_journal();
+                }
+                None => ctx.request_repaint_after(std::time::Duration::from_millis(200)),
+            }
+        }
+
+        let packages = crate::shared_store_stt::get_shared_store().get_installed_packages();
+        let users = crate::calc_permissions::user_ids(&packages);
+        let all_rows = crate::calc_permissions::collect_runtime_permissions(&packages, self.user_id, None);
+
+        let mut close_clicked = false;
+        let mut batch: Option<bool> = None;
+        let mut revert: Option<PermissionJournalEntry> = None;
+
+        egui::Window::new(tr!("permission-manager-title"))
+            .id(egui::Id::new("permission_manager_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("permission-manager-title"));
+                ui.add_space(8.0);
+
+                ui.horizontal_wrapped(|ui| {
+                    if users.len() > 1 {
+                        let previous_user = self.user_id;
+                        egui::ComboBox::from_id_salt("permission_manager_user")
+                            .selected_text(tr!("permission-user", { user: self.user_id }))
+                            .show_ui(ui, |ui| {
+                                for user in &users {
+                                    ui.selectable_value(&mut self.user_id, *user, tr!("permission-user", { user: *user }));
+                                }
+                            });
+                        if self.user_id != previous_user {
+                            self.selected.clear();
+                        }
+                    }
+                    ui.checkbox(&mut self.granted_only, tr!("permission-granted-only"));
+                });
+
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .selectable_label(self.group_filter.is_none(), tr!("permission-all", { count: all_rows.len() }))
+                        .clicked()
+                    {
+                        self.group_filter = None;
+                        self.selected.clear();
+                    }
+                    for group in PermissionGroup::ALL {
+                        let count = all_rows
+                            .iter()
+                            .filter(|p| crate::calc_permissions::permission_group(&p.name) == group)
+                            .count();
+                        if count == 0 {
+                            continue;
+                        }
+                        if ui
+                            .selectable_label(
+                                self.group_filter == Some(group),
+                                format!("{} ({})", Self::group_text(group), count),
+                            )
+                            .clicked()
+                        {
+                            self.group_filter = Some(group);
+                            self.selected.clear();
+                        }
+                    }
+                });
+
+                let rows: Vec<RuntimePermission> = all_rows
+                    .iter()
+                    .filter(|p| {
+                        self.group_filter
+                            .map_or(true, |g| crate::calc_permissions::permission_group(&p.name) == g)
+                    })
+                    .filter(|p| !self.granted_only || p.granted)
+                    .cloned()
+                    .collect();
+                let selected_count = rows
+                    .iter()
+                    .filter(|p| self.selected.contains(&(p.pkg_id.clone(), p.name.clone())))
+                    .count();
+
+                ui.horizontal_wrapped(|ui| {
+                    if ui.add(MaterialButton::outlined(tr!("permission-select-all")).small()).clicked() {
+                        for p in rows.iter().filter(|p| !p.fixed) {
+                            self.selected.insert((p.pkg_id.clone(), p.name.clone()));
+                        }
+                    }
+                    if ui.add(MaterialButton::outlined(tr!("deselect-all")).small()).clicked() {
+                        self.selected.clear();
+                    }
+                    let can_apply = selected_count > 0 && !self.batch_running && self.reverting.is_none();
+                    if ui
+                        .add_enabled(
+                            can_apply,
+                            MaterialButton::filled(tr!("permission-revoke-selected", { count: selected_count })).small(),
+                        )
+                        .clicked()
+                    {
+                        batch = Some(false);
+                    }
+                    if ui
+                        .add_enabled(
+                            can_apply,
+                            MaterialButton::outlined(tr!("permission-grant-selected", { count: selected_count })).small(),
+                        )
+                        .clicked()
+                    {
+                        batch = Some(true);
+                    }
+                    if self.batch_running || self.reverting.is_some() {
+                        ui.spinner();
+                        ui.label(tr!("permission-applying"));
+                    }
+                });
+
+                if let Some((ok, message)) = &self.action_result {
+                    let color = if *ok {
+                        egui::Color32::from_rgb(56, 142, 60)
+                    } else {
+                        egui::Color32::from_rgb(211, 47, 47)
+                    };
+                    ui.colored_label(color, message);
+                }
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("permission_manager_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        if self.show_journal {
+                            revert = self.render_journal(ui);
+                            return;
+                        }
+                        if rows.is_empty() {
+                            ui.label(tr!("permission-none"));
+                        }
+                        for permission in &rows {
+                            self.render_row(ui, permission);
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    let journal_text = if self.show_journal {
+                        tr!("permission-show-list")
+                    } else {
+                        tr!("permission-journal", { count: self.journal.len() })
+                    };
+                    if ui.add(MaterialButton::outlined(journal_text)).clicked() {
+                        self.show_journal = !self.show_journal;
+                        self.refresh_journal();
+                    }
+                });
+            });
+
+        if let Some(grant) = batch {
+            self.start_batch(&all_rows, grant);
+        }
+        if let Some(entry) = revert {
+            self.start_revert(entry);
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::calc_permissions_stt::{PermissionBatchOutcome, PermissionGroup};
+use crate::models::PermissionJournalEntry;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgPermissionManager {
+    pub open: bool,
+    pub device: Option<String>,
+    /// User whose permissions are listed and changed
+    pub user_id: i32,
+    /// Show only one permission group; `None` shows all
+    pub group_filter: Option<PermissionGroup>,
+    /// Show only permissions that are currently granted
+    pub granted_only: bool,
+    /// Selected rows as (package, permission)
+    pub selected: HashSet<(String, String)>,
+    /// Outcome of the running batch; `None` while applying
+    pub batch_result: Arc<Mutex<Option<PermissionBatchOutcome>>>,
+    pub batch_running: bool,
+    /// Journal entry being reverted
+    pub reverting: Option<PermissionJournalEntry>,
+    /// Outcome of the running revert (error text on failure); `None` while reverting
+    pub revert_result: Arc<Mutex<Option<Result<(), String>>>>,
+    /// Outcome of the last batch or revert (success, message)
+    pub action_result: Option<(bool, String)>,
+    /// Recent journaled changes for the device, newest first
+    pub journal: Vec<PermissionJournalEntry>,
+    pub show_journal: bool,
+    /// Package whose details were requested; taken by the owning tab
+    pub open_package: Option<String>,
+}
+
+impl Default for DlgPermissionManager {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            user_id: 0,
+            group_filter: Some(PermissionGroup::Location),
+            granted_only: false,
+            selected: HashSet::new(),
+            batch_result: Arc::new(Mutex::new(None)),
+            batch_running: false,
+            reverting: None,
+            revert_result: Arc::new(Mutex::new(None)),
+            action_result: None,
+            journal: Vec::new(),
+            show_journal: false,
+            open_package: None,
+        }
+    }
+}
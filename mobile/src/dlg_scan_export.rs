@@ -0,0 +1,140 @@
+pub use crate::dlg_scan_export_stt::*;
+use crate::calc_scanreport_stt::ReportFormat;
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+impl DlgScanExport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, device: String) {
+        self.device = Some(device);
+        if self.output_dir.is_empty() {
+            self.output_dir = crate::Config::new()
+                .map(|c| c.download_dir.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        if let Ok(mut r) = self.result.lock() {
+            *r = None;
+        }
+        self.exporting = false;
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn export(&mut self, risk_scores: &HashMap<String, i32>) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.result.lock() {
+            *r = None;
+        }
+        self.exporting = true;
+        crate::calc_scanreport::export_scan_report_async(
+            device,
+            self.format,
+            PathBuf::from(self.output_dir.trim()),
+            risk_scores.clone(),
+            self.result.clone(),
+        );
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, risk_scores: &HashMap<String, i32>) {
+        if !self.open {
+            return;
+        }
+
+        let result = self.result.lock().ok().and_then(|r| r.clone());
+        if result.is_some() {
+            self.exporting = false;
+        } else if self.exporting {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+
+        let mut close_clicked = false;
+        let mut export_clicked = false;
+
+        egui::Window::new(tr!("scan-export-title"))
+            .id(egui::Id::new("scan_export_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(400.0)
+            .min_height(220.0)
+            .resize(|r| r.max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0]))
+            .show(ctx, |ui| {
+                ui.heading(tr!("scan-export-title"));
+                ui.add_space(8.0);
+                ui.add(egui::Label::new(tr!("scan-export-desc")).wrap());
+                ui.add_space(8.0);
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr!("scan-export-format"));
+                    for format in ReportFormat::ALL {
+                        ui.selectable_value(&mut self.format, format, format.label());
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr!("scan-export-folder"));
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.output_dir).desired_width(300.0));
+                    #[cfg(target_os = "android")]
+                    {
+                        if response.gained_focus() {
+                            let _ = crate::android_inputmethod::show_soft_input();
+                        }
+                        if response.lost_focus() {
+                            let _ = crate::android_inputmethod::hide_soft_input();
+                        }
+                    }
+                    crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.output_dir);
+                });
+
+                ui.add_space(8.0);
+                if self.exporting {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr!("scan-export-running"));
+                    });
+                }
+                match &result {
+                    Some(Ok(path)) => {
+                        ui.label(tr!("scan-export-done", { path: path.display().to_string() }));
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e);
+                    }
+                    None => {}
+                }
+
+                ui.add_space(8.0);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                        close_clicked = true;
+                    }
+                    let can_export = !self.exporting && !self.output_dir.trim().is_empty();
+                    if ui
+                        .add_enabled(can_export, MaterialButton::outlined(tr!("scan-export-run")))
+                        .clicked()
+                    {
+                        export_clicked = true;
+                    }
+                });
+            });
+
+        if export_clicked {
+            self.export(risk_scores);
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::calc_scanreport_stt::ReportFormat;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgScanExport {
+    pub open: bool,
+    pub device: Option<String>,
+    pub format: ReportFormat,
+    /// Directory the report file is written to
+    pub output_dir: String,
+    /// Written file path or error; `None` while exporting or before the first export
+    pub result: Arc<Mutex<Option<Result<PathBuf, String>>>>,
+    pub exporting: bool,
+}
+
+impl Default for DlgScanExport {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            format: ReportFormat::Html,
+            output_dir: String::new(),
+            result: Arc::new(Mutex::new(None)),
+            exporting: false,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::Settings;
 use crate::LogLevel;
+use crate::calc_snapshot_stt::{DatasetKind, DatasetSource, DatasetStatus};
+use crate::shared_store_stt::get_shared_store;
 pub use crate::dlg_settings_stt::*;
 use eframe::egui;
 use egui_i18n::tr;
@@ -14,6 +16,10 @@ impl DlgSettings {
     pub fn open(&mut self) {
         self.save_clicked = false;
         self.theme_to_apply = None;
+        self.import_requested = None;
+        self.import_result = None;
+        self.allowlist_requested = None;
+        self.allowlist_result = None;
         self.open = true;
     }
 
@@ -124,6 +130,21 @@ impl DlgSettings {
         }
     }
 
+    fn dataset_status_text(status: Option<DatasetStatus>) -> String {
+        match status {
+            Some(status) => {
+                let date = chrono::DateTime::from_timestamp(status.updated_at, 0)
+                    .map(|dt| dt.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+                match status.source {
+                    DatasetSource::Cache => tr!("dataset-source-cache", { date: date }),
+                    DatasetSource::Embedded => tr!("dataset-source-embedded", { date: date }),
+                }
+            }
+            None => tr!("dataset-source-none"),
+        }
+    }
+
     pub fn show(
         &mut self,
         ctx: &egui::Context,
@@ -412,6 +433,17 @@ impl DlgSettings {
 
                         ui.add_space(8.0);
 
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("virustotal-quota"));
+                            ui.add(egui::DragValue::new(&mut self.virustotal_requests_per_minute).range(1..=100_000).suffix(tr!("per-minute")));
+                            ui.add(egui::DragValue::new(&mut self.virustotal_requests_per_day).range(0..=10_000_000).suffix(tr!("per-day")));
+                            ui.add(egui::DragValue::new(&mut self.virustotal_requests_per_month).range(0..=100_000_000).suffix(tr!("per-month")));
+                            ui.add_space(8.0);
+                            ui.label(tr!("virustotal-quota-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
                         ui.horizontal_wrapped(|ui| {
                             ui.label(tr!("hybridanalysis-api-key"));
                             let response = ui.text_edit_singleline(&mut self.hybridanalysis_apikey);
@@ -461,6 +493,143 @@ impl DlgSettings {
 
                         ui.add_space(8.0);
 
+                        ui.label(tr!("hybridanalysis-environment-desc"));
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("scan-max-age-days"));
+                            ui.add(egui::DragValue::new(&mut self.scan_max_age_days).range(0..=365));
+                            ui.add_space(8.0);
+                            ui.label(tr!("scan-max-age-days-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.spacing_mut().item_spacing.x = 4.0;
+                            ui.add(MaterialCheckbox::new(&mut self.rescan_flagged, tr!("rescan-flagged")));
+                            ui.add_space(8.0);
+                            ui.label(tr!("rescan-flagged-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("rescan-interval-hours"));
+                            ui.add(egui::DragValue::new(&mut self.rescan_interval_hours).range(0..=168));
+                            ui.add_space(8.0);
+                            ui.label(tr!("rescan-interval-hours-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("hash-allowlist", { count: self.allowlist_count }));
+                            ui.add_space(8.0);
+                            if ui
+                                .button(tr!("hash-allowlist-add-clean"))
+                                .on_hover_text(tr!("hash-allowlist-add-clean-desc"))
+                                .clicked()
+                            {
+                                self.allowlist_requested = Some(AllowlistAction::AddCleanResults);
+                            }
+                            if ui.add_enabled(self.allowlist_count > 0, egui::Button::new(tr!("hash-allowlist-clear"))).clicked() {
+                                self.allowlist_requested = Some(AllowlistAction::Clear);
+                            }
+                        });
+                        ui.label(tr!("hash-allowlist-desc"));
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("hash-allowlist-file"));
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.allowlist_path)
+                                    .desired_width(300.0)
+                                    .hint_text(tr!("hash-allowlist-file-hint")),
+                            );
+                            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.allowlist_path);
+                            let has_path = !self.allowlist_path.trim().is_empty();
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("hash-allowlist-import"))).clicked() {
+                                self.allowlist_requested = Some(AllowlistAction::Import(self.allowlist_path.clone()));
+                            }
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("hash-allowlist-export"))).clicked() {
+                                self.allowlist_requested = Some(AllowlistAction::Export(self.allowlist_path.clone()));
+                            }
+                        });
+                        if let Some((success, message)) = &self.allowlist_result {
+                            let color = if *success {
+                                egui::Color32::from_rgb(76, 175, 80)
+                            } else {
+                                egui::Color32::from_rgb(244, 67, 54)
+                            };
+                            ui.colored_label(color, message);
+                        }
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("izzyrisk-weights-file"));
+                            ui.add(egui::Label::new(&self.izzyrisk_weights_path).selectable(true));
+                            ui.add_space(8.0);
+                            ui.label(tr!("izzyrisk-weights-file-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("scan-rules-file"));
+                            ui.add(egui::Label::new(&self.scan_rules_path).selectable(true));
+                            ui.add_space(8.0);
+                            ui.label(tr!("scan-rules-file-desc"));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("malwarebazaar-api-key"));
+                            let response = ui.text_edit_singleline(&mut self.malwarebazaar_apikey);
+                            #[cfg(target_os = "android")]
+                            {
+                                if response.gained_focus() {
+                                    let _ = crate::android_inputmethod::show_soft_input();
+                                }
+                                if response.lost_focus() {
+                                    let _ = crate::android_inputmethod::hide_soft_input();
+                                }
+                            }
+                            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.malwarebazaar_apikey);
+                            if ui.button(tr!("get-api-key")).clicked() {
+                                if let Err(e) = webbrowser::open("https://auth.abuse.ch/") {
+                                    log::error!("Failed to open MalwareBazaar API key URL: {}", e);
+                                }
+                            }
+                            ui.add(MaterialCheckbox::new(&mut self.flush_malwarebazaar, tr!("flush")));
+                        });
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("koodous-api-key"));
+                            let response = ui.text_edit_singleline(&mut self.koodous_apikey);
+                            #[cfg(target_os = "android")]
+                            {
+                                if response.gained_focus() {
+                                    let _ = crate::android_inputmethod::show_soft_input();
+                                }
+                                if response.lost_focus() {
+                                    let _ = crate::android_inputmethod::hide_soft_input();
+                                }
+                            }
+                            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.koodous_apikey);
+                            if ui.button(tr!("get-api-key")).clicked() {
+                                if let Err(e) = webbrowser::open("https://koodous.com/settings/developer") {
+                                    log::error!("Failed to open Koodous API key URL: {}", e);
+                                }
+                            }
+                            ui.add(MaterialCheckbox::new(&mut self.flush_koodous, tr!("flush")));
+                        });
+
+                        ui.add_space(8.0);
+
                         #[cfg(not(target_os = "android"))]
                         {
                             ui.horizontal_wrapped(|ui| {
@@ -527,6 +696,58 @@ impl DlgSettings {
                             ui.add_space(8.0);
                         }
 
+                        // Data sources: cached download, bundled snapshot or manual import
+                        let store = get_shared_store();
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("uad-lists-source"));
+                            ui.label(Self::dataset_status_text(store.get_uad_lists_status()));
+                        });
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("stalkerware-ioc-source"));
+                            ui.label(Self::dataset_status_text(store.get_stalkerware_status()));
+                        });
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("tracker-signatures-source"));
+                            ui.label(Self::dataset_status_text(store.get_tracker_signatures_status()));
+                        });
+                        if !crate::calc_snapshot::EMBEDDED_SNAPSHOT_DATE.is_empty() {
+                            ui.label(tr!("bundled-snapshot-date", {
+                                date: crate::calc_snapshot::EMBEDDED_SNAPSHOT_DATE
+                            }));
+                        }
+
+                        ui.add_space(8.0);
+
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(tr!("import-dataset-path"));
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.import_path)
+                                    .desired_width(300.0)
+                                    .hint_text(tr!("import-dataset-path-hint")),
+                            );
+                            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.import_path);
+                            let has_path = !self.import_path.trim().is_empty();
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("import-uad-lists"))).clicked() {
+                                self.import_requested = Some((DatasetKind::UadLists, self.import_path.clone()));
+                            }
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("import-stalkerware-ioc"))).clicked() {
+                                self.import_requested = Some((DatasetKind::StalkerwareIoc, self.import_path.clone()));
+                            }
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("import-tracker-signatures"))).clicked() {
+                                self.import_requested = Some((DatasetKind::ExodusTrackers, self.import_path.clone()));
+                            }
+                        });
+                        if let Some((success, message)) = &self.import_result {
+                            let color = if *success {
+                                egui::Color32::from_rgb(76, 175, 80)
+                            } else {
+                                egui::Color32::from_rgb(244, 67, 54)
+                            };
+                            ui.colored_label(color, message);
+                        }
+
+                        ui.add_space(8.0);
+
                         ui.horizontal_wrapped(|ui| {
                             ui.spacing_mut().item_spacing.x = 4.0;
                             ui.add(MaterialCheckbox::new(&mut self.invalidate_cache, tr!("invalidate-cache")));
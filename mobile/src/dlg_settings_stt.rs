@@ -1,11 +1,27 @@
+use crate::calc_snapshot_stt::DatasetKind;
+
+/// Known-good hash allowlist operation requested from the settings dialog
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllowlistAction {
+    /// Add hashes of past clean VirusTotal / Hybrid Analysis results
+    AddCleanResults,
+    Import(String),
+    Export(String),
+    Clear,
+}
+
 pub struct DlgSettings {
     pub open: bool,
     // Temporary settings for dialog (applied only on Save)
     pub virustotal_apikey: String,
     pub hybridanalysis_apikey: String,
+    pub malwarebazaar_apikey: String,
+    pub koodous_apikey: String,
     pub invalidate_cache: bool,
     pub flush_virustotal: bool,
     pub flush_hybridanalysis: bool,
+    pub flush_malwarebazaar: bool,
+    pub flush_koodous: bool,
     pub flush_googleplay: bool,
     pub flush_fdroid: bool,
     pub flush_apkmirror: bool,
@@ -15,12 +31,31 @@ pub struct DlgSettings {
     pub virustotal_submit: bool,
     pub hybridanalysis_submit: bool,
     pub hybridanalysis_tag_ignorelist: String,
+    pub virustotal_requests_per_minute: u32,
+    pub virustotal_requests_per_day: u32,
+    pub virustotal_requests_per_month: u32,
+    pub scan_max_age_days: u32,
+    pub rescan_flagged: bool,
+    pub rescan_interval_hours: u32,
+    // Known-good hash allowlist
+    pub allowlist_count: i64,
+    pub allowlist_path: String,
+    pub allowlist_requested: Option<AllowlistAction>,
+    pub allowlist_result: Option<(bool, String)>,
+    // Location of the user-editable IzzyRisk weights
+    pub izzyrisk_weights_path: String,
+    // Location of the user-editable local scan rules
+    pub scan_rules_path: String,
     pub unsafe_app_remove: bool,
     pub autoupdate: bool,
     // Font selector state
     pub selected_font_display: String,
     pub system_fonts: Vec<(String, String)>,
     pub system_fonts_loaded: bool,
+    // Dataset import (UAD-NG lists / stalkerware IoC)
+    pub import_path: String,
+    pub import_requested: Option<(DatasetKind, String)>,
+    pub import_result: Option<(bool, String)>,
     // Action results
     pub save_clicked: bool,
     pub theme_to_apply: Option<String>,
@@ -32,9 +67,13 @@ impl Default for DlgSettings {
             open: false,
             virustotal_apikey: String::new(),
             hybridanalysis_apikey: String::new(),
+            malwarebazaar_apikey: String::new(),
+            koodous_apikey: String::new(),
             invalidate_cache: false,
             flush_virustotal: false,
             flush_hybridanalysis: false,
+            flush_malwarebazaar: false,
+            flush_koodous: false,
             flush_googleplay: false,
             flush_fdroid: false,
             flush_apkmirror: false,
@@ -44,11 +83,30 @@ impl Default for DlgSettings {
             virustotal_submit: false,
             hybridanalysis_submit: false,
             hybridanalysis_tag_ignorelist: String::new(),
+            virustotal_requests_per_minute: crate::calc_virustotal::PUBLIC_REQUESTS_PER_MINUTE,
+            virustotal_requests_per_day: crate::calc_virustotal::PUBLIC_REQUESTS_PER_DAY,
+            virustotal_requests_per_month: crate::calc_virustotal::PUBLIC_REQUESTS_PER_MONTH,
+            scan_max_age_days: 0,
+            rescan_flagged: false,
+            rescan_interval_hours: 0,
+            allowlist_count: 0,
+            allowlist_path: String::new(),
+            allowlist_requested: None,
+            allowlist_result: None,
+            izzyrisk_weights_path: crate::calc_izzyrisk::config_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            scan_rules_path: crate::calc_scanrules::config_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
             unsafe_app_remove: false,
             autoupdate: false,
             selected_font_display: "Default (NotoSansKr)".to_string(),
             system_fonts: Vec::new(),
             system_fonts_loaded: false,
+            import_path: String::new(),
+            import_requested: None,
+            import_result: None,
             save_clicked: false,
             theme_to_apply: None,
         }
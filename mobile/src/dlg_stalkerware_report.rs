@@ -0,0 +1,257 @@
+pub use crate::dlg_stalkerware_report_stt::*;
+use crate::calc_stalkerware_stt::{StalkerwareConfidence, StalkerwareEvidence, StalkerwareFinding};
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+impl DlgStalkerwareReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the dialog and start generating a fresh report for the device
+    pub fn open(&mut self, device: String) {
+        self.device = Some(device);
+        self.open = true;
+        self.refresh();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn refresh(&mut self) {
+        let Some(device) = self.device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.result.lock() {
+            *r = None;
+        }
+        self.copied = false;
+        crate::calc_stalkerware::run_stalkerware_report_async(device, self.result.clone());
+    }
+
+    fn format_timestamp(ts: i64) -> String {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    }
+
+    fn confidence_color(confidence: StalkerwareConfidence) -> egui::Color32 {
+        match confidence {
+            StalkerwareConfidence::High => egui::Color32::from_rgb(211, 47, 47),
+            StalkerwareConfidence::Medium => egui::Color32::from_rgb(255, 152, 0),
+            StalkerwareConfidence::Low => egui::Color32::from_rgb(158, 158, 158),
+        }
+    }
+
+    fn confidence_text(confidence: StalkerwareConfidence) -> String {
+        match confidence {
+            StalkerwareConfidence::High => tr!("stalkerware-confidence-high"),
+            StalkerwareConfidence::Medium => tr!("stalkerware-confidence-medium"),
+            StalkerwareConfidence::Low => tr!("stalkerware-confidence-low"),
+        }
+    }
+
+    fn evidence_text(evidence: &StalkerwareEvidence) -> String {
+        match evidence {
+            StalkerwareEvidence::PackageName { family } => tr!("stalkerware-evidence-package", { family: family }),
+            StalkerwareEvidence::Certificate { family } => tr!("stalkerware-evidence-certificate", { family: family }),
+            StalkerwareEvidence::LabelMatch { family, label } => {
+                tr!("stalkerware-evidence-label", { label: label, family: family })
+            }
+            StalkerwareEvidence::AccessibilityService { component } => {
+                tr!("stalkerware-evidence-accessibility", { component: component })
+            }
+            StalkerwareEvidence::DeviceAdmin { component } => tr!("stalkerware-evidence-admin", { component: component }),
+            StalkerwareEvidence::NotificationListener { component } => {
+                tr!("stalkerware-evidence-notification", { component: component })
+            }
+            StalkerwareEvidence::HiddenLauncherIcon => tr!("stalkerware-evidence-hidden-icon"),
+        }
+    }
+
+    fn render_finding(ui: &mut egui::Ui, finding: &StalkerwareFinding) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(
+                egui::RichText::new(Self::confidence_text(finding.confidence))
+                    .strong()
+                    .color(Self::confidence_color(finding.confidence)),
+            );
+            ui.label(egui::RichText::new(&finding.pkg_id).strong());
+            if let Some(label) = &finding.label {
+                ui.label(format!("({})", label));
+            }
+            if let Some(family) = &finding.family {
+                ui.label(tr!("stalkerware-family", { family: family }));
+            }
+        });
+        for evidence in &finding.evidence {
+            ui.add(egui::Label::new(format!("  • {}", Self::evidence_text(evidence))).wrap());
+        }
+
+        // Removal order matters: an active device admin blocks uninstall
+        let mut step = 1;
+        ui.label(egui::RichText::new(tr!("stalkerware-removal-steps")).italics());
+        if finding.has_device_admin() {
+            ui.add(egui::Label::new(format!("  {}. {}", step, tr!("stalkerware-remove-admin"))).wrap());
+            step += 1;
+        }
+        if finding
+            .evidence
+            .iter()
+            .any(|e| matches!(e, StalkerwareEvidence::AccessibilityService { .. } | StalkerwareEvidence::NotificationListener { .. }))
+        {
+            ui.add(egui::Label::new(format!("  {}. {}", step, tr!("stalkerware-remove-access"))).wrap());
+            step += 1;
+        }
+        let uninstall = if finding.is_system {
+            tr!("stalkerware-remove-disable")
+        } else {
+            tr!("stalkerware-remove-uninstall")
+        };
+        ui.add(egui::Label::new(format!("  {}. {}", step, uninstall)).wrap());
+        ui.add_space(8.0);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let result = self.result.lock().ok().and_then(|r| r.clone());
+        if result.is_none() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+
+        let mut close_clicked = false;
+        let mut refresh_clicked = false;
+
+        egui::Window::new(tr!("stalkerware-report-title"))
+            .id(egui::Id::new("stalkerware_report_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("stalkerware-report-title"));
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("stalkerware_report_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        // Safety guidance comes before any findings
+                        ui.group(|ui| {
+                            ui.label(egui::RichText::new(tr!("stalkerware-safety-title")).strong());
+                            ui.add(egui::Label::new(format!("• {}", tr!("stalkerware-safety-alert"))).wrap());
+                            ui.add(egui::Label::new(format!("• {}", tr!("stalkerware-safety-evidence"))).wrap());
+                            ui.add(egui::Label::new(format!("• {}", tr!("stalkerware-safety-accounts"))).wrap());
+                        });
+                        ui.add_space(8.0);
+
+                        let report = match &result {
+                            None => {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.label(tr!("stalkerware-report-generating"));
+                                });
+                                return;
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), e);
+                                return;
+                            }
+                            Some(Ok(report)) => report,
+                        };
+
+                        ui.label(tr!("stalkerware-report-summary", {
+                            device: report.device_serial,
+                            count: report.scanned_packages,
+                            generated: Self::format_timestamp(report.generated_at)
+                        }));
+                        if report.indicators_updated_at == 0 {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 152, 0),
+                                tr!("stalkerware-report-no-indicators"),
+                            );
+                        }
+                        ui.add_space(8.0);
+
+                        let low_count = report
+                            .findings
+                            .iter()
+                            .filter(|f| f.confidence == StalkerwareConfidence::Low)
+                            .count();
+                        let shown: Vec<&StalkerwareFinding> = report
+                            .findings
+                            .iter()
+                            .filter(|f| self.show_low || f.confidence != StalkerwareConfidence::Low)
+                            .collect();
+
+                        if shown.is_empty() {
+                            ui.label(tr!("stalkerware-report-clean"));
+                        }
+                        for finding in shown {
+                            Self::render_finding(ui, finding);
+                        }
+
+                        if low_count > 0 {
+                            ui.checkbox(&mut self.show_low, tr!("stalkerware-report-show-low", { count: low_count }));
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if let Some(Ok(report)) = &result {
+                        if ui
+                            .add(MaterialButton::outlined(tr!("stalkerware-report-copy")))
+                            .on_hover_text(tr!("stalkerware-report-copy-desc"))
+                            .clicked()
+                        {
+                            let text = crate::calc_stalkerware::format_report_text(report);
+                            #[cfg(target_os = "android")]
+                            {
+                                let _ = crate::android_clipboard::set_text(&text);
+                            }
+                            #[cfg(not(target_os = "android"))]
+                            {
+                                ui.ctx().copy_text(text);
+                            }
+                            self.copied = true;
+                        }
+                        if self.copied {
+                            ui.label(tr!("stalkerware-report-copied"));
+                        }
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                            close_clicked = true;
+                        }
+                        if ui
+                            .add_enabled(result.is_some(), MaterialButton::outlined(tr!("refresh")))
+                            .clicked()
+                        {
+                            refresh_clicked = true;
+                        }
+                    });
+                });
+            });
+
+        if refresh_clicked {
+            self.refresh();
+        }
+        if close_clicked {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::calc_stalkerware_stt::StalkerwareReport;
+use std::sync::{Arc, Mutex};
+
+pub struct DlgStalkerwareReport {
+    pub open: bool,
+    pub device: Option<String>,
+    /// Report generated in the background; `None` while generating
+    pub result: Arc<Mutex<Option<Result<StalkerwareReport, String>>>>,
+    /// Show low-confidence findings (single capability signal, often legitimate)
+    pub show_low: bool,
+    /// Report text was copied to the clipboard
+    pub copied: bool,
+}
+
+impl Default for DlgStalkerwareReport {
+    fn default() -> Self {
+        Self {
+            open: false,
+            device: None,
+            result: Arc::new(Mutex::new(None)),
+            show_low: false,
+            copied: false,
+        }
+    }
+}
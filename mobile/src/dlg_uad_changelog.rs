@@ -0,0 +1,148 @@
+pub use crate::dlg_uad_changelog_stt::*;
+use crate::calc_uadlists_stt::UadActionFlag;
+use crate::shared_store_stt::get_shared_store;
+use eframe::egui;
+use egui_i18n::tr;
+use egui_material3::MaterialButton;
+
+impl DlgUadChangelog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, flagged: Vec<UadActionFlag>) {
+        self.flagged = flagged;
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn format_timestamp(ts: i64) -> String {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let diff = get_shared_store().get_uad_lists_diff();
+        let mut close_clicked = false;
+
+        egui::Window::new(tr!("uad-changelog-title"))
+            .id(egui::Id::new("uad_changelog_window"))
+            .title_bar(false)
+            .resizable(true)
+            .collapsible(false)
+            .scroll([false, false])
+            .min_width(500.0)
+            .min_height(350.0)
+            .resize(|r| {
+                r.default_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+                    .max_size([ctx.content_rect().width() - 40.0, ctx.content_rect().height() - 40.0])
+            })
+            .show(ctx, |ui| {
+                ui.heading(tr!("uad-changelog-title"));
+                ui.add_space(8.0);
+
+                let max_height = ui.available_height() - 50.0;
+
+                egui::ScrollArea::both()
+                    .id_salt("uad_changelog_scroll")
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        // Installed packages needing review come first
+                        if !self.flagged.is_empty() {
+                            ui.label(
+                                egui::RichText::new(tr!("uad-changelog-flagged", { count: self.flagged.len() }))
+                                    .strong()
+                                    .color(egui::Color32::from_rgb(255, 152, 0)),
+                            );
+                            for flag in &self.flagged {
+                                ui.add(egui::Label::new(format!(
+                                    "  • {}: {} → {} ({} {})",
+                                    flag.pkg_id,
+                                    flag.removal_at_action,
+                                    flag.current_removal,
+                                    flag.action,
+                                    Self::format_timestamp(flag.acted_at)
+                                )).wrap());
+                            }
+                            ui.add_space(12.0);
+                        }
+
+                        let Some(diff) = diff.as_ref() else {
+                            ui.label(tr!("uad-changelog-no-history"));
+                            return;
+                        };
+
+                        ui.label(tr!("uad-changelog-compared", {
+                            old: Self::format_timestamp(diff.old_fetched_at),
+                            new: Self::format_timestamp(diff.new_fetched_at)
+                        }));
+                        ui.add_space(8.0);
+
+                        if diff.is_empty() {
+                            ui.label(tr!("uad-changelog-no-changes"));
+                            return;
+                        }
+
+                        if !diff.removal_changed.is_empty() {
+                            ui.label(
+                                egui::RichText::new(tr!("uad-changelog-category-changes", { count: diff.removal_changed.len() }))
+                                    .strong(),
+                            );
+                            for change in &diff.removal_changed {
+                                ui.add(egui::Label::new(format!(
+                                    "  • {}: {} → {}",
+                                    change.pkg_id, change.old_removal, change.new_removal
+                                )).wrap());
+                            }
+                            ui.add_space(8.0);
+                        }
+
+                        if !diff.added.is_empty() {
+                            ui.label(
+                                egui::RichText::new(tr!("uad-changelog-new-entries", { count: diff.added.len() }))
+                                    .strong(),
+                            );
+                            for added in &diff.added {
+                                ui.add(egui::Label::new(format!(
+                                    "  • {} [{} / {}]",
+                                    added.pkg_id, added.list, added.removal
+                                )).wrap());
+                            }
+                            ui.add_space(8.0);
+                        }
+
+                        if !diff.removed.is_empty() {
+                            ui.label(
+                                egui::RichText::new(tr!("uad-changelog-removed-entries", { count: diff.removed.len() }))
+                                    .strong(),
+                            );
+                            for pkg_id in &diff.removed {
+                                ui.label(format!("  • {}", pkg_id));
+                            }
+                        }
+                    });
+
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.add(MaterialButton::filled(tr!("close"))).clicked() {
+                            close_clicked = true;
+                        }
+                    });
+                });
+            });
+
+        if close_clicked {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::calc_uadlists_stt::UadActionFlag;
+
+pub struct DlgUadChangelog {
+    pub open: bool,
+    /// Installed packages whose recommendation changed since the user last acted on them
+    pub flagged: Vec<UadActionFlag>,
+}
+
+impl Default for DlgUadChangelog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            flagged: Vec::new(),
+        }
+    }
+}
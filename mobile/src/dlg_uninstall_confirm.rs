@@ -9,6 +9,7 @@ impl DlgUninstallConfirm {
         self.packages = vec![pkg];
         self.is_system = vec![is_system];
         self.confirmed = false;
+        self.disable = false;
         self.open = true;
     }
 
@@ -16,18 +17,26 @@ impl DlgUninstallConfirm {
         self.packages = packages;
         self.is_system = is_system;
         self.confirmed = false;
+        self.disable = false;
         self.open = true;
     }
 
+    /// Ask before disabling a package handed over from another tab
+    pub fn open_disable(&mut self, pkg: String, is_system: bool) {
+        self.open_single(pkg, is_system);
+        self.disable = true;
+    }
+
     pub fn reset(&mut self) {
         self.open = false;
         self.packages.clear();
         self.is_system.clear();
         self.confirmed = false;
+        self.disable = false;
         self.app_names.clear();
     }
 
-    /// Renders the dialog. Returns true if the user confirmed uninstall this frame.
+    /// Renders the dialog. Returns true if the user confirmed uninstall (or disable) this frame.
     pub fn show(&mut self, ctx: &egui::Context) -> bool {
         if !self.open {
             return false;
@@ -36,7 +45,12 @@ impl DlgUninstallConfirm {
         let do_confirm = Cell::new(false);
         let count = self.packages.len();
 
-        let title = tr!("uninstall-confirm-title");
+        let disable = self.disable;
+        let title = if disable {
+            tr!("disable-confirm-title")
+        } else {
+            tr!("uninstall-confirm-title")
+        };
 
         dialog(
             "uninstall_confirm_dialog",
@@ -45,14 +59,16 @@ impl DlgUninstallConfirm {
         )
         .content(|ui| {
             ui.set_width(300.0);
-            if count == 1 {
+            if disable {
+                ui.label(tr!("disable-confirm-single", { name: self.packages[0].clone() }));
+            } else if count == 1 {
                 ui.label(tr!("uninstall-confirm-single", { name: self.packages[0].clone() }));
             } else {
                 ui.label(tr!("uninstall-confirm-batch", { count: count }));
             }
         })
         .action(tr!("cancel"), || {})
-        .primary_action(tr!("uninstall"), || {
+        .primary_action(if disable { tr!("disable") } else { tr!("uninstall") }, || {
             do_confirm.set(true);
         })
         .show(ctx);
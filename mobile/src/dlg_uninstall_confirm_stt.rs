@@ -3,6 +3,8 @@ pub struct DlgUninstallConfirm {
     pub packages: Vec<String>,
     pub is_system: Vec<bool>,
     pub confirmed: bool,
+    /// Confirms disabling for the current user instead of uninstalling
+    pub disable: bool,
     /// Optional human-readable app names (used by tab_apps_control)
     pub app_names: Vec<Option<String>>,
 }
@@ -14,6 +16,7 @@ impl Default for DlgUninstallConfirm {
             packages: Vec::new(),
             is_system: Vec::new(),
             confirmed: false,
+            disable: false,
             app_names: Vec::new(),
         }
     }
@@ -31,6 +31,22 @@ mod dlg_adb_install;
 pub mod dlg_adb_install_stt;
 mod dlg_update;
 pub mod dlg_update_stt;
+mod dlg_uad_changelog;
+pub mod dlg_uad_changelog_stt;
+mod dlg_stalkerware_report;
+pub mod dlg_stalkerware_report_stt;
+mod dlg_capability_audit;
+pub mod dlg_capability_audit_stt;
+mod dlg_install_provenance;
+pub mod dlg_install_provenance_stt;
+mod dlg_permission_manager;
+pub mod dlg_permission_manager_stt;
+mod dlg_app_ops;
+pub mod dlg_app_ops_stt;
+mod dlg_components;
+pub mod dlg_components_stt;
+mod dlg_scan_export;
+pub mod dlg_scan_export_stt;
 mod dlg_about;
 pub mod dlg_about_stt;
 
@@ -44,6 +60,10 @@ mod app_operations_queue;
 pub mod app_operations_queue_stt;
 pub mod api_hybridanalysis;
 pub mod api_hybridanalysis_stt;
+pub mod api_koodous;
+pub mod api_koodous_stt;
+pub mod api_malwarebazaar;
+pub mod api_malwarebazaar_stt;
 mod api_virustotal;
 pub mod api_virustotal_stt;
 pub mod calc_androidpackage;
@@ -55,17 +75,62 @@ mod calc_googleplay;
 pub mod calc_googleplay_stt;
 mod calc_hybridanalysis;
 pub mod calc_hybridanalysis_stt;
+mod calc_apksig;
+pub mod calc_apksig_stt;
+mod calc_appops;
+pub mod calc_appops_stt;
+mod calc_axml;
+pub mod calc_axml_stt;
+mod calc_background;
+pub mod calc_background_stt;
+mod calc_batterystats;
+pub mod calc_batterystats_stt;
+mod calc_capabilities;
+pub mod calc_capabilities_stt;
+mod calc_components;
+pub mod calc_components_stt;
 mod calc_izzyrisk;
+pub mod calc_izzyrisk_stt;
+mod calc_koodous;
+pub mod calc_koodous_stt;
+mod calc_malwarebazaar;
+pub mod calc_malwarebazaar_stt;
+mod calc_nativelibs;
+pub mod calc_nativelibs_stt;
+mod calc_permissions;
+pub mod calc_permissions_stt;
+mod calc_provenance;
+pub mod calc_provenance_stt;
+mod calc_scanprovider;
+pub mod calc_scanprovider_stt;
+mod calc_scanreport;
+pub mod calc_scanreport_stt;
+mod calc_scanrules;
+pub mod calc_scanrules_stt;
+mod calc_snapshot;
+pub mod calc_snapshot_stt;
 mod calc_stalkerware;
 pub mod calc_stalkerware_stt;
+mod calc_trackers;
+pub mod calc_trackers_stt;
+mod calc_uadlists;
+pub mod calc_uadlists_stt;
+mod calc_usagestats;
+pub mod calc_usagestats_stt;
 mod calc_virustotal;
 pub mod calc_virustotal_stt;
 pub mod db;
+pub mod db_api_quota;
 pub mod db_apkmirror;
 pub mod db_fdroid;
 pub mod db_googleplay;
+pub mod db_hash_allowlist;
 pub mod db_hybridanalysis;
+pub mod db_koodous;
+pub mod db_malwarebazaar;
 pub mod db_package_cache;
+pub mod db_permission_journal;
+pub mod db_uad_lists;
 pub mod db_virustotal;
 mod models;
 mod schema;
@@ -132,6 +197,27 @@ pub struct Settings {
     pub hybridanalysis_submit: bool,
     #[serde(default = "default_hybridanalysis_tag_ignorelist")]
     pub hybridanalysis_tag_ignorelist: String,
+    /// VirusTotal API tier limits (defaults are the free public API)
+    #[serde(default = "default_virustotal_requests_per_minute")]
+    pub virustotal_requests_per_minute: u32,
+    #[serde(default = "default_virustotal_requests_per_day")]
+    pub virustotal_requests_per_day: u32,
+    #[serde(default = "default_virustotal_requests_per_month")]
+    pub virustotal_requests_per_month: u32,
+    /// Re-query VirusTotal/Hybrid Analysis results older than this many days (0 = never).
+    /// Off by default because every re-query spends API quota.
+    #[serde(default)]
+    pub scan_max_age_days: u32,
+    /// Always re-query results flagged malicious or suspicious (opt-in, spends API quota)
+    #[serde(default)]
+    pub rescan_flagged: bool,
+    /// Hours between background rescans while the app is open (0 = disabled)
+    #[serde(default)]
+    pub rescan_interval_hours: u32,
+    #[serde(default)]
+    pub malwarebazaar_apikey: String,
+    #[serde(default)]
+    pub koodous_apikey: String,
     #[serde(default)]
     pub show_logs: bool,
     #[serde(default = "default_log_level")]
@@ -168,11 +254,6 @@ pub struct Settings {
     pub autoupdate: bool,
 }
 
-#[allow(dead_code)]
-fn default_true() -> bool {
-    true
-}
-
 fn default_language() -> String {
     "Auto".to_string()
 }
@@ -205,6 +286,18 @@ fn default_display_size() -> String {
     "Desktop (1024x768)".to_string()
 }
 
+fn default_virustotal_requests_per_minute() -> u32 {
+    calc_virustotal::PUBLIC_REQUESTS_PER_MINUTE
+}
+
+fn default_virustotal_requests_per_day() -> u32 {
+    calc_virustotal::PUBLIC_REQUESTS_PER_DAY
+}
+
+fn default_virustotal_requests_per_month() -> u32 {
+    calc_virustotal::PUBLIC_REQUESTS_PER_MONTH
+}
+
 fn default_hybridanalysis_tag_ignorelist() -> String {
     "rat, jrat".to_string()
 }
@@ -217,6 +310,14 @@ impl Default for Settings {
             virustotal_submit: false,
             hybridanalysis_submit: false,
             hybridanalysis_tag_ignorelist: default_hybridanalysis_tag_ignorelist(),
+            virustotal_requests_per_minute: default_virustotal_requests_per_minute(),
+            virustotal_requests_per_day: default_virustotal_requests_per_day(),
+            virustotal_requests_per_month: default_virustotal_requests_per_month(),
+            scan_max_age_days: 0,
+            rescan_flagged: false,
+            rescan_interval_hours: 0,
+            malwarebazaar_apikey: String::new(),
+            koodous_apikey: String::new(),
             show_logs: false,
             log_level: default_log_level(),
             theme_mode: default_theme_mode(),
@@ -238,6 +339,24 @@ impl Default for Settings {
     }
 }
 
+impl Settings {
+    /// Daily/monthly budget of the VirusTotal API key
+    pub fn virustotal_quota(&self) -> calc_scanprovider_stt::QuotaBudget {
+        calc_scanprovider_stt::QuotaBudget {
+            per_day: self.virustotal_requests_per_day,
+            per_month: self.virustotal_requests_per_month,
+        }
+    }
+
+    /// Freshness policy for cached VirusTotal/Hybrid Analysis results
+    pub fn scan_freshness(&self) -> calc_scanprovider_stt::FreshnessPolicy {
+        calc_scanprovider_stt::FreshnessPolicy {
+            max_age_days: self.scan_max_age_days,
+            rescan_flagged: self.rescan_flagged,
+        }
+    }
+}
+
 impl Config {
     pub fn new() -> Result<Self> {
         #[cfg(target_os = "android")]
@@ -77,6 +77,8 @@ pub struct VirusTotalResult {
     pub raw_response: String,
     pub created_at: i32,
     pub updated_at: i32,
+    pub previous_malicious: Option<i32>,
+    pub previous_suspicious: Option<i32>,
 }
 
 #[derive(Insertable)]
@@ -123,6 +125,8 @@ pub struct HybridAnalysisResult {
     pub created_at: i32,
     pub updated_at: i32,
     pub error_message: Option<String>,
+    pub previous_verdict: Option<String>,
+    pub previous_threat_score: Option<i32>,
 }
 
 #[derive(Insertable)]
@@ -253,3 +257,301 @@ pub struct NewApkMirrorApp<'a> {
     pub created_at: i32,
     pub updated_at: i32,
 }
+
+// uad-ng lists version history
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::uad_lists_versions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UadListsVersion {
+    pub id: i32,
+    pub content_md5: String,
+    pub app_count: i32,
+    pub raw_json: String,
+    pub fetched_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::uad_lists_versions)]
+pub struct NewUadListsVersion<'a> {
+    pub content_md5: &'a str,
+    pub app_count: i32,
+    pub raw_json: &'a str,
+    pub fetched_at: i32,
+}
+
+// last debloat action per package
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::uad_package_actions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UadPackageAction {
+    pub id: i32,
+    pub pkg_id: String,
+    pub device_serial: String,
+    pub action: String,
+    pub removal: String,
+    pub acted_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::uad_package_actions)]
+pub struct NewUadPackageAction<'a> {
+    pub pkg_id: &'a str,
+    pub device_serial: &'a str,
+    pub action: &'a str,
+    pub removal: &'a str,
+    pub acted_at: i32,
+}
+
+// parsed AndroidManifest.xml per APK sha256
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::apk_manifest_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApkManifestCache {
+    pub id: i32,
+    pub apk_sha256sum: String,
+    pub pkg_id: String,
+    pub manifest_json: String,
+    pub created_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::apk_manifest_cache)]
+pub struct NewApkManifestCache<'a> {
+    pub apk_sha256sum: &'a str,
+    pub pkg_id: &'a str,
+    pub manifest_json: &'a str,
+    pub created_at: i32,
+}
+
+// DEX tracker scan result per APK sha256
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::apk_tracker_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApkTrackerCache {
+    pub id: i32,
+    pub apk_sha256sum: String,
+    pub signatures_md5: String,
+    pub pkg_id: String,
+    pub trackers_json: String,
+    pub created_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::apk_tracker_cache)]
+pub struct NewApkTrackerCache<'a> {
+    pub apk_sha256sum: &'a str,
+    pub signatures_md5: &'a str,
+    pub pkg_id: &'a str,
+    pub trackers_json: &'a str,
+    pub created_at: i32,
+}
+
+// local scan rule matches per APK sha256
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::apk_rule_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApkRuleCache {
+    pub id: i32,
+    pub apk_sha256sum: String,
+    pub rules_md5: String,
+    pub pkg_id: String,
+    pub matches_json: String,
+    pub created_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::apk_rule_cache)]
+pub struct NewApkRuleCache<'a> {
+    pub apk_sha256sum: &'a str,
+    pub rules_md5: &'a str,
+    pub pkg_id: &'a str,
+    pub matches_json: &'a str,
+    pub created_at: i32,
+}
+
+// native library inventory per APK sha256
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::apk_native_lib_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApkNativeLibCache {
+    pub id: i32,
+    pub apk_sha256sum: String,
+    pub inventory_version: i32,
+    pub pkg_id: String,
+    pub libs_json: String,
+    pub created_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::apk_native_lib_cache)]
+pub struct NewApkNativeLibCache<'a> {
+    pub apk_sha256sum: &'a str,
+    pub inventory_version: i32,
+    pub pkg_id: &'a str,
+    pub libs_json: &'a str,
+    pub created_at: i32,
+}
+
+// signing certificates per APK sha256
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::apk_signing_cert_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApkSigningCertCache {
+    pub id: i32,
+    pub apk_sha256sum: String,
+    pub pkg_id: String,
+    pub signature_hashes: String,
+    pub certificates_json: String,
+    pub created_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::apk_signing_cert_cache)]
+pub struct NewApkSigningCertCache<'a> {
+    pub apk_sha256sum: &'a str,
+    pub pkg_id: &'a str,
+    pub signature_hashes: &'a str,
+    pub certificates_json: &'a str,
+    pub created_at: i32,
+}
+
+// malwarebazaar hash lookup cache
+#[derive(Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = super::schema::malwarebazaar_results)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct MalwareBazaarResult {
+    pub id: i32,
+    pub package_name: String,
+    pub file_path: String,
+    pub sha256: String,
+    pub verdict: String,
+    pub signature: Option<String>,
+    pub file_type: Option<String>,
+    pub first_seen: Option<String>,
+    pub tags: String,
+    pub raw_response: String,
+    pub created_at: i32,
+    pub updated_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::malwarebazaar_results)]
+pub struct NewMalwareBazaarResult<'a> {
+    pub package_name: &'a str,
+    pub file_path: &'a str,
+    pub sha256: &'a str,
+    pub verdict: &'a str,
+    pub signature: Option<&'a str>,
+    pub file_type: Option<&'a str>,
+    pub first_seen: Option<&'a str>,
+    pub tags: &'a str,
+    pub raw_response: &'a str,
+    pub created_at: i32,
+    pub updated_at: i32,
+}
+
+// koodous hash lookup cache
+#[derive(Queryable, Selectable, Serialize, Clone)]
+#[diesel(table_name = super::schema::koodous_results)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct KoodousResult {
+    pub id: i32,
+    pub package_name: String,
+    pub file_path: String,
+    pub sha256: String,
+    pub verdict: String,
+    pub app: Option<String>,
+    pub company: Option<String>,
+    pub rating: Option<i32>,
+    pub detected: bool,
+    pub trusted: bool,
+    pub tags: String,
+    pub raw_response: String,
+    pub created_at: i32,
+    pub updated_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::koodous_results)]
+pub struct NewKoodousResult<'a> {
+    pub package_name: &'a str,
+    pub file_path: &'a str,
+    pub sha256: &'a str,
+    pub verdict: &'a str,
+    pub app: Option<&'a str>,
+    pub company: Option<&'a str>,
+    pub rating: Option<i32>,
+    pub detected: bool,
+    pub trusted: bool,
+    pub tags: &'a str,
+    pub raw_response: &'a str,
+    pub created_at: i32,
+    pub updated_at: i32,
+}
+
+// API requests per provider and UTC day
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::api_quota_usage)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ApiQuotaUsage {
+    pub id: i32,
+    pub provider: String,
+    pub day: String,
+    pub requests: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::api_quota_usage)]
+pub struct NewApiQuotaUsage<'a> {
+    pub provider: &'a str,
+    pub day: &'a str,
+    pub requests: i32,
+}
+
+// SHA256 hashes known to be clean
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::hash_allowlist)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct HashAllowlistEntry {
+    pub sha256: String,
+    pub label: Option<String>,
+    pub source: String,
+    pub added_at: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::hash_allowlist)]
+pub struct NewHashAllowlistEntry<'a> {
+    pub sha256: &'a str,
+    pub label: Option<&'a str>,
+    pub source: &'a str,
+    pub added_at: i32,
+}
+
+// runtime permission changes made from the app
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = super::schema::permission_journal)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PermissionJournalEntry {
+    pub id: i32,
+    pub device_serial: String,
+    pub user_id: i32,
+    pub pkg_id: String,
+    pub permission: String,
+    pub granted: bool,
+    pub previous_granted: bool,
+    pub changed_at: i32,
+    pub reverted_at: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::permission_journal)]
+pub struct NewPermissionJournalEntry<'a> {
+    pub device_serial: &'a str,
+    pub user_id: i32,
+    pub pkg_id: &'a str,
+    pub permission: &'a str,
+    pub granted: bool,
+    pub previous_granted: bool,
+    pub changed_at: i32,
+}
@@ -45,6 +45,8 @@ diesel::table! {
         raw_response -> Text,
         created_at -> Integer,
         updated_at -> Integer,
+        previous_malicious -> Nullable<Integer>,
+        previous_suspicious -> Nullable<Integer>,
     }
 }
 
@@ -68,6 +70,8 @@ diesel::table! {
         created_at -> Integer,
         updated_at -> Integer,
         error_message -> Nullable<Text>,
+        previous_verdict -> Nullable<Text>,
+        previous_threat_score -> Nullable<Integer>,
     }
 }
 
@@ -122,3 +126,146 @@ diesel::table! {
         updated_at -> Integer,
     }
 }
+
+diesel::table! {
+    uad_lists_versions (id) {
+        id -> Integer,
+        content_md5 -> Text,
+        app_count -> Integer,
+        raw_json -> Text,
+        fetched_at -> Integer,
+    }
+}
+
+diesel::table! {
+    uad_package_actions (id) {
+        id -> Integer,
+        pkg_id -> Text,
+        device_serial -> Text,
+        action -> Text,
+        removal -> Text,
+        acted_at -> Integer,
+    }
+}
+
+diesel::table! {
+    apk_manifest_cache (id) {
+        id -> Integer,
+        apk_sha256sum -> Text,
+        pkg_id -> Text,
+        manifest_json -> Text,
+        created_at -> Integer,
+    }
+}
+
+diesel::table! {
+    apk_tracker_cache (id) {
+        id -> Integer,
+        apk_sha256sum -> Text,
+        signatures_md5 -> Text,
+        pkg_id -> Text,
+        trackers_json -> Text,
+        created_at -> Integer,
+    }
+}
+
+diesel::table! {
+    apk_rule_cache (id) {
+        id -> Integer,
+        apk_sha256sum -> Text,
+        rules_md5 -> Text,
+        pkg_id -> Text,
+        matches_json -> Text,
+        created_at -> Integer,
+    }
+}
+
+diesel::table! {
+    apk_native_lib_cache (id) {
+        id -> Integer,
+        apk_sha256sum -> Text,
+        inventory_version -> Integer,
+        pkg_id -> Text,
+        libs_json -> Text,
+        created_at -> Integer,
+    }
+}
+
+diesel::table! {
+    apk_signing_cert_cache (id) {
+        id -> Integer,
+        apk_sha256sum -> Text,
+        pkg_id -> Text,
+        signature_hashes -> Text,
+        certificates_json -> Text,
+        created_at -> Integer,
+    }
+}
+
+diesel::table! {
+    malwarebazaar_results (id) {
+        id -> Integer,
+        package_name -> Text,
+        file_path -> Text,
+        sha256 -> Text,
+        verdict -> Text,
+        signature -> Nullable<Text>,
+        file_type -> Nullable<Text>,
+        first_seen -> Nullable<Text>,
+        tags -> Text,
+        raw_response -> Text,
+        created_at -> Integer,
+        updated_at -> Integer,
+    }
+}
+
+diesel::table! {
+    koodous_results (id) {
+        id -> Integer,
+        package_name -> Text,
+        file_path -> Text,
+        sha256 -> Text,
+        verdict -> Text,
+        app -> Nullable<Text>,
+        company -> Nullable<Text>,
+        rating -> Nullable<Integer>,
+        detected -> Bool,
+        trusted -> Bool,
+        tags -> Text,
+        raw_response -> Text,
+        created_at -> Integer,
+        updated_at -> Integer,
+    }
+}
+
+diesel::table! {
+    api_quota_usage (id) {
+        id -> Integer,
+        provider -> Text,
+        day -> Text,
+        requests -> Integer,
+    }
+}
+
+diesel::table! {
+    hash_allowlist (sha256) {
+        sha256 -> Text,
+        label -> Nullable<Text>,
+        source -> Text,
+        added_at -> Integer,
+    }
+}
+
+diesel::table! {
+    permission_journal (id) {
+        id -> Integer,
+        device_serial -> Text,
+        user_id -> Integer,
+        pkg_id -> Text,
+        permission -> Text,
+        granted -> Bool,
+        previous_granted -> Bool,
+        changed_at -> Integer,
+        reverted_at -> Nullable<Integer>,
+    }
+}
@@ -1,7 +1,16 @@
 use crate::adb::PackageFingerprint;
 use crate::calc_androidpackage::AndroidPackageInfo;
+use crate::calc_apksig_stt::SigningCertificate;
+use crate::calc_axml_stt::ManifestLoadState;
 use crate::calc_hybridanalysis::ScannerState as HaScannerState;
+use crate::calc_koodous::ScannerState as KoodousScannerState;
+use crate::calc_malwarebazaar::ScannerState as MbScannerState;
+use crate::calc_snapshot_stt::DatasetStatus;
 use crate::calc_stalkerware_stt::StalkerwareIndicators;
+use crate::calc_trackers_stt::{TrackerMatch, TrackerSignatures};
+use crate::calc_nativelibs_stt::NativeLibrary;
+use crate::calc_scanrules_stt::RuleMatch;
+use crate::calc_uadlists_stt::UadListsDiff;
 use crate::calc_virustotal::ScannerState as VtScannerState;
 use crate::models::{ApkMirrorApp, FDroidApp, GooglePlayApp};
 use crate::shared_store_stt::{SharedStore, SharedStoreUpdate};
@@ -23,7 +32,16 @@ impl SharedStore {
         Self {
             installed_packages: Mutex::new(Vec::new()),
             uad_ng_lists: Mutex::new(None),
+            uad_lists_diff: Mutex::new(None),
+            uad_lists_status: Mutex::new(None),
             stalkerware_indicators: Mutex::new(None),
+            stalkerware_status: Mutex::new(None),
+            tracker_signatures: Mutex::new(None),
+            tracker_signatures_status: Mutex::new(None),
+            tracker_results: Mutex::new(HashMap::new()),
+            scan_rules_status: Mutex::new(None),
+            rule_match_results: Mutex::new(HashMap::new()),
+            native_libraries: Mutex::new(HashMap::new()),
             google_play_textures: Mutex::new(HashMap::new()),
             fdroid_textures: Mutex::new(HashMap::new()),
             apkmirror_textures: Mutex::new(HashMap::new()),
@@ -32,8 +50,12 @@ impl SharedStore {
             cached_fdroid_apps: Mutex::new(HashMap::new()),
             cached_apkmirror_apps: Mutex::new(HashMap::new()),
             cached_android_package_apps: Mutex::new(HashMap::new()),
+            apk_manifests: Mutex::new(HashMap::new()),
+            signing_certificates: Mutex::new(HashMap::new()),
             vt_scanner_state: Mutex::new(None),
             ha_scanner_state: Mutex::new(None),
+            mb_scanner_state: Mutex::new(None),
+            koodous_scanner_state: Mutex::new(None),
             update_queue: SegQueue::new(),
         }
     }
@@ -96,6 +118,11 @@ impl SharedStore {
         }
     }
 
+    /// Change the installed packages in place under the lock; `None` when the lock is poisoned
+    pub fn update_installed_packages<R>(&self, update: impl FnOnce(&mut Vec<PackageFingerprint>) -> R) -> Option<R> {
+        self.installed_packages.lock().ok().map(|mut installed| update(&mut installed))
+    }
+
     pub fn queue_installed_packages(&self, packages: Vec<PackageFingerprint>) {
         self.update_queue
             .push(SharedStoreUpdate::InstalledPackages(packages));
@@ -118,6 +145,26 @@ impl SharedStore {
             .push(SharedStoreUpdate::UadNgLists(lists));
     }
 
+    pub fn get_uad_lists_diff(&self) -> Option<UadListsDiff> {
+        self.uad_lists_diff.lock().ok().and_then(|g| g.clone())
+    }
+
+    pub fn set_uad_lists_diff(&self, diff: Option<UadListsDiff>) {
+        if let Ok(mut d) = self.uad_lists_diff.lock() {
+            *d = diff;
+        }
+    }
+
+    pub fn get_uad_lists_status(&self) -> Option<DatasetStatus> {
+        self.uad_lists_status.lock().ok().and_then(|g| *g)
+    }
+
+    pub fn set_uad_lists_status(&self, status: Option<DatasetStatus>) {
+        if let Ok(mut s) = self.uad_lists_status.lock() {
+            *s = status;
+        }
+    }
+
     // === Stalkerware indicators ===
 
     pub fn get_stalkerware_indicators(&self) -> Option<StalkerwareIndicators> {
@@ -133,11 +180,132 @@ impl SharedStore {
         }
     }
 
+    pub fn get_stalkerware_status(&self) -> Option<DatasetStatus> {
+        self.stalkerware_status.lock().ok().and_then(|g| *g)
+    }
+
+    pub fn set_stalkerware_status(&self, status: Option<DatasetStatus>) {
+        if let Ok(mut s) = self.stalkerware_status.lock() {
+            *s = status;
+        }
+    }
+
     pub fn queue_stalkerware_indicators(&self, indicators: Option<StalkerwareIndicators>) {
         self.update_queue
             .push(SharedStoreUpdate::StalkerwareIndicators(indicators));
     }
 
+    // === Tracker signatures and results ===
+
+    pub fn get_tracker_signatures(&self) -> Option<TrackerSignatures> {
+        self.tracker_signatures.lock().ok().and_then(|g| g.clone())
+    }
+
+    pub fn has_tracker_signatures(&self) -> bool {
+        self.tracker_signatures.lock().map(|g| g.is_some()).unwrap_or(false)
+    }
+
+    pub fn set_tracker_signatures(&self, signatures: Option<TrackerSignatures>) {
+        if let Ok(mut s) = self.tracker_signatures.lock() {
+            *s = signatures;
+        }
+    }
+
+    pub fn get_tracker_signatures_status(&self) -> Option<DatasetStatus> {
+        self.tracker_signatures_status.lock().ok().and_then(|g| *g)
+    }
+
+    pub fn set_tracker_signatures_status(&self, status: Option<DatasetStatus>) {
+        if let Ok(mut s) = self.tracker_signatures_status.lock() {
+            *s = status;
+        }
+    }
+
+    pub fn get_tracker_results(&self) -> HashMap<String, Vec<TrackerMatch>> {
+        self.tracker_results
+            .lock()
+            .map(|g| g.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_tracker_result(&self, pkg_id: &str) -> Option<Vec<TrackerMatch>> {
+        self.tracker_results
+            .lock()
+            .ok()
+            .and_then(|g| g.get(pkg_id).cloned())
+    }
+
+    pub fn set_tracker_result(&self, pkg_id: String, trackers: Vec<TrackerMatch>) {
+        if let Ok(mut results) = self.tracker_results.lock() {
+            results.insert(pkg_id, trackers);
+        }
+    }
+
+    pub fn clear_tracker_results(&self) {
+        if let Ok(mut results) = self.tracker_results.lock() {
+            results.clear();
+        }
+    }
+
+    // === Local scan rules ===
+
+    pub fn get_scan_rules_status(&self) -> Option<Result<usize, String>> {
+        self.scan_rules_status.lock().ok().and_then(|g| g.clone())
+    }
+
+    pub fn set_scan_rules_status(&self, status: Option<Result<usize, String>>) {
+        if let Ok(mut s) = self.scan_rules_status.lock() {
+            *s = status;
+        }
+    }
+
+    pub fn get_rule_match_results(&self) -> HashMap<String, Vec<RuleMatch>> {
+        self.rule_match_results
+            .lock()
+            .map(|g| g.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_rule_match_result(&self, pkg_id: String, matches: Vec<RuleMatch>) {
+        if let Ok(mut results) = self.rule_match_results.lock() {
+            results.insert(pkg_id, matches);
+        }
+    }
+
+    pub fn clear_rule_match_results(&self) {
+        if let Ok(mut results) = self.rule_match_results.lock() {
+            results.clear();
+        }
+    }
+
+    // === Native libraries ===
+
+    pub fn get_all_native_libraries(&self) -> HashMap<String, Vec<NativeLibrary>> {
+        self.native_libraries
+            .lock()
+            .map(|g| g.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_native_libraries(&self, pkg_id: &str) -> Option<Vec<NativeLibrary>> {
+        self.native_libraries
+            .lock()
+            .ok()
+            .and_then(|g| g.get(pkg_id).cloned())
+    }
+
+    pub fn set_native_libraries(&self, pkg_id: String, libraries: Vec<NativeLibrary>) {
+        if let Ok(mut results) = self.native_libraries.lock() {
+            results.insert(pkg_id, libraries);
+        }
+    }
+
+    pub fn clear_native_libraries(&self) {
+        if let Ok(mut results) = self.native_libraries.lock() {
+            results.clear();
+        }
+    }
+
     // === Texture caches ===
 
     pub fn get_google_play_texture(&self, pkg_id: &str) -> Option<egui::TextureHandle> {
@@ -319,6 +487,49 @@ impl SharedStore {
         }
     }
 
+    // === APK manifests ===
+
+    pub fn get_apk_manifest_state(&self, pkg_id: &str) -> Option<ManifestLoadState> {
+        self.apk_manifests
+            .lock()
+            .ok()
+            .and_then(|g| g.get(pkg_id).cloned())
+    }
+
+    pub fn set_apk_manifest_state(&self, pkg_id: String, state: ManifestLoadState) {
+        if let Ok(mut manifests) = self.apk_manifests.lock() {
+            manifests.insert(pkg_id, state);
+        }
+    }
+
+    // === Signing certificates ===
+
+    pub fn get_all_signing_certificates(&self) -> HashMap<String, Vec<SigningCertificate>> {
+        self.signing_certificates
+            .lock()
+            .map(|g| g.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_signing_certificates(&self, pkg_id: &str) -> Option<Vec<SigningCertificate>> {
+        self.signing_certificates
+            .lock()
+            .ok()
+            .and_then(|g| g.get(pkg_id).cloned())
+    }
+
+    pub fn set_signing_certificates(&self, pkg_id: String, certificates: Vec<SigningCertificate>) {
+        if let Ok(mut certs) = self.signing_certificates.lock() {
+            certs.insert(pkg_id, certificates);
+        }
+    }
+
+    pub fn clear_signing_certificates(&self) {
+        if let Ok(mut certs) = self.signing_certificates.lock() {
+            certs.clear();
+        }
+    }
+
     // === Scanner states (scan tab only) ===
 
     pub fn get_vt_scanner_state(&self) -> Option<VtScannerState> {
@@ -340,4 +551,24 @@ impl SharedStore {
             *s = state;
         }
     }
+
+    pub fn get_mb_scanner_state(&self) -> Option<MbScannerState> {
+        self.mb_scanner_state.lock().ok().and_then(|g| g.clone())
+    }
+
+    pub fn set_mb_scanner_state(&self, state: Option<MbScannerState>) {
+        if let Ok(mut s) = self.mb_scanner_state.lock() {
+            *s = state;
+        }
+    }
+
+    pub fn get_koodous_scanner_state(&self) -> Option<KoodousScannerState> {
+        self.koodous_scanner_state.lock().ok().and_then(|g| g.clone())
+    }
+
+    pub fn set_koodous_scanner_state(&self, state: Option<KoodousScannerState>) {
+        if let Ok(mut s) = self.koodous_scanner_state.lock() {
+            *s = state;
+        }
+    }
 }
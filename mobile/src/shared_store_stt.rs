@@ -1,7 +1,16 @@
 use crate::adb::PackageFingerprint;
 use crate::calc_androidpackage::AndroidPackageInfo;
+use crate::calc_apksig_stt::SigningCertificate;
+use crate::calc_axml_stt::ManifestLoadState;
 use crate::calc_hybridanalysis::ScannerState as HaScannerState;
+use crate::calc_koodous::ScannerState as KoodousScannerState;
+use crate::calc_malwarebazaar::ScannerState as MbScannerState;
+use crate::calc_snapshot_stt::DatasetStatus;
 use crate::calc_stalkerware_stt::StalkerwareIndicators;
+use crate::calc_trackers_stt::{TrackerMatch, TrackerSignatures};
+use crate::calc_nativelibs_stt::NativeLibrary;
+use crate::calc_scanrules_stt::RuleMatch;
+use crate::calc_uadlists_stt::UadListsDiff;
 use crate::calc_virustotal::ScannerState as VtScannerState;
 use crate::models::{ApkMirrorApp, FDroidApp, GooglePlayApp};
 use crate::uad_shizuku_app::UadNgLists;
@@ -34,8 +43,26 @@ pub struct SharedStore {
     pub installed_packages: Mutex<Vec<PackageFingerprint>>,
     /// UAD-NG bloat lists
     pub uad_ng_lists: Mutex<Option<UadNgLists>>,
+    /// Changes between the two most recent UAD-NG list downloads
+    pub uad_lists_diff: Mutex<Option<UadListsDiff>>,
+    /// Origin and age of the loaded UAD-NG lists
+    pub uad_lists_status: Mutex<Option<DatasetStatus>>,
     /// Stalkerware indicators for package detection
     pub stalkerware_indicators: Mutex<Option<StalkerwareIndicators>>,
+    /// Origin and age of the loaded stalkerware indicators
+    pub stalkerware_status: Mutex<Option<DatasetStatus>>,
+    /// Exodus tracker code signatures
+    pub tracker_signatures: Mutex<Option<TrackerSignatures>>,
+    /// Origin and age of the loaded tracker signatures
+    pub tracker_signatures_status: Mutex<Option<DatasetStatus>>,
+    /// Trackers found in each package's DEX files
+    pub tracker_results: Mutex<HashMap<String, Vec<TrackerMatch>>>,
+    /// Number of loaded local scan rules, or why the rules file failed to load
+    pub scan_rules_status: Mutex<Option<Result<usize, String>>>,
+    /// Local scan rules matched by each package's APK contents
+    pub rule_match_results: Mutex<HashMap<String, Vec<RuleMatch>>>,
+    /// Native libraries inside each package's APKs
+    pub native_libraries: Mutex<HashMap<String, Vec<NativeLibrary>>>,
     /// Texture cache for Google Play icons
     pub google_play_textures: Mutex<HashMap<String, egui::TextureHandle>>,
     /// Texture cache for F-Droid icons
@@ -52,10 +79,18 @@ pub struct SharedStore {
     pub cached_apkmirror_apps: Mutex<HashMap<String, ApkMirrorApp>>,
     /// Cached Android Package app info
     pub cached_android_package_apps: Mutex<HashMap<String, AndroidPackageInfo>>,
+    /// Parsed AndroidManifest.xml per package (package details dialog)
+    pub apk_manifests: Mutex<HashMap<String, ManifestLoadState>>,
+    /// APK signing certificates per package
+    pub signing_certificates: Mutex<HashMap<String, Vec<SigningCertificate>>>,
     /// VirusTotal scanner state (scan tab only)
     pub vt_scanner_state: Mutex<Option<VtScannerState>>,
     /// Hybrid Analysis scanner state (scan tab only)
     pub ha_scanner_state: Mutex<Option<HaScannerState>>,
+    /// MalwareBazaar scanner state (scan tab only)
+    pub mb_scanner_state: Mutex<Option<MbScannerState>>,
+    /// Koodous scanner state (scan tab only)
+    pub koodous_scanner_state: Mutex<Option<KoodousScannerState>>,
     /// Update queue for thread-safe updates from background threads
     pub update_queue: SegQueue<SharedStoreUpdate>,
 }
@@ -1439,7 +1439,12 @@ impl TabAppsControl {
         }
 
         // Show package details dialog
-        self.package_details_dialog.show(ui.ctx(), &self.installed_packages, &None);
+        self.package_details_dialog.show(
+            ui.ctx(),
+            &self.installed_packages,
+            &None,
+            self.selected_device.as_deref(),
+        );
 
         has_error
     }
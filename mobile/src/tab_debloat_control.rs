@@ -1,9 +1,19 @@
 use crate::adb::PackageFingerprint;
+use crate::calc_background_stt::{
+    BackgroundAction, BackgroundState, BatteryWhitelist, DeviceBackgroundStates, StandbyBucket,
+};
 use crate::models::{ApkMirrorApp, FDroidApp, GooglePlayApp};
 use crate::shared_store_stt::get_shared_store;
 use crate::uad_shizuku_app::UadNgLists;
 pub use crate::tab_debloat_control_stt::*;
+use crate::dlg_capability_audit::DlgCapabilityAudit;
+use crate::dlg_install_provenance::DlgInstallProvenance;
+use crate::dlg_permission_manager::DlgPermissionManager;
+use crate::dlg_app_ops::DlgAppOps;
+use crate::dlg_components::DlgComponents;
 use crate::dlg_package_details::DlgPackageDetails;
+use crate::dlg_stalkerware_report::DlgStalkerwareReport;
+use crate::dlg_uad_changelog::DlgUadChangelog;
 use crate::dlg_uninstall_confirm::DlgUninstallConfirm;
 use eframe::egui;
 use egui_i18n::tr;
@@ -31,6 +41,15 @@ impl Default for TabDebloatControl {
             text_filter: String::new(),
             unsafe_app_remove: false,
             uninstall_confirm_dialog: DlgUninstallConfirm::default(),
+            uad_changelog_dialog: DlgUadChangelog::new(),
+            stalkerware_report_dialog: DlgStalkerwareReport::new(),
+            capability_audit_dialog: DlgCapabilityAudit::new(),
+            install_provenance_dialog: DlgInstallProvenance::new(),
+            permission_manager_dialog: DlgPermissionManager::new(),
+            app_ops_dialog: DlgAppOps::new(),
+            components_dialog: DlgComponents::new(),
+            uad_changed_packages: std::collections::HashSet::new(),
+            batch_acted_packages: Arc::new(Mutex::new(Vec::new())),
             batch_uninstall_state: BatchUninstallState::default(),
             batch_uninstall_progress: Arc::new(Mutex::new(None)),
             batch_uninstall_cancelled: Arc::new(Mutex::new(false)),
@@ -40,6 +59,14 @@ impl Default for TabDebloatControl {
             batch_enable_state: BatchUninstallState::default(),
             batch_enable_progress: Arc::new(Mutex::new(None)),
             batch_enable_cancelled: Arc::new(Mutex::new(false)),
+            certificate_scan_state: BatchUninstallState::default(),
+            certificate_scan_progress: Arc::new(Mutex::new(None)),
+            certificate_scan_cancelled: Arc::new(Mutex::new(false)),
+            background_states: Arc::new(Mutex::new(DeviceBackgroundStates::default())),
+            background_loading: Arc::new(Mutex::new(false)),
+            background_batch_result: Arc::new(Mutex::new(None)),
+            background_batch_running: false,
+            background_result: None,
         }
     }
 }
@@ -87,6 +114,256 @@ impl TabDebloatControl {
 
     pub fn set_selected_device(&mut self, device: Option<String>) {
         self.selected_device = device;
+        self.refresh_uad_change_flags();
+
+        // Load signing certificates already cached for the installed packages (no pulling)
+        get_shared_store().clear_signing_certificates();
+        self.run_certificate_scan(false);
+
+        // Tag the states with the new device so loads still running for the old one are dropped
+        if let Ok(mut states) = self.background_states.lock() {
+            states.device = self.selected_device.clone().unwrap_or_default();
+            states.states.clear();
+        }
+        if let Ok(mut loading) = self.background_loading.lock() {
+            *loading = false;
+        }
+        self.background_result = None;
+        if let Some(device) = self.selected_device.clone() {
+            crate::calc_background::run_background_load_async(
+                device,
+                self.background_states.clone(),
+                self.background_loading.clone(),
+            );
+        }
+    }
+
+    /// Apply a background change to the selected packages in a background thread
+    fn start_background_batch(&mut self, action: BackgroundAction) {
+        let Some(device) = self.selected_device.clone() else {
+            return;
+        };
+        let mut packages: Vec<String> = self.selected_packages.iter().cloned().collect();
+        if packages.is_empty() {
+            return;
+        }
+        packages.sort();
+
+        if let Ok(mut r) = self.background_batch_result.lock() {
+            *r = None;
+        }
+        self.background_batch_running = true;
+        self.background_result = None;
+        log::info!("Applying {:?} to {} packages", action, packages.len());
+        crate::calc_background::run_background_actions_async(
+            device,
+            packages,
+            action,
+            self.background_states.clone(),
+            self.background_batch_result.clone(),
+        );
+    }
+
+    /// Menu of background changes for the selected packages; returns the picked change
+    pub fn background_action_menu(ui: &mut egui::Ui, label: String) -> Option<BackgroundAction> {
+        let mut picked = None;
+        ui.menu_button(label, |ui| {
+            ui.label(egui::RichText::new(tr!("background-standby-bucket")).small().weak());
+            for bucket in StandbyBucket::SETTABLE {
+                if ui.button(bucket.as_str()).clicked() {
+                    picked = Some(BackgroundAction::SetBucket(bucket));
+                }
+            }
+            ui.separator();
+            for (action, text) in [
+                (BackgroundAction::Whitelist, tr!("background-whitelist")),
+                (BackgroundAction::Unwhitelist, tr!("background-unwhitelist")),
+                (BackgroundAction::Restrict, tr!("background-restrict")),
+                (BackgroundAction::Unrestrict, tr!("background-unrestrict")),
+            ] {
+                if ui.button(text).clicked() {
+                    picked = Some(action);
+                }
+            }
+            if picked.is_some() {
+                ui.close();
+            }
+        })
+        .response
+        .on_hover_text(tr!("background-desc"));
+        picked
+    }
+
+    /// Disable a package for the current user and update its state in the store
+    fn disable_package(&mut self, pkg_name: &str) -> AdbResult {
+        let Some(device) = self.selected_device.clone() else {
+            log::error!("No device selected for disable");
+            return AdbResult::Failure;
+        };
+        match crate::adb::disable_app_current_user(pkg_name, &device, None) {
+            Ok(output) => {
+                log::info!("App disabled successfully: {}", output);
+                crate::calc_uadlists::record_package_action(pkg_name, &device, "disable");
+                self.uad_changed_packages.remove(pkg_name);
+                let store = get_shared_store();
+                let mut packages = store.get_installed_packages();
+                if let Some(pkg) = packages.iter_mut().find(|p| p.pkg == pkg_name) {
+                    // disable_app_current_user targets user 0
+                    for user in pkg.users.iter_mut().filter(|u| u.userId == 0) {
+                        user.enabled = 3;
+                    }
+                }
+                store.set_installed_packages(packages);
+                AdbResult::Success(pkg_name.to_string())
+            }
+            Err(e) => {
+                log::error!("Failed to disable app: {}", e);
+                AdbResult::Failure
+            }
+        }
+    }
+
+    /// Ask to disable a package picked in another tab, applying the same unsafe guard as the
+    /// table toggle. The confirmation shows when the debloat tab is next drawn.
+    pub fn request_disable(&mut self, pkg_id: String) -> Result<(), String> {
+        let store = get_shared_store();
+        let is_unsafe = store
+            .get_uad_ng_lists()
+            .and_then(|lists| lists.apps.get(&pkg_id).map(|app| app.removal == "Unsafe"))
+            .unwrap_or(false);
+        if is_unsafe && !self.unsafe_app_remove {
+            log::warn!("Refusing to disable unsafe app: {}", pkg_id);
+            return Err(tr!("disable-unsafe-blocked", { name: pkg_id }));
+        }
+        let is_system = store
+            .get_installed_packages()
+            .iter()
+            .find(|p| p.pkg == pkg_id)
+            .map(|p| p.flags.contains("SYSTEM"))
+            .unwrap_or(false);
+        self.uninstall_confirm_dialog.open_disable(pkg_id, is_system);
+        Ok(())
+    }
+
+    /// Labels shown in the background column, with their colors
+    fn background_labels(state: &BackgroundState) -> Vec<(String, egui::Color32)> {
+        let mut labels = Vec::new();
+        if let Some(bucket) = state.bucket {
+            let color = match bucket {
+                StandbyBucket::Exempted | StandbyBucket::Active | StandbyBucket::WorkingSet => {
+                    egui::Color32::from_rgb(255, 152, 0)
+                }
+                StandbyBucket::Frequent => egui::Color32::from_rgb(158, 158, 158),
+                StandbyBucket::Rare | StandbyBucket::Restricted | StandbyBucket::Never => {
+                    egui::Color32::from_rgb(56, 142, 60)
+                }
+            };
+            labels.push((bucket.as_str().to_string(), color));
+        }
+        match state.whitelist {
+            Some(BatteryWhitelist::User) => {
+                labels.push((tr!("background-unoptimized"), egui::Color32::from_rgb(255, 152, 0)))
+            }
+            Some(BatteryWhitelist::System) => {
+                labels.push((tr!("background-unoptimized-system"), egui::Color32::from_rgb(158, 158, 158)))
+            }
+            None => {}
+        }
+        if state.restricted {
+            labels.push((tr!("background-restricted"), egui::Color32::from_rgb(56, 142, 60)));
+        }
+        labels
+    }
+
+    /// Pick up the outcome of a finished background batch
+    fn poll_background_batch(&mut self) {
+        if !self.background_batch_running {
+            return;
+        }
+        let Some(outcome) = self.background_batch_result.lock().ok().and_then(|mut r| r.take()) else {
+            return;
+        };
+        self.background_batch_running = false;
+        self.background_result = Some(if outcome.failures.is_empty() {
+            (true, tr!("background-changes-done", { count: outcome.succeeded }))
+        } else {
+            (
+                false,
+                format!(
+                    "{}\n{}",
+                    tr!("background-changes-failed", {
+                        count: outcome.failures.len(),
+                        succeeded: outcome.succeeded
+                    }),
+                    outcome.failures.join("\n")
+                ),
+            )
+        });
+    }
+
+    /// Clear the changed-recommendation marker of packages a batch action succeeded on
+    fn poll_batch_acted_packages(&mut self) {
+        let Some(acted) = self.batch_acted_packages.lock().ok().map(|mut a| std::mem::take(&mut *a)) else {
+            return;
+        };
+        for pkg_name in &acted {
+            self.uad_changed_packages.remove(pkg_name);
+        }
+    }
+
+    /// Resolve signing certificates of installed packages in a background thread, for
+    /// stalkerware certificate matching. With `pull_missing` false nothing is pulled.
+    fn run_certificate_scan(&mut self, pull_missing: bool) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        let Some(device) = self.selected_device.clone() else {
+            log::debug!("Skipping certificate scan: no device selected");
+            return;
+        };
+        if installed_packages.is_empty() {
+            return;
+        }
+
+        self.certificate_scan_state.start();
+        if let Ok(mut p) = self.certificate_scan_progress.lock() {
+            *p = Some(0.0);
+        }
+        if let Ok(mut cancelled) = self.certificate_scan_cancelled.lock() {
+            *cancelled = false;
+        }
+
+        log::info!(
+            "Starting signing certificate scan for {} packages (pull_missing={})",
+            installed_packages.len(),
+            pull_missing
+        );
+
+        crate::calc_apksig::run_certificate_scan_async(
+            installed_packages,
+            device,
+            pull_missing,
+            self.certificate_scan_progress.clone(),
+            self.certificate_scan_cancelled.clone(),
+        );
+    }
+
+    /// Recompute installed packages whose UAD recommendation changed since the last action on them
+    fn refresh_uad_change_flags(&mut self) -> Vec<crate::calc_uadlists_stt::UadActionFlag> {
+        let store = get_shared_store();
+        let flags = match (&self.selected_device, store.get_uad_ng_lists()) {
+            (Some(device), Some(lists)) => {
+                let actions = crate::db_uad_lists::get_uad_package_actions(device);
+                crate::calc_uadlists::flag_changed_since_action(
+                    &actions,
+                    &lists,
+                    &store.get_installed_packages(),
+                )
+            }
+            _ => Vec::new(),
+        };
+        self.uad_changed_packages = flags.iter().map(|f| f.pkg_id.clone()).collect();
+        flags
     }
 
     pub fn update_uad_ng_lists(&mut self, lists: UadNgLists) {
@@ -137,6 +414,7 @@ impl TabDebloatControl {
         // Clone data needed for background thread
         let progress_clone = self.batch_uninstall_progress.clone();
         let cancelled_clone = self.batch_uninstall_cancelled.clone();
+        let acted_clone = self.batch_acted_packages.clone();
         let unsafe_app_remove = self.unsafe_app_remove;
 
         // Build unsafe app set from uad_ng_lists
@@ -188,6 +466,10 @@ impl TabDebloatControl {
                     Ok(output) => {
                         log::info!("App uninstalled successfully: {}", output);
                         success_count += 1;
+                        crate::calc_uadlists::record_package_action(&pkg_name, &device, "uninstall");
+                        if let Ok(mut acted) = acted_clone.lock() {
+                            acted.push(pkg_name.clone());
+                        }
 
                         // Update package state in shared store
                         let store = get_shared_store();
@@ -245,6 +527,7 @@ impl TabDebloatControl {
         // Clone data needed for background thread
         let progress_clone = self.batch_disable_progress.clone();
         let cancelled_clone = self.batch_disable_cancelled.clone();
+        let acted_clone = self.batch_acted_packages.clone();
 
         // Spawn background thread
         std::thread::spawn(move || {
@@ -271,6 +554,10 @@ impl TabDebloatControl {
                     Ok(output) => {
                         log::info!("App disabled successfully: {}", output);
                         success_count += 1;
+                        crate::calc_uadlists::record_package_action(&pkg_name, &device, "disable");
+                        if let Ok(mut acted) = acted_clone.lock() {
+                            acted.push(pkg_name.clone());
+                        }
 
                         // Update package state in shared store
                         let store = get_shared_store();
@@ -323,6 +610,7 @@ impl TabDebloatControl {
         // Clone data needed for background thread
         let progress_clone = self.batch_enable_progress.clone();
         let cancelled_clone = self.batch_enable_cancelled.clone();
+        let acted_clone = self.batch_acted_packages.clone();
 
         // Spawn background thread
         std::thread::spawn(move || {
@@ -349,6 +637,10 @@ impl TabDebloatControl {
                     Ok(output) => {
                         log::info!("App enabled successfully: {}", output);
                         success_count += 1;
+                        crate::calc_uadlists::record_package_action(&pkg_name, &device, "enable");
+                        if let Ok(mut acted) = acted_clone.lock() {
+                            acted.push(pkg_name.clone());
+                        }
 
                         // Update package state in shared store
                         let store = get_shared_store();
@@ -629,6 +921,8 @@ impl TabDebloatControl {
             let store = get_shared_store();
             let uad_ng_lists = store.get_uad_ng_lists();
             let stalkerware_indicators = store.get_stalkerware_indicators();
+            let signing_certificates = store.get_all_signing_certificates();
+            let background_states = self.background_states.lock().map(|s| s.states.clone()).unwrap_or_default();
             let mut installed_packages = store.get_installed_packages();
 
             installed_packages.sort_by(|a, b| {
@@ -668,11 +962,11 @@ impl TabDebloatControl {
                         // Stalkerware column - sort by boolean (true = stalkerware detected)
                         let is_stalkerware_a = stalkerware_indicators
                             .as_ref()
-                            .map(|ind| ind.is_stalkerware(&a.pkg))
+                            .map(|ind| ind.matches(&a.pkg, signing_certificates.get(&a.pkg).map(|c| c.as_slice())))
                             .unwrap_or(false);
                         let is_stalkerware_b = stalkerware_indicators
                             .as_ref()
-                            .map(|ind| ind.is_stalkerware(&b.pkg))
+                            .map(|ind| ind.matches(&b.pkg, signing_certificates.get(&b.pkg).map(|c| c.as_slice())))
                             .unwrap_or(false);
                         is_stalkerware_a.cmp(&is_stalkerware_b)
                     }
@@ -704,6 +998,13 @@ impl TabDebloatControl {
 
                         reason_str_a.cmp(&reason_str_b)
                     }
+                    5 => {
+                        let key = |pkg: &str| {
+                            let state = background_states.get(pkg).copied().unwrap_or_default();
+                            (state.bucket, state.restricted, state.whitelist)
+                        };
+                        key(&a.pkg).cmp(&key(&b.pkg))
+                    }
                     _ => std::cmp::Ordering::Equal,
                 };
 
@@ -1001,6 +1302,17 @@ impl TabDebloatControl {
         }
 
         // Batch action buttons
+        let uad_change_count = store.get_uad_lists_diff().map(|d| d.change_count()).unwrap_or(0)
+            + self.uad_changed_packages.len();
+        let mut open_uad_changelog = false;
+        let mut open_stalkerware_report = false;
+        let mut open_capability_audit = false;
+        let mut open_install_provenance = false;
+        let mut open_permission_manager = false;
+        let mut open_app_ops = false;
+        let mut open_components = false;
+        let mut background_action: Option<BackgroundAction> = None;
+        let background_busy = self.background_batch_running;
         ui.horizontal_wrapped(|ui| {
             let selected_count = self.selected_packages.len();
 
@@ -1010,6 +1322,50 @@ impl TabDebloatControl {
                     self.selected_packages.clear();
                 }
 
+                if uad_change_count > 0 && ui.button(tr!("uad-changelog", { count: uad_change_count })).clicked() {
+                    open_uad_changelog = true;
+                }
+
+                if ui
+                    .add_enabled(!self.certificate_scan_state.is_running, egui::Button::new(tr!("check-certificates")))
+                    .on_hover_text(tr!("check-certificates-desc"))
+                    .clicked()
+                {
+                    self.run_certificate_scan(true);
+                }
+
+                if ui
+                    .button(tr!("stalkerware-report"))
+                    .on_hover_text(tr!("stalkerware-report-desc"))
+                    .clicked()
+                {
+                    open_stalkerware_report = true;
+                }
+
+                if ui
+                    .button(tr!("capability-audit"))
+                    .on_hover_text(tr!("capability-audit-desc"))
+                    .clicked()
+                {
+                    open_capability_audit = true;
+                }
+
+                if ui
+                    .button(tr!("provenance"))
+                    .on_hover_text(tr!("provenance-desc"))
+                    .clicked()
+                {
+                    open_install_provenance = true;
+                }
+
+                if ui
+                    .button(tr!("permission-manager"))
+                    .on_hover_text(tr!("permission-manager-desc"))
+                    .clicked()
+                {
+                    open_permission_manager = true;
+                }
+
                 ui.separator();
 
                 ui.label(tr!("selected-count", { count: selected_count }));
@@ -1032,6 +1388,30 @@ impl TabDebloatControl {
                             data.insert_temp(egui::Id::new("batch_enable_clicked"), true);
                         });
                     }
+
+                    if ui
+                        .button(&tr!("appops-selected", { count: selected_count }))
+                        .on_hover_text(tr!("appops-desc"))
+                        .clicked()
+                    {
+                        open_app_ops = true;
+                    }
+
+                    if ui
+                        .button(&tr!("components-selected", { count: selected_count }))
+                        .on_hover_text(tr!("components-desc"))
+                        .clicked()
+                    {
+                        open_components = true;
+                    }
+
+                    ui.add_enabled_ui(!background_busy, |ui| {
+                        if let Some(action) =
+                            Self::background_action_menu(ui, tr!("background-selected", { count: selected_count }))
+                        {
+                            background_action = Some(action);
+                        }
+                    });
                 }
             } else {
                 // Desktop: use small MaterialButton
@@ -1039,6 +1419,55 @@ impl TabDebloatControl {
                     self.selected_packages.clear();
                 }
 
+                if uad_change_count > 0
+                    && ui.add(MaterialButton::outlined(tr!("uad-changelog", { count: uad_change_count })).small()).clicked()
+                {
+                    open_uad_changelog = true;
+                }
+
+                if ui
+                    .add_enabled(
+                        !self.certificate_scan_state.is_running,
+                        MaterialButton::outlined(tr!("check-certificates")).small(),
+                    )
+                    .on_hover_text(tr!("check-certificates-desc"))
+                    .clicked()
+                {
+                    self.run_certificate_scan(true);
+                }
+
+                if ui
+                    .add(MaterialButton::outlined(tr!("stalkerware-report")).small())
+                    .on_hover_text(tr!("stalkerware-report-desc"))
+                    .clicked()
+                {
+                    open_stalkerware_report = true;
+                }
+
+                if ui
+                    .add(MaterialButton::outlined(tr!("capability-audit")).small())
+                    .on_hover_text(tr!("capability-audit-desc"))
+                    .clicked()
+                {
+                    open_capability_audit = true;
+                }
+
+                if ui
+                    .add(MaterialButton::outlined(tr!("provenance")).small())
+                    .on_hover_text(tr!("provenance-desc"))
+                    .clicked()
+                {
+                    open_install_provenance = true;
+                }
+
+                if ui
+                    .add(MaterialButton::outlined(tr!("permission-manager")).small())
+                    .on_hover_text(tr!("permission-manager-desc"))
+                    .clicked()
+                {
+                    open_permission_manager = true;
+                }
+
                 ui.separator();
 
                 ui.label(tr!("selected-count", { count: selected_count }));
@@ -1070,11 +1499,96 @@ impl TabDebloatControl {
                             data.insert_temp(egui::Id::new("batch_enable_clicked"), true);
                         });
                     }
+
+                    if ui
+                        .add(MaterialButton::outlined(&tr!("appops-selected", { count: selected_count })).small())
+                        .on_hover_text(tr!("appops-desc"))
+                        .clicked()
+                    {
+                        open_app_ops = true;
+                    }
+
+                    if ui
+                        .add(MaterialButton::outlined(&tr!("components-selected", { count: selected_count })).small())
+                        .on_hover_text(tr!("components-desc"))
+                        .clicked()
+                    {
+                        open_components = true;
+                    }
+
+                    ui.add_enabled_ui(!background_busy, |ui| {
+                        if let Some(action) =
+                            Self::background_action_menu(ui, tr!("background-selected", { count: selected_count }))
+                        {
+                            background_action = Some(action);
+                        }
+                    });
                 }
             }
         });
         ui.add_space(10.0);
 
+        if open_uad_changelog {
+            let flags = self.refresh_uad_change_flags();
+            self.uad_changelog_dialog.open(flags);
+        }
+        if open_stalkerware_report {
+            if let Some(device) = self.selected_device.clone() {
+                self.stalkerware_report_dialog.open(device);
+            }
+        }
+        if open_capability_audit {
+            if let Some(device) = self.selected_device.clone() {
+                self.capability_audit_dialog.open(device);
+            }
+        }
+        if open_install_provenance {
+            self.install_provenance_dialog.open();
+        }
+        if open_permission_manager {
+            if let Some(device) = self.selected_device.clone() {
+                self.permission_manager_dialog.open(device);
+            }
+        }
+        if open_app_ops {
+            if let Some(device) = self.selected_device.clone() {
+                let mut packages: Vec<String> = self.selected_packages.iter().cloned().collect();
+                packages.sort();
+                self.app_ops_dialog.open(device, packages);
+            }
+        }
+        if open_components {
+            if let Some(device) = self.selected_device.clone() {
+                let mut packages: Vec<String> = self.selected_packages.iter().cloned().collect();
+                packages.sort();
+                self.components_dialog.open(device, packages);
+            }
+        }
+        if let Some(action) = background_action {
+            self.start_background_batch(action);
+        }
+        self.poll_background_batch();
+        self.poll_batch_acted_packages();
+        let background_loading = self.background_loading.lock().map(|l| *l).unwrap_or(false);
+        if self.background_batch_running || background_loading {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(if self.background_batch_running {
+                    tr!("background-applying")
+                } else {
+                    tr!("background-loading")
+                });
+            });
+        } else if let Some((ok, message)) = &self.background_result {
+            let color = if *ok {
+                egui::Color32::from_rgb(56, 142, 60)
+            } else {
+                egui::Color32::from_rgb(211, 47, 47)
+            };
+            ui.colored_label(color, message);
+        }
+
         // Show only enabled toggle
         ui.horizontal_wrapped(|ui| {
             ui.label(tr!("show-only-enabled"));
@@ -1177,6 +1691,23 @@ impl TabDebloatControl {
                 }
                 self.sort_packages();
             }
+
+            // Background sort button
+            let background_selected = self.sort_column == Some(5);
+            let background_label = if background_selected {
+                format!("{} {}", tr!("col-background"), if self.sort_ascending { "▲" } else { "▼" })
+            } else {
+                format!("{} {}", tr!("col-background"), "▲") // Default ascending
+            };
+            if ui.selectable_label(background_selected, background_label).clicked() {
+                if self.sort_column == Some(5) {
+                    self.sort_ascending = !self.sort_ascending;
+                } else {
+                    self.sort_column = Some(5);
+                    self.sort_ascending = true;
+                }
+                self.sort_packages();
+            }
         });
 
         let clicked_package_idx = std::sync::Arc::new(std::sync::Mutex::new(None::<usize>));
@@ -1197,20 +1728,21 @@ impl TabDebloatControl {
                 .sortable_column(tr!("col-debloat-category"), 130.0 * width_ratio, false)
                 .sortable_column(tr!("col-runtime-permissions"), 80.0 * width_ratio, true)
                 .sortable_column(tr!("col-stalkerware"), 120.0 * width_ratio, false)
-                .sortable_column(tr!("col-install-reason"), 110.0 * width_ratio, false);
+                .sortable_column(tr!("col-install-reason"), 110.0 * width_ratio, false)
+                .sortable_column(tr!("col-background"), 120.0 * width_ratio, false);
         }
         debloat_table = debloat_table
             .sortable_column(tr!("col-tasks"), if is_desktop { 160.0 * width_ratio } else { (available_width * 0.3 + (50.0/available_width) * 0.3)  }, false)
             .allow_selection(true);
 
         // Sort column index mapping: self.sort_column uses logical (desktop) indices
-        // Desktop: [0=PackageName, 1=DebloatCategory, 2=RP, 3=Stalkerware, 4=InstallReason, 5=Tasks]
+        // Desktop: [0=PackageName, 1=DebloatCategory, 2=RP, 3=Stalkerware, 4=InstallReason, 5=Background, 6=Tasks]
         // Mobile:  [0=PackageName, 1=Tasks]
         let to_physical = |logical: usize| -> usize {
             if is_desktop { logical } else { match logical { 0 => 0, _ => 1 } }
         };
         let to_logical = |physical: usize| -> usize {
-            if is_desktop { physical } else { match physical { 0 => 0, _ => 5 } }
+            if is_desktop { physical } else { match physical { 0 => 0, _ => 6 } }
         };
 
         if let Some(sort_col) = self.sort_column {
@@ -1220,7 +1752,7 @@ impl TabDebloatControl {
             } else {
                 SortDirection::Descending
             };
-            if is_desktop || sort_col == 0 || sort_col == 5 {
+            if is_desktop || sort_col == 0 || sort_col == 6 {
                 debloat_table = debloat_table.sort_by(to_physical(sort_col), direction);
             }
         }
@@ -1230,6 +1762,8 @@ impl TabDebloatControl {
         // Get stalkerware indicators from shared store
         let shared_store = crate::shared_store_stt::get_shared_store();
         let stalkerware_indicators = shared_store.get_stalkerware_indicators();
+        let signing_certificates = shared_store.get_all_signing_certificates();
+        let background_states = self.background_states.lock().map(|s| s.states.clone()).unwrap_or_default();
 
         // Collect filtered packages info first to avoid borrow issues
         // Note: uad_ng_lists_ref is pre-fetched at the start of ui()
@@ -1258,7 +1792,7 @@ impl TabDebloatControl {
                     .to_string();
                 let is_stalkerware = stalkerware_indicators
                     .as_ref()
-                    .map(|ind| ind.is_stalkerware(&package.pkg))
+                    .map(|ind| ind.matches(&package.pkg, signing_certificates.get(&package.pkg).map(|c| c.as_slice())))
                     .unwrap_or(false);
                 let enabled = package
                     .users
@@ -1301,6 +1835,7 @@ impl TabDebloatControl {
             let package_name_clone = package_name.clone();
             let enabled_str = enabled_text.clone();
             let debloat_category_clone = debloat_category.clone();
+            let is_uad_changed = self.uad_changed_packages.contains(&pkg_id);
 
             // Get cached app info from pre-fetched maps (avoids repeated mutex locks)
             let fd_cached = cached_fdroid_apps.get(&pkg_id);
@@ -1505,6 +2040,9 @@ impl TabDebloatControl {
                                     .show(ui, |ui| {
                                         ui.horizontal(|ui| {
                                             render_badges(ui, &runtime_perms_clone, &debloat_category_clone2, is_stalkerware_clone, &enabled_text_clone2, &install_reason_clone);
+                                            if is_uad_changed {
+                                                render_uad_changed_badge(ui);
+                                            }
                                         });
                                     });
                             });
@@ -1544,6 +2082,9 @@ impl TabDebloatControl {
                                 .show(ui, |ui| {
                                     ui.label(egui::RichText::new(&label_text).color(text_color).size(12.0));
                                 });
+                            if is_uad_changed {
+                                render_uad_changed_badge(ui);
+                            }
                         });
                     });
 
@@ -1588,6 +2129,17 @@ impl TabDebloatControl {
                                 });
                         });
                     });
+
+                    // Background column (standby bucket, battery optimization, background restriction)
+                    let background_labels =
+                        Self::background_labels(&background_states.get(&pkg_id_clone).copied().unwrap_or_default());
+                    row_builder = row_builder.widget_cell(move |ui: &mut egui::Ui| {
+                        ui.vertical(|ui| {
+                            for (text, color) in &background_labels {
+                                ui.label(egui::RichText::new(text).color(*color).size(12.0));
+                            }
+                        });
+                    });
                 }
 
                 // Tasks column
@@ -1662,8 +2214,8 @@ impl TabDebloatControl {
         let table_response = debloat_table.show(ui);
 
         // Sync sort state from widget, but only when sorting by a column the widget knows about.
-        // On mobile, hidden columns (1-4) are managed by the mobile sort buttons, not the table widget.
-        let mobile_hidden_sort = !is_desktop && matches!(self.sort_column, Some(1..=4));
+        // On mobile, hidden columns (1-5) are managed by the mobile sort buttons, not the table widget.
+        let mobile_hidden_sort = !is_desktop && matches!(self.sort_column, Some(1..=5));
         if !mobile_hidden_sort {
             let (widget_sort_col, widget_sort_dir) = table_response.sort_state;
             let logical_sort_col = widget_sort_col.map(|c| to_logical(c));
@@ -1710,6 +2262,18 @@ impl TabDebloatControl {
                 self.package_details_dialog.open(idx);
             }
         }
+        // Package linked from the capability audit, provenance view or permission manager
+        let linked_package = self
+            .capability_audit_dialog
+            .open_package
+            .take()
+            .or_else(|| self.install_provenance_dialog.open_package.take())
+            .or_else(|| self.permission_manager_dialog.open_package.take());
+        if let Some(pkg_id) = linked_package {
+            if let Some(idx) = store.get_installed_packages().iter().position(|p| p.pkg == pkg_id) {
+                self.package_details_dialog.open(idx);
+            }
+        }
 
         // Handle button clicks
         let mut uninstall_package: Option<String> = None;
@@ -1760,6 +2324,8 @@ impl TabDebloatControl {
                 match crate::adb::enable_app(&pkg_name, device) {
                     Ok(output) => {
                         log::info!("App enabled successfully: {}", output);
+                        crate::calc_uadlists::record_package_action(&pkg_name, device, "enable");
+                        self.uad_changed_packages.remove(&pkg_name);
                         let mut packages = store.get_installed_packages();
                         if let Some(pkg) = packages.iter_mut().find(|p| p.pkg == pkg_name) {
                             for user in pkg.users.iter_mut() {
@@ -1783,28 +2349,7 @@ impl TabDebloatControl {
 
         // Perform disable
         if let Some(pkg_name) = disable_package {
-            if let Some(ref device) = self.selected_device {
-                match crate::adb::disable_app_current_user(&pkg_name, device, None) {
-                    Ok(output) => {
-                        log::info!("App disabled successfully: {}", output);
-                        let mut packages = store.get_installed_packages();
-                        if let Some(pkg) = packages.iter_mut().find(|p| p.pkg == pkg_name) {
-                            for user in pkg.users.iter_mut() {
-                                user.enabled = 3;
-                            }
-                        }
-                        store.set_installed_packages(packages);
-                        result = Some(AdbResult::Success(pkg_name.clone()));
-                    }
-                    Err(e) => {
-                        log::error!("Failed to disable app: {}", e);
-                        result = Some(AdbResult::Failure);
-                    }
-                }
-            } else {
-                log::error!("No device selected for disable");
-                result = Some(AdbResult::Failure);
-            }
+            result = Some(self.disable_package(&pkg_name));
         }
 
         // Open confirm dialog for batch uninstall
@@ -1846,9 +2391,15 @@ impl TabDebloatControl {
         if self.uninstall_confirm_dialog.show(ui.ctx()) {
             let pkgs = std::mem::take(&mut self.uninstall_confirm_dialog.packages);
             let sys_flags = std::mem::take(&mut self.uninstall_confirm_dialog.is_system);
+            let disable = self.uninstall_confirm_dialog.disable;
             self.uninstall_confirm_dialog.reset();
 
-            if let Some(ref device) = self.selected_device {
+            if disable {
+                // Disable requested from another tab (see `request_disable`)
+                for pkg_name in pkgs {
+                    result = Some(self.disable_package(&pkg_name));
+                }
+            } else if let Some(ref device) = self.selected_device {
                 // Start background batch uninstall
                 self.start_batch_uninstall(pkgs, sys_flags, device.clone(), uad_ng_lists_ref);
             } else {
@@ -1860,12 +2411,43 @@ impl TabDebloatControl {
         // Show package details dialog
         let packages_for_dialog = store.get_installed_packages();
         let uad_lists_for_dialog = store.get_uad_ng_lists();
-        self.package_details_dialog.show(ui.ctx(), &packages_for_dialog, &uad_lists_for_dialog);
+        self.package_details_dialog.show(
+            ui.ctx(),
+            &packages_for_dialog,
+            &uad_lists_for_dialog,
+            self.selected_device.as_deref(),
+        );
+
+        // Show UAD-NG list changelog dialog
+        self.uad_changelog_dialog.show(ui.ctx());
+        self.stalkerware_report_dialog.show(ui.ctx());
+        self.capability_audit_dialog.show(ui.ctx());
+        self.install_provenance_dialog.show(ui.ctx());
+        self.permission_manager_dialog.show(ui.ctx());
+        self.app_ops_dialog.show(ui.ctx());
+        self.components_dialog.show(ui.ctx());
 
         result
     }
 }
 
+/// Badge for packages whose UAD recommendation changed since the user last acted on them
+fn render_uad_changed_badge(ui: &mut egui::Ui) {
+    egui::Frame::new()
+        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 152, 0)))
+        .corner_radius(6.0)
+        .inner_margin(egui::Margin::symmetric(8, 3))
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(tr!("uad-changed-badge"))
+                    .color(egui::Color32::from_rgb(255, 152, 0))
+                    .size(10.0),
+            );
+        })
+        .response
+        .on_hover_text(tr!("uad-changed-badge-info"));
+}
+
 /// iOS-style toggle switch
 fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
     let desired_size = ui.spacing().interact_size.y * egui::vec2(2.0, 1.0);
@@ -1,4 +1,12 @@
+use crate::dlg_capability_audit::DlgCapabilityAudit;
+use crate::dlg_install_provenance::DlgInstallProvenance;
+use crate::dlg_permission_manager::DlgPermissionManager;
+use crate::calc_background_stt::{BackgroundBatchOutcome, BackgroundStates};
+use crate::dlg_app_ops::DlgAppOps;
+use crate::dlg_components::DlgComponents;
 use crate::dlg_package_details::DlgPackageDetails;
+use crate::dlg_stalkerware_report::DlgStalkerwareReport;
+use crate::dlg_uad_changelog::DlgUadChangelog;
 use crate::dlg_uninstall_confirm::DlgUninstallConfirm;
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
@@ -99,6 +107,25 @@ pub struct TabDebloatControl {
     // Uninstall confirmation dialog
     pub uninstall_confirm_dialog: DlgUninstallConfirm,
 
+    // UAD-NG list changelog dialog
+    pub uad_changelog_dialog: DlgUadChangelog,
+    // Per-device stalkerware report dialog
+    pub stalkerware_report_dialog: DlgStalkerwareReport,
+    // Device-wide privileged capability audit dialog
+    pub capability_audit_dialog: DlgCapabilityAudit,
+    // Install provenance view (where each package came from)
+    pub install_provenance_dialog: DlgInstallProvenance,
+    // Cross-package runtime permission manager with change journal
+    pub permission_manager_dialog: DlgPermissionManager,
+    // AppOps editor for the selected packages
+    pub app_ops_dialog: DlgAppOps,
+    // Component browser for the selected packages
+    pub components_dialog: DlgComponents,
+    // Packages whose recommendation changed since the user last acted on them
+    pub uad_changed_packages: HashSet<String>,
+    // Packages acted on by batch threads; drained into uad_changed_packages by the UI
+    pub batch_acted_packages: Arc<Mutex<Vec<String>>>,
+
     // Batch uninstall state machine
     pub batch_uninstall_state: BatchUninstallState,
     // Progress for batch uninstall background task (for thread communication)
@@ -119,4 +146,21 @@ pub struct TabDebloatControl {
     pub batch_enable_progress: Arc<Mutex<Option<f32>>>,
     // Cancellation flag for batch enable
     pub batch_enable_cancelled: Arc<Mutex<bool>>,
+
+    // Signing certificate scan state machine
+    pub certificate_scan_state: BatchUninstallState,
+    // Progress for signing certificate scan background task (for thread communication)
+    pub certificate_scan_progress: Arc<Mutex<Option<f32>>>,
+    // Cancellation flag for signing certificate scan
+    pub certificate_scan_cancelled: Arc<Mutex<bool>>,
+
+    // Standby bucket, battery optimization whitelist and background restriction per package
+    pub background_states: BackgroundStates,
+    // Whether background states are being read from the device
+    pub background_loading: Arc<Mutex<bool>>,
+    // Outcome of the running background batch; None while applying
+    pub background_batch_result: Arc<Mutex<Option<BackgroundBatchOutcome>>>,
+    pub background_batch_running: bool,
+    // Outcome of the last background batch (success, message)
+    pub background_result: Option<(bool, String)>,
 }
@@ -1,6 +1,13 @@
 use crate::adb::PackageFingerprint;
 use crate::calc_hybridanalysis;
 use crate::calc_izzyrisk;
+use crate::calc_izzyrisk_stt::{IzzyRiskBreakdown, PermissionState, RiskReason};
+use crate::calc_koodous;
+use crate::calc_malwarebazaar;
+use crate::calc_nativelibs;
+use crate::calc_scanprovider_stt::{FreshnessPolicy, QuotaBudget};
+use crate::calc_scanrules;
+use crate::calc_trackers;
 use crate::calc_virustotal;
 use crate::db;
 use crate::db_hybridanalysis;
@@ -8,12 +15,14 @@ use crate::db_virustotal;
 use crate::shared_store_stt::get_shared_store;
 pub use crate::tab_scan_control_stt::*;
 use crate::dlg_package_details::DlgPackageDetails;
+use crate::dlg_scan_export::DlgScanExport;
+use crate::dlg_app_ops::DlgAppOps;
 use crate::dlg_uninstall_confirm::DlgUninstallConfirm;
 use eframe::egui;
 use egui_async::Bind;
 use egui_i18n::tr;
 use egui_material3::{data_table, icon_button_standard, theme::get_global_color, MaterialButton};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
@@ -38,23 +47,54 @@ impl Default for TabScanControl {
             ha_rate_limiter: None,
             ha_package_paths_cache: None,
             ha_scan_state: ScanStateMachine::default(),
+            mb_rate_limiter: None,
+            koodous_rate_limiter: None,
+            mb_scan_state: ScanStateMachine::default(),
+            koodous_scan_state: ScanStateMachine::default(),
             izzyrisk_scan_state: ScanStateMachine::default(),
             izzyrisk_scan_progress: Arc::new(Mutex::new(None)),
             izzyrisk_scan_cancelled: Arc::new(Mutex::new(false)),
+            tracker_scan_state: ScanStateMachine::default(),
+            tracker_scan_progress: Arc::new(Mutex::new(None)),
+            tracker_scan_cancelled: Arc::new(Mutex::new(false)),
+            rule_scan_state: ScanStateMachine::default(),
+            rule_scan_progress: Arc::new(Mutex::new(None)),
+            rule_scan_cancelled: Arc::new(Mutex::new(false)),
+            native_lib_scan_state: ScanStateMachine::default(),
+            native_lib_scan_progress: Arc::new(Mutex::new(None)),
+            native_lib_scan_cancelled: Arc::new(Mutex::new(false)),
             shared_package_risk_scores: Arc::new(Mutex::new(HashMap::new())),
+            shared_package_risk_breakdowns: Arc::new(Mutex::new(HashMap::new())),
             vt_api_key: None,
             ha_api_key: None,
+            mb_api_key: None,
+            koodous_api_key: None,
             device_serial: None,
             virustotal_submit_enabled: false,
             hybridanalysis_submit_enabled: false,
+            scan_freshness: FreshnessPolicy::default(),
+            vt_requests_per_minute: calc_virustotal::PUBLIC_REQUESTS_PER_MINUTE,
+            vt_quota_budget: QuotaBudget {
+                per_day: calc_virustotal::PUBLIC_REQUESTS_PER_DAY,
+                per_month: calc_virustotal::PUBLIC_REQUESTS_PER_MONTH,
+            },
+            vt_stored_quota: None,
+            rescan_interval_hours: 0,
+            last_scheduled_rescan: None,
             sort_column: None,
             sort_ascending: true,
             active_vt_filter: VtFilter::All,
             active_ha_filter: HaFilter::All,
+            active_mb_filter: ProviderFilter::All,
+            active_koodous_filter: ProviderFilter::All,
             vt_scan_progress: Arc::new(Mutex::new(None)),
             vt_scan_cancelled: Arc::new(Mutex::new(false)),
             ha_scan_progress: Arc::new(Mutex::new(None)),
             ha_scan_cancelled: Arc::new(Mutex::new(false)),
+            mb_scan_progress: Arc::new(Mutex::new(None)),
+            mb_scan_cancelled: Arc::new(Mutex::new(false)),
+            koodous_scan_progress: Arc::new(Mutex::new(None)),
+            koodous_scan_cancelled: Arc::new(Mutex::new(false)),
             show_only_enabled: false,
             hide_system_app: false,
             google_play_renderer_enabled: false,
@@ -64,10 +104,28 @@ impl Default for TabScanControl {
             text_filter: String::new(),
             unsafe_app_remove: false,
             uninstall_confirm_dialog: DlgUninstallConfirm::default(),
+            scan_export_dialog: DlgScanExport::new(),
+            app_ops_dialog: DlgAppOps::new(),
         }
     }
 }
 
+/// Outline for result chips whose detections changed since the previous lookup
+fn changed_stroke(changed: bool) -> egui::Stroke {
+    if changed {
+        egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 235, 59))
+    } else {
+        egui::Stroke::NONE
+    }
+}
+
+impl ProviderFilter {
+    /// Whether a package in `category` passes this filter
+    pub fn matches(self, category: ProviderFilter) -> bool {
+        self == ProviderFilter::All || self == category
+    }
+}
+
 impl TabScanControl {
     pub fn update_packages(&mut self, packages: Vec<PackageFingerprint>) {
         // Store packages in shared store
@@ -80,6 +138,18 @@ impl TabScanControl {
         // Calculate risk scores using Bind state machine
         self.calculate_all_risk_scores();
 
+        // Load tracker results already cached for the installed APKs (no pulling)
+        store.clear_tracker_results();
+        self.run_tracker_scan(false);
+
+        // Load local rule matches already cached for the installed APKs (no pulling)
+        store.clear_rule_match_results();
+        self.run_rule_scan(false);
+
+        // Load native library inventories already cached for the installed APKs (no pulling)
+        store.clear_native_libraries();
+        self.run_native_lib_scan(false);
+
         // Initialize VirusTotal scanner state
         if self.vt_api_key.as_ref().map_or(false, |k| k.len() >= 10) && self.device_serial.is_some() {
             self.run_virustotal();
@@ -90,6 +160,14 @@ impl TabScanControl {
             self.run_hybridanalysis();
         }
 
+        // Initialize MalwareBazaar and Koodous lookup state
+        if self.mb_api_key.as_ref().map_or(false, |k| k.len() >= 10) && self.device_serial.is_some() {
+            self.run_malwarebazaar();
+        }
+        if self.koodous_api_key.as_ref().map_or(false, |k| k.len() >= 10) && self.device_serial.is_some() {
+            self.run_koodous();
+        }
+
         // Clear textures cache when packages are updated (will be reloaded on demand)
         store.clear_all_textures();
     }
@@ -308,12 +386,57 @@ impl TabScanControl {
         app_data_map
     }
 
+    /// VirusTotal provider configured with the freshness policy and API tier from Settings
+    fn vt_provider(&self, api_key: String) -> calc_virustotal::VirusTotalProvider {
+        calc_virustotal::VirusTotalProvider {
+            freshness: self.scan_freshness,
+            requests_per_minute: self.vt_requests_per_minute,
+            budget: self.vt_quota_budget,
+            ..calc_virustotal::VirusTotalProvider::new(api_key)
+        }
+    }
+
+    /// Hybrid Analysis provider configured with the freshness policy from Settings
+    fn ha_provider(&self, api_key: String) -> calc_hybridanalysis::HybridAnalysisProvider {
+        calc_hybridanalysis::HybridAnalysisProvider {
+            freshness: self.scan_freshness,
+            ..calc_hybridanalysis::HybridAnalysisProvider::new(api_key)
+        }
+    }
+
+    /// Requests left today under the VirusTotal budget: from the running rate limiter, or from
+    /// the stored usage before a scan has started. None when unlimited or no API key is set.
+    fn vt_remaining_quota(&mut self) -> Option<u32> {
+        if let Some(limiter) = &self.vt_rate_limiter {
+            return limiter.lock().ok().and_then(|mut l| l.remaining_quota());
+        }
+        if self.vt_api_key.as_deref().map_or(true, str::is_empty) {
+            return None;
+        }
+        let day = crate::calc_scanprovider::utc_day();
+        let budget = self.vt_quota_budget;
+        match &self.vt_stored_quota {
+            Some((b, d, remaining)) if *b == budget && *d == day => *remaining,
+            _ => {
+                let remaining = crate::calc_scanprovider::QuotaUsage::load("VirusTotal", budget).remaining();
+                self.vt_stored_quota = Some((budget, day, remaining));
+                remaining
+            }
+        }
+    }
+
     fn run_virustotal(&mut self) {
         let store = get_shared_store();
         let installed_packages = store.get_installed_packages();
 
         if let Some(ref device) = self.device_serial {
-            let api_key = self.vt_api_key.clone().unwrap();
+            let provider = self.vt_provider(self.vt_api_key.clone().unwrap());
+            let selected: HashSet<String> = installed_packages
+                .iter()
+                .zip(self.selected_packages.iter())
+                .filter(|(_, selected)| **selected)
+                .map(|(p, _)| p.pkg.clone())
+                .collect();
 
             // Start state machine
             self.vt_scan_state.start();
@@ -322,9 +445,10 @@ impl TabScanControl {
             let (scanner_state, rate_limiter) = calc_virustotal::run_virustotal(
                 installed_packages,
                 device.clone(),
-                api_key,
+                provider,
                 self.virustotal_submit_enabled,
                 self.package_risk_scores.clone(),
+                selected,
                 self.vt_scan_progress.clone(),
                 self.vt_scan_cancelled.clone(),
             );
@@ -340,7 +464,7 @@ impl TabScanControl {
         let installed_packages = store.get_installed_packages();
 
         if let Some(ref device) = self.device_serial {
-            let api_key = self.ha_api_key.clone().unwrap();
+            let provider = self.ha_provider(self.ha_api_key.clone().unwrap());
 
             // Start state machine
             self.ha_scan_state.start();
@@ -349,7 +473,7 @@ impl TabScanControl {
             let (scanner_state, rate_limiter) = calc_hybridanalysis::run_hybridanalysis(
                 installed_packages,
                 device.clone(),
-                api_key,
+                provider,
                 self.hybridanalysis_submit_enabled,
                 self.package_risk_scores.clone(),
                 self.ha_scan_progress.clone(),
@@ -362,6 +486,88 @@ impl TabScanControl {
         }
     }
 
+    /// Re-run the VirusTotal and Hybrid Analysis scans once the rescan interval has elapsed.
+    /// Only stale or flagged results are re-queried; the rest come from the cache.
+    pub fn run_scheduled_rescan(&mut self, ctx: &egui::Context) {
+        if self.rescan_interval_hours == 0 || self.device_serial.is_none() {
+            self.last_scheduled_rescan = None;
+            return;
+        }
+        let interval = std::time::Duration::from_secs(u64::from(self.rescan_interval_hours) * 3600);
+        let Some(last) = self.last_scheduled_rescan else {
+            // Start counting from when scheduling was enabled
+            self.last_scheduled_rescan = Some(std::time::Instant::now());
+            ctx.request_repaint_after(interval);
+            return;
+        };
+        let elapsed = last.elapsed();
+        if elapsed < interval {
+            ctx.request_repaint_after(interval - elapsed);
+            return;
+        }
+        if self.vt_scan_state.is_running || self.ha_scan_state.is_running {
+            // Try again once the running scan has finished
+            ctx.request_repaint_after(std::time::Duration::from_secs(60));
+            return;
+        }
+
+        log::info!("Starting scheduled rescan after {} hours", self.rescan_interval_hours);
+        self.last_scheduled_rescan = Some(std::time::Instant::now());
+        if self.vt_api_key.as_ref().map_or(false, |k| k.len() >= 10) {
+            self.run_virustotal();
+        }
+        if self.ha_api_key.as_ref().map_or(false, |k| k.len() >= 10) {
+            self.run_hybridanalysis();
+        }
+        ctx.request_repaint_after(interval);
+    }
+
+    fn run_malwarebazaar(&mut self) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        if let Some(ref device) = self.device_serial {
+            let api_key = self.mb_api_key.clone().unwrap();
+
+            self.mb_scan_state.start();
+
+            let (scanner_state, rate_limiter) = calc_malwarebazaar::run_malwarebazaar(
+                installed_packages,
+                device.clone(),
+                api_key,
+                self.package_risk_scores.clone(),
+                self.mb_scan_progress.clone(),
+                self.mb_scan_cancelled.clone(),
+            );
+
+            store.set_mb_scanner_state(Some(scanner_state));
+            self.mb_rate_limiter = Some(rate_limiter);
+        }
+    }
+
+    fn run_koodous(&mut self) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        if let Some(ref device) = self.device_serial {
+            let api_key = self.koodous_api_key.clone().unwrap();
+
+            self.koodous_scan_state.start();
+
+            let (scanner_state, rate_limiter) = calc_koodous::run_koodous(
+                installed_packages,
+                device.clone(),
+                api_key,
+                self.package_risk_scores.clone(),
+                self.koodous_scan_progress.clone(),
+                self.koodous_scan_cancelled.clone(),
+            );
+
+            store.set_koodous_scanner_state(Some(scanner_state));
+            self.koodous_rate_limiter = Some(rate_limiter);
+        }
+    }
+
     pub fn update_uad_ng_lists(&mut self, lists: crate::uad_shizuku_app::UadNgLists) {
         let store = get_shared_store();
         store.set_uad_ng_lists(Some(lists));
@@ -374,6 +580,9 @@ impl TabScanControl {
         if let Ok(mut shared) = self.shared_package_risk_scores.lock() {
             shared.clear();
         }
+        if let Ok(mut shared) = self.shared_package_risk_breakdowns.lock() {
+            shared.clear();
+        }
 
         let store = get_shared_store();
         let device_serial = self.device_serial.clone();
@@ -387,6 +596,7 @@ impl TabScanControl {
         }
 
         let shared_scores = self.shared_package_risk_scores.clone();
+        let shared_breakdowns = self.shared_package_risk_breakdowns.clone();
         let progress_clone = self.izzyrisk_scan_progress.clone();
         let cancelled_clone = self.izzyrisk_scan_cancelled.clone();
 
@@ -410,6 +620,136 @@ impl TabScanControl {
             installed_packages,
             device_serial,
             shared_scores,
+            shared_breakdowns,
+            progress_clone,
+            cancelled_clone,
+        );
+    }
+
+    /// Scan installed packages for trackers in a background thread.
+    /// With `pull_missing` false only results cached for the same APK hashes are loaded.
+    fn run_tracker_scan(&mut self, pull_missing: bool) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        let Some(device_serial) = self.device_serial.clone() else {
+            log::debug!("Skipping tracker scan: no device selected");
+            return;
+        };
+        if installed_packages.is_empty() || !store.has_tracker_signatures() {
+            log::debug!("Skipping tracker scan: no packages or tracker signatures");
+            return;
+        }
+
+        let progress_clone = self.tracker_scan_progress.clone();
+        let cancelled_clone = self.tracker_scan_cancelled.clone();
+
+        // Start state machine
+        self.tracker_scan_state.start();
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = Some(0.0);
+        }
+        if let Ok(mut cancelled) = cancelled_clone.lock() {
+            *cancelled = false;
+        }
+
+        log::info!(
+            "Starting tracker scan for {} packages (pull_missing={})",
+            installed_packages.len(),
+            pull_missing
+        );
+
+        calc_trackers::run_tracker_scan_async(
+            installed_packages,
+            device_serial,
+            pull_missing,
+            progress_clone,
+            cancelled_clone,
+        );
+    }
+
+    /// Evaluate the local scan rules against installed packages in a background thread.
+    /// With `pull_missing` false only results cached for the same APK hashes and rules are loaded.
+    fn run_rule_scan(&mut self, pull_missing: bool) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        let Some(device_serial) = self.device_serial.clone() else {
+            log::debug!("Skipping rule scan: no device selected");
+            return;
+        };
+        if installed_packages.is_empty() {
+            log::debug!("Skipping rule scan: no packages");
+            return;
+        }
+
+        let progress_clone = self.rule_scan_progress.clone();
+        let cancelled_clone = self.rule_scan_cancelled.clone();
+
+        // Start state machine
+        self.rule_scan_state.start();
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = Some(0.0);
+        }
+        if let Ok(mut cancelled) = cancelled_clone.lock() {
+            *cancelled = false;
+        }
+
+        log::info!(
+            "Starting rule scan for {} packages (pull_missing={})",
+            installed_packages.len(),
+            pull_missing
+        );
+
+        calc_scanrules::run_rule_scan_async(
+            installed_packages,
+            device_serial,
+            pull_missing,
+            progress_clone,
+            cancelled_clone,
+        );
+    }
+
+    /// Inventory the native libraries of installed packages in a background thread.
+    /// With `pull_missing` false only inventories cached for the same APK hashes are loaded.
+    fn run_native_lib_scan(&mut self, pull_missing: bool) {
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+
+        let Some(device_serial) = self.device_serial.clone() else {
+            log::debug!("Skipping native library scan: no device selected");
+            return;
+        };
+        if installed_packages.is_empty() {
+            log::debug!("Skipping native library scan: no packages");
+            return;
+        }
+
+        let progress_clone = self.native_lib_scan_progress.clone();
+        let cancelled_clone = self.native_lib_scan_cancelled.clone();
+
+        // Start state machine
+        self.native_lib_scan_state.start();
+
+        if let Ok(mut p) = progress_clone.lock() {
+            *p = Some(0.0);
+        }
+        if let Ok(mut cancelled) = cancelled_clone.lock() {
+            *cancelled = false;
+        }
+
+        log::info!(
+            "Starting native library scan for {} packages (pull_missing={})",
+            installed_packages.len(),
+            pull_missing
+        );
+
+        calc_nativelibs::run_native_lib_scan_async(
+            installed_packages,
+            device_serial,
+            pull_missing,
             progress_clone,
             cancelled_clone,
         );
@@ -430,6 +770,73 @@ impl TabScanControl {
         0
     }
 
+    fn get_risk_breakdown(&self, package_name: &str) -> Option<IzzyRiskBreakdown> {
+        self.shared_package_risk_breakdowns
+            .lock()
+            .ok()
+            .and_then(|shared| shared.get(package_name).cloned())
+    }
+
+    /// Hover text listing matched rules with the patterns and APK entries that triggered them
+    fn rule_matches_text(matches: &[calc_scanrules::RuleMatch]) -> String {
+        matches
+            .iter()
+            .map(|m| {
+                let mut text = m.rule.clone();
+                if !m.description.is_empty() {
+                    text.push_str(&format!(" - {}", m.description));
+                }
+                text.push_str(&format!("\n  {}\n  {}", m.patterns.join(", "), m.files.join("\n  ")));
+                text
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Hover text listing bundled native libraries with known-vulnerable versions
+    fn vulnerable_native_libs_text(libraries: &[calc_nativelibs::NativeLibrary]) -> Option<String> {
+        let lines: Vec<String> = libraries
+            .iter()
+            .flat_map(|lib| {
+                lib.vulnerable.iter().map(move |v| {
+                    let version = v.version.as_deref().unwrap_or("?");
+                    format!("{} ({} {}): {}", lib.entry, v.library, version, v.advisory)
+                })
+            })
+            .collect();
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
+    /// Hover text explaining which permissions and combinations make up a risk score
+    fn render_risk_breakdown(ui: &mut egui::Ui, breakdown: &IzzyRiskBreakdown) {
+        if breakdown.contributions.is_empty() {
+            ui.label(tr!("izzyrisk-no-risky-permissions"));
+            return;
+        }
+        for contribution in &breakdown.contributions {
+            let text = match &contribution.reason {
+                RiskReason::Permission { name, state } => {
+                    let state = match state {
+                        PermissionState::Install => tr!("izzyrisk-install"),
+                        PermissionState::RuntimeGranted => tr!("izzyrisk-runtime-granted"),
+                        PermissionState::RuntimeDenied => tr!("izzyrisk-runtime-denied"),
+                    };
+                    format!("+{} {} ({})", contribution.points, name, state)
+                }
+                RiskReason::Combination { name } => {
+                    format!("+{} {}", contribution.points, tr!("izzyrisk-combination", { name: name.clone() }))
+                }
+            };
+            ui.label(text);
+        }
+        ui.separator();
+        ui.label(tr!("izzyrisk-total", { total: breakdown.total }));
+    }
+
     /// Sync shared risk scores from background thread to local cache
     fn sync_risk_scores(&mut self) {
         if let Ok(shared) = self.shared_package_risk_scores.lock() {
@@ -446,6 +853,8 @@ impl TabScanControl {
             let vt_scanner_state = store.get_vt_scanner_state();
             let ha_scanner_state = store.get_ha_scanner_state();
             let package_risk_scores = self.package_risk_scores.clone();
+            let tracker_results = store.get_tracker_results();
+            let rule_match_results = store.get_rule_match_results();
             let sort_ascending = self.sort_ascending;
 
             let mut installed_packages = store.get_installed_packages();
@@ -473,7 +882,10 @@ impl TabScanControl {
                                             .file_results
                                             .iter()
                                             .map(|fr| {
-                                                if fr.skipped {
+                                                if fr.trusted {
+                                                    // Allowlisted hashes sort with clean results
+                                                    3_i64 * 100_000
+                                                } else if fr.skipped {
                                                     1_i64 * 100_000
                                                 } else if fr.not_found {
                                                     2_i64 * 100_000
@@ -522,7 +934,7 @@ impl TabScanControl {
                                                     "suspicious" => 4,
                                                     "no specific threat" => 3,
                                                     "no-result" => 2,
-                                                    "whitelisted" => 1,
+                                                    "whitelisted" | "trusted" => 1,
                                                     "submitted" => 0,
                                                     _ => 2,
                                                 };
@@ -548,6 +960,20 @@ impl TabScanControl {
                         let score_b = get_ha_sort_key(&b.pkg);
                         score_a.cmp(&score_b)
                     }
+                    4 => {
+                        // Not scanned sorts below packages without trackers
+                        let get_tracker_count = |pkg_name: &str| -> i64 {
+                            tracker_results.get(pkg_name).map_or(-1, |t| t.len() as i64)
+                        };
+                        get_tracker_count(&a.pkg).cmp(&get_tracker_count(&b.pkg))
+                    }
+                    5 => {
+                        // Not scanned sorts below packages without rule matches
+                        let get_rule_count = |pkg_name: &str| -> i64 {
+                            rule_match_results.get(pkg_name).map_or(-1, |m| m.len() as i64)
+                        };
+                        get_rule_count(&a.pkg).cmp(&get_rule_count(&b.pkg))
+                    }
                     _ => std::cmp::Ordering::Equal,
                 };
 
@@ -582,11 +1008,15 @@ impl TabScanControl {
         installed_packages: &[PackageFingerprint],
         vt_scanner_state: &Option<calc_virustotal::ScannerState>,
         ha_scanner_state: &Option<calc_hybridanalysis::ScannerState>,
+        mb_scanner_state: &Option<calc_malwarebazaar::ScannerState>,
+        koodous_scanner_state: &Option<calc_koodous::ScannerState>,
         ha_tag_ignorelist: &str,
     ) {
         // Check if cache needs updating based on progress changes
         let vt_progress = self.vt_scan_state.progress;
         let ha_progress = self.ha_scan_state.progress;
+        let mb_progress = self.mb_scan_state.progress;
+        let koodous_progress = self.koodous_scan_state.progress;
 
         // Cache is only valid if progress matches AND at least one scan has been initialized
         // This prevents showing 0 counts when both progresses are None during initialization
@@ -596,6 +1026,8 @@ impl TabScanControl {
 
         let cache_valid = self.cached_scan_counts.vt_progress == vt_progress
             && self.cached_scan_counts.ha_progress == ha_progress
+            && self.cached_scan_counts.mb_progress == mb_progress
+            && self.cached_scan_counts.koodous_progress == koodous_progress
             && !cache_needs_init;
 
         if cache_valid {
@@ -744,6 +1176,82 @@ impl TabScanControl {
         self.cached_scan_counts.ha_counts = (ha_all, ha_malicious, ha_malicious_ignored, ha_suspicious, ha_safe, ha_not_scanned);
         self.cached_scan_counts.vt_progress = vt_progress;
         self.cached_scan_counts.ha_progress = ha_progress;
+
+        // MalwareBazaar/Koodous counts follow the VT and HA filters
+        let passes_vt_ha = |package: &PackageFingerprint| {
+            self.should_show_package_vt_with_state(package, vt_scanner_state)
+                && self.should_show_package_ha_with_state(package, ha_scanner_state, ha_tag_ignorelist)
+        };
+        let mb_counts = Self::lookup_counts(installed_packages, &passes_vt_ha, |pkg| {
+            Self::mb_category(mb_scanner_state, pkg)
+        });
+        let koodous_counts = Self::lookup_counts(installed_packages, &passes_vt_ha, |pkg| {
+            Self::koodous_category(koodous_scanner_state, pkg)
+        });
+        self.cached_scan_counts.mb_counts = mb_counts;
+        self.cached_scan_counts.koodous_counts = koodous_counts;
+        self.cached_scan_counts.mb_progress = mb_progress;
+        self.cached_scan_counts.koodous_progress = koodous_progress;
+    }
+
+    /// Count packages per lookup-provider category as (enabled, total) pairs
+    fn lookup_counts(
+        installed_packages: &[PackageFingerprint],
+        passes: &dyn Fn(&PackageFingerprint) -> bool,
+        category_of: impl Fn(&str) -> ProviderFilter,
+    ) -> ProviderCounts {
+        let mut all = (0usize, 0usize);
+        let mut malicious = (0usize, 0usize);
+        let mut clean = (0usize, 0usize);
+        let mut not_scanned = (0usize, 0usize);
+
+        for package in installed_packages.iter().filter(|p| passes(p)) {
+            let is_enabled = Self::is_package_enabled(package);
+            let bucket = match category_of(&package.pkg) {
+                ProviderFilter::Malicious => &mut malicious,
+                ProviderFilter::Clean => &mut clean,
+                _ => &mut not_scanned,
+            };
+            for counts in [&mut all, bucket] {
+                counts.1 += 1;
+                if is_enabled {
+                    counts.0 += 1;
+                }
+            }
+        }
+
+        (all, malicious, clean, not_scanned)
+    }
+
+    /// Category of a package in a lookup-only provider's scanner state
+    fn lookup_category<R>(
+        scanner_state: &Option<crate::calc_scanprovider_stt::ScannerState<R>>,
+        pkg_name: &str,
+        is_malicious: impl Fn(&R) -> bool,
+    ) -> ProviderFilter {
+        let Some(scanner_state) = scanner_state else {
+            return ProviderFilter::NotScanned;
+        };
+        let state = scanner_state.lock().unwrap();
+        match state.get(pkg_name) {
+            Some(crate::calc_scanprovider_stt::ScanStatus::Completed(result)) if is_malicious(result) => {
+                ProviderFilter::Malicious
+            }
+            Some(crate::calc_scanprovider_stt::ScanStatus::Completed(_)) => ProviderFilter::Clean,
+            _ => ProviderFilter::NotScanned,
+        }
+    }
+
+    fn mb_category(scanner_state: &Option<calc_malwarebazaar::ScannerState>, pkg_name: &str) -> ProviderFilter {
+        Self::lookup_category(scanner_state, pkg_name, |r: &calc_malwarebazaar::CalcMalwareBazaar| {
+            r.file_results.iter().any(|fr| fr.is_listed())
+        })
+    }
+
+    fn koodous_category(scanner_state: &Option<calc_koodous::ScannerState>, pkg_name: &str) -> ProviderFilter {
+        Self::lookup_category(scanner_state, pkg_name, |r: &calc_koodous::CalcKoodous| {
+            r.file_results.iter().any(|fr| fr.is_detected())
+        })
     }
 
     /// Returns ((all_enabled, all_total), (mal_enabled, mal_total), (sus_enabled, sus_total), (safe_enabled, safe_total), (not_scanned_enabled, not_scanned_total))
@@ -756,6 +1264,35 @@ impl TabScanControl {
         self.cached_scan_counts.ha_counts
     }
 
+    /// Filter buttons for a lookup-only provider; `labels` follow the `ProviderFilter` order
+    fn render_provider_filter(ui: &mut egui::Ui, title: &str, active: &mut ProviderFilter, labels: [String; 4]) {
+        ui.horizontal_wrapped(|ui| {
+            ui.vertical(|ui| {
+                ui.set_width(130.0);
+                ui.label(title);
+            });
+
+            let show_all_colors = *active == ProviderFilter::All;
+            let filters = [
+                (ProviderFilter::All, egui::Color32::from_rgb(158, 158, 158)),
+                (ProviderFilter::Malicious, egui::Color32::from_rgb(211, 47, 47)),
+                (ProviderFilter::Clean, egui::Color32::from_rgb(56, 142, 60)),
+                (ProviderFilter::NotScanned, egui::Color32::from_rgb(128, 128, 128)),
+            ];
+            for ((filter, color), text) in filters.into_iter().zip(labels.iter()) {
+                let filled = *active == filter || (show_all_colors && filter != ProviderFilter::All);
+                let button = if filled {
+                    MaterialButton::filled(text).small().fill(color)
+                } else {
+                    MaterialButton::outlined(text).small()
+                };
+                if ui.add(button).clicked() {
+                    *active = filter;
+                }
+            }
+        });
+    }
+
     fn should_show_package_vt_with_state(
         &self,
         package: &PackageFingerprint,
@@ -920,6 +1457,8 @@ impl TabScanControl {
         package: &PackageFingerprint,
         vt_scanner_state: &Option<calc_virustotal::ScannerState>,
         ha_scanner_state: &Option<calc_hybridanalysis::ScannerState>,
+        mb_scanner_state: &Option<calc_malwarebazaar::ScannerState>,
+        koodous_scanner_state: &Option<calc_koodous::ScannerState>,
         ha_tag_ignorelist: &str,
     ) -> bool {
         if self.hide_system_app && package.flags.contains("SYSTEM") {
@@ -950,6 +1489,10 @@ impl TabScanControl {
 
         self.should_show_package_vt_with_state(package, vt_scanner_state)
             && self.should_show_package_ha_with_state(package, ha_scanner_state, ha_tag_ignorelist)
+            && self.active_mb_filter.matches(Self::mb_category(mb_scanner_state, &package.pkg))
+            && self
+                .active_koodous_filter
+                .matches(Self::koodous_category(koodous_scanner_state, &package.pkg))
     }
 
     // Legacy methods that fetch from store (used by get_vt_counts/get_ha_counts)
@@ -970,8 +1513,17 @@ impl TabScanControl {
         let store = get_shared_store();
         let vt_scanner_state = store.get_vt_scanner_state();
         let ha_scanner_state = store.get_ha_scanner_state();
+        let mb_scanner_state = store.get_mb_scanner_state();
+        let koodous_scanner_state = store.get_koodous_scanner_state();
         // Legacy method - uses empty ignorelist for backward compatibility
-        self.should_show_package_with_state(package, &vt_scanner_state, &ha_scanner_state, "")
+        self.should_show_package_with_state(
+            package,
+            &vt_scanner_state,
+            &ha_scanner_state,
+            &mb_scanner_state,
+            &koodous_scanner_state,
+            "",
+        )
     }
 
     fn matches_text_filter_with_cache(
@@ -1066,7 +1618,12 @@ impl TabScanControl {
         let installed_packages = shared_store.get_installed_packages();
         let vt_scanner_state = shared_store.get_vt_scanner_state();
         let ha_scanner_state = shared_store.get_ha_scanner_state();
+        let mb_scanner_state = shared_store.get_mb_scanner_state();
+        let koodous_scanner_state = shared_store.get_koodous_scanner_state();
         let uad_ng_lists = shared_store.get_uad_ng_lists();
+        let tracker_results = shared_store.get_tracker_results();
+        let rule_match_results = shared_store.get_rule_match_results();
+        let native_libraries = shared_store.get_all_native_libraries();
 
         // Pre-fetch cached app data maps for efficient lookups
         let cached_fdroid_apps = shared_store.get_cached_fdroid_apps();
@@ -1074,7 +1631,14 @@ impl TabScanControl {
         let cached_apkmirror_apps = shared_store.get_cached_apkmirror_apps();
 
         // Update cached scan counts if needed (only recomputes when scanner progress changes)
-        self.update_cached_scan_counts(&installed_packages, &vt_scanner_state, &ha_scanner_state, &hybridanalysis_tag_ignorelist);
+        self.update_cached_scan_counts(
+            &installed_packages,
+            &vt_scanner_state,
+            &ha_scanner_state,
+            &mb_scanner_state,
+            &koodous_scanner_state,
+            &hybridanalysis_tag_ignorelist,
+        );
 
         // Check if mobile view for filter button style
         let filter_is_mobile = ui.available_width() < DESKTOP_MIN_WIDTH;
@@ -1192,6 +1756,16 @@ impl TabScanControl {
                         self.active_vt_filter = VtFilter::NotScanned;
                     }
                 }
+
+                // Remaining daily VirusTotal budget of the API key
+                if let Some(remaining) = self.vt_remaining_quota() {
+                    let text = tr!("vt-quota-remaining", { remaining: remaining });
+                    if remaining == 0 {
+                        ui.colored_label(egui::Color32::from_rgb(211, 47, 47), text);
+                    } else {
+                        ui.label(text);
+                    }
+                }
             });
             ui.add_space(5.0);
 
@@ -1326,6 +1900,35 @@ impl TabScanControl {
                     }
                 }
             });
+            ui.add_space(5.0);
+
+            // MalwareBazaar and Koodous Filter Buttons
+            let (all, listed, not_listed, not_scanned) = self.cached_scan_counts.mb_counts;
+            Self::render_provider_filter(
+                ui,
+                &tr!("malwarebazaar-filter"),
+                &mut self.active_mb_filter,
+                [
+                    tr!("all", { enabled: all.0, total: all.1 }),
+                    tr!("malicious", { enabled: listed.0, total: listed.1 }),
+                    tr!("not-listed", { enabled: not_listed.0, total: not_listed.1 }),
+                    tr!("not-scanned", { enabled: not_scanned.0, total: not_scanned.1 }),
+                ],
+            );
+            ui.add_space(5.0);
+
+            let (all, detected, undetected, not_scanned) = self.cached_scan_counts.koodous_counts;
+            Self::render_provider_filter(
+                ui,
+                &tr!("koodous-filter"),
+                &mut self.active_koodous_filter,
+                [
+                    tr!("all", { enabled: all.0, total: all.1 }),
+                    tr!("malicious", { enabled: detected.0, total: detected.1 }),
+                    tr!("safe", { enabled: undetected.0, total: undetected.1 }),
+                    tr!("not-scanned", { enabled: not_scanned.0, total: not_scanned.1 }),
+                ],
+            );
 
 
         }
@@ -1363,6 +1966,88 @@ impl TabScanControl {
             }
         });
 
+        // Offline tracker scan: pulls APKs without a cached result and inspects their DEX classes
+        ui.horizontal_wrapped(|ui| {
+            let has_signatures = shared_store.has_tracker_signatures();
+            let can_scan = has_signatures && self.device_serial.is_some() && !self.tracker_scan_state.is_running;
+            let response = ui.add_enabled(can_scan, MaterialButton::outlined(tr!("scan-trackers")).small());
+            let response = if has_signatures {
+                response.on_hover_text(tr!("scan-trackers-desc"))
+            } else {
+                response.on_disabled_hover_text(tr!("tracker-signatures-unavailable"))
+            };
+            if response.clicked() {
+                self.run_tracker_scan(true);
+            }
+
+            // Local rule scan: evaluates the user's rules file against APK contents without uploading
+            let rules_path = calc_scanrules::config_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default();
+            let can_scan_rules = self.device_serial.is_some() && !self.rule_scan_state.is_running;
+            if ui
+                .add_enabled(can_scan_rules, MaterialButton::outlined(tr!("scan-rules")).small())
+                .on_hover_text(tr!("scan-rules-desc", { path: rules_path }))
+                .clicked()
+            {
+                self.run_rule_scan(true);
+            }
+            match shared_store.get_scan_rules_status() {
+                Some(Ok(0)) => {
+                    ui.label(egui::RichText::new(tr!("scan-rules-none")).small().weak());
+                }
+                Some(Ok(count)) => {
+                    ui.label(egui::RichText::new(tr!("scan-rules-loaded", { count: count })).small().weak());
+                }
+                Some(Err(e)) => {
+                    ui.label(egui::RichText::new(tr!("scan-rules-invalid")).small().color(egui::Color32::from_rgb(211, 47, 47)))
+                        .on_hover_text(e);
+                }
+                None => {}
+            }
+
+            // Native library inventory: lists lib/<abi>/*.so and flags known-vulnerable bundled versions
+            let can_scan_native_libs = self.device_serial.is_some() && !self.native_lib_scan_state.is_running;
+            if ui
+                .add_enabled(can_scan_native_libs, MaterialButton::outlined(tr!("scan-native-libs")).small())
+                .on_hover_text(tr!("scan-native-libs-desc"))
+                .clicked()
+            {
+                self.run_native_lib_scan(true);
+            }
+
+            if ui
+                .add_enabled(self.device_serial.is_some(), MaterialButton::outlined(tr!("scan-export")).small())
+                .on_hover_text(tr!("scan-export-desc"))
+                .clicked()
+            {
+                if let Some(device) = self.device_serial.clone() {
+                    self.scan_export_dialog.open(device);
+                }
+            }
+
+            // AppOps editor for the selected packages
+            let selected_packages: Vec<String> = shared_store
+                .get_installed_packages()
+                .iter()
+                .zip(self.selected_packages.iter())
+                .filter(|(_, selected)| **selected)
+                .map(|(p, _)| p.pkg.clone())
+                .collect();
+            if ui
+                .add_enabled(
+                    self.device_serial.is_some() && !selected_packages.is_empty(),
+                    MaterialButton::outlined(tr!("appops-selected", { count: selected_packages.len() })).small(),
+                )
+                .on_hover_text(tr!("appops-desc"))
+                .clicked()
+            {
+                if let Some(device) = self.device_serial.clone() {
+                    self.app_ops_dialog.open(device, selected_packages);
+                }
+            }
+        });
+
         ui.horizontal(|ui| {
             // Sort buttons for hidden columns in mobile view
             if !filter_is_mobile {
@@ -1421,6 +2106,40 @@ impl TabScanControl {
                 }
                 self.sort_packages();
             }
+
+            // Trackers sort button
+            let trackers_selected = self.sort_column == Some(4);
+            let trackers_label = if trackers_selected {
+                format!("{} {}", tr!("col-trackers"), if self.sort_ascending { "▲" } else { "▼" })
+            } else {
+                format!("{} {}", tr!("col-trackers"), "▼") // Default descending
+            };
+            if ui.selectable_label(trackers_selected, trackers_label).clicked() {
+                if self.sort_column == Some(4) {
+                    self.sort_ascending = !self.sort_ascending;
+                } else {
+                    self.sort_column = Some(4);
+                    self.sort_ascending = false;
+                }
+                self.sort_packages();
+            }
+
+            // Rules sort button
+            let rules_selected = self.sort_column == Some(5);
+            let rules_label = if rules_selected {
+                format!("{} {}", tr!("col-rules"), if self.sort_ascending { "▲" } else { "▼" })
+            } else {
+                format!("{} {}", tr!("col-rules"), "▼") // Default descending
+            };
+            if ui.selectable_label(rules_selected, rules_label).clicked() {
+                if self.sort_column == Some(5) {
+                    self.sort_ascending = !self.sort_ascending;
+                } else {
+                    self.sort_column = Some(5);
+                    self.sort_ascending = false;
+                }
+                self.sort_packages();
+            }
         });
 
         if self.sort_column.is_some() {
@@ -1431,7 +2150,7 @@ impl TabScanControl {
 
         let visible_package_ids: Vec<String> = installed_packages
             .iter()
-            .filter(|p| self.should_show_package_with_state(p, &vt_scanner_state, &ha_scanner_state, &hybridanalysis_tag_ignorelist))
+            .filter(|p| self.should_show_package_with_state(p, &vt_scanner_state, &ha_scanner_state, &mb_scanner_state, &koodous_scanner_state, &hybridanalysis_tag_ignorelist))
             .filter(|p| self.matches_text_filter_with_cache(p, &cached_fdroid_apps, &cached_google_play_apps, &cached_apkmirror_apps))
             .map(|p| p.pkg.clone())
             .collect();
@@ -1461,14 +2180,16 @@ impl TabScanControl {
             interactive_table = interactive_table
                 .sortable_column(tr!("col-izzy-risk"), 80.0 * width_ratio, true)
                 .sortable_column(tr!("col-virustotal"), 200.0 * width_ratio, false)
-                .sortable_column(tr!("col-hybrid-analysis"), 200.0 * width_ratio, false);
+                .sortable_column(tr!("col-hybrid-analysis"), 200.0 * width_ratio, false)
+                .sortable_column(tr!("col-trackers"), 150.0 * width_ratio, false)
+                .sortable_column(tr!("col-rules"), 120.0 * width_ratio, false);
         }
         interactive_table = interactive_table
             .sortable_column(tr!("col-tasks"), if is_desktop { 170.0 * width_ratio } else { (available_width * 0.3 + (50.0/available_width) * 0.3) }, false)
             .allow_selection(false);
 
         for (idx, package) in installed_packages.iter().enumerate() {
-            if !self.should_show_package_with_state(package, &vt_scanner_state, &ha_scanner_state, &hybridanalysis_tag_ignorelist)
+            if !self.should_show_package_with_state(package, &vt_scanner_state, &ha_scanner_state, &mb_scanner_state, &koodous_scanner_state, &hybridanalysis_tag_ignorelist)
                 || !self.matches_text_filter_with_cache(package, &cached_fdroid_apps, &cached_google_play_apps, &cached_apkmirror_apps)
             {
                 continue;
@@ -1478,6 +2199,7 @@ impl TabScanControl {
             let app_display_data = app_data_map.get(&package.pkg).cloned();
             let risk_score = self.get_risk_score(&package.pkg);
             let izzyrisk = risk_score.to_string();
+            let risk_breakdown = self.get_risk_breakdown(&package.pkg);
 
             // Get scan results for closures
             let vt_scan_result = if let Some(ref scanner_state) = vt_scanner_state {
@@ -1492,6 +2214,12 @@ impl TabScanControl {
                 None
             };
 
+            let tracker_result = tracker_results.get(&package.pkg).cloned();
+            let rule_match_result = rule_match_results.get(&package.pkg).cloned();
+            let vulnerable_native_libs = native_libraries
+                .get(&package.pkg)
+                .and_then(|libs| Self::vulnerable_native_libs_text(libs));
+
             let clicked_idx_clone = clicked_package_idx.clone();
             let is_system = package.flags.contains("SYSTEM");
             let enabled = package
@@ -1536,6 +2264,10 @@ impl TabScanControl {
                 let vt_result_for_cell = vt_scan_result.clone();
                 let ha_result_for_cell = ha_scan_result.clone();
                 let izzyrisk_for_cell = izzyrisk.clone();
+                let risk_breakdown_for_cell = risk_breakdown.clone();
+                let trackers_for_cell = tracker_result.clone();
+                let rule_matches_for_cell = rule_match_result.clone();
+                let vulnerable_native_libs_for_cell = vulnerable_native_libs.clone();
                 let ha_tag_ignorelist_for_cell = hybridanalysis_tag_ignorelist.clone();
 
                 // Determine display content (app info or package name)
@@ -1577,7 +2309,15 @@ impl TabScanControl {
                                                 .color(egui::Color32::GRAY),
                                         );
                                     }
-
+                                    // Bundled native library with a known-vulnerable version
+                                    if let Some(ref text) = vulnerable_native_libs_for_cell {
+                                        ui.label(
+                                            egui::RichText::new(tr!("vulnerable-native-lib"))
+                                                .small()
+                                                .color(egui::Color32::from_rgb(211, 47, 47)),
+                                        )
+                                        .on_hover_text(text);
+                                    }
                                 });
                             });
                             if !is_desktop {
@@ -1597,15 +2337,47 @@ impl TabScanControl {
                                                 .corner_radius(6.0)
                                                 .inner_margin(egui::Margin::symmetric(8, 3))
                                                 .show(ui, |ui| {
-                                                    ui.label(egui::RichText::new(format!("Risk:{}", &izzyrisk_for_cell)).size(10.0));
+                                                    let response = ui.label(egui::RichText::new(format!("Risk:{}", &izzyrisk_for_cell)).size(10.0));
+                                                    if let Some(ref breakdown) = risk_breakdown_for_cell {
+                                                        response.on_hover_ui(|ui| Self::render_risk_breakdown(ui, breakdown));
+                                                    }
                                                 });
 
+                                            // Show tracker count in mobile view
+                                            if let Some(ref trackers) = trackers_for_cell {
+                                                let names: Vec<&str> = trackers.iter().map(|t| t.name.as_str()).collect();
+                                                egui::Frame::new()
+                                                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(158, 158, 158)))
+                                                    .corner_radius(6.0)
+                                                    .inner_margin(egui::Margin::symmetric(8, 3))
+                                                    .show(ui, |ui| {
+                                                        ui.label(egui::RichText::new(tr!("trackers-count", { count: trackers.len() })).size(10.0));
+                                                    })
+                                                    .response
+                                                    .on_hover_text(names.join("\n"));
+                                            }
+
+                                            // Show rule matches in mobile view (only when a rule matched)
+                                            if let Some(matches) = rule_matches_for_cell.as_ref().filter(|m| !m.is_empty()) {
+                                                egui::Frame::new()
+                                                    .fill(egui::Color32::from_rgb(211, 47, 47))
+                                                    .corner_radius(6.0)
+                                                    .inner_margin(egui::Margin::symmetric(8, 3))
+                                                    .show(ui, |ui| {
+                                                        ui.label(egui::RichText::new(tr!("rules-count", { count: matches.len() })).color(egui::Color32::WHITE).size(10.0));
+                                                    })
+                                                    .response
+                                                    .on_hover_text(Self::rule_matches_text(matches));
+                                            }
+
                                             // Show VT results in mobile view
                                             match &vt_result_for_cell {
                                                 Some(calc_virustotal::ScanStatus::Completed(result)) => {
                                                     for (i, file_result) in result.file_results.iter().enumerate() {
                                                         let (text, bg_color) = if file_result.error.is_some() {
                                                             (tr!("scan-error"), egui::Color32::from_rgb(211, 47, 47))
+                                                        } else if file_result.trusted {
+                                                            (tr!("scan-trusted"), egui::Color32::from_rgb(56, 142, 60))
                                                         } else if file_result.skipped {
                                                             (tr!("scan-skip"), egui::Color32::from_rgb(128, 128, 128))
                                                         } else if file_result.not_found {
@@ -1618,8 +2390,10 @@ impl TabScanControl {
                                                             (tr!("scan-clean", { count: file_result.total(), total: file_result.total() }), egui::Color32::from_rgb(56, 142, 60))
                                                         };
 
+                                                        let changed = file_result.detections_changed();
                                                         let inner_response = egui::Frame::new()
                                                             .fill(bg_color)
+                                                            .stroke(changed_stroke(changed))
                                                             .corner_radius(6.0)
                                                             .inner_margin(egui::Margin::symmetric(8, 3))
                                                             .show(ui, |ui| {
@@ -1640,7 +2414,14 @@ impl TabScanControl {
                                                                     log::error!("Failed to open VirusTotal link: {}", err);
                                                                 }
                                                             }
-                                                            response.on_hover_text(&file_result.file_path);
+                                                            match file_result.previous_detections.filter(|_| changed) {
+                                                                Some((malicious, suspicious)) => response.on_hover_text(format!(
+                                                                    "{}\n{}",
+                                                                    file_result.file_path,
+                                                                    tr!("scan-changed-vt", { malicious: malicious, suspicious: suspicious })
+                                                                )),
+                                                                None => response.on_hover_text(&file_result.file_path),
+                                                            };
                                                         }
                                                     }
                                                 }
@@ -1721,6 +2502,7 @@ impl TabScanControl {
                                                                         "malicious" => tr!("ha-malicious"),
                                                                         "suspicious" => tr!("ha-suspicious"),
                                                                         "whitelisted" => tr!("ha-whitelisted"),
+                                                                        "trusted" => tr!("ha-trusted"),
                                                                         "no specific threat" => tr!("ha-no-specific-threat"),
                                                                         "no-result" => tr!("ha-no-result"),
                                                                         "rate_limited" => tr!("ha-rate-limited"),
@@ -1788,7 +2570,7 @@ impl TabScanControl {
                                                                 }
                                                             },
                                                             "suspicious" => egui::Color32::from_rgb(255, 152, 0),
-                                                            "whitelisted" => egui::Color32::from_rgb(56, 142, 60),
+                                                            "whitelisted" | "trusted" => egui::Color32::from_rgb(56, 142, 60),
                                                             "no specific threat" => egui::Color32::from_rgb(0, 150, 136),
                                                             "no-result" => egui::Color32::from_rgb(158, 158, 158),
                                                             "rate_limited" => egui::Color32::from_rgb(156, 39, 176),
@@ -1799,8 +2581,10 @@ impl TabScanControl {
                                                             _ => egui::Color32::from_rgb(158, 158, 158),
                                                         };
 
+                                                        let changed = file_result.verdict_changed();
                                                         let inner_response = egui::Frame::new()
                                                             .fill(bg_color)
+                                                            .stroke(changed_stroke(changed))
                                                             .corner_radius(6.0)
                                                             .inner_margin(egui::Margin::symmetric(8, 3))
                                                             .show(ui, |ui| {
@@ -1819,7 +2603,14 @@ impl TabScanControl {
                                                             }
                                                         }
 
-                                                        response.on_hover_text(&file_result.file_path);
+                                                        match file_result.previous_verdict.as_deref().filter(|_| changed) {
+                                                            Some(previous) => response.on_hover_text(format!(
+                                                                "{}\n{}",
+                                                                file_result.file_path,
+                                                                tr!("scan-changed-ha", { verdict: previous.to_string() })
+                                                            )),
+                                                            None => response.on_hover_text(&file_result.file_path),
+                                                        };
                                                     }
                                                 }
                                                 _ => {}
@@ -1836,7 +2627,10 @@ impl TabScanControl {
                 // IzzyRisk column (desktop only)
                 let row_builder = if is_desktop {
                 row_builder.widget_cell(move |ui: &mut egui::Ui| {
-                    ui.label(&izzyrisk);
+                    let response = ui.label(&izzyrisk);
+                    if let Some(ref breakdown) = risk_breakdown {
+                        response.on_hover_ui(|ui| Self::render_risk_breakdown(ui, breakdown));
+                    }
                 })
                 } else { row_builder };
 
@@ -1865,6 +2659,8 @@ impl TabScanControl {
                                         for (i, file_result) in result.file_results.iter().enumerate() {
                                             let (text, bg_color) = if file_result.error.is_some() {
                                                 (tr!("scan-error"), egui::Color32::from_rgb(211, 47, 47))
+                                            } else if file_result.trusted {
+                                                (tr!("scan-trusted"), egui::Color32::from_rgb(56, 142, 60))
                                             } else if file_result.skipped {
                                                 (tr!("scan-skip"), egui::Color32::from_rgb(128, 128, 128))
                                             } else if file_result.not_found {
@@ -1877,8 +2673,10 @@ impl TabScanControl {
                                                 (tr!("scan-clean", { count: file_result.total(), total: file_result.total() }), egui::Color32::from_rgb(56, 142, 60))
                                             };
 
+                                            let changed = file_result.detections_changed();
                                             let inner_response = egui::Frame::new()
                                                 .fill(bg_color)
+                                                .stroke(changed_stroke(changed))
                                                 .corner_radius(8.0)
                                                 .inner_margin(egui::Margin::symmetric(12, 6))
                                                 .show(ui, |ui| {
@@ -1902,7 +2700,14 @@ impl TabScanControl {
                                                         }
                                                     }
                                                 }
-                                                response.on_hover_text(&file_result.file_path);
+                                                match file_result.previous_detections.filter(|_| changed) {
+                                                    Some((malicious, suspicious)) => response.on_hover_text(format!(
+                                                        "{}\n{}",
+                                                        file_result.file_path,
+                                                        tr!("scan-changed-vt", { malicious: malicious, suspicious: suspicious })
+                                                    )),
+                                                    None => response.on_hover_text(&file_result.file_path),
+                                                };
                                             }
                                         }
                                     }
@@ -2019,6 +2824,7 @@ impl TabScanControl {
                                                             "malicious" => tr!("ha-malicious"),
                                                             "suspicious" => tr!("ha-suspicious"),
                                                             "whitelisted" => tr!("ha-whitelisted"),
+                                                            "trusted" => tr!("ha-trusted"),
                                                             "no specific threat" => tr!("ha-no-specific-threat"),
                                                             "no-result" => tr!("ha-no-result"),
                                                             "rate_limited" => tr!("ha-rate-limited"),
@@ -2090,7 +2896,7 @@ impl TabScanControl {
                                                     }
                                                 },
                                                 "suspicious" => egui::Color32::from_rgb(255, 152, 0),
-                                                "whitelisted" => egui::Color32::from_rgb(56, 142, 60),
+                                                "whitelisted" | "trusted" => egui::Color32::from_rgb(56, 142, 60),
                                                 "no specific threat" => egui::Color32::from_rgb(0, 150, 136),
                                                 "no-result" => egui::Color32::from_rgb(158, 158, 158),
                                                 "rate_limited" => egui::Color32::from_rgb(156, 39, 176),
@@ -2101,8 +2907,10 @@ impl TabScanControl {
                                                 _ => egui::Color32::from_rgb(158, 158, 158),
                                             };
 
+                                            let changed = file_result.verdict_changed();
                                             let inner_response = egui::Frame::new()
                                                 .fill(bg_color)
+                                                .stroke(changed_stroke(changed))
                                                 .corner_radius(8.0)
                                                 .inner_margin(egui::Margin::symmetric(12, 6))
                                                 .show(ui, |ui| {
@@ -2124,7 +2932,14 @@ impl TabScanControl {
                                                 }
                                             }
 
-                                            response.on_hover_text(&file_result.file_path);
+                                            match file_result.previous_verdict.as_deref().filter(|_| changed) {
+                                                Some(previous) => response.on_hover_text(format!(
+                                                    "{}\n{}",
+                                                    file_result.file_path,
+                                                    tr!("scan-changed-ha", { verdict: previous.to_string() })
+                                                )),
+                                                None => response.on_hover_text(&file_result.file_path),
+                                            };
                                         }
                                     }
                                     Some(calc_hybridanalysis::ScanStatus::Error(e)) => {
@@ -2136,6 +2951,47 @@ impl TabScanControl {
                 })
                 } else { row_builder };
 
+                // Trackers column (desktop only)
+                let row_builder = if is_desktop { row_builder.widget_cell(move |ui: &mut egui::Ui| {
+                    match &tracker_result {
+                        Some(trackers) if trackers.is_empty() => {
+                            ui.label(tr!("no-trackers"));
+                        }
+                        Some(trackers) => {
+                            let names: Vec<&str> = trackers.iter().map(|t| t.name.as_str()).collect();
+                            let mut categories: Vec<&str> = trackers
+                                .iter()
+                                .flat_map(|t| t.categories.iter().map(|c| c.as_str()))
+                                .collect();
+                            categories.sort_unstable();
+                            categories.dedup();
+                            ui.label(tr!("trackers-count", { count: trackers.len() }))
+                                .on_hover_text(format!("{}\n\n{}", names.join("\n"), categories.join(", ")));
+                        }
+                        None => {
+                            ui.label(tr!("not-scanned-short"));
+                        }
+                    }
+                })
+                } else { row_builder };
+
+                // Rules column (desktop only)
+                let row_builder = if is_desktop { row_builder.widget_cell(move |ui: &mut egui::Ui| {
+                    match &rule_match_result {
+                        Some(matches) if matches.is_empty() => {
+                            ui.label(tr!("no-rule-matches"));
+                        }
+                        Some(matches) => {
+                            ui.colored_label(egui::Color32::from_rgb(211, 47, 47), tr!("rules-count", { count: matches.len() }))
+                                .on_hover_text(Self::rule_matches_text(matches));
+                        }
+                        None => {
+                            ui.label(tr!("not-scanned-short"));
+                        }
+                    }
+                })
+                } else { row_builder };
+
                 // Tasks column
                 let row_builder = row_builder.widget_cell(move |ui: &mut egui::Ui| {
                     egui::ScrollArea::horizontal()
@@ -2197,13 +3053,13 @@ impl TabScanControl {
         }
 
         // Sort column index mapping: self.sort_column uses logical (desktop) indices
-        // Desktop: [0=PackageName, 1=IzzyRisk, 2=VT, 3=HA, 4=Tasks]
+        // Desktop: [0=PackageName, 1=IzzyRisk, 2=VT, 3=HA, 4=Trackers, 5=Rules, 6=Tasks]
         // Mobile:  [0=PackageName, 1=Tasks]
         let to_physical = |logical: usize| -> usize {
             if is_desktop { logical } else { match logical { 0 => 0, _ => 1 } }
         };
         let to_logical = |physical: usize| -> usize {
-            if is_desktop { physical } else { match physical { 0 => 0, _ => 4 } }
+            if is_desktop { physical } else { match physical { 0 => 0, _ => 6 } }
         };
 
         // Set sort state
@@ -2214,7 +3070,7 @@ impl TabScanControl {
             } else {
                 SortDirection::Descending
             };
-            if is_desktop || sort_col == 0 || sort_col == 4 {
+            if is_desktop || sort_col == 0 || sort_col == 6 {
                 interactive_table = interactive_table.sort_by(to_physical(sort_col), direction);
             }
         }
@@ -2222,8 +3078,8 @@ impl TabScanControl {
         let table_response = interactive_table.show(ui);
 
         // Sync sort state from widget, but only when sorting by a column the widget knows about.
-        // On mobile, hidden columns (1-3) are managed by the mobile sort buttons, not the table widget.
-        let mobile_hidden_sort = !is_desktop && matches!(self.sort_column, Some(1..=3));
+        // On mobile, hidden columns (1-5) are managed by the mobile sort buttons, not the table widget.
+        let mobile_hidden_sort = !is_desktop && matches!(self.sort_column, Some(1..=5));
         if !mobile_hidden_sort {
             let (widget_sort_col, widget_sort_dir) = table_response.sort_state;
             let logical_sort_col = widget_sort_col.map(|c| to_logical(c));
@@ -2379,54 +3235,14 @@ impl TabScanControl {
 
             if let Some(package) = package_info {
                 // Get hashes for the package
-                let device_serial = self.device_serial.clone();
-                let cached_packages = if let Some(ref serial) = device_serial {
-                    crate::db_package_cache::get_cached_packages_with_apk(serial)
-                } else {
-                    vec![]
-                };
-                let cached_pkg = cached_packages.iter().find(|cp| cp.pkg_id == pkg_name);
-
-                let mut paths_str = String::new();
-                let mut sha256sums_str = String::new();
-
-                if let Some(cp) = cached_pkg {
-                    if let (Some(path), Some(sha256)) = (&cp.apk_path, &cp.apk_sha256sum) {
-                        paths_str = path.clone();
-                        sha256sums_str = sha256.clone();
-                    }
-                }
-
-                if paths_str.is_empty() || sha256sums_str.is_empty() {
-                    paths_str = package.codePath.clone();
-                    sha256sums_str = package.pkgChecksum.clone();
-                }
-
-                // Get proper hashes if needed
-                if let Some(ref serial) = device_serial {
-                    let paths: Vec<&str> = paths_str.split(' ').collect();
-                    let sha256sums: Vec<&str> = sha256sums_str.split(' ').collect();
-                    let needs_directory_scan = paths.iter().any(|p| !p.ends_with(".apk"));
-                    let has_invalid_hashes = sha256sums.iter().any(|s| s.len() != 64);
-
-                    if needs_directory_scan || has_invalid_hashes {
-                        if let Ok((new_paths, new_sha256sums)) = crate::adb::get_single_package_sha256sum(serial, &pkg_name) {
-                            if !new_paths.is_empty() && !new_sha256sums.is_empty() {
-                                paths_str = new_paths;
-                                sha256sums_str = new_sha256sums;
-                            }
-                        }
+                let hashes = match self.device_serial {
+                    Some(ref serial) => {
+                        let cached_packages = crate::db_package_cache::get_cached_packages_with_apk(serial);
+                        let cached_pkg = cached_packages.iter().find(|cp| cp.pkg_id == pkg_name);
+                        crate::calc_scanprovider::package_file_hashes(serial, &package, cached_pkg)
                     }
-                }
-
-                let final_paths: Vec<&str> = paths_str.split(' ').collect();
-                let final_sha256sums: Vec<&str> = sha256sums_str.split(' ').collect();
-                let hashes: Vec<(String, String)> = final_paths
-                    .iter()
-                    .zip(final_sha256sums.iter())
-                    .filter(|(p, s)| !p.is_empty() && s.len() == 64)
-                    .map(|(p, s)| (p.to_string(), s.to_string()))
-                    .collect();
+                    None => Vec::new(),
+                };
 
                 // Start VirusTotal scan in background
                 let shared_store = crate::shared_store_stt::get_shared_store();
@@ -2439,7 +3255,7 @@ impl TabScanControl {
                 ) {
                     let vt_state_clone = vt_state.clone();
                     let vt_limiter_clone = vt_limiter.clone();
-                    let api_key_clone = api_key.clone();
+                    let provider = self.vt_provider(api_key.clone());
                     let serial_clone = serial.clone();
                     let pkg_name_clone = pkg_name.clone();
                     let hashes_clone = hashes.clone();
@@ -2457,7 +3273,7 @@ impl TabScanControl {
                             hashes_clone,
                             &vt_state_clone,
                             &vt_limiter_clone,
-                            &api_key_clone,
+                            &provider,
                             &serial_clone,
                             vt_submit,
                             &None,
@@ -2478,7 +3294,7 @@ impl TabScanControl {
                 ) {
                     let ha_state_clone = ha_state.clone();
                     let ha_limiter_clone = ha_limiter.clone();
-                    let api_key_clone = api_key.clone();
+                    let provider = self.ha_provider(api_key.clone());
                     let serial_clone = serial.clone();
                     let pkg_name_clone = pkg_name.clone();
                     let hashes_clone = hashes.clone();
@@ -2496,7 +3312,7 @@ impl TabScanControl {
                             hashes_clone,
                             &ha_state_clone,
                             &ha_limiter_clone,
-                            &api_key_clone,
+                            &provider,
                             &serial_clone,
                             ha_submit,
                             &None,
@@ -2567,11 +3383,15 @@ impl TabScanControl {
         }
 
         // Show package details dialog
+        // Cloned rather than locked: the permissions tab updates the shared package list
         let shared_store = crate::shared_store_stt::get_shared_store();
-        let installed_packages = shared_store.installed_packages.lock().unwrap();
-        let uad_ng_lists = shared_store.uad_ng_lists.lock().unwrap();
+        let installed_packages = shared_store.get_installed_packages();
+        let uad_ng_lists = shared_store.get_uad_ng_lists();
         self.package_details_dialog
-            .show(ui.ctx(), &installed_packages, &uad_ng_lists);
+            .show(ui.ctx(), &installed_packages, &uad_ng_lists, self.device_serial.as_deref());
+
+        self.scan_export_dialog.show(ui.ctx(), &self.package_risk_scores);
+        self.app_ops_dialog.show(ui.ctx());
     }
 }
 
@@ -1,6 +1,10 @@
 use crate::calc_hybridanalysis::SharedRateLimiter as HaSharedRateLimiter;
+use crate::calc_izzyrisk_stt::IzzyRiskBreakdown;
+use crate::calc_scanprovider_stt::{FreshnessPolicy, QuotaBudget, SharedRateLimiter};
 use crate::calc_virustotal::SharedRateLimiter as VtSharedRateLimiter;
 use crate::dlg_package_details::DlgPackageDetails;
+use crate::dlg_scan_export::DlgScanExport;
+use crate::dlg_app_ops::DlgAppOps;
 use crate::dlg_uninstall_confirm::DlgUninstallConfirm;
 use egui_async::Bind;
 use std::collections::HashMap;
@@ -41,6 +45,9 @@ impl ScanStateMachine {
     }
 }
 
+/// (enabled_count, total_count) for: (all, malicious, clean, not_scanned)
+pub type ProviderCounts = ((usize, usize), (usize, usize), (usize, usize), (usize, usize));
+
 /// Cached VT/HA counts to avoid recomputing every frame
 /// Each tuple is (enabled_count, total_count) for: (all, malicious, suspicious, safe, not_scanned)
 #[derive(Default, Clone)]
@@ -53,6 +60,14 @@ pub struct CachedScanCounts {
     pub vt_progress: Option<f32>,
     /// HA progress when cache was computed
     pub ha_progress: Option<f32>,
+    /// MalwareBazaar counts: (all, listed, not listed, not scanned)
+    pub mb_counts: ProviderCounts,
+    /// Koodous counts: (all, detected, undetected, not scanned)
+    pub koodous_counts: ProviderCounts,
+    /// MalwareBazaar progress when cache was computed
+    pub mb_progress: Option<f32>,
+    /// Koodous progress when cache was computed
+    pub koodous_progress: Option<f32>,
     /// VT filter when cache was computed
     pub vt_filter: Option<HaFilter>,
     /// HA filter when cache was computed
@@ -90,26 +105,67 @@ pub struct TabScanControl {
         Option<std::sync::Arc<std::sync::Mutex<HashMap<String, Vec<(String, String)>>>>>,
     // HybridAnalysis scan state machine
     pub ha_scan_state: ScanStateMachine,
+    // Shared rate limiters for the MalwareBazaar and Koodous APIs
+    pub mb_rate_limiter: Option<SharedRateLimiter>,
+    pub koodous_rate_limiter: Option<SharedRateLimiter>,
+    // MalwareBazaar and Koodous lookup state machines
+    pub mb_scan_state: ScanStateMachine,
+    pub koodous_scan_state: ScanStateMachine,
     // IzzyRisk scan state machine
     pub izzyrisk_scan_state: ScanStateMachine,
     // Progress for IzzyRisk scan background task (for thread communication)
     pub izzyrisk_scan_progress: Arc<Mutex<Option<f32>>>,
     // Cancellation flag for IzzyRisk scan
     pub izzyrisk_scan_cancelled: Arc<Mutex<bool>>,
+    // Tracker scan state machine
+    pub tracker_scan_state: ScanStateMachine,
+    // Progress for tracker scan background task (for thread communication)
+    pub tracker_scan_progress: Arc<Mutex<Option<f32>>>,
+    // Cancellation flag for tracker scan
+    pub tracker_scan_cancelled: Arc<Mutex<bool>>,
+    // Local rule scan state machine
+    pub rule_scan_state: ScanStateMachine,
+    // Progress for rule scan background task (for thread communication)
+    pub rule_scan_progress: Arc<Mutex<Option<f32>>>,
+    // Cancellation flag for rule scan
+    pub rule_scan_cancelled: Arc<Mutex<bool>>,
+    // Native library scan state machine
+    pub native_lib_scan_state: ScanStateMachine,
+    // Progress for native library scan background task (for thread communication)
+    pub native_lib_scan_progress: Arc<Mutex<Option<f32>>>,
+    // Cancellation flag for native library scan
+    pub native_lib_scan_cancelled: Arc<Mutex<bool>>,
     // Shared risk scores from background thread
     pub shared_package_risk_scores: Arc<Mutex<HashMap<String, i32>>>,
+    // Why each package got its risk score, from the same background thread
+    pub shared_package_risk_breakdowns: Arc<Mutex<HashMap<String, IzzyRiskBreakdown>>>,
     // Config for API keys and device serial
     pub vt_api_key: Option<String>,
     pub ha_api_key: Option<String>,
+    pub mb_api_key: Option<String>,
+    pub koodous_api_key: Option<String>,
     pub device_serial: Option<String>,
     pub virustotal_submit_enabled: bool,
     pub hybridanalysis_submit_enabled: bool,
+    // When cached VirusTotal/Hybrid Analysis results are re-queried (synced from Settings)
+    pub scan_freshness: FreshnessPolicy,
+    // VirusTotal API tier (synced from Settings)
+    pub vt_requests_per_minute: u32,
+    pub vt_quota_budget: QuotaBudget,
+    // Stored VirusTotal usage shown before a scan creates the rate limiter (budget and UTC day it was read for)
+    pub vt_stored_quota: Option<(QuotaBudget, String, Option<u32>)>,
+    // Hours between background rescans while the app is open (0 = disabled)
+    pub rescan_interval_hours: u32,
+    // When the last background rescan was started (or scheduling began)
+    pub last_scheduled_rescan: Option<std::time::Instant>,
     // Sort state
     pub sort_column: Option<usize>,
     pub sort_ascending: bool,
     // Filter state
     pub active_vt_filter: VtFilter,
     pub active_ha_filter: HaFilter,
+    pub active_mb_filter: ProviderFilter,
+    pub active_koodous_filter: ProviderFilter,
 
     // Progress for VirusTotal scan background task (for thread communication)
     pub vt_scan_progress: Arc<Mutex<Option<f32>>>,
@@ -119,6 +175,11 @@ pub struct TabScanControl {
     pub ha_scan_progress: Arc<Mutex<Option<f32>>>,
     // Cancellation flag for HybridAnalysis scan
     pub ha_scan_cancelled: Arc<Mutex<bool>>,
+    // Progress and cancellation flags for MalwareBazaar and Koodous lookups
+    pub mb_scan_progress: Arc<Mutex<Option<f32>>>,
+    pub mb_scan_cancelled: Arc<Mutex<bool>>,
+    pub koodous_scan_progress: Arc<Mutex<Option<f32>>>,
+    pub koodous_scan_cancelled: Arc<Mutex<bool>>,
 
     // Filter to show only enabled (green) packages
     pub show_only_enabled: bool,
@@ -138,6 +199,12 @@ pub struct TabScanControl {
 
     // Uninstall confirmation dialog
     pub uninstall_confirm_dialog: DlgUninstallConfirm,
+
+    // Scan report export dialog
+    pub scan_export_dialog: DlgScanExport,
+
+    // AppOps editor for the selected packages
+    pub app_ops_dialog: DlgAppOps,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -158,3 +225,12 @@ pub enum HaFilter {
     Safe,
     NotScanned,
 }
+
+/// Filter for lookup-only providers (MalwareBazaar, Koodous)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderFilter {
+    All,
+    Malicious,
+    Clean,
+    NotScanned,
+}
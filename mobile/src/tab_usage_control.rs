@@ -1,19 +1,139 @@
 pub use crate::tab_usage_control_stt::*;
+use crate::calc_background_stt::BackgroundAction;
+use crate::calc_batterystats_stt::{BatteryRanking, UidBatteryUsage};
+use crate::shared_store_stt::get_shared_store;
+use crate::{BASE_TABLE_WIDTH, DESKTOP_MIN_WIDTH};
 use eframe::egui;
 use egui_i18n::tr;
+use egui_material3::{data_table, MaterialButton};
+use std::sync::{Arc, Mutex};
+
+/// Packages without use in this many days are offered as debloat candidates
+const UNUSED_DAYS: i64 = 30;
 
 impl Default for TabUsageControl {
     fn default() -> Self {
         Self {
             open: false,
-            usage_stats: String::new(),
+            selected_device: None,
+            view: UsageView::Usage,
+            usage_result: Arc::new(Mutex::new(None)),
+            usage_rows: None,
+            loading: false,
+            sort_column: None,
+            sort_ascending: true,
+            unused_only: false,
+            text_filter: String::new(),
+            selection_candidates: None,
+            disable_candidate: None,
+            battery_result: Arc::new(Mutex::new(None)),
+            battery_loading: false,
+            battery_ranking: BatteryRanking::Power,
+            battery_action_result: Arc::new(Mutex::new(None)),
+            battery_action_running: false,
+            battery_action_message: None,
         }
     }
 }
 
 impl TabUsageControl {
-    pub fn update_usage_stats(&mut self, stats: String) {
-        self.usage_stats = stats;
+    pub fn set_selected_device(&mut self, device: Option<String>) {
+        self.selected_device = device;
+        if let Ok(mut r) = self.usage_result.lock() {
+            *r = None;
+        }
+        self.usage_rows = None;
+        self.loading = false;
+        if let Ok(mut r) = self.battery_result.lock() {
+            *r = None;
+        }
+        self.battery_loading = false;
+        self.battery_action_message = None;
+    }
+
+    fn refresh(&mut self) {
+        let Some(device) = self.selected_device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.usage_result.lock() {
+            *r = None;
+        }
+        self.usage_rows = None;
+        self.loading = true;
+        crate::calc_usagestats::run_usage_stats_load_async(device, self.usage_result.clone());
+    }
+
+    fn format_last_used(last_used: Option<i64>) -> String {
+        last_used
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| tr!("usage-never"))
+    }
+
+    fn refresh_battery(&mut self) {
+        let Some(device) = self.selected_device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.battery_result.lock() {
+            *r = None;
+        }
+        self.battery_loading = true;
+        crate::calc_batterystats::run_battery_report_async(device, self.battery_result.clone());
+    }
+
+    fn start_battery_action(&mut self, packages: Vec<String>, action: BackgroundAction) {
+        let Some(device) = self.selected_device.clone() else {
+            return;
+        };
+        if let Ok(mut r) = self.battery_action_result.lock() {
+            *r = None;
+        }
+        self.battery_action_running = true;
+        self.battery_action_message = None;
+        crate::calc_batterystats::run_battery_action_async(device, packages, action, self.battery_action_result.clone());
+    }
+
+    fn poll_battery_action(&mut self) {
+        if !self.battery_action_running {
+            return;
+        }
+        let Some(outcome) = self.battery_action_result.lock().ok().and_then(|mut r| r.take()) else {
+            return;
+        };
+        self.battery_action_running = false;
+        self.battery_action_message = Some(if outcome.failures.is_empty() {
+            (true, tr!("background-changes-done", { count: outcome.succeeded }))
+        } else {
+            (
+                false,
+                format!(
+                    "{}\n{}",
+                    tr!("background-changes-failed", {
+                        count: outcome.failures.len(),
+                        succeeded: outcome.succeeded
+                    }),
+                    outcome.failures.join("\n")
+                ),
+            )
+        });
+    }
+
+    fn ranking_label(ranking: BatteryRanking) -> String {
+        match ranking {
+            BatteryRanking::Power => tr!("battery-rank-power"),
+            BatteryRanking::WakelockTime => tr!("battery-rank-wakelocks"),
+            BatteryRanking::AlarmWakeups => tr!("battery-rank-alarms"),
+            BatteryRanking::Jobs => tr!("battery-rank-jobs"),
+        }
+    }
+
+    /// "count / total time" for wakelocks and jobs
+    fn format_count_time(count: u64, ms: u64) -> String {
+        if count == 0 {
+            "-".to_string()
+        } else {
+            format!("{} / {}", count, crate::calc_usagestats::format_duration(ms / 1000))
+        }
     }
 
     pub fn ui(&mut self, ui: &mut egui::Ui) {
@@ -22,16 +142,409 @@ impl TabUsageControl {
         });
         ui.add_space(10.0);
 
-        if self.usage_stats.is_empty() {
+        if self.selected_device.is_none() {
             ui.label(tr!("no-usage-stats"));
-        } else {
-            ui.label(tr!("usage-statistics"));
-            ui.add_space(5.0);
-            egui::ScrollArea::vertical()
-                .max_height(500.0)
-                .show(ui, |ui| {
-                    ui.monospace(&self.usage_stats);
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.view, UsageView::Usage, tr!("usage-view-usage"));
+            ui.selectable_value(&mut self.view, UsageView::Battery, tr!("usage-view-battery"));
+        });
+        ui.add_space(8.0);
+
+        match self.view {
+            UsageView::Usage => self.usage_ui(ui),
+            UsageView::Battery => self.battery_ui(ui),
+        }
+    }
+
+    /// Build the table rows once when a load finishes, instead of every frame
+    fn poll_usage_result(&mut self) {
+        let Some(result) = self.usage_result.lock().ok().and_then(|mut r| r.take()) else {
+            return;
+        };
+        self.loading = false;
+        let store = get_shared_store();
+        let installed_packages = store.get_installed_packages();
+        let uad_ng_lists = store.get_uad_ng_lists();
+        let now = chrono::Utc::now().timestamp();
+        self.usage_rows = Some(result.map(|usage| {
+            crate::calc_usagestats::build_usage_rows(
+                &usage,
+                &installed_packages,
+                uad_ng_lists.as_ref(),
+                now,
+                UNUSED_DAYS,
+            )
+        }));
+    }
+
+    fn usage_ui(&mut self, ui: &mut egui::Ui) {
+        self.poll_usage_result();
+        if self.usage_rows.is_none() && !self.loading {
+            self.refresh();
+        }
+        let all_rows = match &self.usage_rows {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr!("usage-loading"));
+                });
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), tr!("usage-load-failed", { error: e }));
+                if ui.add(MaterialButton::outlined(tr!("refresh")).small()).clicked() {
+                    self.refresh();
+                }
+                return;
+            }
+            Some(Ok(rows)) => rows,
+        };
+
+        let candidate_count = all_rows.iter().filter(|row| row.candidate).count();
+        let filter_lower = self.text_filter.to_lowercase();
+        let mut rows: Vec<&UsageRow> = all_rows
+            .iter()
+            .filter(|row| !self.unused_only || row.unused)
+            .filter(|row| filter_lower.is_empty() || row.usage.pkg_id.to_lowercase().contains(&filter_lower))
+            .collect();
+        if let Some(col) = self.sort_column {
+            rows.sort_by(|a, b| {
+                let (a, b) = (&a.usage, &b.usage);
+                let ordering = match col {
+                    1 => a.last_used.cmp(&b.last_used),
+                    2 => a.foreground_day.cmp(&b.foreground_day),
+                    3 => a.foreground_week.cmp(&b.foreground_week),
+                    4 => a.foreground_month.cmp(&b.foreground_month),
+                    5 => a.launch_count.cmp(&b.launch_count),
+                    _ => a.pkg_id.cmp(&b.pkg_id),
+                };
+                if self.sort_ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        let mut select_clicked = false;
+        let mut refresh_clicked = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.checkbox(&mut self.unused_only, tr!("usage-unused-only", { days: UNUSED_DAYS }));
+            ui.add_space(10.0);
+            ui.label(tr!("filter"));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.text_filter)
+                    .hint_text(tr!("filter-hint"))
+                    .desired_width(200.0),
+            );
+            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.text_filter);
+            if !self.text_filter.is_empty() && ui.button("X").clicked() {
+                self.text_filter.clear();
+            }
+            ui.add_space(10.0);
+            select_clicked = ui
+                .add_enabled(
+                    candidate_count > 0,
+                    MaterialButton::filled(tr!("usage-select-unused", { count: candidate_count })).small(),
+                )
+                .on_hover_text(tr!("usage-select-unused-desc", { days: UNUSED_DAYS }))
+                .clicked();
+            refresh_clicked = ui.add(MaterialButton::outlined(tr!("refresh")).small()).clicked();
+        });
+        if select_clicked {
+            self.selection_candidates =
+                Some(all_rows.iter().filter(|row| row.candidate).map(|row| row.usage.pkg_id.clone()).collect());
+        }
+        ui.add_space(8.0);
+
+        let available_width = ui.ctx().content_rect().width();
+        let is_desktop = available_width >= DESKTOP_MIN_WIDTH;
+        let width_ratio = available_width / BASE_TABLE_WIDTH;
+
+        let mut usage_table = data_table()
+            .id(egui::Id::new("usage_data_table"))
+            .default_row_height(if is_desktop { 40.0 } else { 56.0 })
+            .sortable_column(
+                tr!("col-package-name"),
+                if is_desktop { 350.0 * width_ratio } else { available_width * 0.45 },
+                false,
+            )
+            .sortable_column(
+                tr!("col-last-used"),
+                if is_desktop { 160.0 * width_ratio } else { available_width * 0.3 },
+                false,
+            );
+        if is_desktop {
+            usage_table = usage_table
+                .sortable_column(tr!("col-foreground-day"), 110.0 * width_ratio, true)
+                .sortable_column(tr!("col-foreground-week"), 110.0 * width_ratio, true);
+        }
+        usage_table = usage_table.sortable_column(
+            tr!("col-foreground-month"),
+            if is_desktop { 110.0 * width_ratio } else { available_width * 0.2 },
+            true,
+        );
+        if is_desktop {
+            usage_table = usage_table.sortable_column(tr!("col-launches"), 100.0 * width_ratio, true);
+        }
+        usage_table = usage_table.allow_selection(false);
+
+        // Sort column index mapping: self.sort_column uses logical (desktop) indices
+        // Desktop: [0=PackageName, 1=LastUsed, 2=1d, 3=7d, 4=30d, 5=Launches]
+        // Mobile:  [0=PackageName, 1=LastUsed, 2=30d]
+        let to_physical = |logical: usize| -> usize {
+            if is_desktop { logical } else { match logical { 4 => 2, l => l } }
+        };
+        let to_logical = |physical: usize| -> usize {
+            if is_desktop { physical } else { match physical { 2 => 4, p => p } }
+        };
+
+        if let Some(sort_col) = self.sort_column {
+            use egui_material3::SortDirection;
+            let direction = if self.sort_ascending {
+                SortDirection::Ascending
+            } else {
+                SortDirection::Descending
+            };
+            if is_desktop || matches!(sort_col, 0 | 1 | 4) {
+                usage_table = usage_table.sort_by(to_physical(sort_col), direction);
+            }
+        }
+
+        for UsageRow { usage: row, unused, .. } in rows {
+            let name = if *unused {
+                egui::RichText::new(&row.pkg_id).color(egui::Color32::from_rgb(255, 152, 0))
+            } else {
+                egui::RichText::new(&row.pkg_id)
+            };
+            let last_used = Self::format_last_used(row.last_used);
+            let foreground_month = crate::calc_usagestats::format_duration(row.foreground_month);
+            usage_table = usage_table.row(|table_row| {
+                let mut row_builder = table_row
+                    .widget_cell(move |ui: &mut egui::Ui| {
+                        ui.add(egui::Label::new(name.clone()).wrap());
+                    })
+                    .cell(&last_used);
+                if is_desktop {
+                    row_builder = row_builder
+                        .cell(&crate::calc_usagestats::format_duration(row.foreground_day))
+                        .cell(&crate::calc_usagestats::format_duration(row.foreground_week));
+                }
+                row_builder = row_builder.cell(&foreground_month);
+                if is_desktop {
+                    row_builder = row_builder.cell(&row.launch_count.to_string());
+                }
+                row_builder.id(format!("usage_table_row_{}", row.pkg_id))
+            });
+        }
+
+        let table_response = usage_table.show(ui);
+
+        // Sync sort state from the widget; rows are sorted above on the next frame
+        let (widget_sort_col, widget_sort_dir) = table_response.sort_state;
+        let logical_sort_col = widget_sort_col.map(|c| to_logical(c));
+        let widget_sort_ascending = matches!(widget_sort_dir, egui_material3::SortDirection::Ascending);
+        if logical_sort_col != self.sort_column
+            || (logical_sort_col.is_some() && widget_sort_ascending != self.sort_ascending)
+        {
+            self.sort_column = logical_sort_col;
+            self.sort_ascending = widget_sort_ascending;
+            ui.ctx().request_repaint();
+        }
+
+        if let Some(clicked_col) = table_response.column_clicked {
+            let logical_clicked = to_logical(clicked_col);
+            if self.sort_column == Some(logical_clicked) {
+                self.sort_ascending = !self.sort_ascending;
+            } else {
+                self.sort_column = Some(logical_clicked);
+                self.sort_ascending = true;
+            }
+            ui.ctx().request_repaint();
+        }
+
+        if refresh_clicked {
+            self.refresh();
+        }
+    }
+
+    fn battery_ui(&mut self, ui: &mut egui::Ui) {
+        let result = self.battery_result.lock().ok().and_then(|r| r.clone());
+        if result.is_none() && !self.battery_loading {
+            self.refresh_battery();
+        }
+        if result.is_some() {
+            self.battery_loading = false;
+        }
+        let mut rows = match result {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr!("battery-loading"));
+                });
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::from_rgb(211, 47, 47), tr!("battery-load-failed", { error: e }));
+                if ui.add(MaterialButton::outlined(tr!("refresh")).small()).clicked() {
+                    self.refresh_battery();
+                }
+                return;
+            }
+            Some(Ok(rows)) => rows,
+        };
+
+        let filter_lower = self.text_filter.to_lowercase();
+        rows.retain(|row| filter_lower.is_empty() || row.packages.iter().any(|p| p.to_lowercase().contains(&filter_lower)));
+        crate::calc_batterystats::rank(&mut rows, self.battery_ranking);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("battery-rank-by"));
+            egui::ComboBox::from_id_salt("battery_ranking")
+                .selected_text(Self::ranking_label(self.battery_ranking))
+                .show_ui(ui, |ui| {
+                    for ranking in BatteryRanking::ALL {
+                        ui.selectable_value(&mut self.battery_ranking, ranking, Self::ranking_label(ranking));
+                    }
                 });
+            ui.add_space(10.0);
+            ui.label(tr!("filter"));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.text_filter)
+                    .hint_text(tr!("filter-hint"))
+                    .desired_width(200.0),
+            );
+            crate::clipboard_popup::show_clipboard_popup(ui, &response, &mut self.text_filter);
+            if !self.text_filter.is_empty() && ui.button("X").clicked() {
+                self.text_filter.clear();
+            }
+            ui.add_space(10.0);
+            if ui.add(MaterialButton::outlined(tr!("refresh")).small()).clicked() {
+                self.refresh_battery();
+            }
+        });
+        ui.label(egui::RichText::new(tr!("battery-since-charge")).small().weak());
+
+        self.poll_battery_action();
+        if self.battery_action_running {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(200));
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr!("background-applying"));
+            });
+        } else if let Some((ok, message)) = &self.battery_action_message {
+            let color = if *ok {
+                egui::Color32::from_rgb(56, 142, 60)
+            } else {
+                egui::Color32::from_rgb(211, 47, 47)
+            };
+            ui.colored_label(color, message);
+        }
+        ui.add_space(8.0);
+
+        if rows.is_empty() {
+            ui.label(tr!("battery-empty"));
+            return;
+        }
+
+        let available_width = ui.ctx().content_rect().width();
+        let is_desktop = available_width >= DESKTOP_MIN_WIDTH;
+        let width_ratio = available_width / BASE_TABLE_WIDTH;
+
+        // Rows follow the ranking, so columns are not sortable
+        // Desktop: [PackageName, Power, Wakelocks, AlarmWakeups, Jobs, Actions]
+        // Mobile:  [PackageName, Power, Actions]
+        let mut battery_table = data_table()
+            .id(egui::Id::new("battery_data_table"))
+            .default_row_height(if is_desktop { 40.0 } else { 56.0 })
+            .column(
+                tr!("col-package-name"),
+                if is_desktop { 330.0 * width_ratio } else { available_width * 0.45 },
+                false,
+            )
+            .column(
+                tr!("col-power"),
+                if is_desktop { 90.0 * width_ratio } else { available_width * 0.2 },
+                true,
+            );
+        if is_desktop {
+            battery_table = battery_table
+                .column(tr!("col-wakelocks"), 130.0 * width_ratio, true)
+                .column(tr!("col-alarm-wakeups"), 110.0 * width_ratio, true)
+                .column(tr!("col-jobs"), 130.0 * width_ratio, true);
+        }
+        battery_table = battery_table
+            .column(
+                tr!("col-tasks"),
+                if is_desktop { 200.0 * width_ratio } else { available_width * 0.3 },
+                false,
+            )
+            .allow_selection(false);
+
+        let busy = self.battery_action_running;
+        let picked: Arc<Mutex<Option<(Vec<String>, BackgroundAction)>>> = Arc::new(Mutex::new(None));
+        let picked_disable: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        for row in rows {
+            let UidBatteryUsage { uid, packages, power_mah, disable_target, .. } = row.clone();
+            let name = packages.join("\n");
+            let picked = picked.clone();
+            let picked_disable = picked_disable.clone();
+            battery_table = battery_table.row(|table_row| {
+                let mut row_builder = table_row
+                    .widget_cell(move |ui: &mut egui::Ui| {
+                        ui.add(egui::Label::new(name.as_str()).wrap());
+                    })
+                    .cell(&format!("{:.1}", power_mah));
+                if is_desktop {
+                    row_builder = row_builder
+                        .cell(&Self::format_count_time(row.wakelock_count, row.wakelock_ms))
+                        .cell(&row.alarm_wakeups.to_string())
+                        .cell(&Self::format_count_time(row.job_count, row.job_ms));
+                }
+                row_builder
+                    .widget_cell(move |ui: &mut egui::Ui| {
+                        ui.add_enabled_ui(!busy, |ui| {
+                            ui.horizontal(|ui| {
+                                // Shared and system uids only get the background actions
+                                if let Some(pkg_id) = &disable_target {
+                                    if ui
+                                        .add(MaterialButton::filled(tr!("disable")).small())
+                                        .on_hover_text(tr!("battery-disable-desc"))
+                                        .clicked()
+                                    {
+                                        if let Ok(mut p) = picked_disable.lock() {
+                                            *p = Some(pkg_id.clone());
+                                        }
+                                    }
+                                }
+                                if let Some(action) = crate::tab_debloat_control::TabDebloatControl::background_action_menu(
+                                    ui,
+                                    tr!("col-background"),
+                                ) {
+                                    if let Ok(mut p) = picked.lock() {
+                                        *p = Some((packages.clone(), action));
+                                    }
+                                }
+                            });
+                        });
+                    })
+                    .id(format!("battery_table_row_{}", uid))
+            });
+        }
+
+        battery_table.show(ui);
+
+        let picked = picked.lock().ok().and_then(|mut p| p.take());
+        if let Some((packages, action)) = picked {
+            self.start_battery_action(packages, action);
+        }
+        if let Some(pkg_id) = picked_disable.lock().ok().and_then(|mut p| p.take()) {
+            self.disable_candidate = Some(pkg_id);
         }
     }
 }
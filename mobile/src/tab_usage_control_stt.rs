@@ -1,4 +1,41 @@
+use crate::calc_background_stt::BackgroundBatchOutcome;
+use crate::calc_batterystats_stt::{BatteryRanking, BatteryStatsResult};
+use crate::calc_usagestats_stt::{UsageRow, UsageStatsResult};
+use std::sync::{Arc, Mutex};
+
+/// Report shown in the usage tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageView {
+    Usage,
+    Battery,
+}
+
 pub struct TabUsageControl {
     pub open: bool,
-    pub usage_stats: String,
+    pub selected_device: Option<String>,
+    pub view: UsageView,
+    /// Usage stats loaded in the background; `None` until loaded
+    pub usage_result: UsageStatsResult,
+    /// Table rows built from the last loaded stats; `None` until loaded
+    pub usage_rows: Option<Result<Vec<UsageRow>, String>>,
+    pub loading: bool,
+    // Sort state (logical column indices, see `ui`)
+    pub sort_column: Option<usize>,
+    pub sort_ascending: bool,
+    /// Show only installed packages not used in the last 30 days
+    pub unused_only: bool,
+    pub text_filter: String,
+    /// Packages added to the debloat tab's selection; taken by the app
+    pub selection_candidates: Option<Vec<String>>,
+    /// Package handed to the debloat tab to disable behind its unsafe guard and confirmation; taken by the app
+    pub disable_candidate: Option<String>,
+    /// Battery report loaded in the background; `None` until loaded
+    pub battery_result: BatteryStatsResult,
+    pub battery_loading: bool,
+    pub battery_ranking: BatteryRanking,
+    /// Outcome of the running row action; `Some` once finished
+    pub battery_action_result: Arc<Mutex<Option<BackgroundBatchOutcome>>>,
+    pub battery_action_running: bool,
+    /// Last row action outcome (success, message)
+    pub battery_action_message: Option<(bool, String)>,
 }
@@ -14,9 +14,11 @@ use egui_material3::{dialog, menu, menu_item, tabs_primary, MaterialButton};
 use egui_material3::{get_global_theme, ContrastLevel, MaterialThemeContext, ThemeMode};
 
 use crate::db::{
-    flush_apkmirror, flush_fdroid, flush_googleplay, flush_hybridanalysis, flush_virustotal,
-    invalidate_cache,
+    flush_apkmirror, flush_fdroid, flush_googleplay, flush_hybridanalysis, flush_koodous,
+    flush_malwarebazaar, flush_virustotal, invalidate_cache,
 };
+use crate::calc_snapshot_stt::DatasetKind;
+use crate::dlg_settings_stt::AllowlistAction;
 use crate::db_package_cache::get_cached_packages_with_apk;
 use crate::material_symbol_icons::ICON_REFRESH;
 use crate::models::PackageInfoCache;
@@ -67,6 +69,10 @@ pub fn init_common() {
     // Initialize Hybrid Analysis database upsert queue
     crate::db_hybridanalysis::init_upsert_queue();
 
+    // Initialize MalwareBazaar and Koodous database upsert queues
+    crate::db_malwarebazaar::init_upsert_queue();
+    crate::db_koodous::init_upsert_queue();
+
     // Initialize i18n
     crate::init_i18n();
 }
@@ -271,12 +277,20 @@ impl Default for UadShizukuApp {
             dlg_settings: crate::dlg_settings_stt::DlgSettings {
                 virustotal_apikey: settings.virustotal_apikey.clone(),
                 hybridanalysis_apikey: settings.hybridanalysis_apikey.clone(),
+                malwarebazaar_apikey: settings.malwarebazaar_apikey.clone(),
+                koodous_apikey: settings.koodous_apikey.clone(),
                 google_play_renderer: settings.google_play_renderer,
                 fdroid_renderer: settings.fdroid_renderer,
                 apkmirror_renderer: settings.apkmirror_renderer,
                 virustotal_submit: settings.virustotal_submit,
                 hybridanalysis_submit: settings.hybridanalysis_submit,
                 hybridanalysis_tag_ignorelist: settings.hybridanalysis_tag_ignorelist.clone(),
+                virustotal_requests_per_minute: settings.virustotal_requests_per_minute,
+                virustotal_requests_per_day: settings.virustotal_requests_per_day,
+                virustotal_requests_per_month: settings.virustotal_requests_per_month,
+                scan_max_age_days: settings.scan_max_age_days,
+                rescan_flagged: settings.rescan_flagged,
+                rescan_interval_hours: settings.rescan_interval_hours,
                 unsafe_app_remove: settings.unsafe_app_remove,
                 autoupdate: settings.autoupdate,
                 ..Default::default()
@@ -638,6 +652,22 @@ impl UadShizukuApp {
                 self.tab_scan_control.ha_scan_state.complete();
             }
         }
+        // Sync MalwareBazaar progress
+        if let Ok(progress) = self.tab_scan_control.mb_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_scan_control.mb_scan_state.update_progress(p);
+            } else if self.tab_scan_control.mb_scan_state.is_running {
+                self.tab_scan_control.mb_scan_state.complete();
+            }
+        }
+        // Sync Koodous progress
+        if let Ok(progress) = self.tab_scan_control.koodous_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_scan_control.koodous_scan_state.update_progress(p);
+            } else if self.tab_scan_control.koodous_scan_state.is_running {
+                self.tab_scan_control.koodous_scan_state.complete();
+            }
+        }
         // Sync IzzyRisk progress
         if let Ok(progress) = self.tab_scan_control.izzyrisk_scan_progress.lock() {
             if let Some(p) = *progress {
@@ -646,6 +676,30 @@ impl UadShizukuApp {
                 self.tab_scan_control.izzyrisk_scan_state.complete();
             }
         }
+        // Sync tracker scan progress
+        if let Ok(progress) = self.tab_scan_control.tracker_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_scan_control.tracker_scan_state.update_progress(p);
+            } else if self.tab_scan_control.tracker_scan_state.is_running {
+                self.tab_scan_control.tracker_scan_state.complete();
+            }
+        }
+        // Sync rule scan progress
+        if let Ok(progress) = self.tab_scan_control.rule_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_scan_control.rule_scan_state.update_progress(p);
+            } else if self.tab_scan_control.rule_scan_state.is_running {
+                self.tab_scan_control.rule_scan_state.complete();
+            }
+        }
+        // Sync native library scan progress
+        if let Ok(progress) = self.tab_scan_control.native_lib_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_scan_control.native_lib_scan_state.update_progress(p);
+            } else if self.tab_scan_control.native_lib_scan_state.is_running {
+                self.tab_scan_control.native_lib_scan_state.complete();
+            }
+        }
         // Sync batch uninstall progress
         if let Ok(progress) = self.tab_debloat_control.batch_uninstall_progress.lock() {
             if let Some(p) = *progress {
@@ -670,6 +724,14 @@ impl UadShizukuApp {
                 self.tab_debloat_control.batch_enable_state.complete();
             }
         }
+        // Sync signing certificate scan progress
+        if let Ok(progress) = self.tab_debloat_control.certificate_scan_progress.lock() {
+            if let Some(p) = *progress {
+                self.tab_debloat_control.certificate_scan_state.update_progress(p);
+            } else if self.tab_debloat_control.certificate_scan_state.is_running {
+                self.tab_debloat_control.certificate_scan_state.complete();
+            }
+        }
     }
 
     pub fn ui(&mut self, ui: &mut egui::Ui) {
@@ -683,6 +745,7 @@ impl UadShizukuApp {
         
         // Sync scan progress states before rendering progress bars
         self.sync_scan_progress();
+        self.tab_scan_control.run_scheduled_rescan(ui.ctx());
 
         // === top app bar area start
         ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
@@ -924,6 +987,52 @@ impl UadShizukuApp {
                         });
                     }
 
+                    // MalwareBazaar lookup progress
+                    if let Some(p) = self.tab_scan_control.mb_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("malwarebazaar-filter"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("scanning-packages"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop MalwareBazaar lookup clicked");
+                                self.tab_scan_control.mb_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_scan_control.mb_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_scan_control.mb_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
+                    // Koodous lookup progress
+                    if let Some(p) = self.tab_scan_control.koodous_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("koodous-filter"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("scanning-packages"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop Koodous lookup clicked");
+                                self.tab_scan_control.koodous_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_scan_control.koodous_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_scan_control.koodous_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
                     // IzzyRisk calculation progress
                     if let Some(p) = self.tab_scan_control.izzyrisk_scan_state.progress {
                         let progress_bar = egui::ProgressBar::new(p)
@@ -947,6 +1056,75 @@ impl UadShizukuApp {
                         });
                     }
 
+                    // Tracker scan progress
+                    if let Some(p) = self.tab_scan_control.tracker_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("tracker-scan"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("scanning-trackers"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop tracker scan clicked");
+                                self.tab_scan_control.tracker_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_scan_control.tracker_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_scan_control.tracker_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
+                    // Rule scan progress
+                    if let Some(p) = self.tab_scan_control.rule_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("rule-scan"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("scanning-rules"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop rule scan clicked");
+                                self.tab_scan_control.rule_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_scan_control.rule_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_scan_control.rule_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
+                    // Native library scan progress
+                    if let Some(p) = self.tab_scan_control.native_lib_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("native-lib-scan"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("scanning-native-libs"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop native library scan clicked");
+                                self.tab_scan_control.native_lib_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_scan_control.native_lib_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_scan_control.native_lib_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
                     // Batch uninstall progress
                     if let Some(p) = self.tab_debloat_control.batch_uninstall_state.progress {
                         let progress_bar = egui::ProgressBar::new(p)
@@ -1016,6 +1194,29 @@ impl UadShizukuApp {
                         });
                     }
 
+                    // Signing certificate scan progress
+                    if let Some(p) = self.tab_debloat_control.certificate_scan_state.progress {
+                        let progress_bar = egui::ProgressBar::new(p)
+                            .show_percentage()
+                            .desired_width(100.0)
+                            .animate(true);
+                        ui.label(tr!("certificate-scan"));
+                        ui.horizontal(|ui| {
+                            ui.add(progress_bar).on_hover_text(tr!("checking-certificates"));
+
+                            if ui.button(tr!("stop")).clicked() {
+                                log::info!("Stop signing certificate scan clicked");
+                                self.tab_debloat_control.certificate_scan_state.cancel();
+                                if let Ok(mut cancelled) = self.tab_debloat_control.certificate_scan_cancelled.lock() {
+                                    *cancelled = true;
+                                }
+                                if let Ok(mut progress) = self.tab_debloat_control.certificate_scan_progress.lock() {
+                                    *progress = None;
+                                }
+                            }
+                        });
+                    }
+
                     // App operations progress (install/uninstall)
                     if let Some(queue) = &self.tab_apps_control.operations_queue {
                         if let Ok(progress) = queue.progress.lock() {
@@ -1062,6 +1263,12 @@ impl UadShizukuApp {
         if self.dlg_settings.save_clicked {
             self.save_settings();
         }
+        if let Some((kind, path)) = self.dlg_settings.import_requested.take() {
+            self.import_dataset_from_disk(kind, &path);
+        }
+        if let Some(action) = self.dlg_settings.allowlist_requested.take() {
+            self.apply_allowlist_action(action);
+        }
         if let Some(theme_name) = self.dlg_settings.theme_to_apply.take() {
             if theme_name == "default" {
                 setup_local_theme(Some("resources/material-theme.json"));
@@ -1228,11 +1435,20 @@ impl UadShizukuApp {
                 self.dlg_settings.apkmirror_renderer = self.settings.apkmirror_renderer;
                 self.dlg_settings.virustotal_apikey = self.settings.virustotal_apikey.clone();
                 self.dlg_settings.hybridanalysis_apikey = self.settings.hybridanalysis_apikey.clone();
+                self.dlg_settings.malwarebazaar_apikey = self.settings.malwarebazaar_apikey.clone();
+                self.dlg_settings.koodous_apikey = self.settings.koodous_apikey.clone();
                 self.dlg_settings.virustotal_submit = self.settings.virustotal_submit;
                 self.dlg_settings.hybridanalysis_submit = self.settings.hybridanalysis_submit;
                 self.dlg_settings.hybridanalysis_tag_ignorelist = self.settings.hybridanalysis_tag_ignorelist.clone();
+                self.dlg_settings.virustotal_requests_per_minute = self.settings.virustotal_requests_per_minute;
+                self.dlg_settings.virustotal_requests_per_day = self.settings.virustotal_requests_per_day;
+                self.dlg_settings.virustotal_requests_per_month = self.settings.virustotal_requests_per_month;
+                self.dlg_settings.scan_max_age_days = self.settings.scan_max_age_days;
+                self.dlg_settings.rescan_flagged = self.settings.rescan_flagged;
+                self.dlg_settings.rescan_interval_hours = self.settings.rescan_interval_hours;
                 self.dlg_settings.unsafe_app_remove = self.settings.unsafe_app_remove;
                 self.dlg_settings.autoupdate = self.settings.autoupdate;
+                self.dlg_settings.allowlist_count = crate::db_hash_allowlist::count();
                 self.dlg_settings.open();
             }
 
@@ -1417,7 +1633,8 @@ impl UadShizukuApp {
         if show_apps_tab {
             tabs = tabs.tab(tr!("apps"));
         }
-        //.tab(tr!("usage"));
+        tabs = tabs.tab(tr!("usage"));
+        let usage_tab_index = if show_apps_tab { 3 } else { 2 };
 
         if self.is_scrolled {
             tabs = tabs.height(24.0);
@@ -1482,9 +1699,8 @@ impl UadShizukuApp {
                     });
                 self.is_scrolled = scroll_output.state.offset.y > 0.0;
             }
-            3 => {
-                // Usage tab (currently commented out in tab list)
-                // ui.colored_label(egui::Color32::from_rgb(103, 80, 164), "Usage");
+            i if i == usage_tab_index => {
+                // Usage tab (index depends on whether the apps tab is shown)
                 ui.label(tr!("usage-description"));
                 ui.add_space(8.0);
 
@@ -1859,6 +2075,21 @@ impl UadShizukuApp {
 
     fn render_usage_tab(&mut self, ui: &mut egui::Ui) {
         self.tab_usage_control.ui(ui);
+
+        // Add packages picked in the usage tab to the debloat tab's selection
+        if let Some(packages) = self.tab_usage_control.selection_candidates.take() {
+            log::info!("Selecting {} unused packages in the debloat tab", packages.len());
+            self.tab_debloat_control.selected_packages.extend(packages);
+            self.custom_selected = 0;
+        }
+
+        // Disabling from the battery report goes through the debloat tab's unsafe guard and confirmation
+        if let Some(pkg_id) = self.tab_usage_control.disable_candidate.take() {
+            match self.tab_debloat_control.request_disable(pkg_id) {
+                Ok(()) => self.custom_selected = 0,
+                Err(message) => self.tab_usage_control.battery_action_message = Some((false, message)),
+            }
+        }
     }
 
     fn render_logs(&mut self, ui: &mut egui::Ui) {
@@ -2045,6 +2276,9 @@ impl UadShizukuApp {
         // Load stalkerware indicators
         self.retrieve_stalkerware_indicators();
 
+        // Load tracker signatures
+        self.retrieve_tracker_signatures();
+
         let Some(device) = self.selected_device.clone() else {
             log::debug!("No device selected, skipping package retrieval");
             return;
@@ -2249,10 +2483,18 @@ impl UadShizukuApp {
                         self.tab_scan_control.vt_api_key = Some(self.settings.virustotal_apikey.clone());
                         self.tab_scan_control.ha_api_key =
                             Some(self.settings.hybridanalysis_apikey.clone());
+                        self.tab_scan_control.mb_api_key =
+                            Some(self.settings.malwarebazaar_apikey.clone());
+                        self.tab_scan_control.koodous_api_key =
+                            Some(self.settings.koodous_apikey.clone());
                         self.tab_scan_control.device_serial = self.selected_device.clone();
                         self.tab_scan_control.virustotal_submit_enabled = self.settings.virustotal_submit;
                         self.tab_scan_control.hybridanalysis_submit_enabled =
                             self.settings.hybridanalysis_submit;
+                        self.tab_scan_control.scan_freshness = self.settings.scan_freshness();
+                        self.tab_scan_control.vt_requests_per_minute = self.settings.virustotal_requests_per_minute;
+                        self.tab_scan_control.vt_quota_budget = self.settings.virustotal_quota();
+                        self.tab_scan_control.rescan_interval_hours = self.settings.rescan_interval_hours;
                         log::info!(
                             "Synced hybridanalysis_submit_enabled={} to tab_scan_control",
                             self.settings.hybridanalysis_submit
@@ -2266,6 +2508,8 @@ impl UadShizukuApp {
                             .update_packages(installed_packages.clone());
                         self.tab_apps_control
                             .set_selected_device(self.selected_device.clone());
+                        self.tab_usage_control
+                            .set_selected_device(self.selected_device.clone());
                         log::debug!("Updated tab controls with packages");
 
                         // Close dialog
@@ -2286,7 +2530,6 @@ impl UadShizukuApp {
     // another lists https://github.com/MuntashirAkon/android-debloat-list
     fn retrieve_uad_ng_lists(&mut self) {
         const UAD_LISTS_URL: &str = "https://raw.githubusercontent.com/Universal-Debloater-Alliance/universal-android-debloater-next-generation/refs/heads/main/resources/assets/uad_lists.json";
-        const UAD_LISTS_FILENAME: &str = "uad_lists.json";
 
         // Get cache directory from config
         let cache_dir = match &self.config {
@@ -2297,109 +2540,79 @@ impl UadShizukuApp {
             }
         };
 
-        let cache_file_path = cache_dir.join(UAD_LISTS_FILENAME);
+        Self::refresh_cached_dataset(&cache_dir, DatasetKind::UadLists, UAD_LISTS_URL, "UAD lists");
 
-        // Check if file exists in cache or is older than 7 days
-        let should_download = !cache_file_path.exists() || {
-            cache_file_path
-                .metadata()
-                .and_then(|m| m.modified())
-                .map(|modified| {
-                    modified
-                        .elapsed()
-                        .map(|elapsed| elapsed.as_secs() > 7 * 24 * 60 * 60)
-                        .unwrap_or(false)
-                })
-                .unwrap_or(false)
-        };
+        // Load whichever is newer: the cached download or the bundled snapshot
+        match crate::calc_snapshot::load_dataset(DatasetKind::UadLists, &cache_dir) {
+            Some((json_content, status)) => {
+                if let Err(e) = crate::calc_snapshot::apply_uad_lists(&json_content, status) {
+                    log::error!("{}", e);
+                }
+            }
+            None => {
+                log::error!("No UAD lists available (download failed and no bundled snapshot)");
+            }
+        }
+    }
 
-        if should_download {
-            log::info!(
-                "UAD lists not found in cache or older than 7 days, downloading from {}",
-                UAD_LISTS_URL
-            );
+    fn retrieve_stalkerware_indicators(&mut self) {
+        const IOC_URL: &str = "https://raw.githubusercontent.com/AssoEchap/stalkerware-indicators/master/ioc.yaml";
 
-            // Download the file
-            let request = ehttp::Request::get(UAD_LISTS_URL);
-            let (sender, receiver) = std::sync::mpsc::channel();
+        // Get cache directory from config
+        let cache_dir = match &self.config {
+            Some(config) => config.cache_dir.clone(),
+            None => {
+                log::error!("Config not available, cannot retrieve stalkerware indicators");
+                return;
+            }
+        };
 
-            ehttp::fetch(request, move |result| {
-                sender.send(result).ok();
-            });
+        Self::refresh_cached_dataset(&cache_dir, DatasetKind::StalkerwareIoc, IOC_URL, "stalkerware IoC");
 
-            // Wait for the response (blocking)
-            match receiver.recv() {
-                Ok(Ok(response)) => {
-                    if response.ok {
-                        // Save to cache
-                        match std::fs::write(&cache_file_path, &response.bytes) {
-                            Ok(_) => {
-                                log::info!(
-                                    "Successfully downloaded and cached UAD lists to {:?}",
-                                    cache_file_path
-                                );
-                            }
-                            Err(e) => {
-                                log::error!("Failed to write UAD lists to cache: {}", e);
-                                return;
-                            }
-                        }
-                    } else {
-                        log::error!("Failed to download UAD lists: HTTP {}", response.status);
-                        return;
-                    }
-                }
-                Ok(Err(e)) => {
-                    log::error!("Failed to download UAD lists: {}", e);
-                    return;
-                }
-                Err(e) => {
-                    log::error!("Failed to receive download response: {}", e);
-                    return;
+        // Load whichever is newer: the cached download or the bundled snapshot
+        match crate::calc_snapshot::load_dataset(DatasetKind::StalkerwareIoc, &cache_dir) {
+            Some((yaml_content, status)) => {
+                if let Err(e) = crate::calc_snapshot::apply_stalkerware_ioc(&yaml_content, status) {
+                    log::error!("Failed to parse stalkerware IoC YAML: {}", e);
                 }
             }
-        } else {
-            log::info!("UAD lists found in cache at {:?}", cache_file_path);
-        }
-
-        // Load and parse the JSON file
-        match std::fs::read_to_string(&cache_file_path) {
-            Ok(json_content) => match serde_json::from_str::<UadNgLists>(&json_content) {
-                Ok(uad_lists) => {
-                    log::info!(
-                        "Successfully parsed UAD lists with {} apps",
-                        uad_lists.apps.len()
-                    );
-                    let shared_store = crate::shared_store_stt::get_shared_store();
-                    {
-                        let mut lists = shared_store.uad_ng_lists.lock().unwrap();
-                        *lists = Some(uad_lists);
-                    }
-                }
-                Err(e) => {
-                    log::error!("Failed to parse UAD lists JSON: {}", e);
-                }
-            },
-            Err(e) => {
-                log::error!("Failed to read UAD lists from cache: {}", e);
+            None => {
+                log::error!("No stalkerware IoC available (download failed and no bundled snapshot)");
             }
         }
     }
 
-    fn retrieve_stalkerware_indicators(&mut self) {
-        const IOC_URL: &str = "https://raw.githubusercontent.com/AssoEchap/stalkerware-indicators/master/ioc.yaml";
-        const IOC_FILENAME: &str = "stalkerware_ioc.yaml";
+    fn retrieve_tracker_signatures(&mut self) {
+        const TRACKERS_URL: &str = "https://reports.exodus-privacy.eu.org/api/trackers";
 
         // Get cache directory from config
         let cache_dir = match &self.config {
             Some(config) => config.cache_dir.clone(),
             None => {
-                log::error!("Config not available, cannot retrieve stalkerware indicators");
+                log::error!("Config not available, cannot retrieve tracker signatures");
                 return;
             }
         };
 
-        let cache_file_path = cache_dir.join(IOC_FILENAME);
+        Self::refresh_cached_dataset(&cache_dir, DatasetKind::ExodusTrackers, TRACKERS_URL, "tracker signatures");
+
+        // Load whichever is newer: the cached download or the bundled snapshot
+        match crate::calc_snapshot::load_dataset(DatasetKind::ExodusTrackers, &cache_dir) {
+            Some((json_content, status)) => {
+                if let Err(e) = crate::calc_snapshot::apply_tracker_signatures(&json_content, status) {
+                    log::error!("{}", e);
+                }
+            }
+            None => {
+                log::error!("No tracker signatures available (download failed and no bundled snapshot)");
+            }
+        }
+    }
+
+    /// Download a dataset into the cache when missing or older than 7 days.
+    /// Failures are logged and leave the previous cache (or bundled snapshot) in use.
+    fn refresh_cached_dataset(cache_dir: &std::path::Path, kind: DatasetKind, url: &str, label: &str) {
+        let cache_file_path = cache_dir.join(kind.cache_filename());
 
         // Check if file exists in cache or is older than 7 days
         let should_download = !cache_file_path.exists() || {
@@ -2415,73 +2628,100 @@ impl UadShizukuApp {
                 .unwrap_or(false)
         };
 
-        if should_download {
-            log::info!(
-                "Stalkerware IoC not found in cache or older than 7 days, downloading from {}",
-                IOC_URL
-            );
+        if !should_download {
+            log::info!("{} found in cache at {:?}", label, cache_file_path);
+            return;
+        }
 
-            // Download the file
-            let request = ehttp::Request::get(IOC_URL);
-            let (sender, receiver) = std::sync::mpsc::channel();
+        log::info!(
+            "{} not found in cache or older than 7 days, downloading from {}",
+            label,
+            url
+        );
 
-            ehttp::fetch(request, move |result| {
-                sender.send(result).ok();
-            });
+        // Download the file
+        let request = ehttp::Request::get(url);
+        let (sender, receiver) = std::sync::mpsc::channel();
 
-            // Wait for the response (blocking)
-            match receiver.recv() {
-                Ok(Ok(response)) => {
-                    if response.ok {
-                        // Save to cache
-                        match std::fs::write(&cache_file_path, &response.bytes) {
-                            Ok(_) => {
-                                log::info!(
-                                    "Successfully downloaded and cached stalkerware IoC to {:?}",
-                                    cache_file_path
-                                );
-                            }
-                            Err(e) => {
-                                log::error!("Failed to write stalkerware IoC to cache: {}", e);
-                                return;
-                            }
+        ehttp::fetch(request, move |result| {
+            sender.send(result).ok();
+        });
+
+        // Wait for the response (blocking)
+        match receiver.recv() {
+            Ok(Ok(response)) => {
+                if response.ok {
+                    // Save to cache
+                    match std::fs::write(&cache_file_path, &response.bytes) {
+                        Ok(_) => {
+                            log::info!(
+                                "Successfully downloaded and cached {} to {:?}",
+                                label,
+                                cache_file_path
+                            );
+                        }
+                        Err(e) => {
+                            log::error!("Failed to write {} to cache: {}", label, e);
                         }
-                    } else {
-                        log::error!("Failed to download stalkerware IoC: HTTP {}", response.status);
-                        return;
                     }
-                }
-                Ok(Err(e)) => {
-                    log::error!("Failed to download stalkerware IoC: {}", e);
-                    return;
-                }
-                Err(e) => {
-                    log::error!("Failed to receive download response: {}", e);
-                    return;
+                } else {
+                    log::error!("Failed to download {}: HTTP {}", label, response.status);
                 }
             }
-        } else {
-            log::info!("Stalkerware IoC found in cache at {:?}", cache_file_path);
+            Ok(Err(e)) => {
+                log::error!("Failed to download {}: {}", label, e);
+            }
+            Err(e) => {
+                log::error!("Failed to receive download response: {}", e);
+            }
         }
+    }
 
-        // Load and parse the YAML file
-        match std::fs::read_to_string(&cache_file_path) {
-            Ok(yaml_content) => {
-                match crate::calc_stalkerware::parse_stalkerware_yaml(&yaml_content) {
-                    Ok(indicators) => {
-                        log::info!("Successfully parsed stalkerware IoC");
-                        let shared_store = crate::shared_store_stt::get_shared_store();
-                        shared_store.set_stalkerware_indicators(Some(indicators));
-                    }
-                    Err(e) => {
-                        log::error!("Failed to parse stalkerware IoC YAML: {}", e);
-                    }
-                }
+    /// Import a dataset file (UAD-NG lists, stalkerware IoC, tracker signatures) chosen in the settings dialog
+    fn import_dataset_from_disk(&mut self, kind: DatasetKind, path: &str) {
+        let Some(config) = &self.config else {
+            log::error!("Config not available, cannot import {:?}", kind);
+            return;
+        };
+
+        let result = crate::calc_snapshot::import_dataset(kind, std::path::Path::new(path.trim()), &config.cache_dir);
+        self.dlg_settings.import_result = Some(match result {
+            Ok(count) => (true, tr!("import-dataset-success", { count: count })),
+            Err(e) => {
+                log::error!("Import failed: {}", e);
+                (false, tr!("import-dataset-failed", { error: e }))
             }
+        });
+    }
+
+    fn apply_allowlist_action(&mut self, action: AllowlistAction) {
+        use crate::db_hash_allowlist;
+        let result = match &action {
+            AllowlistAction::AddCleanResults => db_hash_allowlist::add_clean_results()
+                .map(|count| tr!("hash-allowlist-added", { count: count }))
+                .map_err(|e| e.to_string()),
+            AllowlistAction::Import(path) => db_hash_allowlist::import_file(std::path::Path::new(path.trim()))
+                .map(|(count, refused)| {
+                    if refused > 0 {
+                        tr!("hash-allowlist-added-refused", { count: count, refused: refused })
+                    } else {
+                        tr!("hash-allowlist-added", { count: count })
+                    }
+                }),
+            AllowlistAction::Export(path) => db_hash_allowlist::export_file(std::path::Path::new(path.trim()))
+                .map(|count| tr!("hash-allowlist-exported", { count: count })),
+            AllowlistAction::Clear => db_hash_allowlist::clear()
+                .map(|count| tr!("hash-allowlist-cleared", { count: count }))
+                .map_err(|e| e.to_string()),
+        };
+        self.dlg_settings.allowlist_result = Some(match result {
+            Ok(message) => (true, message),
             Err(e) => {
-                log::error!("Failed to read stalkerware IoC from cache: {}", e);
+                log::error!("Hash allowlist {:?} failed: {}", action, e);
+                (false, tr!("hash-allowlist-failed", { error: e }))
             }
-        }
+        });
+        self.dlg_settings.allowlist_count = db_hash_allowlist::count();
     }
 
     // Flags : https://android.googlesource.com/platform/frameworks/base/+/master/core/java/android/content/pm/ApplicationInfo.java
@@ -2516,6 +2756,8 @@ impl UadShizukuApp {
         // Store old values for comparison
         let old_vt_apikey = self.settings.virustotal_apikey.clone();
         let old_ha_apikey = self.settings.hybridanalysis_apikey.clone();
+        let old_mb_apikey = self.settings.malwarebazaar_apikey.clone();
+        let old_koodous_apikey = self.settings.koodous_apikey.clone();
         let old_vt_submit = self.settings.virustotal_submit;
         let old_ha_submit = self.settings.hybridanalysis_submit;
         let old_google_play_renderer = self.settings.google_play_renderer;
@@ -2526,9 +2768,17 @@ impl UadShizukuApp {
         // Update settings struct from temporary values
         self.settings.virustotal_apikey = self.dlg_settings.virustotal_apikey.clone();
         self.settings.hybridanalysis_apikey = self.dlg_settings.hybridanalysis_apikey.clone();
+        self.settings.malwarebazaar_apikey = self.dlg_settings.malwarebazaar_apikey.clone();
+        self.settings.koodous_apikey = self.dlg_settings.koodous_apikey.clone();
         self.settings.virustotal_submit = self.dlg_settings.virustotal_submit;
         self.settings.hybridanalysis_submit = self.dlg_settings.hybridanalysis_submit;
         self.settings.hybridanalysis_tag_ignorelist = self.dlg_settings.hybridanalysis_tag_ignorelist.clone();
+        self.settings.virustotal_requests_per_minute = self.dlg_settings.virustotal_requests_per_minute;
+        self.settings.virustotal_requests_per_day = self.dlg_settings.virustotal_requests_per_day;
+        self.settings.virustotal_requests_per_month = self.dlg_settings.virustotal_requests_per_month;
+        self.settings.scan_max_age_days = self.dlg_settings.scan_max_age_days;
+        self.settings.rescan_flagged = self.dlg_settings.rescan_flagged;
+        self.settings.rescan_interval_hours = self.dlg_settings.rescan_interval_hours;
         self.settings.google_play_renderer = self.dlg_settings.google_play_renderer;
         self.settings.fdroid_renderer = self.dlg_settings.fdroid_renderer;
         self.settings.apkmirror_renderer = self.dlg_settings.apkmirror_renderer;
@@ -2543,6 +2793,17 @@ impl UadShizukuApp {
         self.tab_scan_control.virustotal_submit_enabled = self.settings.virustotal_submit;
         self.tab_scan_control.hybridanalysis_submit_enabled = self.settings.hybridanalysis_submit;
 
+        // Sync rescan and VirusTotal tier settings to tab_scan_control
+        self.tab_scan_control.scan_freshness = self.settings.scan_freshness();
+        self.tab_scan_control.vt_requests_per_minute = self.settings.virustotal_requests_per_minute;
+        self.tab_scan_control.vt_quota_budget = self.settings.virustotal_quota();
+        if let Some(ref limiter) = self.tab_scan_control.vt_rate_limiter {
+            if let Some(quota) = limiter.lock().unwrap().quota.as_mut() {
+                quota.budget = self.settings.virustotal_quota();
+            }
+        }
+        self.tab_scan_control.rescan_interval_hours = self.settings.rescan_interval_hours;
+
         // Check if VirusTotal API key was removed -> stop running scans
         if !old_vt_apikey.is_empty() && self.settings.virustotal_apikey.is_empty() {
             log::info!("VirusTotal API key removed, cancelling running scans");
@@ -2559,6 +2820,22 @@ impl UadShizukuApp {
             }
         }
 
+        // Check if MalwareBazaar API key was removed -> stop running lookups
+        if !old_mb_apikey.is_empty() && self.settings.malwarebazaar_apikey.is_empty() {
+            log::info!("MalwareBazaar API key removed, cancelling running lookups");
+            if let Ok(mut cancelled) = self.tab_scan_control.mb_scan_cancelled.lock() {
+                *cancelled = true;
+            }
+        }
+
+        // Check if Koodous API key was removed -> stop running lookups
+        if !old_koodous_apikey.is_empty() && self.settings.koodous_apikey.is_empty() {
+            log::info!("Koodous API key removed, cancelling running lookups");
+            if let Ok(mut cancelled) = self.tab_scan_control.koodous_scan_cancelled.lock() {
+                *cancelled = true;
+            }
+        }
+
         // Check if VirusTotal upload was disabled -> stop uploading
         if old_vt_submit && !self.settings.virustotal_submit {
             log::info!("VirusTotal upload disabled, cancelling uploads");
@@ -2660,6 +2937,34 @@ impl UadShizukuApp {
             }
         }
 
+        // Check if MalwareBazaar API key was added -> start lookups
+        if old_mb_apikey.is_empty() && !self.settings.malwarebazaar_apikey.is_empty() {
+            log::info!("MalwareBazaar API key added, starting lookups");
+            self.tab_scan_control.mb_api_key = Some(self.settings.malwarebazaar_apikey.clone());
+            if let Ok(mut cancelled) = self.tab_scan_control.mb_scan_cancelled.lock() {
+                *cancelled = false;
+            }
+            let shared_store = crate::shared_store_stt::get_shared_store();
+            let installed_packages = shared_store.installed_packages.lock().unwrap().clone();
+            if !installed_packages.is_empty() {
+                self.tab_scan_control.update_packages(installed_packages);
+            }
+        }
+
+        // Check if Koodous API key was added -> start lookups
+        if old_koodous_apikey.is_empty() && !self.settings.koodous_apikey.is_empty() {
+            log::info!("Koodous API key added, starting lookups");
+            self.tab_scan_control.koodous_api_key = Some(self.settings.koodous_apikey.clone());
+            if let Ok(mut cancelled) = self.tab_scan_control.koodous_scan_cancelled.lock() {
+                *cancelled = false;
+            }
+            let shared_store = crate::shared_store_stt::get_shared_store();
+            let installed_packages = shared_store.installed_packages.lock().unwrap().clone();
+            if !installed_packages.is_empty() {
+                self.tab_scan_control.update_packages(installed_packages);
+            }
+        }
+
         if self.dlg_settings.invalidate_cache {
             invalidate_cache();
             self.dlg_settings.invalidate_cache = false;
@@ -2674,6 +2979,14 @@ impl UadShizukuApp {
             flush_hybridanalysis();
             self.dlg_settings.flush_hybridanalysis = false;
         }
+        if self.dlg_settings.flush_malwarebazaar {
+            flush_malwarebazaar();
+            self.dlg_settings.flush_malwarebazaar = false;
+        }
+        if self.dlg_settings.flush_koodous {
+            flush_koodous();
+            self.dlg_settings.flush_koodous = false;
+        }
         if self.dlg_settings.flush_googleplay {
             flush_googleplay();
             self.dlg_settings.flush_googleplay = false;
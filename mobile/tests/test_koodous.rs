@@ -0,0 +1,99 @@
+use std::thread;
+use uad_shizuku::api_koodous::{self, KoodousError};
+
+const SHA256: &str = "8a1b2c3d4e5f60718293a4b5c6d7e8f90123456789abcdef0123456789abcdef";
+
+/// Request seen by the mock server
+struct Recorded {
+    method: String,
+    url: String,
+    authorization: Option<String>,
+}
+
+/// Serve a single request on a local port, answering with `status` and `body`
+fn serve_once(status: u16, body: &'static str) -> (String, thread::JoinHandle<Recorded>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+    let handle = thread::spawn(move || {
+        let request = server.recv().unwrap();
+        let recorded = Recorded {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            authorization: request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Authorization"))
+                .map(|h| h.value.to_string()),
+        };
+        let header: tiny_http::Header = "Content-Type: application/json".parse().unwrap();
+        request
+            .respond(tiny_http::Response::from_string(body).with_status_code(status).with_header(header))
+            .unwrap();
+        recorded
+    });
+
+    (base_url, handle)
+}
+
+#[test]
+fn test_koodous_get_apk_detected() {
+    let (base_url, handle) = serve_once(
+        200,
+        r#"{
+            "sha256": "8a1b2c3d4e5f60718293a4b5c6d7e8f90123456789abcdef0123456789abcdef",
+            "app": "System Update",
+            "package_name": "com.android.sysupdate",
+            "company": null,
+            "version": "1.0",
+            "size": 2048576,
+            "rating": -3,
+            "detected": true,
+            "trusted": false,
+            "corrupted": false,
+            "tags": ["spyware"]
+        }"#,
+    );
+
+    let apk = api_koodous::get_apk_at(&base_url, SHA256, "test-token").unwrap();
+    assert_eq!(apk.sha256, SHA256);
+    assert_eq!(apk.app.as_deref(), Some("System Update"));
+    assert_eq!(apk.company, None);
+    assert_eq!(apk.rating, Some(-3));
+    assert!(apk.detected);
+    assert!(!apk.trusted);
+    assert_eq!(apk.tags, vec!["spyware".to_string()]);
+
+    let recorded = handle.join().unwrap();
+    assert_eq!(recorded.method, "GET");
+    assert_eq!(recorded.url, format!("/apks/{}/", SHA256));
+    assert_eq!(recorded.authorization.as_deref(), Some("Token test-token"));
+}
+
+#[test]
+fn test_koodous_get_apk_missing_fields() {
+    // Older entries lack most fields
+    let (base_url, handle) = serve_once(200, r#"{"sha256": "8a1b2c3d4e5f60718293a4b5c6d7e8f90123456789abcdef0123456789abcdef"}"#);
+
+    let apk = api_koodous::get_apk_at(&base_url, SHA256, "test-token").unwrap();
+    assert!(!apk.detected);
+    assert!(apk.tags.is_empty());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_koodous_get_apk_errors() {
+    let (base_url, handle) = serve_once(404, r#"{"detail": "Not found."}"#);
+    assert!(matches!(
+        api_koodous::get_apk_at(&base_url, SHA256, "test-token"),
+        Err(KoodousError::NotFound)
+    ));
+    handle.join().unwrap();
+
+    let (base_url, handle) = serve_once(429, r#"{"detail": "Request was throttled."}"#);
+    assert!(matches!(
+        api_koodous::get_apk_at(&base_url, SHA256, "test-token"),
+        Err(KoodousError::RateLimit { .. })
+    ));
+    handle.join().unwrap();
+}
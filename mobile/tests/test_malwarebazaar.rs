@@ -0,0 +1,98 @@
+use std::io::Read;
+use std::thread;
+use uad_shizuku::api_malwarebazaar::{self, MbError};
+
+const SHA256: &str = "0f9a4b1c2d3e4f5061728394a5b6c7d8e9f00112233445566778899aabbccdd";
+
+/// Request seen by the mock server
+struct Recorded {
+    method: String,
+    auth_key: Option<String>,
+    body: String,
+}
+
+/// Serve a single request on a local port, answering with `status` and `body`
+fn serve_once(status: u16, body: &'static str) -> (String, thread::JoinHandle<Recorded>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api/v1/", server.server_addr().to_ip().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut request = server.recv().unwrap();
+        let mut request_body = String::new();
+        request.as_reader().read_to_string(&mut request_body).unwrap();
+        let recorded = Recorded {
+            method: request.method().to_string(),
+            auth_key: request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Auth-Key"))
+                .map(|h| h.value.to_string()),
+            body: request_body,
+        };
+        let header: tiny_http::Header = "Content-Type: application/json".parse().unwrap();
+        request
+            .respond(tiny_http::Response::from_string(body).with_status_code(status).with_header(header))
+            .unwrap();
+        recorded
+    });
+
+    (url, handle)
+}
+
+#[test]
+fn test_mb_query_hash_listed() {
+    let (url, handle) = serve_once(
+        200,
+        r#"{
+            "query_status": "ok",
+            "data": [{
+                "sha256_hash": "0f9a4b1c2d3e4f5061728394a5b6c7d8e9f00112233445566778899aabbccdd",
+                "file_name": "update.apk",
+                "file_type": "apk",
+                "signature": "SpyNote",
+                "first_seen": "2025-03-01 10:00:00",
+                "last_seen": null,
+                "tags": ["apk", "rat", "SpyNote"]
+            }]
+        }"#,
+    );
+
+    let sample = api_malwarebazaar::query_hash_at(&url, SHA256, "test-key").unwrap();
+    assert_eq!(sample.sha256_hash, SHA256);
+    assert_eq!(sample.signature.as_deref(), Some("SpyNote"));
+    assert_eq!(sample.file_type.as_deref(), Some("apk"));
+    assert_eq!(sample.last_seen, None);
+    assert_eq!(sample.tags.unwrap().len(), 3);
+
+    let recorded = handle.join().unwrap();
+    assert_eq!(recorded.method, "POST");
+    assert_eq!(recorded.auth_key.as_deref(), Some("test-key"));
+    assert!(recorded.body.contains("query=get_info"));
+    assert!(recorded.body.contains(&format!("hash={}", SHA256)));
+}
+
+#[test]
+fn test_mb_query_hash_not_found() {
+    let (url, handle) = serve_once(200, r#"{"query_status": "hash_not_found"}"#);
+
+    let result = api_malwarebazaar::query_hash_at(&url, SHA256, "test-key");
+    assert!(matches!(result, Err(MbError::NotFound)));
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_mb_query_hash_errors() {
+    let (url, handle) = serve_once(200, r#"{"query_status": "unknown_auth_key"}"#);
+    match api_malwarebazaar::query_hash_at(&url, SHA256, "bad-key") {
+        Err(MbError::ApiError(status)) => assert_eq!(status, "unknown_auth_key"),
+        other => panic!("unexpected result: {:?}", other.map(|s| s.sha256_hash)),
+    }
+    handle.join().unwrap();
+
+    let (url, handle) = serve_once(429, "");
+    assert!(matches!(
+        api_malwarebazaar::query_hash_at(&url, SHA256, "test-key"),
+        Err(MbError::RateLimit { .. })
+    ));
+    handle.join().unwrap();
+}
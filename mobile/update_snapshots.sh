@@ -0,0 +1,64 @@
+#!/usr/bin/env bash
+# Refresh the UAD-NG lists, stalkerware IoC and tracker signature snapshots embedded into the binary.
+# Run before release builds so the app works on first launch without network access.
+#
+# Usage: update_snapshots.sh          download, validate and replace the snapshots
+#        update_snapshots.sh --check  only validate the committed snapshots
+# Exits non-zero when a download fails or a snapshot is empty, so releases never ship placeholders.
+
+set -euo pipefail
+
+SNAPSHOT_DIR="$(cd "$(dirname "$0")" && pwd)/resources/snapshots"
+UAD_LISTS_URL="https://raw.githubusercontent.com/Universal-Debloater-Alliance/universal-android-debloater-next-generation/refs/heads/main/resources/assets/uad_lists.json"
+IOC_URL="https://raw.githubusercontent.com/AssoEchap/stalkerware-indicators/master/ioc.yaml"
+TRACKERS_URL="https://reports.exodus-privacy.eu.org/api/trackers"
+
+# validate <name> <file>: the file must hold real data, not the `{}` / `[]` placeholders
+validate() {
+    local name="$1" file="$2"
+    case "$name" in
+        uad_lists.json)
+            jq -e 'type == "object" and length > 0' "$file" > /dev/null ;;
+        stalkerware_ioc.yaml)
+            grep -q '^- name:' "$file" ;;
+        exodus_trackers.json)
+            jq -e '(.trackers | type == "object") and (.trackers | length > 0)' "$file" > /dev/null ;;
+    esac || { echo "Snapshot $name is empty or invalid" >&2; return 1; }
+}
+
+check_all() {
+    local name
+    for name in uad_lists.json stalkerware_ioc.yaml exodus_trackers.json; do
+        validate "$name" "$SNAPSHOT_DIR/$name"
+    done
+    grep -Eq '^[0-9]{4}-[0-9]{2}-[0-9]{2}$' "$SNAPSHOT_DIR/snapshot_date.txt" \
+        || { echo "snapshot_date.txt is missing a YYYY-MM-DD date" >&2; return 1; }
+}
+
+if [[ "${1:-}" == "--check" ]]; then
+    check_all
+    echo "Snapshots valid: $(cat "$SNAPSHOT_DIR/snapshot_date.txt")"
+    exit 0
+fi
+
+mkdir -p "$SNAPSHOT_DIR"
+
+# download <name> <url>: replace the snapshot only after the download validates
+download() {
+    local name="$1" url="$2"
+    echo "Downloading $name..."
+    curl -fsSL "$url" -o "$SNAPSHOT_DIR/$name.tmp"
+    if ! validate "$name" "$SNAPSHOT_DIR/$name.tmp"; then
+        rm -f "$SNAPSHOT_DIR/$name.tmp"
+        return 1
+    fi
+    mv "$SNAPSHOT_DIR/$name.tmp" "$SNAPSHOT_DIR/$name"
+}
+
+download uad_lists.json "$UAD_LISTS_URL"
+download stalkerware_ioc.yaml "$IOC_URL"
+download exodus_trackers.json "$TRACKERS_URL"
+
+date -u +%Y-%m-%d > "$SNAPSHOT_DIR/snapshot_date.txt"
+check_all
+echo "Snapshots updated: $(cat "$SNAPSHOT_DIR/snapshot_date.txt")"